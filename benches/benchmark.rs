@@ -4,9 +4,10 @@
 
 use cc_validator::{
     batch::{count_valid, validate_batch, BatchValidator},
-    luhn,
+    bin::{BinInfo, MemoryBinDb},
+    detect, luhn,
     stream::ValidateExt,
-    validate, validate_digits,
+    validate, validate_digits, Validator, Workspace,
 };
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 
@@ -33,6 +34,15 @@ fn bench_single_validation(c: &mut Criterion) {
 
     group.bench_function("amex_15", |b| b.iter(|| validate(black_box(AMEX))));
 
+    let mut ws = Workspace::new();
+    group.bench_function("visa_16_workspace", |b| {
+        b.iter(|| ws.validate_into(black_box(VISA_16)))
+    });
+
+    group.bench_function("short_invalid_workspace", |b| {
+        b.iter(|| ws.validate_into(black_box("4111")))
+    });
+
     group.finish();
 }
 
@@ -103,6 +113,15 @@ fn bench_batch_validation(c: &mut Criterion) {
                 b.iter(|| batch.validate_all(black_box(cards)))
             },
         );
+
+        group.bench_with_input(
+            BenchmarkId::new("passes_luhn_all", size),
+            &cards,
+            |b, cards| {
+                let batch = BatchValidator::new();
+                b.iter(|| batch.passes_luhn_all(black_box(cards)))
+            },
+        );
     }
 
     group.finish();
@@ -158,11 +177,40 @@ fn bench_card_operations(c: &mut Criterion) {
         b.iter(|| black_box(&card).masked_with_bin())
     });
 
+    #[cfg(not(feature = "no-pan-exposure"))]
     group.bench_function("number", |b| b.iter(|| black_box(&card).number()));
 
     group.finish();
 }
 
+/// Benchmark brand detection over the prefix table
+fn bench_detect(c: &mut Criterion) {
+    let mut group = c.benchmark_group("detect_brand");
+
+    // One prefix per brand plus an unknown one
+    let prefixes: [&[u8]; 8] = [
+        &VISA_DIGITS,
+        &AMEX_DIGITS,
+        &[2, 7, 2, 0, 9, 9],
+        &[6, 0, 1, 1, 0, 0],
+        &[5, 0, 6, 1, 0, 0],
+        &[6, 3, 6, 3, 0, 0],
+        &[9, 7, 9, 2, 0, 0],
+        &[1, 2, 3, 4, 5, 6],
+    ];
+    group.throughput(Throughput::Elements(prefixes.len() as u64));
+
+    group.bench_function("range_table", |b| {
+        b.iter(|| {
+            for p in &prefixes {
+                black_box(detect::detect_brand(black_box(p)));
+            }
+        })
+    });
+
+    group.finish();
+}
+
 /// Benchmark with mixed valid/invalid cards
 fn bench_mixed_batch(c: &mut Criterion) {
     let mut group = c.benchmark_group("mixed_batch");
@@ -195,6 +243,72 @@ fn bench_mixed_batch(c: &mut Criterion) {
     group.finish();
 }
 
+/// Builds `per_bin` random 16-digit cards under each of `bins`, sorted like
+/// a settlement file.
+fn clustered_cards(bins: &[&str], per_bin: usize) -> Vec<String> {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut cards = Vec::with_capacity(bins.len() * per_bin);
+    for bin in bins {
+        for _ in 0..per_bin {
+            let mut card = bin.to_string();
+            while card.len() < 15 {
+                card.push(char::from(b'0' + rng.gen_range(0..10)));
+            }
+            cards.push(luhn::complete(&card).unwrap());
+        }
+    }
+    cards.sort();
+    cards
+}
+
+/// Benchmark batches clustered by BIN, where the prefix memo pays off
+fn bench_clustered_batch(c: &mut Criterion) {
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+    let mut group = c.benchmark_group("clustered_batch");
+
+    let bins = [
+        "411111", "424242", "400048", "453201", "510000", "555555", "222300", "601100", "353011",
+        "622126",
+    ];
+    let clustered = clustered_cards(&bins, 1000);
+    let mut shuffled = clustered.clone();
+    shuffled.shuffle(&mut StdRng::seed_from_u64(7));
+
+    let mut db = MemoryBinDb::new();
+    for bin in bins {
+        db.insert(bin, BinInfo::with_bin(bin).issuer("Bench Bank"));
+    }
+    let validator = Validator::new().with_bin_database(db);
+
+    group.throughput(Throughput::Elements(clustered.len() as u64));
+
+    for (name, cards) in [("clustered", &clustered), ("shuffled", &shuffled)] {
+        group.bench_with_input(BenchmarkId::new("validate_all", name), cards, |b, cards| {
+            let mut batch = BatchValidator::new();
+            b.iter(|| batch.validate_all(black_box(cards)))
+        });
+
+        group.bench_with_input(BenchmarkId::new("profile_each", name), cards, |b, cards| {
+            b.iter(|| {
+                cards
+                    .iter()
+                    .map(|c| validator.profile(black_box(c)))
+                    .collect::<Vec<_>>()
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("profile_all", name), cards, |b, cards| {
+            let mut batch = BatchValidator::with_validator(validator.clone());
+            b.iter(|| batch.profile_all(black_box(cards)))
+        });
+    }
+
+    group.finish();
+}
+
 #[cfg(feature = "parallel")]
 fn bench_parallel(c: &mut Criterion) {
     use cc_validator::batch::{count_valid_parallel, validate_batch_parallel};
@@ -235,7 +349,9 @@ criterion_group!(
     bench_batch_validation,
     bench_streaming,
     bench_card_operations,
+    bench_detect,
     bench_mixed_batch,
+    bench_clustered_batch,
     bench_parallel,
 );
 
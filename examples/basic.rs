@@ -73,6 +73,11 @@ fn main() {
                     ValidationError::InvalidLengthForBrand { .. } => "InvalidLengthForBrand",
                     ValidationError::UnknownBrand => "UnknownBrand",
                     ValidationError::NoDigits => "NoDigits",
+                    ValidationError::TestCard => "TestCard",
+                    ValidationError::BrandNotAllowed { .. } => "BrandNotAllowed",
+                    ValidationError::NotAcceptedInCountry { .. } => "NotAcceptedInCountry",
+                    // ValidationError is non-exhaustive
+                    _ => "Other",
                 };
                 println!("  {}: {} - {}", description, error_type, e);
             }
@@ -82,24 +87,7 @@ fn main() {
 
     // Example 4: All supported card brands
     println!("Supported card brands:");
-    let brands = [
-        CardBrand::Visa,
-        CardBrand::Mastercard,
-        CardBrand::Amex,
-        CardBrand::Discover,
-        CardBrand::DinersClub,
-        CardBrand::Jcb,
-        CardBrand::UnionPay,
-        CardBrand::Maestro,
-        CardBrand::Mir,
-        CardBrand::RuPay,
-        CardBrand::Verve,
-        CardBrand::Elo,
-        CardBrand::Troy,
-        CardBrand::BcCard,
-    ];
-
-    for brand in brands {
+    for &brand in CardBrand::all() {
         let lengths: Vec<String> = brand
             .valid_lengths()
             .iter()
@@ -2,8 +2,15 @@
 //!
 //! Run with: `cargo run --example generate --features generate`
 
+#[cfg(not(feature = "no-pan-exposure"))]
 use cc_validator::{generate, is_valid, CardBrand};
 
+#[cfg(feature = "no-pan-exposure")]
+fn main() {
+    eprintln!("Test card generation is not available with the `no-pan-exposure` feature");
+}
+
+#[cfg(not(feature = "no-pan-exposure"))]
 fn main() {
     println!("=== Test Card Generation ===\n");
 
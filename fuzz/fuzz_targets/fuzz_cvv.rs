@@ -29,7 +29,8 @@ fuzz_target!(|data: &str| {
 
     // If validation succeeds, test other methods
     if let Ok(validated) = cvv::validate_cvv(data) {
-        let _ = validated.as_str();
+        let _ = validated.as_str_unchecked();
+        let _ = validated.matches(data);
         let _ = validated.length();
         let _ = validated.is_four_digit();
         let _ = validated.digits();
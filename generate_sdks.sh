@@ -0,0 +1,43 @@
+#!/bin/bash
+# Generate client SDKs from the server's OpenAPI document
+#
+# Usage: ./generate_sdks.sh [spec] [languages...]
+#   spec       Path or URL of the OpenAPI document
+#              (default: openapi.json; a running server serves
+#              http://localhost:3000/api-docs/openapi.yaml)
+#   languages  openapi-generator targets (default: typescript-fetch python go)
+#
+# Output goes to sdk/<language>. Requires openapi-generator-cli, either
+# installed (npm install -g @openapitools/openapi-generator-cli) or via Docker.
+
+set -e
+
+cd "$(dirname "$0")"
+
+SPEC="${1:-openapi.json}"
+shift || true
+LANGUAGES=("$@")
+if [ ${#LANGUAGES[@]} -eq 0 ]; then
+    LANGUAGES=(typescript-fetch python go)
+fi
+
+if command -v openapi-generator-cli >/dev/null 2>&1; then
+    generate() { openapi-generator-cli generate -i "$SPEC" -g "$1" -o "sdk/$1"; }
+elif command -v docker >/dev/null 2>&1; then
+    case "$SPEC" in
+        http://*|https://*) INPUT="$SPEC" ;;
+        *) INPUT="/local/$SPEC" ;;
+    esac
+    generate() {
+        docker run --rm --network host -v "$PWD:/local" openapitools/openapi-generator-cli \
+            generate -i "$INPUT" -g "$1" -o "/local/sdk/$1"
+    }
+else
+    echo "openapi-generator-cli or docker is required" >&2
+    exit 1
+fi
+
+for lang in "${LANGUAGES[@]}"; do
+    echo "Generating $lang SDK..."
+    generate "$lang"
+done
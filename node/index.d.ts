@@ -12,18 +12,108 @@
  * ```
  */
 
+/**
+ * Card brand display names, generated from the Rust `CardBrand`.
+ *
+ * @example
+ * ```typescript
+ * switch (validateCard(input).brand) {
+ *   case CardBrand.Amex: // "American Express"
+ *     ...
+ * }
+ * ```
+ */
+export enum CardBrand {
+  Visa = 'Visa',
+  Mastercard = 'Mastercard',
+  Amex = 'American Express',
+  Discover = 'Discover',
+  DinersClub = 'Diners Club',
+  Jcb = 'JCB',
+  UnionPay = 'UnionPay',
+  Maestro = 'Maestro',
+  Mir = 'Mir',
+  RuPay = 'RuPay',
+  Verve = 'Verve',
+  Elo = 'Elo',
+  Troy = 'Troy',
+  BcCard = 'BC Card',
+  Interac = 'Interac',
+  Dankort = 'Dankort',
+  Bancontact = 'Bancontact',
+  Uatp = 'UATP',
+  Wex = 'WEX',
+  Voyager = 'Voyager',
+  /** A brand added to the Rust crate after this binding was written */
+  Other = 'Other'
+}
+
+/** Validation error codes, generated from the Rust `ValidationError` */
+export enum ErrorCode {
+  Empty = 'empty',
+  TooShort = 'too_short',
+  TooLong = 'too_long',
+  InvalidCharacter = 'invalid_character',
+  InvalidChecksum = 'invalid_checksum',
+  InvalidLengthForBrand = 'invalid_length_for_brand',
+  UnknownBrand = 'unknown_brand',
+  NoDigits = 'no_digits',
+  TestCard = 'test_card',
+  BrandNotAllowed = 'brand_not_allowed',
+  NotAcceptedInCountry = 'not_accepted_in_country',
+  /** An error added to the Rust crate after this binding was written */
+  Other = 'other'
+}
+
+/** Non-fatal condition reported in ValidationResult.warnings */
+export type Warning = 'unusual_length' | 'test_card' | 'tentative_brand';
+
 /** Result of card validation */
 export interface ValidationResult {
   /** Whether the card is valid */
   valid: boolean;
-  /** Card brand name (e.g., "Visa", "Mastercard") */
-  brand: string | null;
+  /** Card brand name (e.g., "Visa", "Mastercard"); null if invalid or unknown */
+  brand: CardBrand | null;
+  /** Whether the brand was detected; false for unknown brands accepted by validateAny */
+  brandKnown: boolean | null;
   /** Last 4 digits of the card */
   lastFour: string | null;
   /** Masked card number safe for display */
   masked: string | null;
+  /** Whether the number is a well-known processor test card */
+  isTestCard: boolean | null;
   /** Error message if validation failed */
   error: string | null;
+  /** Machine-readable error code (e.g. "invalid_checksum") */
+  errorCode: ErrorCode | null;
+  /**
+   * Error category: "format", "length", "checksum" and "brand" errors are fixed by
+   * correcting the input, "policy" errors need a different card
+   */
+  errorCategory: string | null;
+  /** Character index (0-based) of the invalid character, for invalid_character errors */
+  errorPosition: number | null;
+  /** Number of digits before the invalid character, for invalid_character errors */
+  errorDigitIndex: number | null;
+  /**
+   * Codes of conditions that don't fail validation but deserve a look;
+   * empty for invalid cards
+   */
+  warnings: Warning[];
+}
+
+/** Details of a supported card brand */
+export interface BrandDetails {
+  /** Identifier accepted wherever a brand is requested (e.g. "amex") */
+  id: string;
+  /** Display name (e.g. "American Express") */
+  name: string;
+  /** Stable numeric id (1 and up), as used in `validateBatchBuffer` results */
+  numericId: number;
+  /** Valid card number lengths */
+  validLengths: number[];
+  /** CVV length (3 or 4; 0 for UATP and fleet cards, which have none) */
+  cvvLength: number;
 }
 
 /** Result of CVV validation */
@@ -36,6 +126,19 @@ export interface CvvResult {
   error: string | null;
 }
 
+/** Result of validateWithCvv */
+export interface CardCvvResult {
+  /** Whether both the card number and the CVV are valid */
+  valid: boolean;
+  /** Result for the card number */
+  card: ValidationResult;
+  /**
+   * Result for the CVV, checked against the card's brand when the card is
+   * valid; length is null for brands without a CVV
+   */
+  cvv: CvvResult;
+}
+
 /** Result of expiry date validation */
 export interface ExpiryResult {
   /** Whether the expiry is valid */
@@ -57,6 +160,7 @@ export interface ExpiryResult {
  *
  * @param cardNumber - Card number (can include spaces or dashes)
  * @returns Validation result with card details
+ * @throws Error if the `CC_VALIDATOR_*` configuration is invalid
  *
  * @example
  * ```javascript
@@ -66,6 +170,19 @@ export interface ExpiryResult {
  */
 export function validateCard(cardNumber: string): ValidationResult;
 
+/**
+ * Validates a credit card number, accepting numbers that match no known
+ * brand as long as they pass the Luhn check.
+ *
+ * Such cards come back with `brand: null` and `brandKnown: false`. With
+ * `CC_VALIDATOR_ALLOWED_BRANDS` set they are rejected as `unknown_brand`,
+ * since their brand can't be checked against the allowlist.
+ *
+ * @param cardNumber - The card number to validate
+ * @returns Validation result
+ */
+export function validateAny(cardNumber: string): ValidationResult;
+
 /**
  * Quick check if a card number is valid.
  *
@@ -88,7 +205,7 @@ export function passesLuhn(cardNumber: string): boolean;
  * @param cardNumber - Card number or prefix
  * @returns Brand name or null if unknown
  */
-export function detectBrand(cardNumber: string): string | null;
+export function detectBrand(cardNumber: string): CardBrand | null;
 
 /**
  * Formats a card number with spaces (brand-aware grouping).
@@ -107,6 +224,31 @@ export function formatCard(cardNumber: string): string;
  */
 export function formatCardWithSeparator(cardNumber: string, separator: string): string;
 
+/**
+ * Options for `formatCardWithOptions`.
+ */
+export interface FormatOptions {
+  /** String placed between digit groups (default: a space) */
+  separator?: string;
+  /**
+   * Wrap the output in a left-to-right isolate (U+2066 ... U+2069) so its
+   * groups keep their order on right-to-left (Arabic, Hebrew) pages
+   */
+  directionalIsolates?: boolean;
+  /** Mask all but the last 4 digits (the card must be valid) */
+  masked?: boolean;
+}
+
+/**
+ * Formats a card number with brand grouping and the given options.
+ *
+ * @param cardNumber - Raw card number
+ * @param options - Separator, directional isolates and masking
+ * @returns Formatted card (e.g., "\u2066•••• •••• •••• 1111\u2069")
+ * @throws Error if masking is requested and the card is invalid
+ */
+export function formatCardWithOptions(cardNumber: string, options?: FormatOptions): string;
+
 /**
  * Removes all formatting from a card number.
  *
@@ -124,16 +266,65 @@ export function stripFormatting(cardNumber: string): string;
  */
 export function maskCard(cardNumber: string): string;
 
+/**
+ * Masks a partly typed card number for debug logging.
+ *
+ * Unlike `maskCard`, the input needn't be valid. At most the first 6
+ * digits are shown; the rest print as `*`.
+ *
+ * @param input - Digits typed so far
+ * @returns Masked prefix, e.g. "411111****"
+ */
+export function maskPartial(input: string): string;
+
+/**
+ * Masks a card number without validating it.
+ *
+ * Non-digits are dropped and all but the last 4 digits are masked, so it
+ * is safe for numbers of unknown quality, e.g. from logs or imports.
+ *
+ * @param input - Card number, valid or not
+ * @returns Masked number, e.g. "****-****-****-1111"
+ */
+export function maskString(input: string): string;
+
+/**
+ * Masks a card number, keeping the BIN visible.
+ *
+ * Shows the first 6 and last 4 digits, fewer for short PANs, as PCI DSS
+ * allows.
+ *
+ * @param cardNumber - Card number to mask
+ * @returns Masked number, e.g. "411111******1111"
+ * @throws Error if card is invalid
+ */
+export function maskWithBin(cardNumber: string): string;
+
+/**
+ * Compares two strings or Buffers in constant time.
+ *
+ * The time taken doesn't depend on where the values differ, so comparing
+ * card numbers, tokens or CVVs this way leaks nothing but their lengths.
+ * Use it instead of `===` or `Buffer.compare` for secrets.
+ *
+ * @param a - First value
+ * @param b - Second value
+ * @returns True if the values are byte-for-byte equal
+ */
+export function constantTimeEquals(a: string | Buffer, b: string | Buffer): boolean;
+
 /**
  * Generates a valid test card number.
  *
- * Supported brands: visa, mastercard, amex, discover, jcb, diners, unionpay, maestro
+ * Accepts any id or name from `supportedBrands()`, and optionally one of
+ * the brand's valid lengths (the brand's usual length if omitted).
  *
  * @param brand - Card brand name
+ * @param length - Card length, e.g. 19 for Visa or 12 for Maestro
  * @returns Valid card number
- * @throws Error if brand is unknown
+ * @throws Error if brand is unknown or length isn't valid for the brand
  */
-export function generateTestCard(brand: string): string;
+export function generateTestCard(brand: string, length?: number): string;
 
 /**
  * Validates a CVV/CVC code.
@@ -152,6 +343,16 @@ export function validateCvv(cvv: string): CvvResult;
  */
 export function validateCvvForBrand(cvv: string, brand: string): CvvResult;
 
+/**
+ * Validates a card number and checks its CVV against the detected brand,
+ * in one call.
+ *
+ * @param cardNumber - The card number to validate
+ * @param cvv - CVV code; empty for brands without one
+ * @returns Both results
+ */
+export function validateWithCvv(cardNumber: string, cvv: string): CardCvvResult;
+
 /**
  * Validates an expiry date (checks if not expired).
  *
@@ -178,11 +379,45 @@ export function parseExpiry(date: string): ExpiryResult;
  */
 export function validateBatch(cardNumbers: string[]): ValidationResult[];
 
+/**
+ * Validates delimited card numbers straight from a Buffer, without
+ * creating a JS string per card.
+ *
+ * The result holds two bytes per record: a status (0 for valid, otherwise
+ * see `batchStatusCode`) and a brand (0 for none, otherwise the brand's
+ * `numericId`). Synchronous and stateless, so it can be
+ * called from `worker_threads`.
+ *
+ * @param buffer - Card numbers separated by `delimiter`
+ * @param delimiter - Single-byte record separator (default "\n")
+ * @returns Buffer of (status, brand) byte pairs
+ * @throws Error if the delimiter is not a single byte, or the `CC_VALIDATOR_*`
+ *   configuration is invalid
+ *
+ * @example
+ * ```javascript
+ * const out = validateBatchBuffer(fs.readFileSync('pans.txt'));
+ * const brands = new Map(supportedBrands().map((b) => [b.numericId, b]));
+ * for (let i = 0; i < out.length; i += 2) {
+ *     console.log(batchStatusCode(out[i]), brands.get(out[i + 1])?.id);
+ * }
+ * ```
+ */
+export function validateBatchBuffer(buffer: Buffer, delimiter?: string): Buffer;
+
+/**
+ * Gets the error code for a `validateBatchBuffer` status byte.
+ *
+ * @param status - Status byte
+ * @returns Code such as "invalid_checksum" ("valid" for 0), or null if unknown
+ */
+export function batchStatusCode(status: number): string | null;
+
 /**
  * Gets the expected CVV length for a card brand.
  *
  * @param brand - Card brand name
- * @returns CVV length (3 or 4)
+ * @returns CVV length (3 or 4; 0 for UATP and fleet cards)
  * @throws Error if brand is unknown
  */
 export function cvvLengthForBrand(brand: string): number;
@@ -195,3 +430,10 @@ export function cvvLengthForBrand(brand: string): number;
  * @throws Error if brand is unknown
  */
 export function validLengthsForBrand(brand: string): number[];
+
+/**
+ * Lists every supported card brand.
+ *
+ * @returns Array of BrandDetails
+ */
+export function supportedBrands(): BrandDetails[];
@@ -119,39 +119,63 @@ if (!nativeBinding) {
 }
 
 const {
+  CardBrand,
+  ErrorCode,
   validateCard,
+  validateAny,
   isValid,
   passesLuhn,
   detectBrand,
   formatCard,
   formatCardWithSeparator,
+  formatCardWithOptions,
   stripFormatting,
   maskCard,
+  maskPartial,
+  maskString,
+  maskWithBin,
+  constantTimeEquals,
   generateTestCard,
   validateCvv,
   validateCvvForBrand,
+  validateWithCvv,
   validateExpiry,
   parseExpiry,
   validateBatch,
+  validateBatchBuffer,
+  batchStatusCode,
   cvvLengthForBrand,
   validLengthsForBrand,
+  supportedBrands,
 } = nativeBinding;
 
 module.exports = {
+  CardBrand,
+  ErrorCode,
   validateCard,
+  validateAny,
   isValid,
   passesLuhn,
   detectBrand,
   formatCard,
   formatCardWithSeparator,
+  formatCardWithOptions,
   stripFormatting,
   maskCard,
+  maskPartial,
+  maskString,
+  maskWithBin,
+  constantTimeEquals,
   generateTestCard,
   validateCvv,
   validateCvvForBrand,
+  validateWithCvv,
   validateExpiry,
   parseExpiry,
   validateBatch,
+  validateBatchBuffer,
+  batchStatusCode,
   cvvLengthForBrand,
   validLengthsForBrand,
+  supportedBrands,
 };
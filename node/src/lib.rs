@@ -33,18 +33,127 @@ use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
 use cc_validator::{
-    validate, is_valid as cc_is_valid, passes_luhn as cc_passes_luhn,
-    CardBrand, detect, format, expiry, cvv, generate,
+    batch, cvv, detect, expiry, format, generate, is_valid as cc_is_valid, mask, partial,
+    passes_luhn as cc_passes_luhn, validate, CardBrand, ValidationError, ValidationOutcome,
+    ValidationWarning,
 };
 
+/// Card brand display names, exported to JS as the `CardBrand` object.
+///
+/// The conversion from `cc_validator::CardBrand` is an exhaustive match, so
+/// a brand added to the core fails to compile here until it is listed.
+#[napi(string_enum, js_name = "CardBrand")]
+pub enum JsCardBrand {
+    Visa,
+    Mastercard,
+    #[napi(value = "American Express")]
+    Amex,
+    Discover,
+    #[napi(value = "Diners Club")]
+    DinersClub,
+    #[napi(value = "JCB")]
+    Jcb,
+    UnionPay,
+    Maestro,
+    Mir,
+    RuPay,
+    Verve,
+    Elo,
+    Troy,
+    #[napi(value = "BC Card")]
+    BcCard,
+    Interac,
+    Dankort,
+    Bancontact,
+    #[napi(value = "UATP")]
+    Uatp,
+    #[napi(value = "WEX")]
+    Wex,
+    Voyager,
+    /// A brand added to the Rust crate after this binding was written.
+    Other,
+}
+
+impl From<CardBrand> for JsCardBrand {
+    fn from(brand: CardBrand) -> Self {
+        match brand {
+            CardBrand::Visa => Self::Visa,
+            CardBrand::Mastercard => Self::Mastercard,
+            CardBrand::Amex => Self::Amex,
+            CardBrand::Discover => Self::Discover,
+            CardBrand::DinersClub => Self::DinersClub,
+            CardBrand::Jcb => Self::Jcb,
+            CardBrand::UnionPay => Self::UnionPay,
+            CardBrand::Maestro => Self::Maestro,
+            CardBrand::Mir => Self::Mir,
+            CardBrand::RuPay => Self::RuPay,
+            CardBrand::Verve => Self::Verve,
+            CardBrand::Elo => Self::Elo,
+            CardBrand::Troy => Self::Troy,
+            CardBrand::BcCard => Self::BcCard,
+            CardBrand::Interac => Self::Interac,
+            CardBrand::Dankort => Self::Dankort,
+            CardBrand::Bancontact => Self::Bancontact,
+            CardBrand::Uatp => Self::Uatp,
+            CardBrand::Wex => Self::Wex,
+            CardBrand::Voyager => Self::Voyager,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Validation error codes, exported to JS as the `ErrorCode` object.
+#[napi(string_enum = "snake_case", js_name = "ErrorCode")]
+pub enum JsErrorCode {
+    Empty,
+    TooShort,
+    TooLong,
+    InvalidCharacter,
+    InvalidChecksum,
+    InvalidLengthForBrand,
+    UnknownBrand,
+    NoDigits,
+    TestCard,
+    BrandNotAllowed,
+    NotAcceptedInCountry,
+    /// An error added to the Rust crate after this binding was written.
+    Other,
+}
+
+impl From<&ValidationError> for JsErrorCode {
+    fn from(error: &ValidationError) -> Self {
+        match error {
+            ValidationError::Empty => Self::Empty,
+            ValidationError::TooShort { .. } => Self::TooShort,
+            ValidationError::TooLong { .. } => Self::TooLong,
+            ValidationError::InvalidCharacter { .. } => Self::InvalidCharacter,
+            ValidationError::InvalidChecksum => Self::InvalidChecksum,
+            ValidationError::InvalidLengthForBrand { .. } => Self::InvalidLengthForBrand,
+            ValidationError::UnknownBrand => Self::UnknownBrand,
+            ValidationError::NoDigits => Self::NoDigits,
+            ValidationError::TestCard => Self::TestCard,
+            ValidationError::BrandNotAllowed { .. } => Self::BrandNotAllowed,
+            ValidationError::NotAcceptedInCountry { .. } => Self::NotAcceptedInCountry,
+            _ => Self::Other,
+        }
+    }
+}
+
 /// Result of card validation.
 #[napi(object)]
 pub struct ValidationResult {
     pub valid: bool,
-    pub brand: Option<String>,
+    pub brand: Option<JsCardBrand>,
+    pub brand_known: Option<bool>,
     pub last_four: Option<String>,
     pub masked: Option<String>,
+    pub is_test_card: Option<bool>,
     pub error: Option<String>,
+    pub error_code: Option<JsErrorCode>,
+    pub error_category: Option<String>,
+    pub error_position: Option<u32>,
+    pub error_digit_index: Option<u32>,
+    pub warnings: Vec<String>,
 }
 
 /// Validates a credit card number.
@@ -53,23 +162,78 @@ pub struct ValidationResult {
 ///
 /// @param cardNumber - The card number to validate
 /// @returns ValidationResult with card details or error
+/// @throws Error if the `CC_VALIDATOR_*` configuration is invalid
 #[napi]
-pub fn validate_card(card_number: String) -> ValidationResult {
-    match validate(&card_number) {
-        Ok(card) => ValidationResult {
-            valid: true,
-            brand: Some(card.brand().name().to_string()),
-            last_four: Some(card.last_four().to_string()),
-            masked: Some(card.masked()),
-            error: None,
-        },
-        Err(e) => ValidationResult {
-            valid: false,
-            brand: None,
-            last_four: None,
-            masked: None,
-            error: Some(e.to_string()),
-        },
+pub fn validate_card(card_number: String) -> Result<ValidationResult> {
+    let result = default_validator()?.validate_with_warnings(&card_number);
+    Ok(validation_result(result))
+}
+
+/// Validates a credit card number, accepting numbers that match no known
+/// brand as long as they pass the Luhn check.
+///
+/// Such cards come back with `brand: null` and `brandKnown: false`. With
+/// `CC_VALIDATOR_ALLOWED_BRANDS` set they are rejected as `unknown_brand`,
+/// since their brand can't be checked against the allowlist.
+///
+/// @param cardNumber - The card number to validate
+/// @returns ValidationResult with card details or error
+#[napi]
+pub fn validate_any(card_number: String) -> Result<ValidationResult> {
+    let result = default_validator()?.validate_any(&card_number);
+    Ok(validation_result(result.map(ValidationOutcome::new)))
+}
+
+/// The process-wide validator, with its configuration error as a JS error.
+fn default_validator() -> Result<&'static cc_validator::Validator> {
+    cc_validator::default_validator().map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+}
+
+fn validation_result(
+    result: std::result::Result<ValidationOutcome, ValidationError>,
+) -> ValidationResult {
+    match result {
+        Ok(ValidationOutcome { card, warnings }) => {
+            let brand_known = !warnings.contains(&ValidationWarning::TentativeBrand);
+            ValidationResult {
+                valid: true,
+                brand: brand_known.then(|| card.brand().into()),
+                brand_known: Some(brand_known),
+                last_four: Some(card.last_four().to_string()),
+                masked: Some(card.masked()),
+                is_test_card: Some(card.is_known_test_card()),
+                error: None,
+                error_code: None,
+                error_category: None,
+                error_position: None,
+                error_digit_index: None,
+                warnings: warnings.iter().map(|w| w.code().to_string()).collect(),
+            }
+        }
+        Err(e) => {
+            let (error_position, error_digit_index) = match e {
+                ValidationError::InvalidCharacter {
+                    position,
+                    digit_index,
+                    ..
+                } => (Some(position as u32), Some(digit_index as u32)),
+                _ => (None, None),
+            };
+            ValidationResult {
+                valid: false,
+                brand: None,
+                brand_known: None,
+                last_four: None,
+                masked: None,
+                is_test_card: None,
+                error: Some(e.to_string()),
+                error_code: Some((&e).into()),
+                error_category: Some(e.category().to_string()),
+                error_position,
+                error_digit_index,
+                warnings: Vec::new(),
+            }
+        }
     }
 }
 
@@ -96,14 +260,14 @@ pub fn passes_luhn(card_number: String) -> bool {
 /// @param cardNumber - The card number or prefix
 /// @returns Brand name or null
 #[napi]
-pub fn detect_brand(card_number: String) -> Option<String> {
+pub fn detect_brand(card_number: String) -> Option<JsCardBrand> {
     let digits: Vec<u8> = card_number
         .chars()
         .filter(|c| c.is_ascii_digit())
         .map(|c| c as u8 - b'0')
         .collect();
 
-    detect::detect_brand(&digits).map(|b| b.name().to_string())
+    detect::detect_brand(&digits).map(JsCardBrand::from)
 }
 
 /// Formats a card number with spaces.
@@ -125,6 +289,51 @@ pub fn format_card_with_separator(card_number: String, separator: String) -> Str
     format::format_with_separator(&card_number, &separator)
 }
 
+/// Options for `formatCardWithOptions`.
+#[napi(object)]
+pub struct FormatOptions {
+    /// String placed between digit groups (default: a space)
+    pub separator: Option<String>,
+    /// Wrap the output in a left-to-right isolate so its groups keep their
+    /// order on right-to-left (Arabic, Hebrew) pages
+    pub directional_isolates: Option<bool>,
+    /// Mask all but the last 4 digits (the card must be valid)
+    pub masked: Option<bool>,
+}
+
+/// Formats a card number with brand grouping and the given options.
+///
+/// @param cardNumber - Raw card number
+/// @param options - Separator, directional isolates and masking
+/// @returns Formatted card number, e.g. "\u2066•••• •••• •••• 1111\u2069"
+/// @throws Error if masking is requested and the card is invalid
+#[napi]
+pub fn format_card_with_options(
+    card_number: String,
+    options: Option<FormatOptions>,
+) -> Result<String> {
+    let options = options.unwrap_or(FormatOptions {
+        separator: None,
+        directional_isolates: None,
+        masked: None,
+    });
+    let separator = options.separator.unwrap_or_else(|| " ".to_string());
+    let directional_isolates = options.directional_isolates.unwrap_or(false);
+
+    if options.masked.unwrap_or(false) {
+        let card =
+            validate(&card_number).map_err(|e| Error::new(Status::InvalidArg, e.to_string()))?;
+        let style = format::MaskedFormat::new()
+            .separator(separator)
+            .directional_isolates(directional_isolates);
+        return Ok(format::format_masked(&card, &style));
+    }
+    let format_options = format::FormatOptions::new()
+        .separator(separator)
+        .directional_isolates(directional_isolates);
+    Ok(format::format_with_options(&card_number, &format_options))
+}
+
 /// Strips all formatting from a card number.
 ///
 /// @param cardNumber - Formatted card number
@@ -146,33 +355,81 @@ pub fn mask_card(card_number: String) -> Result<String> {
     }
 }
 
+/// Masks a partly typed card number for debug logging.
+///
+/// Unlike `maskCard`, the input needn't be valid. At most the first 6
+/// digits are shown; the rest print as `*`.
+///
+/// @param input - Digits typed so far
+/// @returns Masked prefix, e.g. "411111****"
+#[napi]
+pub fn mask_partial(input: String) -> String {
+    partial::PartialCard::new(&input).to_string()
+}
+
+/// Masks a card number without validating it.
+///
+/// Non-digits are dropped and all but the last 4 digits are masked, so it
+/// is safe for numbers of unknown quality, e.g. from logs or imports.
+///
+/// @param input - Card number, valid or not
+/// @returns Masked number, e.g. "****-****-****-1111"
+#[napi]
+pub fn mask_string(input: String) -> String {
+    mask::mask_string(&input)
+}
+
+/// Masks a card number, keeping the BIN visible.
+///
+/// Shows the first 6 and last 4 digits, fewer for short PANs, as PCI DSS
+/// allows.
+///
+/// @param cardNumber - Card number to mask
+/// @returns Masked number, e.g. "411111******1111"
+#[napi]
+pub fn mask_with_bin(card_number: String) -> Result<String> {
+    match validate(&card_number) {
+        Ok(card) => Ok(card.masked_with_bin()),
+        Err(e) => Err(Error::new(Status::InvalidArg, e.to_string())),
+    }
+}
+
+/// Compares two strings or Buffers in constant time.
+///
+/// The time taken doesn't depend on where the values differ, so comparing
+/// card numbers, tokens or CVVs this way leaks nothing but their lengths.
+///
+/// @param a - First value
+/// @param b - Second value
+/// @returns True if the values are byte-for-byte equal
+#[napi]
+pub fn constant_time_equals(a: Either<String, Buffer>, b: Either<String, Buffer>) -> bool {
+    fn bytes(value: &Either<String, Buffer>) -> &[u8] {
+        match value {
+            Either::A(s) => s.as_bytes(),
+            Either::B(buf) => buf,
+        }
+    }
+    mask::constant_time_eq(bytes(&a), bytes(&b))
+}
+
 /// Generates a valid test card number.
 ///
-/// Supported brands: visa, mastercard, amex, discover, jcb, diners, unionpay, maestro
+/// Accepts any id or name from `supportedBrands()`, and optionally one of
+/// the brand's valid lengths (the brand's usual length if omitted).
 ///
 /// @param brand - Card brand name
+/// @param length - Optional card length, e.g. 19 for Visa
 /// @returns Valid card number
 #[napi]
-pub fn generate_test_card(brand: String) -> Result<String> {
-    let card_brand = match brand.to_lowercase().as_str() {
-        "visa" => CardBrand::Visa,
-        "mastercard" | "mc" => CardBrand::Mastercard,
-        "amex" | "american express" => CardBrand::Amex,
-        "discover" => CardBrand::Discover,
-        "jcb" => CardBrand::Jcb,
-        "diners" | "dinersclub" | "diners club" => CardBrand::DinersClub,
-        "unionpay" | "union pay" => CardBrand::UnionPay,
-        "maestro" => CardBrand::Maestro,
-        "mir" => CardBrand::Mir,
-        "rupay" => CardBrand::RuPay,
-        "verve" => CardBrand::Verve,
-        "elo" => CardBrand::Elo,
-        "troy" => CardBrand::Troy,
-        "bccard" | "bc card" => CardBrand::BcCard,
-        _ => return Err(Error::new(Status::InvalidArg, format!("Unknown brand: {}", brand))),
-    };
+pub fn generate_test_card(brand: String, length: Option<u32>) -> Result<String> {
+    let card_brand = parse_brand(&brand)?;
 
-    Ok(generate::generate_card(card_brand))
+    match length {
+        Some(length) => generate::generate_card_with_length(card_brand, length as usize)
+            .map_err(|e| Error::new(Status::InvalidArg, e.to_string())),
+        None => Ok(generate::generate_card(card_brand)),
+    }
 }
 
 /// Result of CVV validation.
@@ -189,10 +446,14 @@ pub struct CvvResult {
 /// @returns CvvResult
 #[napi]
 pub fn validate_cvv(input: String) -> CvvResult {
-    match cvv::validate_cvv(&input) {
+    cvv_result(cvv::validate_cvv(&input).map(Some))
+}
+
+fn cvv_result(result: std::result::Result<Option<cvv::ValidatedCvv>, cvv::CvvError>) -> CvvResult {
+    match result {
         Ok(validated) => CvvResult {
             valid: true,
-            length: Some(validated.length() as u32),
+            length: validated.map(|cvv| cvv.length() as u32),
             error: None,
         },
         Err(e) => CvvResult {
@@ -210,34 +471,39 @@ pub fn validate_cvv(input: String) -> CvvResult {
 /// @returns CvvResult
 #[napi]
 pub fn validate_cvv_for_brand(input: String, brand: String) -> CvvResult {
-    let card_brand = match brand.to_lowercase().as_str() {
-        "visa" => CardBrand::Visa,
-        "mastercard" | "mc" => CardBrand::Mastercard,
-        "amex" | "american express" => CardBrand::Amex,
-        "discover" => CardBrand::Discover,
-        "jcb" => CardBrand::Jcb,
-        "diners" | "dinersclub" => CardBrand::DinersClub,
-        _ => {
-            return CvvResult {
-                valid: false,
-                length: None,
-                error: Some(format!("Unknown brand: {}", brand)),
-            }
-        }
-    };
-
-    match cvv::validate_cvv_for_brand(&input, card_brand) {
-        Ok(validated) => CvvResult {
-            valid: true,
-            length: Some(validated.length() as u32),
-            error: None,
-        },
-        Err(e) => CvvResult {
+    let Some(card_brand) = CardBrand::from_name(&brand) else {
+        return CvvResult {
             valid: false,
             length: None,
-            error: Some(e.to_string()),
-        },
-    }
+            error: Some(format!("Unknown brand: {}", brand)),
+        };
+    };
+
+    cvv_result(cvv::validate_cvv_for_brand(&input, card_brand).map(Some))
+}
+
+/// Result of validating a card number and its CVV together.
+#[napi(object)]
+pub struct CardCvvResult {
+    pub valid: bool,
+    pub card: ValidationResult,
+    pub cvv: CvvResult,
+}
+
+/// Validates a card number and checks its CVV against the detected brand,
+/// in one call.
+///
+/// @param cardNumber - The card number to validate
+/// @param cvv - CVV code; empty for brands without one
+/// @returns CardCvvResult with both results
+#[napi]
+pub fn validate_with_cvv(card_number: String, cvv: String) -> Result<CardCvvResult> {
+    let result = default_validator()?.validate_with_cvv(&card_number, &cvv);
+    Ok(CardCvvResult {
+        valid: result.is_valid(),
+        card: validation_result(result.card.map(ValidationOutcome::new)),
+        cvv: cvv_result(result.cvv),
+    })
 }
 
 /// Result of expiry validation.
@@ -310,11 +576,56 @@ pub fn parse_expiry(date: String) -> ExpiryResult {
 /// @param cardNumbers - Array of card numbers
 /// @returns Array of ValidationResults
 #[napi]
-pub fn validate_batch(card_numbers: Vec<String>) -> Vec<ValidationResult> {
-    card_numbers
-        .into_iter()
-        .map(|card| validate_card(card))
-        .collect()
+pub fn validate_batch(card_numbers: Vec<String>) -> Result<Vec<ValidationResult>> {
+    let validator = default_validator()?;
+    Ok(card_numbers
+        .iter()
+        .map(|card| validation_result(validator.validate_with_warnings(card)))
+        .collect())
+}
+
+/// Batch validates delimited card numbers straight from a Buffer.
+///
+/// No JS string is created per card, which dominates the cost of
+/// `validateBatch` for millions of rows. The result holds two bytes per
+/// record: a status (0 for valid, otherwise see `batchStatusCode`) and a
+/// brand (0 for none, otherwise the brand's `numericId`).
+/// A trailing delimiter is ignored, as is `\r` before a newline.
+///
+/// The call is synchronous and keeps no state between calls, so it can run
+/// inside `worker_threads` to spread a large job over several cores.
+///
+/// @param buffer - Card numbers separated by `delimiter`
+/// @param delimiter - Single-byte record separator (default "\n")
+/// @returns Buffer of (status, brand) byte pairs
+#[napi]
+pub fn validate_batch_buffer(buffer: Buffer, delimiter: Option<String>) -> Result<Buffer> {
+    let delimiter = match delimiter.as_deref().map(str::as_bytes) {
+        None => b'\n',
+        Some(&[byte]) => byte,
+        Some(_) => {
+            return Err(Error::new(
+                Status::InvalidArg,
+                "delimiter must be a single byte".to_string(),
+            ))
+        }
+    };
+
+    batch::validate_compact(&buffer, delimiter)
+        .map(Buffer::from)
+        .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+}
+
+/// Gets the error code for a `validateBatchBuffer` status byte.
+///
+/// @param status - Status byte
+/// @returns Code such as "invalid_checksum" ("valid" for 0), or null
+#[napi]
+pub fn batch_status_code(status: u32) -> Option<String> {
+    u8::try_from(status)
+        .ok()
+        .and_then(batch::compact_status_code)
+        .map(str::to_string)
 }
 
 /// Gets the expected CVV length for a card brand.
@@ -323,15 +634,7 @@ pub fn validate_batch(card_numbers: Vec<String>) -> Vec<ValidationResult> {
 /// @returns CVV length (3 or 4)
 #[napi]
 pub fn cvv_length_for_brand(brand: String) -> Result<u32> {
-    let card_brand = match brand.to_lowercase().as_str() {
-        "visa" => CardBrand::Visa,
-        "mastercard" | "mc" => CardBrand::Mastercard,
-        "amex" | "american express" => CardBrand::Amex,
-        "discover" => CardBrand::Discover,
-        "jcb" => CardBrand::Jcb,
-        "diners" | "dinersclub" => CardBrand::DinersClub,
-        _ => return Err(Error::new(Status::InvalidArg, format!("Unknown brand: {}", brand))),
-    };
+    let card_brand = parse_brand(&brand)?;
 
     Ok(cvv::cvv_length_for_brand(card_brand) as u32)
 }
@@ -342,19 +645,43 @@ pub fn cvv_length_for_brand(brand: String) -> Result<u32> {
 /// @returns Array of valid lengths
 #[napi]
 pub fn valid_lengths_for_brand(brand: String) -> Result<Vec<u32>> {
-    let card_brand = match brand.to_lowercase().as_str() {
-        "visa" => CardBrand::Visa,
-        "mastercard" | "mc" => CardBrand::Mastercard,
-        "amex" | "american express" => CardBrand::Amex,
-        "discover" => CardBrand::Discover,
-        "jcb" => CardBrand::Jcb,
-        "diners" | "dinersclub" => CardBrand::DinersClub,
-        "unionpay" | "union pay" => CardBrand::UnionPay,
-        "maestro" => CardBrand::Maestro,
-        "mir" => CardBrand::Mir,
-        "rupay" => CardBrand::RuPay,
-        _ => return Err(Error::new(Status::InvalidArg, format!("Unknown brand: {}", brand))),
-    };
+    let card_brand = parse_brand(&brand)?;
+
+    Ok(card_brand
+        .valid_lengths()
+        .iter()
+        .map(|&l| l as u32)
+        .collect())
+}
+
+/// Details of a supported card brand.
+#[napi(object)]
+pub struct BrandDetails {
+    pub id: String,
+    pub name: String,
+    pub numeric_id: u32,
+    pub valid_lengths: Vec<u32>,
+    pub cvv_length: u32,
+}
+
+/// Lists every supported card brand.
+///
+/// @returns Array of BrandDetails
+#[napi]
+pub fn supported_brands() -> Vec<BrandDetails> {
+    CardBrand::all()
+        .iter()
+        .map(|b| BrandDetails {
+            id: b.id().to_string(),
+            name: b.name().to_string(),
+            numeric_id: u32::from(b.numeric_id()),
+            valid_lengths: b.valid_lengths().iter().map(|&l| l as u32).collect(),
+            cvv_length: b.cvv_length() as u32,
+        })
+        .collect()
+}
 
-    Ok(card_brand.valid_lengths().iter().map(|&l| l as u32).collect())
+fn parse_brand(brand: &str) -> Result<CardBrand> {
+    CardBrand::from_name(brand)
+        .ok_or_else(|| Error::new(Status::InvalidArg, format!("Unknown brand: {}", brand)))
 }
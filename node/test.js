@@ -2,25 +2,40 @@
  * Simple test script for cc-validator Node.js bindings.
  *
  * Run: npm run build && node test.js
+ *
+ * Set CC_VALIDATOR_CORPUS to a file written by `ccvalidator corpus` to also
+ * check every vector in it against the Rust core's expected results.
  */
 
 const {
+  CardBrand,
+  ErrorCode,
   validateCard,
+  validateAny,
   isValid,
   passesLuhn,
   detectBrand,
   formatCard,
   formatCardWithSeparator,
+  formatCardWithOptions,
   stripFormatting,
   maskCard,
+  maskPartial,
+  maskString,
+  maskWithBin,
+  constantTimeEquals,
   generateTestCard,
   validateCvv,
   validateCvvForBrand,
+  validateWithCvv,
   validateExpiry,
   parseExpiry,
   validateBatch,
+  validateBatchBuffer,
+  batchStatusCode,
   cvvLengthForBrand,
   validLengthsForBrand,
+  supportedBrands,
 } = require('./index.js');
 
 console.log('Testing cc-validator Node.js bindings\n');
@@ -31,10 +46,32 @@ const result = validateCard('4111-1111-1111-1111');
 console.log('validateCard("4111-1111-1111-1111"):', result);
 console.assert(result.valid === true, 'Should be valid');
 console.assert(result.brand === 'Visa', 'Should be Visa');
+console.assert(result.warnings.join() === 'test_card', 'Should warn about the test card');
 
 const invalid = validateCard('4111111111111112');
 console.log('validateCard("4111111111111112"):', invalid);
 console.assert(invalid.valid === false, 'Should be invalid');
+console.assert(invalid.errorCode === 'invalid_checksum', 'Should report the error code');
+console.assert(invalid.errorCategory === 'checksum', 'Should report the error category');
+console.assert(invalid.warnings.length === 0, 'Should have no warnings');
+
+// Test validateAny
+console.log('\n=== validateAny ===');
+const unknown = validateAny('9000000000000001');
+console.log('validateAny("9000000000000001"):', unknown);
+console.assert(unknown.valid === true, 'Luhn-valid unknown brand should be accepted');
+console.assert(unknown.brand == null && unknown.brandKnown === false, 'Unknown brand should not be reported as Visa');
+console.assert(validateCard('9000000000000001').valid === false, 'validateCard should reject unknown brands');
+console.assert(validateAny('4111111111111111').brandKnown === true, 'Known brand should be reported');
+
+// Test the CardBrand and ErrorCode enums
+console.log('\n=== enums ===');
+console.assert(result.brand === CardBrand.Visa, 'Brand should match the enum');
+console.assert(invalid.errorCode === ErrorCode.InvalidChecksum, 'Code should match the enum');
+console.assert(
+  JSON.stringify(Object.values(CardBrand)) === JSON.stringify(supportedBrands().map((b) => b.name)),
+  'CardBrand should list every supported brand'
+);
 
 // Test isValid
 console.log('\n=== isValid ===');
@@ -65,6 +102,24 @@ console.log('formatCard("378282246310005"):', formatCard('378282246310005'));
 console.log('\n=== formatCardWithSeparator ===');
 console.log('formatCardWithSeparator("4111111111111111", "-"):', formatCardWithSeparator('4111111111111111', '-'));
 
+// Test formatCardWithOptions
+console.log('\n=== formatCardWithOptions ===');
+const rtlFormatted = formatCardWithOptions('4111111111111111', { directionalIsolates: true });
+console.log('formatCardWithOptions(..., { directionalIsolates: true }):', JSON.stringify(rtlFormatted));
+console.assert(rtlFormatted === '\u20664111 1111 1111 1111\u2069', 'Should wrap in a left-to-right isolate');
+console.assert(
+  formatCardWithOptions('378282246310005', { separator: '-', masked: true }) === '\u2022\u2022\u2022\u2022-\u2022\u2022\u2022\u2022\u2022\u2022-\u20220005',
+  'Should mask and group for Amex'
+);
+console.assert(formatCardWithOptions('4111111111111111') === formatCard('4111111111111111'), 'Defaults match formatCard');
+let formatThrew = false;
+try {
+  formatCardWithOptions('4111111111111112', { masked: true });
+} catch (e) {
+  formatThrew = true;
+}
+console.assert(formatThrew, 'Masking an invalid card should throw');
+
 // Test stripFormatting
 console.log('\n=== stripFormatting ===');
 console.log('stripFormatting("4111 1111 1111 1111"):', stripFormatting('4111 1111 1111 1111'));
@@ -74,6 +129,31 @@ console.assert(stripFormatting('4111-1111-1111-1111') === '4111111111111111', 'S
 console.log('\n=== maskCard ===');
 console.log('maskCard("4111111111111111"):', maskCard('4111111111111111'));
 
+// Test maskPartial
+console.log('\n=== maskPartial ===');
+console.log('maskPartial("4111 1111 11"):', maskPartial('4111 1111 11'));
+console.assert(maskPartial('4111 1111 11') === '411111****', 'Should mask past the BIN');
+
+// Test maskString and maskWithBin
+console.log('\n=== maskString / maskWithBin ===');
+console.log('maskString("4111-1111-1111-1112"):', maskString('4111-1111-1111-1112'));
+console.assert(maskString('4111-1111-1111-1112') === '****-****-****-1112', 'Should mask without validating');
+console.assert(maskWithBin('4111 1111 1111 1111') === '411111******1111', 'Should keep the BIN');
+let maskThrew = false;
+try {
+  maskWithBin('4111111111111112');
+} catch (e) {
+  maskThrew = true;
+}
+console.assert(maskThrew, 'Should throw for invalid cards');
+
+// Test constantTimeEquals
+console.log('\n=== constantTimeEquals ===');
+console.assert(constantTimeEquals('4111111111111111', '4111111111111111'), 'Equal strings should match');
+console.assert(!constantTimeEquals('4111111111111111', '4111111111111112'), 'Different strings should not match');
+console.assert(!constantTimeEquals('123', '1234'), 'Different lengths should not match');
+console.assert(constantTimeEquals(Buffer.from('tok_1'), 'tok_1'), 'Buffers and strings should compare by bytes');
+
 // Test generateTestCard
 console.log('\n=== generateTestCard ===');
 const visaCard = generateTestCard('visa');
@@ -85,6 +165,18 @@ const amexCard = generateTestCard('amex');
 console.log('generateTestCard("amex"):', amexCard);
 console.assert(detectBrand(amexCard) === 'American Express', 'Should be Amex');
 
+const longVisa = generateTestCard('visa', 19);
+console.assert(longVisa.length === 19 && isValid(longVisa), '19-digit Visa should be valid');
+const discover = generateTestCard('discover', 17);
+console.assert(discover.length === 17 && isValid(discover), '17-digit Discover should be valid');
+let generateThrew = false;
+try {
+  generateTestCard('amex', 16);
+} catch (e) {
+  generateThrew = true;
+}
+console.assert(generateThrew, 'Invalid length should throw');
+
 // Test validateCvv
 console.log('\n=== validateCvv ===');
 console.log('validateCvv("123"):', validateCvv('123'));
@@ -100,6 +192,15 @@ console.log('validateCvvForBrand("123", "visa"):', validateCvvForBrand('123', 'v
 console.assert(validateCvvForBrand('1234', 'amex').valid === true, 'Amex 4-digit CVV should be valid');
 console.assert(validateCvvForBrand('123', 'amex').valid === false, 'Amex 3-digit CVV should be invalid');
 
+// Test validateWithCvv
+console.log('\n=== validateWithCvv ===');
+const combined = validateWithCvv('4111111111111111', '1234');
+console.log('validateWithCvv("4111111111111111", "1234"):', combined);
+console.assert(combined.valid === false, 'Visa with a 4-digit CVV should be invalid');
+console.assert(combined.card.valid === true, 'Card number should still be valid');
+console.assert(combined.cvv.valid === false, 'CVV should fail for Visa');
+console.assert(validateWithCvv('378282246310005', '1234').valid === true, 'Amex with a 4-digit CVV should be valid');
+
 // Test validateExpiry
 console.log('\n=== validateExpiry ===');
 console.log('validateExpiry("12/30"):', validateExpiry('12/30'));
@@ -127,6 +228,20 @@ console.assert(batchResults.length === 4, 'Should have 4 results');
 console.assert(batchResults[0].valid === true, 'First should be valid');
 console.assert(batchResults[2].valid === false, 'Third should be invalid');
 
+// Test validateBatchBuffer
+console.log('\n=== validateBatchBuffer ===');
+const compact = validateBatchBuffer(Buffer.from('4111111111111111\r\n4111111111111112\n378282246310005\n'));
+console.log('validateBatchBuffer result:', compact);
+console.assert(compact.length === 6, 'Should have 2 bytes per card');
+console.assert(compact[0] === 0 && compact[1] > 0, 'First should be valid with a brand');
+console.assert(
+  supportedBrands().find((b) => b.numericId === compact[1]).id === 'visa',
+  'Brand byte should be the numeric id'
+);
+console.assert(batchStatusCode(compact[2]) === 'invalid_checksum', 'Second should fail Luhn');
+const commas = validateBatchBuffer(Buffer.from('4111111111111111,5500000000000004'), ',');
+console.assert(commas.length === 4 && commas[0] === 0 && commas[2] === 0, 'Custom delimiter');
+
 // Test cvvLengthForBrand
 console.log('\n=== cvvLengthForBrand ===');
 console.log('cvvLengthForBrand("amex"):', cvvLengthForBrand('amex'));
@@ -139,4 +254,18 @@ console.log('\n=== validLengthsForBrand ===');
 console.log('validLengthsForBrand("visa"):', validLengthsForBrand('visa'));
 console.log('validLengthsForBrand("amex"):', validLengthsForBrand('amex'));
 
+// Shared test-vector corpus
+if (process.env.CC_VALIDATOR_CORPUS) {
+  console.log('\n=== corpus ===');
+  const corpus = JSON.parse(require('fs').readFileSync(process.env.CC_VALIDATOR_CORPUS, 'utf8'));
+  for (const vector of corpus.vectors) {
+    const r = validateCard(vector.input);
+    const ok = r.valid === vector.valid
+      && (r.brand ?? null) === vector.brand
+      && (r.errorCode ?? null) === vector.error;
+    console.assert(ok, `Corpus mismatch for ${vector.variant} ${vector.input}`);
+  }
+  console.log(`checked ${corpus.vectors.length} vectors (seed ${corpus.seed})`);
+}
+
 console.log('\n\x1b[32mAll tests passed!\x1b[0m');
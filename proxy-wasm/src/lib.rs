@@ -0,0 +1,176 @@
+//! Proxy-WASM HTTP filter that masks card numbers in flight.
+//!
+//! Buffers each inspected request and response body, runs it through
+//! [`GatewayConfig::redact_body`] and forwards the redacted body, so an
+//! Envoy (or Istio, or any Proxy-WASM host) gateway keeps PANs away from
+//! upstreams and clients without an application change. Which bodies are
+//! inspected is set by the plugin configuration, the JSON form of
+//! [`GatewayConfig`]; an empty configuration uses the defaults.
+//!
+//! # Build
+//!
+//! ```bash
+//! cargo build --release --target wasm32-wasip1
+//! # Filter at target/wasm32-wasip1/release/cc_validator_proxy_wasm.wasm
+//! ```
+//!
+//! # Envoy
+//!
+//! ```yaml
+//! http_filters:
+//!   - name: envoy.filters.http.wasm
+//!     typed_config:
+//!       "@type": type.googleapis.com/envoy.extensions.filters.http.wasm.v3.Wasm
+//!       config:
+//!         name: pan_redaction
+//!         configuration:
+//!           "@type": type.googleapis.com/google.protobuf.StringValue
+//!           value: |
+//!             {"paths": ["/api/"], "responses": false, "reject_oversized": true}
+//!         vm_config:
+//!           runtime: envoy.wasm.runtime.v8
+//!           code:
+//!             local:
+//!               filename: /etc/envoy/cc_validator_proxy_wasm.wasm
+//!   - name: envoy.filters.http.router
+//! ```
+
+use cc_validator::gateway::GatewayConfig;
+use proxy_wasm::traits::{Context, HttpContext, RootContext};
+use proxy_wasm::types::{Action, ContextType, LogLevel};
+use std::rc::Rc;
+
+proxy_wasm::main! {{
+    proxy_wasm::set_log_level(LogLevel::Info);
+    proxy_wasm::set_root_context(|_| -> Box<dyn RootContext> {
+        Box::new(RedactionRoot {
+            config: Rc::new(GatewayConfig::default()),
+        })
+    });
+}}
+
+/// Holds the parsed plugin configuration shared by every stream.
+struct RedactionRoot {
+    config: Rc<GatewayConfig>,
+}
+
+impl Context for RedactionRoot {}
+
+impl RootContext for RedactionRoot {
+    fn on_configure(&mut self, _plugin_configuration_size: usize) -> bool {
+        let Some(json) = self.get_plugin_configuration().filter(|c| !c.is_empty()) else {
+            return true;
+        };
+        match GatewayConfig::from_json(&json) {
+            Ok(config) => {
+                self.config = Rc::new(config);
+                true
+            }
+            Err(e) => {
+                log::error!("invalid pan_redaction configuration: {}", e);
+                false
+            }
+        }
+    }
+
+    fn create_http_context(&self, _context_id: u32) -> Option<Box<dyn HttpContext>> {
+        Some(Box::new(RedactionFilter {
+            config: Rc::clone(&self.config),
+            path: String::new(),
+            request: false,
+            response: false,
+        }))
+    }
+
+    fn get_type(&self) -> Option<ContextType> {
+        Some(ContextType::HttpContext)
+    }
+}
+
+/// One HTTP stream; remembers which of its bodies to redact.
+struct RedactionFilter {
+    config: Rc<GatewayConfig>,
+    path: String,
+    request: bool,
+    response: bool,
+}
+
+impl RedactionFilter {
+    /// Handles a body over `max_body_bytes`: rejects it with `status`, or
+    /// lets it through unredacted.
+    fn oversized(&self, status: u32) -> Action {
+        if self.config.reject_oversized {
+            self.send_http_response(status, vec![], None);
+            return Action::Pause;
+        }
+        log::warn!("{}: body over max_body_bytes passed unredacted", self.path);
+        Action::Continue
+    }
+}
+
+impl Context for RedactionFilter {}
+
+impl HttpContext for RedactionFilter {
+    fn on_http_request_headers(&mut self, _num_headers: usize, end_of_stream: bool) -> Action {
+        self.path = self.get_http_request_header(":path").unwrap_or_default();
+        self.request = self.config.requests
+            && !end_of_stream
+            && self.config.inspects(
+                &self.path,
+                self.get_http_request_header("content-type").as_deref(),
+                self.get_http_request_header("content-encoding").as_deref(),
+            );
+        Action::Continue
+    }
+
+    fn on_http_request_body(&mut self, body_size: usize, end_of_stream: bool) -> Action {
+        if !self.request {
+            return Action::Continue;
+        }
+        if !self.config.fits(body_size) {
+            self.request = false;
+            return self.oversized(413);
+        }
+        if !end_of_stream {
+            // Let the host buffer the rest of the body
+            return Action::Pause;
+        }
+        if let Some(body) = self.get_http_request_body(0, body_size) {
+            if let Some(redacted) = self.config.redact_body(&body) {
+                self.set_http_request_body(0, body_size, &redacted);
+            }
+        }
+        Action::Continue
+    }
+
+    fn on_http_response_headers(&mut self, _num_headers: usize, end_of_stream: bool) -> Action {
+        self.response = self.config.responses
+            && !end_of_stream
+            && self.config.inspects(
+                &self.path,
+                self.get_http_response_header("content-type").as_deref(),
+                self.get_http_response_header("content-encoding").as_deref(),
+            );
+        Action::Continue
+    }
+
+    fn on_http_response_body(&mut self, body_size: usize, end_of_stream: bool) -> Action {
+        if !self.response {
+            return Action::Continue;
+        }
+        if !self.config.fits(body_size) {
+            self.response = false;
+            return self.oversized(502);
+        }
+        if !end_of_stream {
+            // Let the host buffer the rest of the body
+            return Action::Pause;
+        }
+        if let Some(body) = self.get_http_response_body(0, body_size) {
+            if let Some(redacted) = self.config.redact_body(&body) {
+                self.set_http_response_body(0, body_size, &redacted);
+            }
+        }
+        Action::Continue
+    }
+}
@@ -0,0 +1,130 @@
+//! Where card brands are accepted.
+//!
+//! Domestic-only schemes (see [`CardBrand::home_country`]) are usually
+//! declined by acquirers outside their home country. An
+//! [`AcceptanceMatrix`] starts from those rules and lets a gateway adjust
+//! them, e.g. to accept RuPay abroad through a partner network or to stop
+//! accepting a brand in one market.
+//!
+//! [`Validator::with_merchant_country`](crate::Validator::with_merchant_country)
+//! applies a matrix during validation, so unsupported cards are rejected
+//! before they reach the processor.
+//!
+//! # Example
+//!
+//! ```
+//! use cc_validator::acceptance::AcceptanceMatrix;
+//! use cc_validator::CardBrand;
+//!
+//! let matrix = AcceptanceMatrix::new()
+//!     .allow(CardBrand::RuPay, "AE")
+//!     .restrict(CardBrand::Amex, ["US", "CA"]);
+//!
+//! assert!(matrix.is_accepted(CardBrand::RuPay, "ae"));
+//! assert!(!matrix.is_accepted(CardBrand::Mir, "AE"));
+//! assert!(!matrix.is_accepted(CardBrand::Amex, "DE"));
+//! assert!(matrix.is_accepted(CardBrand::Visa, "DE"));
+//! ```
+
+use crate::CardBrand;
+use std::collections::HashMap;
+
+/// Per-brand acceptance rules keyed by ISO 3166-1 alpha-2 country code.
+///
+/// Brands without an override follow [`CardBrand::is_accepted_in`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AcceptanceMatrix {
+    /// `None` means accepted everywhere; otherwise the accepted countries.
+    overrides: HashMap<CardBrand, Option<Vec<String>>>,
+}
+
+impl AcceptanceMatrix {
+    /// Creates a matrix with the default rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also accepts `brand` in `country`.
+    ///
+    /// Has no effect if the brand is already accepted everywhere.
+    pub fn allow(mut self, brand: CardBrand, country: &str) -> Self {
+        if let Some(countries) = self.entry(brand) {
+            let country = normalize(country);
+            if !countries.contains(&country) {
+                countries.push(country);
+            }
+        }
+        self
+    }
+
+    /// Accepts `brand` in every country.
+    pub fn allow_everywhere(mut self, brand: CardBrand) -> Self {
+        self.overrides.insert(brand, None);
+        self
+    }
+
+    /// Accepts `brand` only in `countries` (an empty list accepts it nowhere).
+    pub fn restrict<I, S>(mut self, brand: CardBrand, countries: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let countries = countries.into_iter().map(|c| normalize(c.as_ref()));
+        self.overrides.insert(brand, Some(countries.collect()));
+        self
+    }
+
+    /// Returns true if `brand` is accepted in `country` (either case).
+    pub fn is_accepted(&self, brand: CardBrand, country: &str) -> bool {
+        match self.overrides.get(&brand) {
+            Some(Some(countries)) => countries.contains(&normalize(country)),
+            Some(None) => true,
+            None => brand.is_accepted_in(country),
+        }
+    }
+
+    /// Returns the brand's country list, seeding it from the default rules.
+    fn entry(&mut self, brand: CardBrand) -> Option<&mut Vec<String>> {
+        self.overrides
+            .entry(brand)
+            .or_insert_with(|| brand.home_country().map(|home| vec![home.to_string()]))
+            .as_mut()
+    }
+}
+
+fn normalize(country: &str) -> String {
+    country.trim().to_ascii_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_rules() {
+        let matrix = AcceptanceMatrix::new();
+        for &brand in CardBrand::all() {
+            assert_eq!(matrix.is_accepted(brand, "US"), !brand.is_domestic_only());
+            if let Some(home) = brand.home_country() {
+                assert!(matrix.is_accepted(brand, home));
+            }
+        }
+        assert!(matrix.is_accepted(CardBrand::Elo, " br "));
+    }
+
+    #[test]
+    fn test_overrides() {
+        let matrix = AcceptanceMatrix::new()
+            .allow(CardBrand::Troy, "az")
+            .allow(CardBrand::Visa, "KP")
+            .allow_everywhere(CardBrand::Verve)
+            .restrict(CardBrand::Mastercard, Vec::<String>::new());
+
+        assert!(matrix.is_accepted(CardBrand::Troy, "TR"));
+        assert!(matrix.is_accepted(CardBrand::Troy, "AZ"));
+        assert!(!matrix.is_accepted(CardBrand::Troy, "DE"));
+        assert!(matrix.is_accepted(CardBrand::Visa, "FR"));
+        assert!(matrix.is_accepted(CardBrand::Verve, "GB"));
+        assert!(!matrix.is_accepted(CardBrand::Mastercard, "US"));
+    }
+}
@@ -8,15 +8,288 @@
 //! - Pre-allocated buffers avoid per-card allocation overhead
 //! - Optional parallel processing with the `parallel` feature
 //! - Process millions of cards per second on modern hardware
+//!
+//! # Progress and metrics
+//!
+//! Attach a [`BatchObserver`] with [`BatchValidator::with_observer`] to be
+//! told about every card and every finished chunk (one call to a
+//! `validate_*` method, or one [`BatchValidator::validate_chunk`]), e.g. to
+//! drive a progress bar or export throughput metrics.
+//!
+//! # Clustered batches
+//!
+//! Settlement files and exports are often sorted or grouped by BIN, so the
+//! same prefixes come up again and again. [`BatchValidator`] remembers the
+//! brand and BIN lookup of recent prefixes for the length of one call, so
+//! each distinct prefix is detected and looked up about once per batch.
 
+use crate::bin::BinInfo;
+use crate::detect::{detect_brand, LONG_PREFIX_DIGITS};
 use crate::error::ValidationError;
+#[cfg(feature = "fingerprint")]
+use crate::fingerprint::Fingerprint;
+use crate::output::{ResultRow, ResultWriter};
+use crate::profile::CardProfile;
+#[cfg(feature = "parallel")]
 use crate::validate::validate;
-use crate::ValidatedCard;
+use crate::validator::{ConfigError, Validator};
+use crate::{CardBrand, ValidatedCard, Workspace};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, BufRead, Seek, SeekFrom};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use zeroize::Zeroize;
+
+/// Receives progress from a [`BatchValidator`].
+///
+/// Both methods default to doing nothing. They are called on the
+/// validating thread (rayon worker threads for the parallel methods), so
+/// keep them cheap: bump a counter or an atomic rather than doing I/O per
+/// item.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::batch::{BatchObserver, ChunkStats};
+/// use cc_validator::BatchValidator;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// #[derive(Default)]
+/// struct Progress(AtomicUsize);
+///
+/// impl BatchObserver for Progress {
+///     fn on_chunk_complete(&self, stats: &ChunkStats) {
+///         self.0.fetch_add(stats.items, Ordering::Relaxed);
+///     }
+/// }
+///
+/// let progress = Arc::new(Progress::default());
+/// let mut batch = BatchValidator::new().with_observer(progress.clone());
+/// batch.validate_all(&["4111111111111111", "1234"]);
+/// assert_eq!(progress.0.load(Ordering::Relaxed), 2);
+/// ```
+pub trait BatchObserver: Send + Sync {
+    /// Called after each card is validated, with its index in the input
+    /// (its 0-based line number for [`BatchValidator::validate_chunk`]).
+    fn on_item(&self, index: usize, result: &Result<ValidatedCard, ValidationError>) {
+        let _ = (index, result);
+    }
+
+    /// Called when a chunk of cards has been validated.
+    fn on_chunk_complete(&self, stats: &ChunkStats) {
+        let _ = stats;
+    }
+}
+
+/// Counts and timing for one chunk, passed to
+/// [`BatchObserver::on_chunk_complete`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChunkStats {
+    /// Cards validated in the chunk.
+    pub items: usize,
+    /// Cards that passed.
+    pub valid: usize,
+    /// Cards that failed.
+    pub invalid: usize,
+    /// Wall-clock time spent on the chunk.
+    pub elapsed: Duration,
+}
+
+impl ChunkStats {
+    /// Returns the chunk's throughput in cards per second (0 if no time
+    /// was measured).
+    pub fn items_per_sec(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 {
+            self.items as f64 / secs
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Running counts for the chunk in progress.
+struct ChunkRun {
+    start: Instant,
+    valid: usize,
+    invalid: usize,
+}
+
+impl ChunkRun {
+    fn start() -> Self {
+        Self {
+            start: Instant::now(),
+            valid: 0,
+            invalid: 0,
+        }
+    }
+}
+
+/// Number of slots in each [`PrefixMemo`] table.
+const MEMO_SLOTS: usize = 64;
+
+/// Digits a BIN lookup is keyed on (see [`Validator::lookup_bin`]).
+const BIN_KEY_DIGITS: usize = 8;
+
+/// Direct-mapped memo of brand detection and BIN lookups, keyed by prefix.
+///
+/// Each prefix maps to one slot, so a lookup is a division and a compare.
+/// That keeps misses cheap on shuffled input while catching nearly every
+/// repeat in sorted or clustered batches.
+struct PrefixMemo {
+    brands: [Option<(u32, Option<CardBrand>)>; MEMO_SLOTS],
+    bins: Vec<Option<(u32, Option<Arc<BinInfo>>)>>,
+}
+
+impl Default for PrefixMemo {
+    fn default() -> Self {
+        Self {
+            brands: [None; MEMO_SLOTS],
+            bins: Vec::new(),
+        }
+    }
+}
+
+impl PrefixMemo {
+    /// Forgets everything, so a new batch sees fresh BIN data.
+    fn clear(&mut self) {
+        self.brands = [None; MEMO_SLOTS];
+        self.bins.clear();
+    }
+
+    /// Detects the brand of `digits`, reusing the result for its prefix.
+    fn brand(&mut self, digits: &[u8]) -> Option<CardBrand> {
+        let Some(key) = prefix_key(digits, LONG_PREFIX_DIGITS) else {
+            return detect_brand(digits);
+        };
+        let slot = &mut self.brands[key as usize % MEMO_SLOTS];
+        match *slot {
+            Some((cached, brand)) if cached == key => brand,
+            _ => {
+                let brand = detect_brand(digits);
+                *slot = Some((key, brand));
+                brand
+            }
+        }
+    }
+
+    /// Looks up `card` in `validator`'s BIN database, reusing the result
+    /// for its prefix.
+    fn bin(&mut self, validator: &Validator, card: &ValidatedCard) -> Option<Arc<BinInfo>> {
+        let Some(key) = prefix_key(card.digits(), BIN_KEY_DIGITS) else {
+            return validator.lookup_bin(card).map(Arc::new);
+        };
+        if self.bins.is_empty() {
+            self.bins.resize(MEMO_SLOTS, None);
+        }
+        let slot = &mut self.bins[key as usize % MEMO_SLOTS];
+        match slot {
+            Some((cached, info)) if *cached == key => info.clone(),
+            _ => {
+                let info = validator.lookup_bin(card).map(Arc::new);
+                *slot = Some((key, info.clone()));
+                info
+            }
+        }
+    }
+}
+
+/// Reads the first `len` digits as a number, if there are that many.
+fn prefix_key(digits: &[u8], len: usize) -> Option<u32> {
+    let head = digits.get(..len)?;
+    Some(head.iter().fold(0, |key, &d| key * 10 + u32::from(d)))
+}
+
+/// Pass/fail results of a batch as a bitmap, plus the error code of each
+/// failure.
+///
+/// Returned by [`BatchValidator::validate_compact`] and
+/// [`BatchValidator::validate_compact_bytes`] for callers that only need
+/// statistics: a million results take 125 KB of bitmap plus one entry per
+/// invalid card.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompactResults {
+    bits: Vec<u64>,
+    len: usize,
+    errors: Vec<(usize, &'static str)>,
+}
+
+impl CompactResults {
+    fn push(&mut self, result: Result<ValidatedCard, ValidationError>) {
+        if self.len.is_multiple_of(64) {
+            self.bits.push(0);
+        }
+        match result {
+            Ok(_) => self.bits[self.len / 64] |= 1 << (self.len % 64),
+            Err(e) => self.errors.push((self.len, e.code())),
+        }
+        self.len += 1;
+    }
+
+    /// Returns the number of cards checked.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the batch was empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns whether the card at `index` is valid, or `None` past the end.
+    pub fn get(&self, index: usize) -> Option<bool> {
+        (index < self.len).then(|| self.bits[index / 64] & (1 << (index % 64)) != 0)
+    }
+
+    /// Returns the number of valid cards.
+    pub fn valid_count(&self) -> usize {
+        self.len - self.errors.len()
+    }
+
+    /// Returns the number of invalid cards.
+    pub fn invalid_count(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns the [error code](ValidationError::code) of the card at
+    /// `index`, or `None` if it is valid or past the end.
+    pub fn error_code(&self, index: usize) -> Option<&'static str> {
+        let i = self.errors.binary_search_by_key(&index, |&(i, _)| i).ok()?;
+        Some(self.errors[i].1)
+    }
+
+    /// Returns `(index, error code)` for every invalid card, in input order.
+    pub fn errors(&self) -> &[(usize, &'static str)] {
+        &self.errors
+    }
+
+    /// Returns the bitmap: bit `i % 64` of word `i / 64` is set if card `i`
+    /// is valid. Bits past [`len`](Self::len) are zero.
+    pub fn words(&self) -> &[u64] {
+        &self.bits
+    }
+}
+
+/// A distinct card found by [`BatchValidator::validate_unique`].
+#[cfg(feature = "fingerprint")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UniqueCard {
+    /// Keyed fingerprint identifying the card.
+    pub fingerprint: Fingerprint,
+    /// Detected card brand.
+    pub brand: CardBrand,
+    /// Index of the card's first occurrence in the input.
+    pub first_index: usize,
+    /// Number of times the card occurs in the input.
+    pub count: usize,
+}
 
 /// Batch validator for processing multiple card numbers efficiently.
 ///
-/// The batch validator maintains internal state optimized for processing
-/// many cards in sequence.
+/// The batch validator keeps a [`Workspace`] so sequential validation
+/// reuses one digit buffer for the whole batch.
 ///
 /// # Example
 ///
@@ -34,10 +307,22 @@ use crate::ValidatedCard;
 ///     }
 /// }
 /// ```
-#[derive(Debug, Default)]
+#[derive(Default)]
 pub struct BatchValidator {
-    // Reserved for future optimizations (e.g., thread-local buffers)
-    _private: (),
+    validator: Validator,
+    workspace: Workspace,
+    observer: Option<Arc<dyn BatchObserver>>,
+    memo: PrefixMemo,
+}
+
+impl fmt::Debug for BatchValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchValidator")
+            .field("validator", &self.validator)
+            .field("workspace", &self.workspace)
+            .field("observer", &self.observer.is_some())
+            .finish()
+    }
 }
 
 impl BatchValidator {
@@ -47,6 +332,78 @@ impl BatchValidator {
         Self::default()
     }
 
+    /// Creates a batch validator that applies `validator`'s brand policy
+    /// and strictness to every card.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::{BatchValidator, CardBrand, Validator};
+    ///
+    /// let validator = Validator::new().with_allowed_brands([CardBrand::Visa]);
+    /// let mut batch = BatchValidator::with_validator(validator);
+    /// let results = batch.validate_all(&["4111111111111111", "5500000000000004"]);
+    /// assert!(results[0].is_ok());
+    /// assert!(results[1].is_err());
+    /// ```
+    #[inline]
+    pub fn with_validator(validator: Validator) -> Self {
+        Self {
+            validator,
+            workspace: Workspace::new(),
+            observer: None,
+            memo: PrefixMemo::default(),
+        }
+    }
+
+    /// Reports progress to `observer` (see [`BatchObserver`]).
+    pub fn with_observer(mut self, observer: Arc<dyn BatchObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Starts a chunk with an empty prefix memo.
+    fn start(&mut self) -> ChunkRun {
+        self.memo.clear();
+        ChunkRun::start()
+    }
+
+    /// Validates one card, counting it in `run` and reporting it.
+    fn check(
+        &mut self,
+        run: &mut ChunkRun,
+        index: usize,
+        input: &str,
+    ) -> Result<ValidatedCard, ValidationError> {
+        let memo = &mut self.memo;
+        let result = self
+            .validator
+            .validate_detected(&mut self.workspace, input, |digits| memo.brand(digits));
+        if result.is_ok() {
+            run.valid += 1;
+        } else {
+            run.invalid += 1;
+        }
+        if let Some(observer) = &self.observer {
+            observer.on_item(index, &result);
+        }
+        result
+    }
+
+    /// Reports a finished chunk.
+    fn finish(&self, run: ChunkRun) -> ChunkStats {
+        let stats = ChunkStats {
+            items: run.valid + run.invalid,
+            valid: run.valid,
+            invalid: run.invalid,
+            elapsed: run.start.elapsed(),
+        };
+        if let Some(observer) = &self.observer {
+            observer.on_chunk_complete(&stats);
+        }
+        stats
+    }
+
     /// Validates a batch of card numbers.
     ///
     /// Returns a vector of results in the same order as the input.
@@ -58,7 +415,14 @@ impl BatchValidator {
         &mut self,
         cards: &[S],
     ) -> Vec<Result<ValidatedCard, ValidationError>> {
-        cards.iter().map(|c| validate(c.as_ref())).collect()
+        let mut run = self.start();
+        let results = cards
+            .iter()
+            .enumerate()
+            .map(|(i, c)| self.check(&mut run, i, c.as_ref()))
+            .collect();
+        self.finish(run);
+        results
     }
 
     /// Validates a batch and returns only the valid cards.
@@ -69,10 +433,14 @@ impl BatchValidator {
     ///
     /// * `cards` - Slice of card number strings to validate.
     pub fn validate_valid_only<S: AsRef<str>>(&mut self, cards: &[S]) -> Vec<ValidatedCard> {
-        cards
+        let mut run = self.start();
+        let valid = cards
             .iter()
-            .filter_map(|c| validate(c.as_ref()).ok())
-            .collect()
+            .enumerate()
+            .filter_map(|(i, c)| self.check(&mut run, i, c.as_ref()).ok())
+            .collect();
+        self.finish(run);
+        valid
     }
 
     /// Validates a batch and partitions into valid and invalid.
@@ -88,17 +456,392 @@ impl BatchValidator {
     ) -> (Vec<ValidatedCard>, Vec<(usize, ValidationError)>) {
         let mut valid = Vec::new();
         let mut invalid = Vec::new();
+        let mut run = self.start();
 
         for (i, card) in cards.iter().enumerate() {
-            match validate(card.as_ref()) {
+            match self.check(&mut run, i, card.as_ref()) {
                 Ok(c) => valid.push(c),
                 Err(e) => invalid.push((i, e)),
             }
         }
 
+        self.finish(run);
         (valid, invalid)
     }
 
+    /// Validates a batch and returns only pass/fail and error codes (see
+    /// [`CompactResults`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::BatchValidator;
+    ///
+    /// let mut batch = BatchValidator::new();
+    /// let results = batch.validate_compact(&["4111111111111111", "4111111111111112"]);
+    /// assert_eq!(results.get(0), Some(true));
+    /// assert_eq!(results.error_code(1), Some("invalid_checksum"));
+    /// assert_eq!((results.valid_count(), results.invalid_count()), (1, 1));
+    /// ```
+    pub fn validate_compact<S: AsRef<str>>(&mut self, cards: &[S]) -> CompactResults {
+        let mut results = CompactResults {
+            bits: Vec::with_capacity(cards.len().div_ceil(64)),
+            ..CompactResults::default()
+        };
+        let mut run = self.start();
+        for (i, card) in cards.iter().enumerate() {
+            results.push(self.check(&mut run, i, card.as_ref()));
+        }
+        self.finish(run);
+        results
+    }
+
+    /// Validates delimiter-separated card numbers in a byte buffer, like
+    /// [`validate_compact`](fn@validate_compact), and returns
+    /// [`CompactResults`].
+    ///
+    /// Records are split the same way as by the free function: a trailing
+    /// delimiter is ignored, and so is a `\r` before a `\n` delimiter.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::BatchValidator;
+    ///
+    /// let mut batch = BatchValidator::new();
+    /// let results = batch.validate_compact_bytes(b"4111111111111111\r\nbad\r\n", b'\n');
+    /// assert_eq!(results.len(), 2);
+    /// assert_eq!(results.errors(), [(1, "invalid_character")]);
+    /// ```
+    pub fn validate_compact_bytes(&mut self, input: &[u8], delimiter: u8) -> CompactResults {
+        let mut results = CompactResults::default();
+        let mut run = self.start();
+        for (i, record) in records(input, delimiter).enumerate() {
+            let text = String::from_utf8_lossy(record);
+            results.push(self.check(&mut run, i, &text));
+        }
+        self.finish(run);
+        results
+    }
+
+    /// Luhn-checks a batch, like [`passes_luhn`](crate::passes_luhn) on
+    /// each card: no brand, length or policy checks.
+    ///
+    /// Cards are checked 32 at a time by
+    /// [`simd::validate_batch_simd`](crate::simd::validate_batch_simd), in
+    /// SIMD lanes with the `simd` feature. The observer is not called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::BatchValidator;
+    ///
+    /// let batch = BatchValidator::new();
+    /// let results = batch.passes_luhn_all(&["4111 1111 1111 1111", "4111111111111112", ""]);
+    /// assert_eq!(results, [true, false, false]);
+    /// ```
+    pub fn passes_luhn_all<S: AsRef<str>>(&self, cards: &[S]) -> Vec<bool> {
+        let mut digits = Vec::new();
+        let mut ends = Vec::with_capacity(cards.len());
+        for card in cards {
+            let card = card.as_ref().bytes().filter(u8::is_ascii_digit);
+            digits.extend(card.map(|b| b - b'0'));
+            ends.push(digits.len());
+        }
+
+        let mut start = 0;
+        let slices: Vec<&[u8]> = ends
+            .iter()
+            .map(|&end| &digits[std::mem::replace(&mut start, end)..end])
+            .collect();
+        let results = crate::simd::validate_batch_simd(&slices);
+        digits.zeroize();
+        results
+    }
+
+    /// Validates a batch and groups the valid cards by brand.
+    ///
+    /// Invalid cards are dropped; cards within each group keep their input
+    /// order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::{BatchValidator, CardBrand};
+    ///
+    /// let mut batch = BatchValidator::new();
+    /// let groups = batch.group_by_brand(&["4111111111111111", "4012888888881881", "5500000000000004"]);
+    /// assert_eq!(groups[&CardBrand::Visa].len(), 2);
+    /// assert_eq!(groups[&CardBrand::Mastercard].len(), 1);
+    /// ```
+    pub fn group_by_brand<S: AsRef<str>>(
+        &mut self,
+        cards: &[S],
+    ) -> HashMap<CardBrand, Vec<ValidatedCard>> {
+        let mut groups: HashMap<CardBrand, Vec<ValidatedCard>> = HashMap::new();
+        for card in self.validate_valid_only(cards) {
+            groups.entry(card.brand()).or_default().push(card);
+        }
+        groups
+    }
+
+    /// Validates a batch and groups the valid cards by their 6-digit BIN.
+    ///
+    /// Invalid cards are dropped; cards within each group keep their input
+    /// order.
+    pub fn group_by_bin<S: AsRef<str>>(
+        &mut self,
+        cards: &[S],
+    ) -> HashMap<String, Vec<ValidatedCard>> {
+        let mut groups: HashMap<String, Vec<ValidatedCard>> = HashMap::new();
+        for card in self.validate_valid_only(cards) {
+            groups.entry(card.bin6()).or_default().push(card);
+        }
+        groups
+    }
+
+    /// Validates a batch like [`Validator::profile`], attaching BIN
+    /// information from the validator's database to every valid card.
+    ///
+    /// Each distinct BIN is looked up about once per call (see the
+    /// [module docs](self#clustered-batches)).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::bin::{BinInfo, MemoryBinDbBuilder};
+    /// use cc_validator::{BatchValidator, Validator};
+    ///
+    /// let db = MemoryBinDbBuilder::new()
+    ///     .add("411111", BinInfo::with_bin("411111").issuer("Test Bank"))
+    ///     .build();
+    /// let mut batch = BatchValidator::with_validator(Validator::new().with_bin_database(db));
+    /// let profiles = batch.profile_all(&["4111111111111111", "4111-1111-1111-1111", "bad"]);
+    /// let issuer = |i: usize| profiles[i].as_ref().unwrap().bin_info().unwrap().issuer.clone();
+    /// assert_eq!(issuer(0).as_deref(), Some("Test Bank"));
+    /// assert_eq!(issuer(1), issuer(0));
+    /// assert!(profiles[2].is_err());
+    /// ```
+    pub fn profile_all<S: AsRef<str>>(
+        &mut self,
+        cards: &[S],
+    ) -> Vec<Result<CardProfile, ValidationError>> {
+        let mut run = self.start();
+        let results = cards
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let card = self.check(&mut run, i, c.as_ref())?;
+                let profile = match self.memo.bin(&self.validator, &card) {
+                    Some(info) => CardProfile::new(card).with_shared_bin_info(info),
+                    None => CardProfile::new(card),
+                };
+                Ok(profile)
+            })
+            .collect();
+        self.finish(run);
+        results
+    }
+
+    /// Validates a batch and groups the invalid entries by error kind.
+    ///
+    /// Keys are [`ValidationError::code`] values; each entry pairs the
+    /// input index with its error, in input order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::BatchValidator;
+    ///
+    /// let mut batch = BatchValidator::new();
+    /// let errors = batch.group_errors(&["4111111111111112", "", "4111111111111111"]);
+    /// assert_eq!(errors["invalid_checksum"][0].0, 0);
+    /// assert_eq!(errors["empty"][0].0, 1);
+    /// ```
+    pub fn group_errors<S: AsRef<str>>(
+        &mut self,
+        cards: &[S],
+    ) -> HashMap<&'static str, Vec<(usize, ValidationError)>> {
+        let (_, invalid) = self.validate_partitioned(cards);
+        let mut groups: HashMap<&'static str, Vec<(usize, ValidationError)>> = HashMap::new();
+        for (i, err) in invalid {
+            groups.entry(err.code()).or_default().push((i, err));
+        }
+        groups
+    }
+
+    /// Validates a batch and returns each distinct valid card once, keyed by
+    /// its [fingerprint](ValidatedCard::fingerprint) under `key`.
+    ///
+    /// Cards are in order of first appearance. Formatting doesn't matter:
+    /// `"4111 1111 1111 1111"` and `"4111111111111111"` are the same card.
+    /// No plaintext is kept, so the result can be logged or stored.
+    ///
+    /// Requires the `fingerprint` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::BatchValidator;
+    ///
+    /// let mut batch = BatchValidator::new();
+    /// let cards = ["4111 1111 1111 1111", "bad", "5500000000000004", "4111111111111111"];
+    /// let unique = batch.validate_unique(&cards, b"migration key");
+    /// assert_eq!(unique.len(), 2);
+    /// assert_eq!((unique[0].first_index, unique[0].count), (0, 2));
+    /// assert_eq!((unique[1].first_index, unique[1].count), (2, 1));
+    /// ```
+    #[cfg(feature = "fingerprint")]
+    pub fn validate_unique<S: AsRef<str>>(&mut self, cards: &[S], key: &[u8]) -> Vec<UniqueCard> {
+        let mut unique: Vec<UniqueCard> = Vec::new();
+        let mut seen: HashMap<Fingerprint, usize> = HashMap::new();
+        let mut run = self.start();
+
+        for (i, card) in cards.iter().enumerate() {
+            let Ok(card) = self.check(&mut run, i, card.as_ref()) else {
+                continue;
+            };
+            let fingerprint = card.fingerprint(key);
+            match seen.get(&fingerprint) {
+                Some(&slot) => unique[slot].count += 1,
+                None => {
+                    seen.insert(fingerprint, unique.len());
+                    unique.push(UniqueCard {
+                        fingerprint,
+                        brand: card.brand(),
+                        first_index: i,
+                        count: 1,
+                    });
+                }
+            }
+        }
+        self.finish(run);
+        unique
+    }
+
+    /// Validates the next chunk of a newline-delimited input and advances
+    /// `checkpoint` past it.
+    ///
+    /// Reads up to `max_records` non-blank lines from `reader`, which must
+    /// be positioned at `checkpoint.offset` (see [`Checkpoint::resume`]).
+    /// Each result is paired with its 0-based line number. Lines that aren't
+    /// valid UTF-8 are validated lossily, so they fail instead of aborting
+    /// the scan. An empty result means the input is exhausted.
+    ///
+    /// Persist [`Checkpoint::to_token`] after handling each chunk; a job that
+    /// dies restarts from the last saved token without re-reading or
+    /// double-counting earlier records.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::batch::Checkpoint;
+    /// use cc_validator::BatchValidator;
+    /// use std::io::Cursor;
+    ///
+    /// let data = "4111111111111111\n1234\n5500000000000004\n";
+    /// let mut batch = BatchValidator::new();
+    ///
+    /// // First run stops after one chunk
+    /// let mut checkpoint = Checkpoint::default();
+    /// let mut reader = Cursor::new(data);
+    /// batch.validate_chunk(&mut reader, &mut checkpoint, 2).unwrap();
+    /// let token = checkpoint.to_token();
+    ///
+    /// // A later run resumes where it left off
+    /// let mut checkpoint = Checkpoint::from_token(&token).unwrap();
+    /// let mut reader = Cursor::new(data);
+    /// checkpoint.resume(&mut reader).unwrap();
+    /// let results = batch.validate_chunk(&mut reader, &mut checkpoint, 2).unwrap();
+    /// assert_eq!(results.len(), 1);
+    /// assert_eq!(results[0].0, 2);
+    /// assert_eq!((checkpoint.valid, checkpoint.invalid), (2, 1));
+    /// ```
+    pub fn validate_chunk<R: BufRead>(
+        &mut self,
+        reader: &mut R,
+        checkpoint: &mut Checkpoint,
+        max_records: usize,
+    ) -> io::Result<Vec<(u64, Result<ValidatedCard, ValidationError>)>> {
+        let mut results = Vec::with_capacity(max_records.min(4096));
+        let mut line = Vec::new();
+        let mut run = self.start();
+
+        while results.len() < max_records {
+            line.clear();
+            let read = reader.read_until(b'\n', &mut line)?;
+            if read == 0 {
+                break;
+            }
+            let number = checkpoint.lines;
+            checkpoint.offset += read as u64;
+            checkpoint.lines += 1;
+
+            let text = String::from_utf8_lossy(&line);
+            let input = text.trim();
+            if input.is_empty() {
+                continue;
+            }
+
+            let result = self.check(&mut run, number as usize, input);
+            if result.is_ok() {
+                checkpoint.valid += 1;
+            } else {
+                checkpoint.invalid += 1;
+            }
+            results.push((number, result));
+        }
+        // Card numbers may be left in the line buffer
+        line.zeroize();
+        if !results.is_empty() {
+            self.finish(run);
+        }
+
+        Ok(results)
+    }
+
+    /// Validates cards one at a time, writing each result to `writer` as it
+    /// is produced.
+    ///
+    /// Results are never collected, so `cards` can be any iterator, such as
+    /// the lines of a file, and memory use stays flat however large the
+    /// batch is. Card numbers are masked before they reach the writer (see
+    /// [`ResultRow`]). Call [`ResultWriter::finish`] once all batches
+    /// destined for the same output have been written.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from the writer; cards after it are not
+    /// validated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::output::{JsonWriter, ResultWriter};
+    /// use cc_validator::BatchValidator;
+    ///
+    /// let mut writer = JsonWriter::new(Vec::new());
+    /// let stats = BatchValidator::new()
+    ///     .validate_into(["4111111111111111", "1234"], &mut writer)
+    ///     .unwrap();
+    /// writer.finish().unwrap();
+    /// assert_eq!((stats.valid, stats.invalid), (1, 1));
+    /// ```
+    pub fn validate_into<I, W>(&mut self, cards: I, mut writer: W) -> io::Result<ChunkStats>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+        W: ResultWriter,
+    {
+        let mut run = self.start();
+        for (index, card) in cards.into_iter().enumerate() {
+            let input = card.as_ref();
+            let result = self.check(&mut run, index, input);
+            writer.write_row(&ResultRow::new(index as u64, input, &result))?;
+        }
+        Ok(self.finish(run))
+    }
+
     /// Validates cards in parallel using rayon.
     ///
     /// This is typically faster for large batches (>1000 cards) on
@@ -117,7 +860,16 @@ impl BatchValidator {
         cards: &[S],
     ) -> Vec<Result<ValidatedCard, ValidationError>> {
         use rayon::prelude::*;
-        cards.par_iter().map(|c| validate(c.as_ref())).collect()
+        let mut run = self.start();
+        let results: Vec<_> = cards
+            .par_iter()
+            .enumerate()
+            .map(|(i, c)| self.check_shared(i, c.as_ref()))
+            .collect();
+        run.valid = results.iter().filter(|r| r.is_ok()).count();
+        run.invalid = results.len() - run.valid;
+        self.finish(run);
+        results
     }
 
     /// Validates cards in parallel, returning only valid ones.
@@ -131,10 +883,91 @@ impl BatchValidator {
         cards: &[S],
     ) -> Vec<ValidatedCard> {
         use rayon::prelude::*;
-        cards
+        let mut run = self.start();
+        let valid: Vec<_> = cards
             .par_iter()
-            .filter_map(|c| validate(c.as_ref()).ok())
-            .collect()
+            .enumerate()
+            .filter_map(|(i, c)| self.check_shared(i, c.as_ref()).ok())
+            .collect();
+        run.valid = valid.len();
+        run.invalid = cards.len() - valid.len();
+        self.finish(run);
+        valid
+    }
+
+    /// Validates one card without the workspace, for worker threads.
+    #[cfg(feature = "parallel")]
+    fn check_shared(&self, index: usize, input: &str) -> Result<ValidatedCard, ValidationError> {
+        let result = self.validator.validate(input);
+        if let Some(observer) = &self.observer {
+            observer.on_item(index, &result);
+        }
+        result
+    }
+}
+
+/// Progress of a resumable scan with [`BatchValidator::validate_chunk`].
+///
+/// Holds the byte offset to resume from and running counts, and round-trips
+/// through a short text token for storage between runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// Bytes of input consumed so far.
+    pub offset: u64,
+    /// Lines consumed so far, including blank ones.
+    pub lines: u64,
+    /// Records that passed validation.
+    pub valid: u64,
+    /// Records that failed validation.
+    pub invalid: u64,
+}
+
+impl Checkpoint {
+    /// Prefix identifying the token format version.
+    const TOKEN_PREFIX: &'static str = "ckpt1";
+
+    /// Returns the number of records validated so far.
+    #[inline]
+    pub fn records(&self) -> u64 {
+        self.valid + self.invalid
+    }
+
+    /// Encodes the checkpoint as a token such as `ckpt1:1024:64:60:4`.
+    pub fn to_token(&self) -> String {
+        format!(
+            "{}:{}:{}:{}:{}",
+            Self::TOKEN_PREFIX,
+            self.offset,
+            self.lines,
+            self.valid,
+            self.invalid
+        )
+    }
+
+    /// Decodes a token produced by [`to_token`](Self::to_token).
+    ///
+    /// Returns `None` for malformed tokens or an unknown format version.
+    pub fn from_token(token: &str) -> Option<Self> {
+        let mut parts = token.trim().split(':');
+        if parts.next()? != Self::TOKEN_PREFIX {
+            return None;
+        }
+        let mut next = || parts.next()?.parse::<u64>().ok();
+        let checkpoint = Self {
+            offset: next()?,
+            lines: next()?,
+            valid: next()?,
+            invalid: next()?,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(checkpoint)
+    }
+
+    /// Seeks `reader` to where this checkpoint left off.
+    pub fn resume<R: Seek>(&self, reader: &mut R) -> io::Result<()> {
+        reader.seek(SeekFrom::Start(self.offset)).map(|_| ())
     }
 }
 
@@ -154,7 +987,11 @@ impl BatchValidator {
 /// ```
 #[inline]
 pub fn validate_batch<S: AsRef<str>>(cards: &[S]) -> Vec<Result<ValidatedCard, ValidationError>> {
-    cards.iter().map(|c| validate(c.as_ref())).collect()
+    let mut workspace = Workspace::new();
+    cards
+        .iter()
+        .map(|c| workspace.validate_into(c.as_ref()))
+        .collect()
 }
 
 /// Validates a slice of cards in parallel.
@@ -192,11 +1029,12 @@ pub fn validate_batch_parallel<S: AsRef<str> + Sync>(
 /// ```
 #[inline]
 pub fn count_valid<S: AsRef<str>>(cards: &[S]) -> (usize, usize) {
+    let mut workspace = Workspace::new();
     let mut valid = 0;
     let mut invalid = 0;
 
     for card in cards {
-        if validate(card.as_ref()).is_ok() {
+        if workspace.validate_into(card.as_ref()).is_ok() {
             valid += 1;
         } else {
             invalid += 1;
@@ -224,6 +1062,100 @@ pub fn count_valid_parallel<S: AsRef<str> + Sync>(cards: &[S]) -> (usize, usize)
     (valid, cards.len() - valid)
 }
 
+/// Status codes used by [`validate_compact`]; the index is the status byte.
+///
+/// Status `0` is `"valid"`; the others match [`ValidationError::code`].
+pub const COMPACT_STATUS_CODES: [&str; 12] = [
+    "valid",
+    "empty",
+    "too_short",
+    "too_long",
+    "invalid_character",
+    "invalid_checksum",
+    "invalid_length_for_brand",
+    "unknown_brand",
+    "no_digits",
+    "test_card",
+    "brand_not_allowed",
+    "not_accepted_in_country",
+];
+
+/// Returns the [`validate_compact`] status byte for `error`.
+///
+/// The match is exhaustive so a new [`ValidationError`] variant can't
+/// silently fall back to `0`, which means valid.
+fn compact_status(error: &ValidationError) -> u8 {
+    match error {
+        ValidationError::Empty => 1,
+        ValidationError::TooShort { .. } => 2,
+        ValidationError::TooLong { .. } => 3,
+        ValidationError::InvalidCharacter { .. } => 4,
+        ValidationError::InvalidChecksum => 5,
+        ValidationError::InvalidLengthForBrand { .. } => 6,
+        ValidationError::UnknownBrand => 7,
+        ValidationError::NoDigits => 8,
+        ValidationError::TestCard => 9,
+        ValidationError::BrandNotAllowed { .. } => 10,
+        ValidationError::NotAcceptedInCountry { .. } => 11,
+    }
+}
+
+/// Returns the code for a [`validate_compact`] status byte, e.g.
+/// `"invalid_checksum"`.
+pub fn compact_status_code(status: u8) -> Option<&'static str> {
+    COMPACT_STATUS_CODES.get(status as usize).copied()
+}
+
+/// Validates delimiter-separated card numbers in a byte buffer.
+///
+/// Each record yields two bytes: a status (`0` for valid, otherwise see
+/// [`compact_status_code`]) and a brand (`0` for none, otherwise its
+/// [`CardBrand::numeric_id`]). A trailing delimiter is ignored, and so is
+/// a `\r` before a `\n` delimiter. Records are validated in place with the
+/// [default validator](crate::default_validator), so no string is allocated
+/// per card; its configuration error is returned if it can't be loaded.
+///
+/// This is the wire format of the WASM and Node.js batch APIs, where the
+/// whole batch crosses the language boundary once in each direction.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::batch::{compact_status_code, validate_compact};
+/// use cc_validator::CardBrand;
+///
+/// let out = validate_compact(b"4111111111111111,4111111111111112", b',').unwrap();
+/// assert_eq!(out.len(), 4);
+/// assert_eq!(out[0], 0);
+/// assert_eq!(CardBrand::try_from(out[1]), Ok(CardBrand::Visa));
+/// assert_eq!(compact_status_code(out[2]), Some("invalid_checksum"));
+/// ```
+pub fn validate_compact(input: &[u8], delimiter: u8) -> Result<Vec<u8>, ConfigError> {
+    let validator = crate::default_validator()?;
+    let mut workspace = Workspace::new();
+    let mut out = Vec::with_capacity(input.len() / 8);
+    for record in records(input, delimiter) {
+        let text = String::from_utf8_lossy(record);
+        match validator.validate_with(&mut workspace, &text) {
+            Ok(card) => out.extend_from_slice(&[0, card.brand().numeric_id()]),
+            Err(e) => out.extend_from_slice(&[compact_status(&e), 0]),
+        }
+    }
+    Ok(out)
+}
+
+/// Splits a byte buffer into records for the compact batch APIs.
+fn records(input: &[u8], delimiter: u8) -> impl Iterator<Item = &[u8]> {
+    let input = input.strip_suffix(&[delimiter]).unwrap_or(input);
+    input
+        .split(move |&b| b == delimiter)
+        .take_while(move |_| !input.is_empty())
+        .map(move |record| match delimiter {
+            b'\n' => record.strip_suffix(b"\r").unwrap_or(record),
+            _ => record,
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -267,6 +1199,128 @@ mod tests {
         assert_eq!(invalid[1].0, 3); // Index of second invalid
     }
 
+    #[test]
+    fn test_group_by_brand() {
+        let mut batch = BatchValidator::new();
+        let cards = vec![
+            VALID_VISA,
+            VALID_MC,
+            INVALID,
+            VALID_AMEX,
+            "4012888888881881",
+        ];
+        let groups = batch.group_by_brand(&cards);
+
+        assert_eq!(groups.len(), 3);
+        let visa = &groups[&CardBrand::Visa];
+        assert_eq!(visa.len(), 2);
+        assert_eq!(visa[0].last_four(), "1111");
+        assert_eq!(visa[1].last_four(), "1881");
+        assert_eq!(groups[&CardBrand::Amex].len(), 1);
+    }
+
+    #[test]
+    fn test_group_by_bin() {
+        let mut batch = BatchValidator::new();
+        let cards = vec![VALID_VISA, VALID_VISA, VALID_MC, "378282246310005", INVALID];
+        let groups = batch.group_by_bin(&cards);
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups["411111"].len(), 2);
+        assert_eq!(groups["550000"].len(), 1);
+        assert_eq!(groups["378282"].len(), 1);
+    }
+
+    #[test]
+    fn test_prefix_memo() {
+        use crate::bin::{BinDatabase, MemoryBinDb};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct Counting(MemoryBinDb, Arc<AtomicUsize>);
+        impl BinDatabase for Counting {
+            fn lookup(&self, bin: &[u8]) -> Option<BinInfo> {
+                self.1.fetch_add(1, Ordering::Relaxed);
+                self.0.lookup(bin)
+            }
+            fn len(&self) -> usize {
+                self.0.len()
+            }
+        }
+
+        // 400048 and 510000 share a memo slot
+        let cards = [
+            "4000480000000006",
+            "5100000000000008",
+            "4000480000000006",
+            VALID_VISA,
+            "5100000000000008",
+            INVALID,
+        ];
+        let mut batch = BatchValidator::new();
+        let results = batch.validate_all(&cards);
+        // Cards compare by digits only, so check the brands separately
+        let parts = |result: &Result<ValidatedCard, ValidationError>| {
+            result
+                .as_ref()
+                .map(|card| (card.brand(), card.last_four()))
+                .map_err(Clone::clone)
+        };
+        for (card, result) in cards.iter().zip(&results) {
+            assert_eq!(result, &crate::validate(card));
+            assert_eq!(parts(result), parts(&crate::validate(card)));
+        }
+
+        let lookups = Arc::new(AtomicUsize::new(0));
+        let mut db = MemoryBinDb::new();
+        db.insert("411111", BinInfo::with_bin("411111").issuer("Test Bank"));
+        let validator = Validator::new().with_bin_database(Counting(db, Arc::clone(&lookups)));
+        let mut batch = BatchValidator::with_validator(validator);
+        let profiles = batch.profile_all(&[VALID_VISA, VALID_VISA, VALID_MC, VALID_VISA]);
+        assert_eq!(lookups.load(Ordering::Relaxed), 2);
+        assert!(profiles[3].as_ref().unwrap().bin_info().is_some());
+        assert!(profiles[2].as_ref().unwrap().bin_info().is_none());
+
+        // Every call starts afresh
+        batch.profile_all(&[VALID_VISA]);
+        assert_eq!(lookups.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_group_errors() {
+        let mut batch = BatchValidator::new();
+        let cards = vec![VALID_VISA, INVALID, "bad", "4111111111111112", ""];
+        let groups = batch.group_errors(&cards);
+
+        assert!(groups["invalid_checksum"].iter().any(|(i, _)| *i == 3));
+        assert_eq!(groups["empty"][0].0, 4);
+        let total: usize = groups.values().map(Vec::len).sum();
+        assert_eq!(total, 4);
+    }
+
+    #[cfg(feature = "fingerprint")]
+    #[test]
+    fn test_validate_unique() {
+        let mut batch = BatchValidator::new();
+        let cards = [
+            VALID_MC,
+            INVALID,
+            "4111-1111-1111-1111",
+            VALID_MC,
+            VALID_VISA,
+        ];
+        let unique = batch.validate_unique(&cards, b"key");
+        assert_eq!(unique.len(), 2);
+        assert_eq!(unique[0].brand, CardBrand::Mastercard);
+        assert_eq!((unique[0].first_index, unique[0].count), (0, 2));
+        assert_eq!((unique[1].first_index, unique[1].count), (2, 2));
+
+        // Fingerprints depend on the key
+        let other = batch.validate_unique(&cards, b"other key");
+        assert_ne!(unique[0].fingerprint, other[0].fingerprint);
+        let card = crate::validate(VALID_MC).unwrap();
+        assert!(card.verify_fingerprint(b"key", &unique[0].fingerprint));
+    }
+
     #[test]
     fn test_validate_batch_fn() {
         let cards = [VALID_VISA, VALID_MC];
@@ -309,4 +1363,237 @@ mod tests {
         assert_eq!(valid, 2);
         assert_eq!(invalid, 2);
     }
+
+    #[test]
+    fn test_checkpoint_token() {
+        let checkpoint = Checkpoint {
+            offset: 1024,
+            lines: 64,
+            valid: 60,
+            invalid: 3,
+        };
+        assert_eq!(checkpoint.to_token(), "ckpt1:1024:64:60:3");
+        assert_eq!(
+            Checkpoint::from_token(&checkpoint.to_token()),
+            Some(checkpoint)
+        );
+        assert_eq!(checkpoint.records(), 63);
+
+        assert!(Checkpoint::from_token("ckpt1:1:2:3").is_none());
+        assert!(Checkpoint::from_token("ckpt1:1:2:3:4:5").is_none());
+        assert!(Checkpoint::from_token("ckpt2:1:2:3:4").is_none());
+        assert!(Checkpoint::from_token("ckpt1:a:2:3:4").is_none());
+    }
+
+    #[test]
+    fn test_chunked_resume_matches_single_pass() {
+        use std::io::Cursor;
+
+        let mut data = Vec::new();
+        for i in 0..25 {
+            let line: &[u8] = match i % 5 {
+                0 => b"4111111111111111\r\n",
+                1 => b"5500 0000 0000 0004\n",
+                2 => b"\n",
+                3 => b"1234\n",
+                _ => b"4111\xff1111\n",
+            };
+            data.extend_from_slice(line);
+        }
+        data.extend_from_slice(b"378282246310005");
+
+        let mut batch = BatchValidator::new();
+        let mut whole = Checkpoint::default();
+        let all = batch
+            .validate_chunk(&mut Cursor::new(&data), &mut whole, usize::MAX)
+            .unwrap();
+        assert_eq!(all.len(), 21);
+        assert_eq!(whole.offset, data.len() as u64);
+        assert_eq!(whole.lines, 26);
+        assert_eq!((whole.valid, whole.invalid), (11, 10));
+
+        // Restart from a saved token after every chunk
+        let mut token = Checkpoint::default().to_token();
+        let mut lines = Vec::new();
+        loop {
+            let mut checkpoint = Checkpoint::from_token(&token).unwrap();
+            let mut reader = Cursor::new(&data);
+            checkpoint.resume(&mut reader).unwrap();
+            let chunk = batch
+                .validate_chunk(&mut reader, &mut checkpoint, 4)
+                .unwrap();
+            if chunk.is_empty() {
+                assert_eq!(checkpoint, whole);
+                break;
+            }
+            lines.extend(chunk.iter().map(|(line, r)| (*line, r.is_ok())));
+            token = checkpoint.to_token();
+        }
+        let expected: Vec<_> = all.iter().map(|(line, r)| (*line, r.is_ok())).collect();
+        assert_eq!(lines, expected);
+    }
+
+    #[test]
+    fn test_passes_luhn_all() {
+        let cards: Vec<String> = (0..70)
+            .map(|i| match i % 4 {
+                0 => "4111-1111-1111-1111".to_string(),
+                1 => format!("41111111111111{:02}", i),
+                2 => "378282246310005".to_string(),
+                _ => "x".repeat(i % 3),
+            })
+            .collect();
+        let expected: Vec<bool> = cards.iter().map(|c| crate::passes_luhn(c)).collect();
+        assert!(expected.contains(&true) && expected.contains(&false));
+        assert_eq!(BatchValidator::new().passes_luhn_all(&cards), expected);
+    }
+
+    #[test]
+    fn test_validate_compact() {
+        let out = validate_compact(b"4111111111111111|bad|\xff4111|", b'|').unwrap();
+        assert_eq!(out.len(), 6);
+        assert_eq!(
+            (out[0], CardBrand::all()[out[1] as usize - 1]),
+            (0, CardBrand::Visa)
+        );
+        assert_eq!(compact_status_code(out[2]), Some("invalid_character"));
+        assert_eq!(compact_status_code(out[4]), Some("invalid_character"));
+        assert_eq!((out[3], out[5]), (0, 0));
+
+        // \r is only stripped for newline-delimited input
+        assert_eq!(
+            validate_compact(b"4111111111111111\r\n", b'\n').unwrap()[0],
+            0
+        );
+        assert_ne!(
+            validate_compact(b"4111111111111111\r,", b',').unwrap()[0],
+            0
+        );
+        assert!(validate_compact(b"", b'\n').unwrap().is_empty());
+        assert!(compact_status_code(200).is_none());
+    }
+
+    #[test]
+    fn test_compact_results() {
+        let mut cards = vec![VALID_VISA; 130];
+        cards[3] = INVALID;
+        cards[64] = "";
+        cards[129] = VALID_AMEX;
+        let mut batch = BatchValidator::new();
+        let results = batch.validate_compact(&cards);
+
+        assert_eq!(results.len(), 130);
+        assert_eq!(results.words().len(), 3);
+        assert_eq!(results.words()[2], 0b11);
+        assert_eq!((results.valid_count(), results.invalid_count()), (128, 2));
+        assert_eq!(results.errors(), [(3, "invalid_checksum"), (64, "empty")]);
+        assert_eq!(results.get(64), Some(false));
+        assert_eq!(results.get(129), Some(true));
+        assert_eq!(results.get(130), None);
+        assert_eq!(results.error_code(0), None);
+
+        let bytes = cards.join(",");
+        assert_eq!(
+            batch.validate_compact_bytes(bytes.as_bytes(), b','),
+            results
+        );
+        assert!(batch.validate_compact_bytes(b"\n", b'\n').is_empty());
+    }
+
+    #[test]
+    fn test_compact_status_codes_cover_errors() {
+        let errors = [
+            ValidationError::Empty,
+            ValidationError::TooShort {
+                length: 1,
+                minimum: 12,
+            },
+            ValidationError::TooLong {
+                length: 20,
+                maximum: 19,
+            },
+            ValidationError::InvalidCharacter {
+                position: 0,
+                digit_index: 0,
+                character: 'x',
+            },
+            ValidationError::InvalidChecksum,
+            ValidationError::InvalidLengthForBrand {
+                brand: CardBrand::Visa,
+                length: 14,
+                valid_lengths: &[16],
+            },
+            ValidationError::UnknownBrand,
+            ValidationError::NoDigits,
+            ValidationError::TestCard,
+            ValidationError::BrandNotAllowed {
+                brand: CardBrand::Visa,
+            },
+            ValidationError::NotAcceptedInCountry {
+                brand: CardBrand::Mir,
+                country: "US".to_string(),
+            },
+        ];
+        let mut statuses: Vec<u8> = errors.iter().map(compact_status).collect();
+        for (e, &status) in errors.iter().zip(&statuses) {
+            assert_eq!(compact_status_code(status), Some(e.code()));
+        }
+        // Every non-valid status is produced by exactly one sample
+        statuses.sort_unstable();
+        let expected: Vec<u8> = (1..COMPACT_STATUS_CODES.len() as u8).collect();
+        assert_eq!(statuses, expected);
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        items: std::sync::Mutex<Vec<(usize, bool)>>,
+        chunks: std::sync::Mutex<Vec<ChunkStats>>,
+    }
+
+    impl BatchObserver for Recorder {
+        fn on_item(&self, index: usize, result: &Result<ValidatedCard, ValidationError>) {
+            self.items.lock().unwrap().push((index, result.is_ok()));
+        }
+
+        fn on_chunk_complete(&self, stats: &ChunkStats) {
+            self.chunks.lock().unwrap().push(*stats);
+        }
+    }
+
+    #[test]
+    fn test_observer() {
+        let recorder = Arc::new(Recorder::default());
+        let mut batch = BatchValidator::new().with_observer(recorder.clone());
+
+        let (valid, invalid) = batch.validate_partitioned(&[VALID_VISA, "1234", VALID_VISA]);
+        assert_eq!((valid.len(), invalid.len()), (2, 1));
+        assert_eq!(
+            *recorder.items.lock().unwrap(),
+            [(0, true), (1, false), (2, true)]
+        );
+
+        let input = format!("{}\n\n1234\n", VALID_VISA);
+        let mut reader = io::Cursor::new(input);
+        let mut checkpoint = Checkpoint::default();
+        batch
+            .validate_chunk(&mut reader, &mut checkpoint, 10)
+            .unwrap();
+        // An exhausted reader doesn't report an empty chunk
+        batch
+            .validate_chunk(&mut reader, &mut checkpoint, 10)
+            .unwrap();
+
+        let chunks = recorder.chunks.lock().unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(
+            (chunks[0].items, chunks[0].valid, chunks[0].invalid),
+            (3, 2, 1)
+        );
+        assert_eq!(
+            (chunks[1].items, chunks[1].valid, chunks[1].invalid),
+            (2, 1, 1)
+        );
+        assert_eq!(recorder.items.lock().unwrap()[4], (2, false));
+        assert!(format!("{:?}", batch).contains("observer: true"));
+    }
 }
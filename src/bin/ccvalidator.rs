@@ -6,8 +6,15 @@
 //! # Validate a card number
 //! ccvalidator validate 4111111111111111
 //!
+//! # Validate several, failing unless all are valid Visa cards
+//! ccvalidator validate 4111111111111111 4012888888881881 --brand visa
+//!
 //! # Generate test card numbers
 //! ccvalidator generate --brand visa --count 5
+//! ccvalidator generate --brand discover --length 18
+//!
+//! # Export a reproducible test-vector corpus shared by every binding
+//! ccvalidator corpus --per-brand 20 --seed 42 --output corpus.json
 //!
 //! # Format a card number
 //! ccvalidator format 4111111111111111
@@ -17,10 +24,72 @@
 //!
 //! # Validate expiry
 //! ccvalidator expiry 12/25
+//!
+//! # Check a BIN database file and look up a BIN
+//! ccvalidator bindb bins.csv --bin 411111
+//!
+//! # Validate a file of card numbers (one per line) and print a JSON report
+//! ccvalidator batch cards.txt
+//!
+//! # Compare two card lists (first CSV column) by fingerprint
+//! ccvalidator diff old.csv new.csv
+//!
+//! # Scan files for card numbers, then keep watching a directory
+//! ccvalidator scan logs/
+//! ccvalidator scan --watch /var/log/app
+//! ```
+//!
+//! # Keeping PANs Out of argv
+//!
+//! Arguments show up in `ps`, `/proc/<pid>/cmdline` and shell history.
+//! Commands taking a card number (`validate`, `format`, `mask`, `luhn`,
+//! `detect`) read it from stdin when it is omitted or `-`; `validate` reads
+//! one number per line:
+//!
+//! ```bash
+//! printf '%s\n' "$PAN" | ccvalidator validate
+//! ```
+//!
+//! `--stdin-only` (or `CC_VALIDATOR_STDIN_ONLY=1`) makes that mandatory:
+//! card numbers given as arguments, and any argument that looks like one,
+//! are refused with exit status 2 before anything is validated. Builds with
+//! the `cli-stdin-only` feature are always in this mode. Copies of card
+//! numbers the tool makes are zeroized after use; the kernel's copy of argv
+//! can't be, which is why the mode refuses them outright.
+//!
+//! # WASI
+//!
+//! With only the `cli-core` feature the tool keeps the subcommands that
+//! touch nothing but their arguments, stdin and stdout (`validate`, `format`,
+//! `cvv`, `mask`, `luhn` and `detect`), so it builds for `wasm32-wasip1` and
+//! runs in sandboxes without a filesystem, clock or entropy source:
+//!
+//! ```bash
+//! cargo build --release --target wasm32-wasip1 --no-default-features --features cli-core --bin ccvalidator
+//! wasmtime target/wasm32-wasip1/release/ccvalidator.wasm validate 4111111111111111
 //! ```
 
-use cc_validator::{cvv, expiry, format, generate, is_valid, mask, validate, CardBrand};
+#[cfg(feature = "cli")]
+use cc_validator::expiry;
+#[cfg(feature = "cli")]
+use cc_validator::fingerprint::Fingerprint;
+#[cfg(all(feature = "cli", not(feature = "no-pan-exposure")))]
+use cc_validator::generate;
+use cc_validator::{
+    cvv, format, is_valid, mask, scan, validate, CardBrand, ValidatedCard, ValidationError,
+    Validator,
+};
+use clap::builder::{PossibleValue, PossibleValuesParser, TypedValueParser};
 use clap::{Parser, Subcommand, ValueEnum};
+use std::ffi::OsStr;
+#[cfg(feature = "cli")]
+use std::{
+    collections::{HashMap, HashSet},
+    io::BufRead,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use zeroize::{Zeroize, Zeroizing};
 
 #[derive(Parser)]
 #[command(name = "ccvalidator")]
@@ -30,41 +99,80 @@ use clap::{Parser, Subcommand, ValueEnum};
     about = "Enterprise-grade credit card validation tool"
 )]
 struct Cli {
+    /// Refuse card numbers given as arguments; read them from stdin (also
+    /// set by CC_VALIDATOR_STDIN_ONLY=1)
+    #[arg(long, global = true)]
+    stdin_only: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Validate a credit card number
+    /// Validate one or more credit card numbers
+    ///
+    /// Exits 1 if any number is invalid, or with --brand, has another brand.
     Validate {
-        /// Card number to validate (spaces and dashes allowed)
-        card_number: String,
+        /// Card numbers to validate (spaces and dashes allowed; quote a
+        /// number that contains spaces). Reads one per line from stdin if
+        /// omitted or "-"
+        card_numbers: Vec<String>,
+
+        /// Fail numbers whose detected brand isn't this one
+        #[arg(short, long, value_parser = BrandParser)]
+        brand: Option<CardBrand>,
 
         /// Output format
         #[arg(short, long, default_value = "text")]
         output: OutputFormat,
     },
 
+    #[cfg(all(feature = "cli", not(feature = "no-pan-exposure")))]
     /// Generate test card numbers (for testing only)
     Generate {
         /// Card brand to generate
-        #[arg(short, long, default_value = "visa")]
-        brand: BrandArg,
+        #[arg(short, long, default_value = "visa", value_parser = BrandParser)]
+        brand: CardBrand,
 
         /// Number of cards to generate
         #[arg(short, long, default_value = "1")]
         count: usize,
 
+        /// Card length, one of the brand's valid lengths (default: the
+        /// brand's usual length)
+        #[arg(short, long)]
+        length: Option<usize>,
+
         /// Output formatted (with spaces)
         #[arg(short, long)]
         formatted: bool,
     },
 
+    #[cfg(all(feature = "cli", not(feature = "no-pan-exposure")))]
+    /// Export a reproducible JSON corpus of valid and invalid test vectors
+    Corpus {
+        /// Cards generated per brand (each yields five vectors)
+        #[arg(short = 'n', long, default_value = "10")]
+        per_brand: usize,
+
+        /// Seed for the generator; the same seed gives the same corpus
+        #[arg(short, long, default_value = "0")]
+        seed: u64,
+
+        /// Write the corpus here instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Also write each input as a file in this directory (fuzzer seeds)
+        #[arg(long)]
+        fuzz_dir: Option<PathBuf>,
+    },
+
     /// Format a card number
     Format {
-        /// Card number to format
-        card_number: String,
+        /// Card number to format (reads stdin if omitted or "-")
+        card_number: Option<String>,
 
         /// Separator to use
         #[arg(short, long, default_value = " ")]
@@ -77,10 +185,11 @@ enum Commands {
         cvv: String,
 
         /// Card brand (affects valid length)
-        #[arg(short, long)]
-        brand: Option<BrandArg>,
+        #[arg(short, long, value_parser = BrandParser)]
+        brand: Option<CardBrand>,
     },
 
+    #[cfg(feature = "cli")]
     /// Validate an expiry date
     Expiry {
         /// Expiry date (MM/YY, MM/YYYY, etc.)
@@ -93,8 +202,8 @@ enum Commands {
 
     /// Mask a card number (PCI-DSS compliant)
     Mask {
-        /// Card number to mask
-        card_number: String,
+        /// Card number to mask (reads stdin if omitted or "-")
+        card_number: Option<String>,
 
         /// Include BIN (first 6 digits)
         #[arg(short, long)]
@@ -103,14 +212,65 @@ enum Commands {
 
     /// Check if a card passes Luhn algorithm
     Luhn {
-        /// Card number to check
-        card_number: String,
+        /// Card number to check (reads stdin if omitted or "-")
+        card_number: Option<String>,
     },
 
     /// Detect card brand from number
     Detect {
-        /// Card number (or partial number)
-        card_number: String,
+        /// Card number, partial number, or masked PAN such as
+        /// 411111******1111 (reads stdin if omitted or "-")
+        card_number: Option<String>,
+
+        /// Show the prefix rule that matched
+        #[arg(short, long)]
+        verbose: bool,
+    },
+
+    #[cfg(feature = "cli")]
+    /// Load a BIN database file and report problems
+    Bindb {
+        /// Path to a .json, .csv, or .db/.sqlite BIN database
+        path: String,
+
+        /// BIN to look up after loading
+        #[arg(short, long)]
+        bin: Option<String>,
+    },
+
+    #[cfg(feature = "cli")]
+    /// Validate card numbers, one per line, and print a JSON report
+    Batch {
+        /// Input file (reads stdin if omitted or "-")
+        input: Option<PathBuf>,
+    },
+
+    #[cfg(feature = "cli")]
+    /// Compare two card lists by fingerprint and print a JSON report
+    ///
+    /// Reads the first column of each line. Exits 1 if the lists differ.
+    Diff {
+        /// Old card list
+        old: PathBuf,
+
+        /// New card list
+        new: PathBuf,
+
+        /// Masked examples to show per category
+        #[arg(short, long, default_value = "5")]
+        examples: usize,
+    },
+
+    #[cfg(feature = "cli")]
+    /// Scan files for card numbers and print NDJSON findings
+    Scan {
+        /// Files or directories (scanned recursively)
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Keep running and scan files as they are created or modified
+        #[arg(short, long)]
+        watch: bool,
     },
 }
 
@@ -120,74 +280,86 @@ enum OutputFormat {
     Json,
 }
 
-#[derive(Clone, Copy, ValueEnum)]
-enum BrandArg {
-    Visa,
-    Mastercard,
-    Amex,
-    Discover,
-    DinersClub,
-    Jcb,
-    UnionPay,
-    Maestro,
-    Mir,
-    Rupay,
-    Verve,
-    Elo,
-    Troy,
-    BcCard,
-}
+/// Parses a card brand, listing `CardBrand::all()` ids as possible values.
+#[derive(Clone)]
+struct BrandParser;
+
+impl TypedValueParser for BrandParser {
+    type Value = CardBrand;
 
-impl From<BrandArg> for CardBrand {
-    fn from(arg: BrandArg) -> Self {
-        match arg {
-            BrandArg::Visa => CardBrand::Visa,
-            BrandArg::Mastercard => CardBrand::Mastercard,
-            BrandArg::Amex => CardBrand::Amex,
-            BrandArg::Discover => CardBrand::Discover,
-            BrandArg::DinersClub => CardBrand::DinersClub,
-            BrandArg::Jcb => CardBrand::Jcb,
-            BrandArg::UnionPay => CardBrand::UnionPay,
-            BrandArg::Maestro => CardBrand::Maestro,
-            BrandArg::Mir => CardBrand::Mir,
-            BrandArg::Rupay => CardBrand::RuPay,
-            BrandArg::Verve => CardBrand::Verve,
-            BrandArg::Elo => CardBrand::Elo,
-            BrandArg::Troy => CardBrand::Troy,
-            BrandArg::BcCard => CardBrand::BcCard,
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<CardBrand, clap::Error> {
+        if let Some(brand) = value.to_str().and_then(CardBrand::from_name) {
+            return Ok(brand);
         }
+        // Not a known brand: let clap report it with the list of ids
+        let ids = CardBrand::all().iter().map(|b| b.id());
+        PossibleValuesParser::new(ids)
+            .parse_ref(cmd, arg, value)
+            .map(|id| CardBrand::from_name(&id).expect("id is a known brand"))
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            CardBrand::all().iter().map(|b| PossibleValue::new(b.id())),
+        ))
     }
 }
 
 fn main() {
     let cli = Cli::parse();
+    let stdin_only = stdin_only(cli.stdin_only);
+    if stdin_only
+        && std::env::args_os()
+            .skip(1)
+            .any(|arg| arg.to_str().is_some_and(scan::contains_card_number))
+    {
+        refuse_argv_pans();
+    }
 
     match cli.command {
         Commands::Validate {
-            card_number,
+            card_numbers,
+            brand,
             output,
         } => {
-            cmd_validate(&card_number, output);
+            cmd_validate(card_numbers_input(card_numbers, stdin_only), brand, output);
         }
+        #[cfg(all(feature = "cli", not(feature = "no-pan-exposure")))]
         Commands::Generate {
             brand,
             count,
+            length,
             formatted,
         } => {
-            cmd_generate(brand.into(), count, formatted);
+            cmd_generate(brand, count, length, formatted);
+        }
+        #[cfg(all(feature = "cli", not(feature = "no-pan-exposure")))]
+        Commands::Corpus {
+            per_brand,
+            seed,
+            output,
+            fuzz_dir,
+        } => {
+            cmd_corpus(per_brand, seed, output.as_deref(), fuzz_dir.as_deref());
         }
         Commands::Format {
             card_number,
             separator,
         } => {
-            cmd_format(&card_number, &separator);
+            cmd_format(&card_number_input(card_number, stdin_only), &separator);
         }
         Commands::Cvv {
             cvv: cvv_input,
             brand,
         } => {
-            cmd_cvv(&cvv_input, brand.map(|b| b.into()));
+            cmd_cvv(&cvv_input, brand);
         }
+        #[cfg(feature = "cli")]
         Commands::Expiry { date, max_years } => {
             cmd_expiry(&date, max_years);
         }
@@ -195,59 +367,226 @@ fn main() {
             card_number,
             with_bin,
         } => {
-            cmd_mask(&card_number, with_bin);
+            cmd_mask(&card_number_input(card_number, stdin_only), with_bin);
         }
         Commands::Luhn { card_number } => {
-            cmd_luhn(&card_number);
+            cmd_luhn(&card_number_input(card_number, stdin_only));
+        }
+        Commands::Detect {
+            card_number,
+            verbose,
+        } => {
+            cmd_detect(&card_number_input(card_number, stdin_only), verbose);
+        }
+        #[cfg(feature = "cli")]
+        Commands::Bindb { path, bin } => {
+            cmd_bindb(&path, bin.as_deref());
+        }
+        #[cfg(feature = "cli")]
+        Commands::Batch { input } => {
+            cmd_batch(input.as_deref());
         }
-        Commands::Detect { card_number } => {
-            cmd_detect(&card_number);
+        #[cfg(feature = "cli")]
+        Commands::Diff { old, new, examples } => {
+            cmd_diff(&old, &new, examples);
+        }
+        #[cfg(feature = "cli")]
+        Commands::Scan { paths, watch } => {
+            cmd_scan(&paths, watch);
         }
     }
 }
 
-fn cmd_validate(card_number: &str, output: OutputFormat) {
-    match validate(card_number) {
-        Ok(card) => {
-            match output {
-                OutputFormat::Text => {
-                    println!("Valid: yes");
-                    println!("Brand: {}", card.brand().name());
-                    println!("Last Four: {}", card.last_four());
-                    println!("Masked: {}", card.masked());
-                }
-                OutputFormat::Json => {
-                    println!("{{");
-                    println!("  \"valid\": true,");
-                    println!("  \"brand\": \"{}\",", card.brand().name());
-                    println!("  \"last_four\": \"{}\",", card.last_four());
-                    println!("  \"masked\": \"{}\"", card.masked());
-                    println!("}}");
-                }
-            }
-            std::process::exit(0);
+/// Returns true if card numbers may only come from stdin: with
+/// `--stdin-only`, `CC_VALIDATOR_STDIN_ONLY` set to anything but `0`, or in
+/// a `cli-stdin-only` build.
+fn stdin_only(flag: bool) -> bool {
+    cfg!(feature = "cli-stdin-only")
+        || flag
+        || std::env::var("CC_VALIDATOR_STDIN_ONLY").is_ok_and(|v| !v.is_empty() && v != "0")
+}
+
+/// Exits with status 2, explaining why card numbers can't be arguments.
+fn refuse_argv_pans() -> ! {
+    eprintln!(
+        "Error: card numbers in arguments show up in process listings and shell history \
+         (--stdin-only); pipe them on stdin instead"
+    );
+    std::process::exit(2);
+}
+
+/// Returns the card numbers given as arguments, or if there are none (or
+/// just "-"), the non-empty lines of stdin.
+fn card_numbers_input(mut args: Vec<String>, stdin_only: bool) -> Vec<String> {
+    if !args.is_empty() && args != ["-"] {
+        if stdin_only {
+            args.zeroize();
+            refuse_argv_pans();
         }
+        return args;
+    }
+
+    let mut numbers = Vec::new();
+    for line in std::io::stdin().lines() {
+        let Ok(mut line) = line else { break };
+        let number = line.trim();
+        if !number.is_empty() {
+            numbers.push(number.to_string());
+        }
+        line.zeroize();
+    }
+    if numbers.is_empty() {
+        eprintln!("Error: No card numbers on stdin");
+        std::process::exit(2);
+    }
+    numbers
+}
+
+/// Returns the card number given as an argument, or if there is none (or
+/// it is "-"), the first non-empty line of stdin.
+fn card_number_input(arg: Option<String>, stdin_only: bool) -> Zeroizing<String> {
+    let args = arg.into_iter().collect();
+    let mut numbers = card_numbers_input(args, stdin_only);
+    let number = Zeroizing::new(std::mem::take(&mut numbers[0]));
+    numbers.zeroize();
+    number
+}
+
+fn cmd_validate(mut card_numbers: Vec<String>, brand: Option<CardBrand>, output: OutputFormat) {
+    let validator = match brand {
+        Some(brand) => Validator::new().with_allowed_brands([brand]),
+        None => Validator::new(),
+    };
+    let results: Vec<_> = card_numbers
+        .iter()
+        .map(|number| validator.validate(number))
+        .collect();
+    let invalid = results.iter().filter(|r| r.is_err()).count();
+
+    match results.as_slice() {
+        [result] => print_validation(result, brand, output),
+        _ => print_validations(&card_numbers, &results, brand, output),
+    }
+    card_numbers.zeroize();
+    std::process::exit(if invalid == 0 { 0 } else { 1 });
+}
+
+/// Describes a failed validation, naming the expected brand on a mismatch.
+fn validation_error(e: &ValidationError, expected: Option<CardBrand>) -> String {
+    match (e, expected) {
+        (ValidationError::BrandNotAllowed { brand }, Some(expected)) => {
+            format!("brand is {}, expected {}", brand.name(), expected.name())
+        }
+        _ => e.to_string(),
+    }
+}
+
+/// Prints the result for a single number.
+fn print_validation(
+    result: &Result<ValidatedCard, ValidationError>,
+    expected: Option<CardBrand>,
+    output: OutputFormat,
+) {
+    match result {
+        Ok(card) => match output {
+            OutputFormat::Text => {
+                println!("Valid: yes");
+                println!("Brand: {}", card.brand().name());
+                println!("Last Four: {}", card.last_four());
+                println!("Masked: {}", card.masked());
+            }
+            OutputFormat::Json => {
+                println!("{{");
+                println!("  \"valid\": true,");
+                println!("  \"brand\": \"{}\",", card.brand().name());
+                println!("  \"last_four\": \"{}\",", card.last_four());
+                println!("  \"masked\": \"{}\"", card.masked());
+                println!("}}");
+            }
+        },
         Err(e) => {
+            let error = validation_error(e, expected);
             match output {
                 OutputFormat::Text => {
                     println!("Valid: no");
-                    println!("Error: {}", e);
+                    println!("Error: {}", error);
+                    println!("Hint: {}", e.suggestion());
                 }
                 OutputFormat::Json => {
                     println!("{{");
                     println!("  \"valid\": false,");
-                    println!("  \"error\": \"{}\"", e);
+                    println!("  \"error\": \"{}\",", error);
+                    println!("  \"suggestion\": \"{}\"", e.suggestion());
                     println!("}}");
                 }
             }
-            std::process::exit(1);
         }
     }
 }
 
-fn cmd_generate(brand: CardBrand, count: usize, formatted: bool) {
+/// Prints one line (or JSON object) per number, then a summary.
+fn print_validations(
+    card_numbers: &[String],
+    results: &[Result<ValidatedCard, ValidationError>],
+    expected: Option<CardBrand>,
+    output: OutputFormat,
+) {
+    let valid = results.iter().filter(|r| r.is_ok()).count();
+    let entries = card_numbers.iter().zip(results);
+    match output {
+        OutputFormat::Text => {
+            for (number, result) in entries {
+                match result {
+                    Ok(card) => println!("{}: valid ({})", card.masked(), card.brand().name()),
+                    Err(e) => println!(
+                        "{}: invalid ({})",
+                        mask::mask_string(number),
+                        validation_error(e, expected)
+                    ),
+                }
+            }
+            println!("{} of {} valid", valid, results.len());
+        }
+        OutputFormat::Json => {
+            println!("{{");
+            println!("  \"results\": [");
+            for (i, (number, result)) in entries.enumerate() {
+                let entry = match result {
+                    Ok(card) => format!(
+                        "{{\"masked\": \"{}\", \"valid\": true, \"brand\": \"{}\", \"last_four\": \"{}\"}}",
+                        card.masked(),
+                        card.brand().name(),
+                        card.last_four()
+                    ),
+                    Err(e) => format!(
+                        "{{\"masked\": \"{}\", \"valid\": false, \"error\": \"{}\", \"suggestion\": \"{}\"}}",
+                        mask::mask_string(number),
+                        validation_error(e, expected),
+                        e.suggestion()
+                    ),
+                };
+                let comma = if i + 1 < results.len() { "," } else { "" };
+                println!("    {}{}", entry, comma);
+            }
+            println!("  ],");
+            println!("  \"valid\": {},", valid);
+            println!("  \"invalid\": {}", results.len() - valid);
+            println!("}}");
+        }
+    }
+}
+
+#[cfg(all(feature = "cli", not(feature = "no-pan-exposure")))]
+fn cmd_generate(brand: CardBrand, count: usize, length: Option<usize>, formatted: bool) {
+    let generator = match length {
+        Some(length) => generate::CardGenerator::for_length(brand, length).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(2);
+        }),
+        None => generate::CardGenerator::new(brand),
+    };
     for _ in 0..count {
-        let card = generate::generate_card(brand);
+        let card = generator.generate();
         if formatted {
             println!("{}", format::format_card_number(&card));
         } else {
@@ -256,6 +595,56 @@ fn cmd_generate(brand: CardBrand, count: usize, formatted: bool) {
     }
 }
 
+#[cfg(all(feature = "cli", not(feature = "no-pan-exposure")))]
+fn cmd_corpus(per_brand: usize, seed: u64, output: Option<&Path>, fuzz_dir: Option<&Path>) {
+    let corpus = generate::generate_corpus(per_brand, seed);
+
+    if let Some(dir) = fuzz_dir {
+        let written = std::fs::create_dir_all(dir).and_then(|()| {
+            corpus.iter().enumerate().try_for_each(|(i, entry)| {
+                std::fs::write(
+                    dir.join(format!("{:05}-{}", i, entry.variant)),
+                    &entry.input,
+                )
+            })
+        });
+        if let Err(e) = written {
+            eprintln!("Error: {}: {}", dir.display(), e);
+            std::process::exit(2);
+        }
+    }
+
+    let vectors: Vec<_> = corpus
+        .iter()
+        .map(|entry| {
+            serde_json::json!({
+                "input": entry.input,
+                "variant": entry.variant,
+                "generated_for": entry.generated_for.id(),
+                "valid": entry.expected.is_ok(),
+                "brand": entry.expected.ok().map(|b| b.name()),
+                "error": entry.expected.err(),
+            })
+        })
+        .collect();
+    let json = serde_json::to_string_pretty(&serde_json::json!({
+        "seed": seed,
+        "per_brand": per_brand,
+        "vectors": vectors,
+    }))
+    .expect("corpus serializes");
+
+    match output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, json + "\n") {
+                eprintln!("Error: {}: {}", path.display(), e);
+                std::process::exit(2);
+            }
+        }
+        None => println!("{}", json),
+    }
+}
+
 fn cmd_format(card_number: &str, separator: &str) {
     let formatted = format::format_with_separator(card_number, separator);
     println!("{}", formatted);
@@ -281,6 +670,7 @@ fn cmd_cvv(cvv_input: &str, brand: Option<CardBrand>) {
     }
 }
 
+#[cfg(feature = "cli")]
 fn cmd_expiry(date: &str, max_years: Option<u16>) {
     let result = match max_years {
         Some(years) => expiry::validate_expiry_with_options(date, true, Some(years)),
@@ -342,11 +732,28 @@ fn cmd_luhn(card_number: &str) {
         std::process::exit(0);
     } else {
         println!("Luhn check: FAIL");
+        let digits: Vec<u8> = card_number
+            .chars()
+            .filter(|c| c.is_ascii_digit())
+            .map(|c| c as u8 - b'0')
+            .collect();
+        let report = cc_validator::luhn::check(&digits);
+        if digits.len() > 1 && !report.valid {
+            println!(
+                "Check digit should be {} (found {})",
+                report.computed_check, report.found_check
+            );
+        }
         std::process::exit(1);
     }
 }
 
-fn cmd_detect(card_number: &str) {
+fn cmd_detect(card_number: &str, verbose: bool) {
+    if card_number.contains(cc_validator::detect::MASK_CHARS) {
+        cmd_detect_masked(card_number);
+        return;
+    }
+
     let digits: Vec<u8> = card_number
         .chars()
         .filter(|c| c.is_ascii_digit())
@@ -358,14 +765,336 @@ fn cmd_detect(card_number: &str) {
         std::process::exit(1);
     }
 
-    let brand = cc_validator::detect::detect_brand(&digits);
-    match brand {
-        Some(b) => {
+    match cc_validator::detect::detect_brand_range(&digits) {
+        Some(range) => {
+            let b = range.brand;
             println!("Detected Brand: {}", b.name());
             println!("Valid Lengths: {:?}", b.valid_lengths());
+            if verbose {
+                println!("Matched Rule: {} ({} digits)", range, range.digits);
+            }
         }
         None => {
             println!("Detected Brand: Unknown");
         }
     }
 }
+
+fn cmd_detect_masked(masked: &str) {
+    let Some(pan) = cc_validator::detect::detect_from_masked(masked) else {
+        eprintln!("Error: Expected digits, mask characters (* x X # \u{2022}) and separators");
+        std::process::exit(1);
+    };
+
+    match pan.brand {
+        Some(b) => println!("Detected Brand: {}", b.name()),
+        None => println!("Detected Brand: Unknown"),
+    }
+    if let Some(bin) = &pan.bin6 {
+        println!("BIN: {}", bin);
+    }
+    if let Some(last_four) = &pan.last_four {
+        println!("Last Four: {}", last_four);
+    }
+    println!("Length: {}", pan.length);
+}
+
+#[cfg(feature = "cli")]
+fn cmd_bindb(path: &str, bin: Option<&str>) {
+    let db = match cc_validator::bin::open_database(path) {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("Error [{}]: {}", e.code(), e);
+            std::process::exit(1);
+        }
+    };
+
+    println!("Entries: {}", db.len());
+
+    if let Some(bin) = bin {
+        match db.lookup_str(bin) {
+            Some(info) => {
+                println!("BIN: {}", info.bin);
+                if let Some(issuer) = &info.issuer {
+                    println!("Issuer: {}", issuer);
+                }
+                if let Some(card_type) = &info.card_type {
+                    println!("Card Type: {}", card_type);
+                }
+                if let Some(country) = &info.country {
+                    println!("Country: {}", country);
+                }
+            }
+            None => {
+                println!("BIN {} not found", bin);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "cli")]
+/// Number of records validated between reads of the input.
+const BATCH_CHUNK: usize = 4096;
+
+#[cfg(feature = "cli")]
+fn cmd_batch(input: Option<&Path>) {
+    let mut reader: Box<dyn BufRead> = match input {
+        None => Box::new(std::io::stdin().lock()),
+        Some(path) if path == Path::new("-") => Box::new(std::io::stdin().lock()),
+        Some(path) => match std::fs::File::open(path) {
+            Ok(file) => Box::new(std::io::BufReader::new(file)),
+            Err(e) => {
+                eprintln!("Error: {}: {}", path.display(), e);
+                std::process::exit(2);
+            }
+        },
+    };
+
+    let mut batch = cc_validator::BatchValidator::new();
+    let mut checkpoint = cc_validator::batch::Checkpoint::default();
+    let mut brands: HashMap<&'static str, u64> = HashMap::new();
+    let mut errors: HashMap<&'static str, u64> = HashMap::new();
+    let start = Instant::now();
+
+    loop {
+        let results = match batch.validate_chunk(&mut reader, &mut checkpoint, BATCH_CHUNK) {
+            Ok(results) => results,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(2);
+            }
+        };
+        if results.is_empty() {
+            break;
+        }
+        for (_, result) in results {
+            match result {
+                Ok(card) => *brands.entry(card.brand().id()).or_default() += 1,
+                Err(e) => *errors.entry(e.code()).or_default() += 1,
+            }
+        }
+    }
+
+    let duration = start.elapsed().as_secs_f64();
+    let total = checkpoint.valid + checkpoint.invalid;
+    let cards_per_sec = if duration > 0.0 {
+        total as f64 / duration
+    } else {
+        0.0
+    };
+    let report = serde_json::json!({
+        "total": total,
+        "valid": checkpoint.valid,
+        "invalid": checkpoint.invalid,
+        "lines": checkpoint.lines,
+        "brands": brands,
+        "errors": errors,
+        "duration_ms": duration * 1000.0,
+        "cards_per_sec": cards_per_sec,
+    });
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&report).expect("report serializes")
+    );
+}
+
+#[cfg(feature = "cli")]
+/// Cards in one list, by fingerprint, with the masked number and how often
+/// each appears, plus the number of lines that didn't hold a valid card.
+struct CardList {
+    cards: HashMap<Fingerprint, (String, u64)>,
+    invalid: u64,
+}
+
+#[cfg(feature = "cli")]
+/// Reads the first CSV column of every line of `path` as a card number.
+///
+/// Only fingerprints and masked numbers are kept, never the PANs.
+fn read_card_list(path: &Path, key: &[u8]) -> CardList {
+    let file = std::fs::File::open(path).unwrap_or_else(|e| {
+        eprintln!("Error: {}: {}", path.display(), e);
+        std::process::exit(2);
+    });
+
+    let mut list = CardList {
+        cards: HashMap::new(),
+        invalid: 0,
+    };
+    for line in std::io::BufReader::new(file).lines() {
+        let line = line.unwrap_or_else(|e| {
+            eprintln!("Error: {}: {}", path.display(), e);
+            std::process::exit(2);
+        });
+        let field = line
+            .split(',')
+            .next()
+            .unwrap_or("")
+            .trim()
+            .trim_matches('"');
+        if field.is_empty() {
+            continue;
+        }
+        match validate(field) {
+            Ok(card) => {
+                list.cards
+                    .entry(card.fingerprint(key))
+                    .or_insert_with(|| (card.masked_with_bin(), 0))
+                    .1 += 1;
+            }
+            Err(_) => list.invalid += 1,
+        }
+    }
+    list
+}
+
+#[cfg(feature = "cli")]
+fn cmd_diff(old: &Path, new: &Path, examples: usize) {
+    // A fresh key per run: fingerprints only need to match within the run
+    let key: [u8; 32] = rand::random();
+    let old_list = read_card_list(old, &key);
+    let new_list = read_card_list(new, &key);
+
+    let only_in = |a: &CardList, b: &CardList| -> Vec<String> {
+        let mut masked: Vec<String> = a
+            .cards
+            .iter()
+            .filter(|(fp, _)| !b.cards.contains_key(fp))
+            .map(|(_, (masked, _))| masked.clone())
+            .collect();
+        masked.sort();
+        masked
+    };
+    let added = only_in(&new_list, &old_list);
+    let removed = only_in(&old_list, &new_list);
+    let unchanged = old_list.cards.len() - removed.len();
+
+    let summary = |list: &CardList| {
+        serde_json::json!({
+            "distinct": list.cards.len(),
+            "duplicates": list.cards.values().map(|(_, n)| n - 1).sum::<u64>(),
+            "invalid": list.invalid,
+        })
+    };
+    let report = serde_json::json!({
+        "old": summary(&old_list),
+        "new": summary(&new_list),
+        "added": added.len(),
+        "removed": removed.len(),
+        "unchanged": unchanged,
+        "examples": {
+            "added": &added[..added.len().min(examples)],
+            "removed": &removed[..removed.len().min(examples)],
+        },
+    });
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&report).expect("report serializes")
+    );
+    let differ = !added.is_empty() || !removed.is_empty();
+    std::process::exit(if differ { 1 } else { 0 });
+}
+
+#[cfg(feature = "cli")]
+/// Findings already printed, per file, so rescans of a modified file only
+/// report new ones.
+type Reported = HashMap<PathBuf, HashSet<cc_validator::scan::Finding>>;
+
+#[cfg(feature = "cli")]
+fn cmd_scan(paths: &[PathBuf], watch: bool) {
+    if watch {
+        watch_paths(paths);
+    }
+
+    let mut reported = Reported::new();
+    let mut found = 0;
+    for path in paths {
+        found += scan_path(path, &mut reported);
+    }
+    std::process::exit(if found > 0 { 1 } else { 0 });
+}
+
+/// Scans a file, or every file below a directory, returning the number of
+/// findings printed.
+#[cfg(feature = "cli")]
+fn scan_path(path: &Path, reported: &mut Reported) -> usize {
+    if path.is_dir() {
+        let entries = match std::fs::read_dir(path) {
+            Ok(entries) => entries,
+            Err(e) => {
+                eprintln!("Error: {}: {}", path.display(), e);
+                return 0;
+            }
+        };
+        return entries
+            .flatten()
+            .map(|entry| scan_path(&entry.path(), reported))
+            .sum();
+    }
+
+    let findings = match std::fs::File::open(path)
+        .and_then(|file| cc_validator::scan::scan_reader(std::io::BufReader::new(file)))
+    {
+        Ok(findings) => findings,
+        Err(e) => {
+            eprintln!("Error: {}: {}", path.display(), e);
+            return 0;
+        }
+    };
+
+    let seen = reported.entry(path.to_path_buf()).or_default();
+    let mut printed = 0;
+    for finding in findings {
+        if seen.contains(&finding) {
+            continue;
+        }
+        let record = serde_json::json!({
+            "path": path.display().to_string(),
+            "line": finding.line,
+            "column": finding.column,
+            "brand": finding.brand.name(),
+            "masked": finding.masked,
+        });
+        println!("{}", record);
+        seen.insert(finding);
+        printed += 1;
+    }
+    printed
+}
+
+/// Watches `paths` and scans files as they are created or modified. Only
+/// returns by exiting the process.
+#[cfg(feature = "cli")]
+fn watch_paths(paths: &[PathBuf]) -> ! {
+    use notify::{EventKind, RecursiveMode, Watcher};
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(tx) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(2);
+        }
+    };
+    for path in paths {
+        if let Err(e) = watcher.watch(path, RecursiveMode::Recursive) {
+            eprintln!("Error: {}: {}", path.display(), e);
+            std::process::exit(2);
+        }
+    }
+
+    let mut reported = Reported::new();
+    for event in rx {
+        match event {
+            Ok(event) => {
+                if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                    for path in event.paths.iter().filter(|p| p.is_file()) {
+                        scan_path(path, &mut reported);
+                    }
+                }
+            }
+            Err(e) => eprintln!("Error: {}", e),
+        }
+    }
+    std::process::exit(2);
+}
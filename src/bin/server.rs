@@ -8,27 +8,110 @@
 //!
 //! # With custom port
 //! ccvalidator-server --port 8080
+//!
+//! # Never return more than the last four digits, even when clients ask
+//! # for masked numbers
+//! ccvalidator-server --max-mask last_four
+//!
+//! # Reject well-known processor test cards
+//! ccvalidator-server --reject-test-cards
+//!
+//! # Log request and response bodies, with card numbers redacted
+//! ccvalidator-server --log-bodies
+//!
+//! # Serve several merchants with their own profiles
+//! ccvalidator-server --tenants tenants.json
+//! ```
+//!
+//! Validation is further configured through `CC_VALIDATOR_*` environment
+//! variables (allowed brands, strictness, BIN database); see
+//! `cc_validator::validator`.
+//!
+//! With a BIN database, validation responses carry the issuer details of
+//! valid cards. Lookups go through `cc_validator::bin::AsyncBinDatabase`, so
+//! a Redis database is awaited rather than blocking a worker thread: on the
+//! async connection with the `bin-redis-async` feature, otherwise on tokio's
+//! blocking pool.
+//!
+//! Built with the `no-pan-exposure` feature, the server leaves out
+//! `/generate`, the only endpoint that returns full card numbers.
+//!
+//! # Tenants
+//!
+//! `--tenants` loads named profiles from a JSON file, each with its own BIN
+//! database, brand policy and mask cap:
+//!
+//! ```json
+//! {
+//!   "acme": {
+//!     "api_keys": ["acme-secret"],
+//!     "allowed_brands": ["visa", "mastercard"],
+//!     "strictness": "strict",
+//!     "bin_db_path": "acme-bins.json",
+//!     "max_mask": "masked"
+//!   },
+//!   "globex": { "allowed_brands": ["amex"] }
+//! }
 //! ```
 //!
+//! Requests pick a profile with an `X-API-Key` header, or an `X-Tenant`
+//! header naming a tenant that has no API keys. Requests with neither use
+//! the environment configuration. A tenant's `max_mask` can only tighten
+//! `--max-mask`, and `--reject-test-cards` applies to every tenant.
+//!
+//! # Metrics
+//!
+//! `/metrics` serves validation counts by outcome, brand and error code in
+//! the Prometheus text format (see `cc_validator::metrics`), summed over
+//! the default profile and every tenant.
+//!
 //! # Swagger UI
 //!
 //! Visit http://localhost:3000/swagger-ui/ for interactive API documentation.
+//! The OpenAPI document is served at `/api-docs/openapi.json` and
+//! `/api-docs/openapi.yaml`.
+//!
+//! # Versioning
+//!
+//! Every response carries an `api_version` field (see
+//! `cc_validator::schemas::API_VERSION`). Endpoints scheduled for removal
+//! answer with `Deprecation` and `Sunset` headers.
 
 use axum::{
-    extract::Query,
-    http::{header, Method, StatusCode},
-    response::Json,
+    async_trait,
+    body::Body,
+    extract::{FromRequestParts, Query, Request, State},
+    http::{header, request::Parts, HeaderMap, HeaderName, HeaderValue, Method, StatusCode, Uri},
+    middleware::{self, Next},
+    response::{IntoResponse, Json, Response},
     routing::{get, post},
     Router,
 };
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
+use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
 use tower_http::cors::{Any, CorsLayer};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-use utoipa::{IntoParams, OpenApi, ToSchema};
+use utoipa::openapi::{Deprecated, PathItemType};
+use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
-use cc_validator::{cvv, detect, expiry, format, generate, validate, CardBrand};
+use cc_validator::bin::{lookup_cards, open_async_database, AsyncBinDatabase};
+use cc_validator::cvv::CardWithCvv;
+#[cfg(not(feature = "no-pan-exposure"))]
+use cc_validator::generate;
+use cc_validator::metrics::ValidationMetrics;
+use cc_validator::scan::redact;
+use cc_validator::schemas::*;
+use cc_validator::testcard::TestCardPolicy;
+use cc_validator::validator::{ConfigError, Strictness, ValidatorConfig};
+use cc_validator::{
+    cvv, detect, expiry, format, luhn, mask, set_default_validator, validate, BatchValidator,
+    CardBrand, ValidatedCard, ValidationError, ValidationOutcome, ValidationWarning, Validator,
+};
 
 // ============================================================================
 // OpenAPI Documentation
@@ -38,8 +121,8 @@ use cc_validator::{cvv, detect, expiry, format, generate, validate, CardBrand};
 #[openapi(
     info(
         title = "Credit Card Validator API",
-        version = "0.1.0",
-        description = "Credit card validation REST API. Supports 14 card brands, CVV, expiry validation. Work in progress - no auth or rate limiting.",
+        version = "1.0.0",
+        description = "Credit card validation REST API. Supports 20 card brands, CVV, expiry validation. Work in progress - no auth or rate limiting.",
         license(name = "MIT OR Apache-2.0"),
         contact(name = "API Support")
     ),
@@ -55,209 +138,441 @@ use cc_validator::{cvv, detect, expiry, format, generate, validate, CardBrand};
     paths(
         validate_card,
         validate_batch,
+        detect_brand_post,
         detect_brand_handler,
+        list_brands,
         format_card,
-        generate_cards,
+        luhn_check,
+        mask_card,
         validate_cvv_handler,
         validate_expiry_handler,
         health,
+        metrics_handler,
     ),
     components(schemas(
         ValidateRequest,
         ValidateResponse,
+        BinDetails,
         BatchValidateRequest,
         BatchValidateResponse,
         BatchSummary,
+        MaskStyle,
         DetectQuery,
+        DetectRequest,
         DetectResponse,
+        BrandSummary,
+        BrandsResponse,
         FormatRequest,
         FormatResponse,
-        GenerateRequest,
-        GenerateResponse,
+        LuhnRequest,
+        LuhnResponse,
+        MaskRequest,
+        MaskResponse,
         CvvRequest,
         CvvResponse,
         ExpiryRequest,
         ExpiryResponse,
+        expiry::ExpiryDate,
         HealthResponse,
     ))
 )]
 struct ApiDoc;
 
+/// The test card generation endpoint, left out of `no-pan-exposure` builds.
+#[cfg(not(feature = "no-pan-exposure"))]
+#[derive(OpenApi)]
+#[openapi(
+    paths(generate_cards),
+    components(schemas(GenerateRequest, GenerateResponse))
+)]
+struct GenerateApiDoc;
+
+/// Builds the OpenAPI document, stamped with the schema version and with
+/// deprecated endpoints flagged.
+fn api_doc() -> utoipa::openapi::OpenApi {
+    let mut doc = ApiDoc::openapi();
+    #[cfg(not(feature = "no-pan-exposure"))]
+    doc.merge(GenerateApiDoc::openapi());
+    doc.info.version = API_VERSION.to_string();
+    for deprecation in DEPRECATED_ENDPOINTS {
+        if let Some(item) = doc.paths.paths.get_mut(deprecation.path) {
+            for (method, operation) in item.operations.iter_mut() {
+                if method_name(method) == deprecation.method {
+                    operation.deprecated = Some(Deprecated::True);
+                }
+            }
+        }
+    }
+    doc
+}
+
+/// Returns the HTTP method of an OpenAPI operation, e.g. `"GET"`.
+fn method_name(method: &PathItemType) -> &'static str {
+    match method {
+        PathItemType::Get => "GET",
+        PathItemType::Post => "POST",
+        PathItemType::Put => "PUT",
+        PathItemType::Delete => "DELETE",
+        PathItemType::Options => "OPTIONS",
+        PathItemType::Head => "HEAD",
+        PathItemType::Patch => "PATCH",
+        PathItemType::Trace => "TRACE",
+        PathItemType::Connect => "CONNECT",
+    }
+}
+
+// ============================================================================
+// Deprecation
+// ============================================================================
+
+/// An endpoint scheduled for removal.
+///
+/// Responses from a deprecated endpoint carry `Deprecation: true` and a
+/// `Sunset` header (RFC 8594), plus a `Link` to the replacement when there is
+/// one. The endpoint is also marked `deprecated` in the OpenAPI document.
+struct Deprecation {
+    /// HTTP method, e.g. `"GET"`
+    method: &'static str,
+    /// Route path, as registered with the router
+    path: &'static str,
+    /// HTTP-date after which the endpoint may be removed
+    sunset: &'static str,
+    /// Path of the replacement endpoint, if any
+    successor: Option<&'static str>,
+}
+
+/// Endpoints currently scheduled for removal.
+///
+/// An endpoint stays listed for at least one minor release before it is
+/// removed in the next major version of the API.
+const DEPRECATED_ENDPOINTS: &[Deprecation] = &[
+    // The card prefix in the query string ends up in access logs
+    Deprecation {
+        method: "GET",
+        path: "/detect",
+        sunset: "Thu, 01 Apr 2027 00:00:00 GMT",
+        successor: None,
+    },
+];
+
+/// Adds deprecation headers to responses from deprecated endpoints.
+async fn deprecation_headers(req: Request, next: Next) -> Response {
+    let deprecation = deprecation_headers_for(DEPRECATED_ENDPOINTS, req.method(), req.uri().path());
+    let mut response = next.run(req).await;
+
+    if let Some(headers) = deprecation {
+        response.headers_mut().extend(headers);
+    }
+    response
+}
+
+/// Returns the headers for a request to `method path` if `table` lists it
+/// as deprecated.
+fn deprecation_headers_for(
+    table: &[Deprecation],
+    method: &Method,
+    path: &str,
+) -> Option<HeaderMap> {
+    let deprecation = table
+        .iter()
+        .find(|d| d.method == method.as_str() && d.path == path)?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert("deprecation", HeaderValue::from_static("true"));
+    headers.insert("sunset", HeaderValue::from_static(deprecation.sunset));
+    if let Some(successor) = deprecation.successor {
+        let link = format!("<{}>; rel=\"successor-version\"", successor);
+        if let Ok(value) = HeaderValue::from_str(&link) {
+            headers.insert(header::LINK, value);
+        }
+    }
+    Some(headers)
+}
+
+// ============================================================================
+// Body Logging
+// ============================================================================
+
+/// Query parameters that carry card digits and are never logged.
+const SCRUBBED_PARAMS: &[&str] = &["card"];
+
+/// Body fields holding card verification codes, which PCI DSS forbids
+/// storing, so they are never logged.
+const SCRUBBED_FIELDS: &[&str] = &["cvv", "cvc", "cvv2", "cvc2", "security_code"];
+
+/// Returns `uri` for the request span, with the values of
+/// [`SCRUBBED_PARAMS`] replaced and other card numbers redacted.
+fn scrubbed_uri(uri: &Uri) -> String {
+    match uri.query() {
+        Some(query) => format!("{}?{}", uri.path(), scrubbed_pairs(query, SCRUBBED_PARAMS)),
+        None => uri.path().to_string(),
+    }
+}
+
+/// Replaces the values of `keys` in `key=value&...` text and redacts card
+/// numbers in the rest.
+fn scrubbed_pairs(text: &str, keys: &[&str]) -> String {
+    let pairs: Vec<String> = text
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((key, _)) if keys.contains(&key) => format!("{}=[redacted]", key),
+            _ => redact(pair),
+        })
+        .collect();
+    pairs.join("&")
+}
+
+/// Returns a request or response body for the log: JSON fields and form
+/// fields named in [`SCRUBBED_FIELDS`] are replaced, and card numbers are
+/// redacted everywhere else.
+fn scrubbed_body(bytes: &[u8]) -> String {
+    match serde_json::from_slice::<serde_json::Value>(bytes) {
+        Ok(mut json) => {
+            scrub_json(&mut json);
+            redact(&json.to_string())
+        }
+        Err(_) => scrubbed_pairs(&String::from_utf8_lossy(bytes), SCRUBBED_FIELDS),
+    }
+}
+
+/// Replaces the values of [`SCRUBBED_FIELDS`] anywhere in `value`.
+fn scrub_json(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if SCRUBBED_FIELDS.contains(&key.as_str()) {
+                    *value = serde_json::Value::from("[redacted]");
+                } else {
+                    scrub_json(value);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(scrub_json),
+        _ => {}
+    }
+}
+
+/// Largest body buffered for logging, matching axum's default body limit.
+const MAX_LOGGED_BODY: usize = 2 * 1024 * 1024;
+
+/// Logs request and response bodies with card numbers redacted.
+///
+/// Bodies go through `cc_validator::scan::redact` before they are written,
+/// so every card-number-like run of digits (valid or not) is masked down to
+/// its last four digits, and CVV fields are dropped (see [`scrubbed_body`]).
+/// Enabled with `--log-bodies`.
+async fn log_bodies(req: Request, next: Next) -> Response {
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+
+    let (parts, body) = req.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, MAX_LOGGED_BODY).await else {
+        return StatusCode::PAYLOAD_TOO_LARGE.into_response();
+    };
+    let request_body = scrubbed_body(&bytes);
+    let response = next
+        .run(Request::from_parts(parts, Body::from(bytes)))
+        .await;
+
+    let (parts, body) = response.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, usize::MAX).await else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+    tracing::info!(
+        %method,
+        %path,
+        status = parts.status.as_u16(),
+        request = %request_body,
+        response = %scrubbed_body(&bytes),
+        "request"
+    );
+    Response::from_parts(parts, Body::from(bytes))
+}
+
 // ============================================================================
-// Request/Response Types
+// Server State
 // ============================================================================
 
-#[derive(Deserialize, ToSchema)]
-#[schema(example = json!({"card_number": "4111-1111-1111-1111"}))]
-struct ValidateRequest {
-    /// Card number to validate. Accepts digits with optional spaces or dashes as separators.
-    /// Example formats: "4111111111111111", "4111-1111-1111-1111", "4111 1111 1111 1111"
-    card_number: String,
-}
-
-#[derive(Serialize, ToSchema)]
-#[schema(example = json!({
-    "valid": true,
-    "brand": "Visa",
-    "last_four": "1111",
-    "masked": "****-****-****-1111"
-}))]
-struct ValidateResponse {
-    /// Whether the card number passed all validation checks (Luhn checksum, length, brand detection)
-    valid: bool,
-    /// Detected card brand. Supports: Visa, Mastercard, American Express, Discover, Diners Club, JCB, UnionPay, Maestro, Mir, RuPay, Verve, Elo, Troy, BC Card
-    #[serde(skip_serializing_if = "Option::is_none")]
-    brand: Option<String>,
-    /// Last 4 digits of the card number (safe for display per PCI-DSS)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    last_four: Option<String>,
-    /// Masked card number in format ****-****-****-1234 (safe for logging and display)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    masked: Option<String>,
-    /// Human-readable error message explaining why validation failed
-    #[serde(skip_serializing_if = "Option::is_none")]
-    error: Option<String>,
-}
-
-#[derive(Deserialize, ToSchema)]
-#[schema(example = json!({"card_numbers": ["4111111111111111", "5500000000000004", "378282246310005"]}))]
-struct BatchValidateRequest {
-    /// List of card numbers to validate. Each number can include spaces or dashes.
-    card_numbers: Vec<String>,
-}
-
-#[derive(Serialize, ToSchema)]
-struct BatchValidateResponse {
-    /// Validation results for each card
-    results: Vec<ValidateResponse>,
-    /// Summary statistics
-    summary: BatchSummary,
-}
-
-#[derive(Serialize, ToSchema)]
-struct BatchSummary {
-    /// Total cards processed
-    total: usize,
-    /// Number of valid cards
-    valid: usize,
-    /// Number of invalid cards
-    invalid: usize,
-}
-
-#[derive(Deserialize, ToSchema, IntoParams)]
-struct DetectQuery {
-    /// Card number or prefix to detect
-    card: String,
-}
-
-#[derive(Serialize, ToSchema)]
-struct DetectResponse {
-    /// Detected brand name
-    brand: Option<String>,
-    /// Valid lengths for this brand
-    valid_lengths: Option<Vec<usize>>,
-}
-
-#[derive(Deserialize, ToSchema)]
-struct FormatRequest {
-    /// Card number to format
-    card_number: String,
-    /// Separator character (default: space)
-    #[serde(default = "default_separator")]
-    separator: String,
-}
-
-fn default_separator() -> String {
-    " ".to_string()
-}
-
-#[derive(Serialize, ToSchema)]
-struct FormatResponse {
-    /// Formatted card number
-    formatted: String,
-    /// Card number with formatting stripped
-    stripped: String,
-}
-
-#[derive(Deserialize, ToSchema)]
-#[schema(example = json!({"brand": "visa", "count": 3, "formatted": true}))]
-struct GenerateRequest {
-    /// Card brand to generate. Options: visa, mastercard, amex, discover, jcb, diners, unionpay, maestro, mir, rupay, verve, elo, troy, bccard
-    brand: String,
-    /// Number of test cards to generate (1-100, default: 1)
-    #[serde(default = "default_count")]
-    count: usize,
-    /// Whether to format output with spaces (e.g., "4111 1111 1111 1111")
+/// Header naming the tenant profile of a request.
+const TENANT_HEADER: HeaderName = HeaderName::from_static("x-tenant");
+
+/// Header carrying a tenant's API key.
+const API_KEY_HEADER: HeaderName = HeaderName::from_static("x-api-key");
+
+/// Validation policy for one tenant, or for requests without one.
+struct Profile {
+    validator: Validator,
+    /// BIN database for issuer details, if configured.
+    bins: Option<Arc<dyn AsyncBinDatabase>>,
+    /// Most exposed mask style clients may request.
+    max_mask: MaskStyle,
+    /// Whether the profile can only be selected by API key.
+    requires_key: bool,
+}
+
+impl Profile {
+    /// Resolves a requested mask style against the profile's policy.
+    fn mask_style(&self, requested: Option<MaskStyle>) -> MaskStyle {
+        requested.unwrap_or_default().min(self.max_mask)
+    }
+
+    /// Looks up the issuer of a valid card in the profile's BIN database.
+    async fn bin_details(
+        &self,
+        card: &Result<ValidatedCard, ValidationError>,
+    ) -> Option<BinDetails> {
+        let (Some(bins), Ok(card)) = (&self.bins, card) else {
+            return None;
+        };
+        bins.lookup_card(card).await.map(BinDetails::from)
+    }
+}
+
+/// Builds a validator from `config`, opening its BIN database for async
+/// lookups instead of attaching it to the validator.
+fn validator_and_bins(
+    mut config: ValidatorConfig,
+) -> Result<(Validator, Option<Arc<dyn AsyncBinDatabase>>), ConfigError> {
+    let bins = config
+        .bin_db_path
+        .take()
+        .map(open_async_database)
+        .transpose()?;
+    Ok((Validator::from_config(&config)?, bins))
+}
+
+/// Profiles keyed by tenant name or API key.
+type Profiles = HashMap<String, Arc<Profile>>;
+
+/// Server-wide configuration shared by handlers.
+struct AppState {
+    /// Profile for requests that don't name a tenant.
+    default: Arc<Profile>,
+    /// Tenant profiles by name.
+    tenants: Profiles,
+    /// Tenant profiles by API key.
+    api_keys: Profiles,
+    /// OpenAPI document rendered as YAML, served at `/api-docs/openapi.yaml`.
+    openapi_yaml: String,
+    /// Validation counters shared by every profile, served at `/metrics`.
+    metrics: Arc<ValidationMetrics>,
+}
+
+impl AppState {
+    /// Selects the profile for a request from its `X-API-Key` or
+    /// `X-Tenant` header.
+    fn profile(&self, headers: &HeaderMap) -> Result<Arc<Profile>, (StatusCode, String)> {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::trim)
+        };
+        if let Some(key) = header(API_KEY_HEADER) {
+            return self
+                .api_keys
+                .get(key)
+                .cloned()
+                .ok_or_else(|| (StatusCode::UNAUTHORIZED, "Unknown API key".to_string()));
+        }
+        match header(TENANT_HEADER) {
+            None => Ok(self.default.clone()),
+            Some(name) => match self.tenants.get(name) {
+                Some(profile) if profile.requires_key => Err((
+                    StatusCode::UNAUTHORIZED,
+                    format!("Tenant {} requires an API key", name),
+                )),
+                Some(profile) => Ok(profile.clone()),
+                None => Err((StatusCode::NOT_FOUND, format!("Unknown tenant: {}", name))),
+            },
+        }
+    }
+}
+
+/// Headers selecting a tenant profile, documented on the endpoints that
+/// use one.
+#[derive(utoipa::IntoParams)]
+#[into_params(names("X-Tenant", "X-API-Key"), parameter_in = Header)]
+#[allow(dead_code)]
+struct TenantHeaders(
+    /// Tenant name, for tenants without API keys
+    Option<String>,
+    /// API key of a tenant; takes precedence over `X-Tenant`
+    Option<String>,
+);
+
+/// Extracts the request's [`Profile`] (see [`AppState::profile`]).
+struct Tenant(Arc<Profile>);
+
+#[async_trait]
+impl FromRequestParts<Arc<AppState>> for Tenant {
+    type Rejection = (StatusCode, String);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        state.profile(&parts.headers).map(Tenant)
+    }
+}
+
+/// One entry of the `--tenants` file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TenantConfig {
+    /// Keys that select this tenant; if any are set, `X-Tenant` alone won't.
     #[serde(default)]
-    formatted: bool,
-}
-
-fn default_count() -> usize {
-    1
-}
-
-#[derive(Serialize, ToSchema)]
-struct GenerateResponse {
-    /// Generated card numbers
-    cards: Vec<String>,
-}
-
-#[derive(Deserialize, ToSchema)]
-#[schema(example = json!({"cvv": "123", "brand": "visa"}))]
-struct CvvRequest {
-    /// CVV/CVC/CID code to validate (3-4 digits)
-    cvv: String,
-    /// Card brand for brand-specific validation. American Express requires 4 digits, all others require 3.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    brand: Option<String>,
-}
-
-#[derive(Serialize, ToSchema)]
-struct CvvResponse {
-    /// Whether the CVV is valid
-    valid: bool,
-    /// CVV length
-    #[serde(skip_serializing_if = "Option::is_none")]
-    length: Option<usize>,
-    /// Error message if validation failed
-    #[serde(skip_serializing_if = "Option::is_none")]
-    error: Option<String>,
-}
-
-#[derive(Deserialize, ToSchema)]
-#[schema(example = json!({"date": "12/25"}))]
-struct ExpiryRequest {
-    /// Expiry date in various formats: MM/YY, MM/YYYY, MMYY, MMYYYY, MM-YY, MM-YYYY
-    date: String,
-}
-
-#[derive(Serialize, ToSchema)]
-struct ExpiryResponse {
-    /// Whether the expiry is valid
-    valid: bool,
-    /// Month (1-12)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    month: Option<u8>,
-    /// Year (4 digits)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    year: Option<u16>,
-    /// Whether the card is expired
-    #[serde(skip_serializing_if = "Option::is_none")]
-    expired: Option<bool>,
-    /// Formatted date (MM/YY)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    formatted: Option<String>,
-    /// Error message if validation failed
-    #[serde(skip_serializing_if = "Option::is_none")]
-    error: Option<String>,
-}
-
-#[derive(Serialize, ToSchema)]
-struct HealthResponse {
-    /// Service status
-    status: String,
-    /// API version
-    version: String,
+    api_keys: Vec<String>,
+    allowed_brands: Option<Vec<String>>,
+    strictness: Option<String>,
+    bin_db_path: Option<PathBuf>,
+    max_mask: Option<MaskStyle>,
+}
+
+/// Loads tenant profiles, returning them by name and by API key.
+fn load_tenants(
+    path: &str,
+    max_mask: MaskStyle,
+    test_cards: TestCardPolicy,
+    metrics: &Arc<ValidationMetrics>,
+) -> Result<(Profiles, Profiles), String> {
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let configs: HashMap<String, TenantConfig> =
+        serde_json::from_str(&text).map_err(|e| e.to_string())?;
+
+    let mut tenants = HashMap::new();
+    let mut api_keys = HashMap::new();
+    for (name, config) in configs {
+        let strictness = match &config.strictness {
+            Some(s) => Strictness::from_name(s).ok_or_else(|| {
+                format!("{}: {}", name, ConfigError::InvalidStrictness(s.clone()))
+            })?,
+            None => Strictness::default(),
+        };
+        let (validator, bins) = validator_and_bins(ValidatorConfig {
+            allowed_brands: config.allowed_brands,
+            strictness,
+            bin_db_path: config.bin_db_path,
+        })
+        .map_err(|e| format!("{}: {}", name, e))?;
+        let validator = validator
+            .with_metrics(metrics.clone())
+            .with_test_card_policy(test_cards);
+
+        let profile = Arc::new(Profile {
+            validator,
+            bins,
+            max_mask: config.max_mask.unwrap_or(max_mask).min(max_mask),
+            requires_key: !config.api_keys.is_empty(),
+        });
+        for key in config.api_keys {
+            if api_keys.insert(key, profile.clone()).is_some() {
+                return Err(format!("{}: API key is shared with another tenant", name));
+            }
+        }
+        tenants.insert(name, profile);
+    }
+    Ok((tenants, api_keys))
 }
 
 // ============================================================================
@@ -269,28 +584,46 @@ struct HealthResponse {
     post,
     path = "/validate",
     request_body = ValidateRequest,
+    params(TenantHeaders),
     responses(
-        (status = 200, description = "Validation result", body = ValidateResponse)
+        (status = 200, description = "Validation result", body = ValidateResponse),
+        (status = 401, description = "Unknown API key, or the tenant requires one"),
+        (status = 404, description = "Unknown tenant")
     ),
     tag = "Validation"
 )]
-async fn validate_card(Json(req): Json<ValidateRequest>) -> Json<ValidateResponse> {
-    match validate(&req.card_number) {
-        Ok(card) => Json(ValidateResponse {
-            valid: true,
-            brand: Some(card.brand().name().to_string()),
-            last_four: Some(card.last_four().to_string()),
-            masked: Some(card.masked()),
-            error: None,
-        }),
-        Err(e) => Json(ValidateResponse {
-            valid: false,
-            brand: None,
-            last_four: None,
-            masked: None,
-            error: Some(e.to_string()),
-        }),
-    }
+async fn validate_card(
+    Tenant(profile): Tenant,
+    Json(req): Json<ValidateRequest>,
+) -> Json<ValidateResponse> {
+    let style = profile.mask_style(req.mask);
+    let card = if req.allow_unknown_brand {
+        profile.validator.validate_any(&req.card_number)
+    } else {
+        profile.validator.validate(&req.card_number)
+    };
+    let bin = profile.bin_details(&card).await;
+    let currency = bin
+        .as_ref()
+        .and_then(BinDetails::currency)
+        .map(str::to_string);
+    let Some(cvv) = req.cvv else {
+        return Json(ValidateResponse {
+            api_version: api_version(),
+            currency,
+            bin,
+            ..card_response(card, style)
+        });
+    };
+    let result = CardWithCvv::new(card, &cvv);
+    Json(ValidateResponse {
+        api_version: api_version(),
+        currency,
+        bin,
+        cvv_valid: Some(result.cvv.is_ok()),
+        cvv_error: result.cvv.err().map(|e| e.to_string()),
+        ..card_response(result.card, style)
+    })
 }
 
 /// Validate multiple card numbers
@@ -298,46 +631,83 @@ async fn validate_card(Json(req): Json<ValidateRequest>) -> Json<ValidateRespons
     post,
     path = "/validate/batch",
     request_body = BatchValidateRequest,
+    params(TenantHeaders),
     responses(
-        (status = 200, description = "Batch validation results", body = BatchValidateResponse)
+        (status = 200, description = "Batch validation results", body = BatchValidateResponse),
+        (status = 401, description = "Unknown API key, or the tenant requires one"),
+        (status = 404, description = "Unknown tenant"),
+        (status = 500, description = "Validation task failed")
     ),
     tag = "Validation"
 )]
-async fn validate_batch(Json(req): Json<BatchValidateRequest>) -> Json<BatchValidateResponse> {
-    let results: Vec<ValidateResponse> = req
-        .card_numbers
-        .iter()
-        .map(|card| match validate(card) {
-            Ok(c) => ValidateResponse {
-                valid: true,
-                brand: Some(c.brand().name().to_string()),
-                last_four: Some(c.last_four().to_string()),
-                masked: Some(c.masked()),
-                error: None,
-            },
-            Err(e) => ValidateResponse {
-                valid: false,
-                brand: None,
-                last_four: None,
-                masked: None,
-                error: Some(e.to_string()),
-            },
-        })
-        .collect();
+async fn validate_batch(
+    Tenant(profile): Tenant,
+    Json(req): Json<BatchValidateRequest>,
+) -> Result<Json<BatchValidateResponse>, (StatusCode, String)> {
+    let style = profile.mask_style(req.mask);
+    let parallel = req.parallel;
+    let validator = profile.validator.clone();
+    let lookup_bins = profile.bins.is_some();
+
+    // Validation is CPU-bound; keep it off the async worker threads
+    let (mut results, cards, elapsed) = tokio::task::spawn_blocking(move || {
+        let start = Instant::now();
+        let mut batch = BatchValidator::with_validator(validator);
+        let results = if parallel {
+            batch.validate_parallel(&req.card_numbers)
+        } else {
+            batch.validate_all(&req.card_numbers)
+        };
+        let cards: Vec<Option<ValidatedCard>> = results
+            .iter()
+            .map(|result| match result {
+                Ok(card) if lookup_bins => Some(card.clone()),
+                _ => None,
+            })
+            .collect();
+        let results: Vec<ValidateResponse> = results
+            .into_iter()
+            .map(|result| card_response(result, style))
+            .collect();
+        (results, cards, start.elapsed())
+    })
+    .await
+    .map_err(|e| {
+        tracing::error!("Batch validation task failed: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Batch validation failed".to_string(),
+        )
+    })?;
+
+    if let Some(db) = &profile.bins {
+        let details = lookup_cards(db, &cards).await;
+        for (result, info) in results.iter_mut().zip(details) {
+            result.bin = info.map(BinDetails::from);
+            result.currency = result
+                .bin
+                .as_ref()
+                .and_then(BinDetails::currency)
+                .map(str::to_string);
+        }
+    }
 
     let valid_count = results.iter().filter(|r| r.valid).count();
 
-    Json(BatchValidateResponse {
+    Ok(Json(BatchValidateResponse {
+        api_version: api_version(),
         summary: BatchSummary {
             total: results.len(),
             valid: valid_count,
             invalid: results.len() - valid_count,
+            parallel,
+            elapsed_ms: elapsed.as_secs_f64() * 1000.0,
         },
         results,
-    })
+    }))
 }
 
-/// Detect card brand from number
+/// Detect card brand from a query string (deprecated: use POST /detect)
 #[utoipa::path(
     get,
     path = "/detect",
@@ -348,25 +718,85 @@ async fn validate_batch(Json(req): Json<BatchValidateRequest>) -> Json<BatchVali
     tag = "Detection"
 )]
 async fn detect_brand_handler(Query(query): Query<DetectQuery>) -> Json<DetectResponse> {
-    let digits: Vec<u8> = query
-        .card
+    Json(detect_response(&query.card))
+}
+
+/// Detect card brand from number
+#[utoipa::path(
+    post,
+    path = "/detect",
+    request_body = DetectRequest,
+    responses(
+        (status = 200, description = "Detected brand", body = DetectResponse)
+    ),
+    tag = "Detection"
+)]
+async fn detect_brand_post(Json(req): Json<DetectRequest>) -> Json<DetectResponse> {
+    Json(detect_response(&req.card_number))
+}
+
+/// Detects the brand of a card number or prefix.
+fn detect_response(card: &str) -> DetectResponse {
+    let digits: Vec<u8> = card
         .chars()
         .filter(|c| c.is_ascii_digit())
         .map(|c| c as u8 - b'0')
         .collect();
 
-    match detect::detect_brand(&digits) {
-        Some(brand) => Json(DetectResponse {
-            brand: Some(brand.name().to_string()),
-            valid_lengths: Some(brand.valid_lengths().iter().map(|&l| l as usize).collect()),
-        }),
-        None => Json(DetectResponse {
+    let tentative = !detect::is_detection_final(&digits);
+    match detect::detect_brand_range(&digits) {
+        Some(range) => DetectResponse {
+            api_version: api_version(),
+            brand: Some(range.brand.name().to_string()),
+            valid_lengths: Some(
+                range
+                    .brand
+                    .valid_lengths()
+                    .iter()
+                    .map(|&l| l as usize)
+                    .collect(),
+            ),
+            matched_range: Some(range.to_string()),
+            prefix_length: Some(range.digits as usize),
+            tentative,
+        },
+        None => DetectResponse {
+            api_version: api_version(),
             brand: None,
             valid_lengths: None,
-        }),
+            matched_range: None,
+            prefix_length: None,
+            tentative,
+        },
     }
 }
 
+/// List supported card brands
+#[utoipa::path(
+    get,
+    path = "/brands",
+    responses(
+        (status = 200, description = "Supported brands", body = BrandsResponse)
+    ),
+    tag = "Detection"
+)]
+async fn list_brands() -> Json<BrandsResponse> {
+    let brands = CardBrand::all()
+        .iter()
+        .map(|b| BrandSummary {
+            id: b.id().to_string(),
+            name: b.name().to_string(),
+            valid_lengths: b.valid_lengths().iter().map(|&l| l as usize).collect(),
+            cvv_length: b.cvv_length(),
+        })
+        .collect();
+
+    Json(BrandsResponse {
+        api_version: api_version(),
+        brands,
+    })
+}
+
 /// Format a card number
 #[utoipa::path(
     post,
@@ -379,19 +809,96 @@ async fn detect_brand_handler(Query(query): Query<DetectQuery>) -> Json<DetectRe
 )]
 async fn format_card(Json(req): Json<FormatRequest>) -> Json<FormatResponse> {
     Json(FormatResponse {
+        api_version: api_version(),
         formatted: format::format_with_separator(&req.card_number, &req.separator),
         stripped: format::strip_formatting(&req.card_number),
     })
 }
 
+/// Check the Luhn checksum of a number
+#[utoipa::path(
+    post,
+    path = "/luhn",
+    request_body = LuhnRequest,
+    responses(
+        (status = 200, description = "Luhn check result", body = LuhnResponse)
+    ),
+    tag = "Validation"
+)]
+async fn luhn_check(Json(req): Json<LuhnRequest>) -> Json<LuhnResponse> {
+    let digits: Vec<u8> = req
+        .card_number
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .map(|b| b - b'0')
+        .collect();
+    let report = luhn::check(&digits);
+    let has_check = digits.len() > 1;
+    Json(LuhnResponse {
+        api_version: api_version(),
+        valid: has_check && report.valid,
+        check_digit: has_check.then_some(report.computed_check),
+        found: has_check.then_some(report.found_check),
+    })
+}
+
+/// Mask a card number
+#[utoipa::path(
+    post,
+    path = "/mask",
+    request_body = MaskRequest,
+    params(TenantHeaders),
+    responses(
+        (status = 200, description = "Masked card", body = MaskResponse),
+        (status = 401, description = "Unknown API key, or the tenant requires one"),
+        (status = 404, description = "Unknown tenant")
+    ),
+    tag = "Formatting"
+)]
+async fn mask_card(Tenant(profile): Tenant, Json(req): Json<MaskRequest>) -> Json<MaskResponse> {
+    let requested = if req.with_bin {
+        Some(MaskStyle::MaskedWithBin)
+    } else {
+        req.style
+    };
+    let error = |message: String| MaskResponse {
+        api_version: api_version(),
+        masked: None,
+        last_four: None,
+        error: Some(message),
+    };
+
+    // Like the CLI, only showing the BIN needs a valid card
+    let masked = match profile.mask_style(requested) {
+        MaskStyle::MaskedWithBin => match validate(&req.card_number) {
+            Ok(card) => Some(card.masked_with_bin()),
+            Err(e) => return Json(error(e.to_string())),
+        },
+        MaskStyle::Masked => Some(mask::mask_string(&req.card_number)),
+        MaskStyle::LastFour => None,
+    };
+    let last_four = mask::last_four_from_string(&req.card_number);
+    if last_four.is_empty() {
+        return Json(error("card number has fewer than 4 digits".to_string()));
+    }
+
+    Json(MaskResponse {
+        api_version: api_version(),
+        masked,
+        last_four: Some(last_four),
+        error: None,
+    })
+}
+
 /// Generate test card numbers
+#[cfg(not(feature = "no-pan-exposure"))]
 #[utoipa::path(
     post,
     path = "/generate",
     request_body = GenerateRequest,
     responses(
         (status = 200, description = "Generated cards", body = GenerateResponse),
-        (status = 400, description = "Invalid brand")
+        (status = 400, description = "Invalid brand or length")
     ),
     tag = "Generation"
 )]
@@ -407,18 +914,43 @@ async fn generate_cards(
 
     let count = req.count.min(100); // Limit to 100 cards
 
-    let cards: Vec<String> = (0..count)
-        .map(|_| {
-            let card = generate::generate_card(brand);
-            if req.formatted {
-                format::format_card_number(&card)
-            } else {
-                card
-            }
-        })
-        .collect();
+    let seed = req
+        .seed
+        .or(req.deterministic.then_some(0))
+        .unwrap_or_else(rand::random);
+    let mut rng = generate::seeded_rng(seed);
+    let generator = match req.length {
+        Some(length) => generate::CardGenerator::for_length(brand, length)
+            .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?,
+        None => generate::CardGenerator::new(brand),
+    };
+
+    let mut cards = Vec::with_capacity(count);
+    let mut expiry = req.include_expiry.then(|| Vec::with_capacity(count));
+    let mut cvv = req.include_cvv.then(|| Vec::with_capacity(count));
+
+    for _ in 0..count {
+        let card = generator.generate_with_rng(&mut rng);
+        cards.push(if req.formatted {
+            format::format_card_number(&card)
+        } else {
+            card
+        });
+        if let Some(expiry) = expiry.as_mut() {
+            expiry.push(generate::generate_expiry_with_rng(&mut rng).format_short());
+        }
+        if let Some(cvv) = cvv.as_mut() {
+            cvv.push(generate::generate_cvv_with_rng(brand, &mut rng));
+        }
+    }
 
-    Ok(Json(GenerateResponse { cards }))
+    Ok(Json(GenerateResponse {
+        api_version: api_version(),
+        cards,
+        seed,
+        expiry,
+        cvv,
+    }))
 }
 
 /// Validate a CVV/CVC code
@@ -437,6 +969,7 @@ async fn validate_cvv_handler(Json(req): Json<CvvRequest>) -> Json<CvvResponse>
             cvv::validate_cvv_for_brand(&req.cvv, brand)
         } else {
             return Json(CvvResponse {
+                api_version: api_version(),
                 valid: false,
                 length: None,
                 error: Some(format!("Unknown brand: {}", brand_str)),
@@ -448,11 +981,13 @@ async fn validate_cvv_handler(Json(req): Json<CvvRequest>) -> Json<CvvResponse>
 
     match result {
         Ok(validated) => Json(CvvResponse {
+            api_version: api_version(),
             valid: true,
             length: Some(validated.length()),
             error: None,
         }),
         Err(e) => Json(CvvResponse {
+            api_version: api_version(),
             valid: false,
             length: None,
             error: Some(e.to_string()),
@@ -473,19 +1008,23 @@ async fn validate_cvv_handler(Json(req): Json<CvvRequest>) -> Json<CvvResponse>
 async fn validate_expiry_handler(Json(req): Json<ExpiryRequest>) -> Json<ExpiryResponse> {
     match expiry::validate_expiry(&req.date) {
         Ok(exp) => Json(ExpiryResponse {
+            api_version: api_version(),
             valid: true,
             month: Some(exp.month()),
             year: Some(exp.year()),
             expired: Some(exp.is_expired()),
             formatted: Some(exp.format_short()),
+            expiry: Some(exp),
             error: None,
         }),
         Err(e) => Json(ExpiryResponse {
+            api_version: api_version(),
             valid: false,
             month: None,
             year: None,
             expired: None,
             formatted: None,
+            expiry: None,
             error: Some(e.to_string()),
         }),
     }
@@ -502,6 +1041,7 @@ async fn validate_expiry_handler(Json(req): Json<ExpiryRequest>) -> Json<ExpiryR
 )]
 async fn health() -> Json<HealthResponse> {
     Json(HealthResponse {
+        api_version: api_version(),
         status: "ok".to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
     })
@@ -511,24 +1051,122 @@ async fn health() -> Json<HealthResponse> {
 // Helpers
 // ============================================================================
 
+fn card_response(
+    result: Result<ValidatedCard, ValidationError>,
+    style: MaskStyle,
+) -> ValidateResponse {
+    match result {
+        Ok(card) => {
+            let outcome = ValidationOutcome::new(card);
+            let card = &outcome.card;
+            let brand_known = !outcome
+                .warnings
+                .contains(&ValidationWarning::TentativeBrand);
+            ValidateResponse {
+                api_version: String::new(),
+                valid: true,
+                brand: brand_known.then(|| card.brand().name().to_string()),
+                brand_known: Some(brand_known),
+                last_four: Some(card.last_four().to_string()),
+                test_card: Some(card.is_known_test_card()),
+                currency: None,
+                masked: match style {
+                    MaskStyle::LastFour => None,
+                    MaskStyle::Masked => Some(card.masked()),
+                    MaskStyle::MaskedWithBin => Some(card.masked_with_bin()),
+                },
+                error: None,
+                error_code: None,
+                error_category: None,
+                suggestion: None,
+                error_position: None,
+                error_digit_index: None,
+                warnings: outcome
+                    .warnings
+                    .iter()
+                    .map(|w| w.code().to_string())
+                    .collect(),
+                cvv_valid: None,
+                cvv_error: None,
+                bin: None,
+            }
+        }
+        Err(e) => {
+            let (error_position, error_digit_index) = match e {
+                ValidationError::InvalidCharacter {
+                    position,
+                    digit_index,
+                    ..
+                } => (Some(position), Some(digit_index)),
+                _ => (None, None),
+            };
+            ValidateResponse {
+                api_version: String::new(),
+                valid: false,
+                brand: None,
+                brand_known: None,
+                last_four: None,
+                test_card: None,
+                currency: None,
+                masked: None,
+                error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
+                error_category: Some(e.category().to_string()),
+                suggestion: Some(e.suggestion().to_string()),
+                error_position,
+                error_digit_index,
+                warnings: Vec::new(),
+                cvv_valid: None,
+                cvv_error: None,
+                bin: None,
+            }
+        }
+    }
+}
+
+/// Validation counters in the Prometheus text format
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    responses(
+        (status = 200, description = "Validation counts by outcome, brand and error code", body = String, content_type = "text/plain")
+    ),
+    tag = "System"
+)]
+async fn metrics_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render_prometheus(),
+    )
+}
+
+/// Serves the OpenAPI document as YAML, for SDK generators that prefer it.
+async fn openapi_yaml_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "application/yaml")],
+        state.openapi_yaml.clone(),
+    )
+}
+
 fn parse_brand(s: &str) -> Option<CardBrand> {
-    match s.to_lowercase().as_str() {
-        "visa" => Some(CardBrand::Visa),
-        "mastercard" | "mc" => Some(CardBrand::Mastercard),
-        "amex" | "american express" => Some(CardBrand::Amex),
-        "discover" => Some(CardBrand::Discover),
-        "jcb" => Some(CardBrand::Jcb),
-        "diners" | "dinersclub" | "diners club" => Some(CardBrand::DinersClub),
-        "unionpay" | "union pay" => Some(CardBrand::UnionPay),
-        "maestro" => Some(CardBrand::Maestro),
-        "mir" => Some(CardBrand::Mir),
-        "rupay" => Some(CardBrand::RuPay),
-        "verve" => Some(CardBrand::Verve),
-        "elo" => Some(CardBrand::Elo),
-        "troy" => Some(CardBrand::Troy),
-        "bccard" | "bc card" => Some(CardBrand::BcCard),
-        _ => None,
+    CardBrand::from_name(s)
+}
+
+/// Reads the `--max-mask` cap from the command line, defaulting to
+/// `masked` (which shows no more than the last four digits either) when the
+/// flag is absent.
+fn max_mask_arg(args: impl IntoIterator<Item = String>) -> Result<MaskStyle, String> {
+    let mut args = args.into_iter().skip_while(|a| a != "--max-mask");
+    if args.next().is_none() {
+        return Ok(MaskStyle::Masked);
     }
+    let value = args.next().ok_or("--max-mask requires a style")?;
+    MaskStyle::from_name(&value).ok_or_else(|| {
+        format!(
+            "Unknown --max-mask style: {} (expected last_four, masked or masked_with_bin)",
+            value
+        )
+    })
 }
 
 // ============================================================================
@@ -553,25 +1191,117 @@ async fn main() {
         .and_then(|p| p.parse().ok())
         .unwrap_or(3000);
 
+    let max_mask = max_mask_arg(std::env::args()).unwrap_or_else(|e| {
+        tracing::error!("{}", e);
+        std::process::exit(1);
+    });
+
+    let metrics = Arc::new(ValidationMetrics::new());
+    let (validator, bins) = ValidatorConfig::from_env()
+        .and_then(validator_and_bins)
+        .unwrap_or_else(|e| {
+            match &e {
+                ConfigError::BinDb(err) => tracing::error!(
+                    code = err.code(),
+                    line = err.line(),
+                    "Failed to load BIN database: {}",
+                    err
+                ),
+                _ => tracing::error!("Invalid validator configuration: {}", e),
+            }
+            std::process::exit(1);
+        });
+    let test_cards = if std::env::args().any(|a| a == "--reject-test-cards") {
+        TestCardPolicy::Reject
+    } else {
+        TestCardPolicy::Allow
+    };
+    let validator = validator
+        .with_metrics(metrics.clone())
+        .with_test_card_policy(test_cards);
+    tracing::info!("Validator configuration: {:?}", validator);
+    if bins.is_some() {
+        tracing::info!("BIN database loaded; validation responses include issuer details");
+    }
+    let _ = set_default_validator(validator.clone());
+
+    let (tenants, api_keys) = match std::env::args().skip_while(|a| a != "--tenants").nth(1) {
+        Some(path) => load_tenants(&path, max_mask, test_cards, &metrics).unwrap_or_else(|e| {
+            tracing::error!("Failed to load tenants from {}: {}", path, e);
+            std::process::exit(1);
+        }),
+        None => Default::default(),
+    };
+    if !tenants.is_empty() {
+        let mut names: Vec<&String> = tenants.keys().collect();
+        names.sort();
+        tracing::info!("Tenants: {:?}", names);
+    }
+
+    let doc = api_doc();
+    let openapi_yaml = doc.to_yaml().unwrap_or_else(|e| {
+        tracing::error!("Failed to render OpenAPI document as YAML: {}", e);
+        std::process::exit(1);
+    });
+    let state = Arc::new(AppState {
+        default: Arc::new(Profile {
+            validator,
+            bins,
+            max_mask,
+            requires_key: false,
+        }),
+        tenants,
+        api_keys,
+        openapi_yaml,
+        metrics,
+    });
+
     // CORS configuration
     let cors = CorsLayer::new()
         .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
-        .allow_headers([header::CONTENT_TYPE, header::ACCEPT])
+        .allow_headers([
+            header::CONTENT_TYPE,
+            header::ACCEPT,
+            TENANT_HEADER,
+            API_KEY_HEADER,
+        ])
         .allow_origin(Any);
 
     // Build router with Swagger UI
     let app = Router::new()
-        .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
+        .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", doc))
+        .route("/api-docs/openapi.yaml", get(openapi_yaml_handler))
         .route("/validate", post(validate_card))
         .route("/validate/batch", post(validate_batch))
-        .route("/detect", get(detect_brand_handler))
+        .route("/detect", get(detect_brand_handler).post(detect_brand_post))
+        .route("/brands", get(list_brands))
         .route("/format", post(format_card))
-        .route("/generate", post(generate_cards))
+        .route("/luhn", post(luhn_check))
+        .route("/mask", post(mask_card))
         .route("/cvv/validate", post(validate_cvv_handler))
         .route("/expiry/validate", post(validate_expiry_handler))
         .route("/health", get(health))
+        .route("/metrics", get(metrics_handler));
+    #[cfg(not(feature = "no-pan-exposure"))]
+    let app = app.route("/generate", post(generate_cards));
+    let mut app = app.layer(middleware::from_fn(deprecation_headers));
+    if std::env::args().any(|a| a == "--log-bodies") {
+        tracing::info!("Logging request and response bodies (card numbers redacted)");
+        app = app.layer(middleware::from_fn(log_bodies));
+    }
+    let app = app
         .layer(cors)
-        .layer(tower_http::trace::TraceLayer::new_for_http());
+        .layer(
+            tower_http::trace::TraceLayer::new_for_http().make_span_with(|req: &Request| {
+                tracing::debug_span!(
+                    "request",
+                    method = %req.method(),
+                    uri = %scrubbed_uri(req.uri()),
+                    version = ?req.version(),
+                )
+            }),
+        )
+        .with_state(state);
 
     let addr = SocketAddr::from(([0, 0, 0, 0], port));
     tracing::info!("Starting server on http://{}", addr);
@@ -584,3 +1314,152 @@ async fn main() {
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
     axum::serve(listener, app).await.unwrap();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_deprecation_headers() {
+        let table = [Deprecation {
+            method: "GET",
+            path: "/old",
+            sunset: "Thu, 01 Apr 2027 00:00:00 GMT",
+            successor: Some("/new"),
+        }];
+
+        let headers = deprecation_headers_for(&table, &Method::GET, "/old").unwrap();
+        assert_eq!(headers["deprecation"], "true");
+        assert_eq!(headers["sunset"], "Thu, 01 Apr 2027 00:00:00 GMT");
+        assert_eq!(headers[header::LINK], "</new>; rel=\"successor-version\"");
+
+        assert!(deprecation_headers_for(&table, &Method::POST, "/old").is_none());
+        assert!(deprecation_headers_for(&table, &Method::GET, "/new").is_none());
+
+        let table = [Deprecation {
+            successor: None,
+            ..table[0]
+        }];
+        let headers = deprecation_headers_for(&table, &Method::GET, "/old").unwrap();
+        assert!(!headers.contains_key(header::LINK));
+
+        assert!(deprecation_headers_for(DEPRECATED_ENDPOINTS, &Method::GET, "/detect").is_some());
+        assert!(deprecation_headers_for(DEPRECATED_ENDPOINTS, &Method::POST, "/detect").is_none());
+    }
+
+    #[test]
+    fn test_scrubbed_uri() {
+        let uri: Uri = "/detect?card=4111111111111111&mask=masked".parse().unwrap();
+        assert_eq!(scrubbed_uri(&uri), "/detect?card=[redacted]&mask=masked");
+
+        let uri: Uri = "/luhn?number=4111%201111%201111%201111".parse().unwrap();
+        assert_eq!(scrubbed_uri(&uri), "/luhn?number=****%20****%20****%201111");
+
+        let uri: Uri = "/health".parse().unwrap();
+        assert_eq!(scrubbed_uri(&uri), "/health");
+    }
+
+    #[test]
+    fn test_scrubbed_body() {
+        let body =
+            br#"{"card_number":"4111 1111 1111 1111","cvv":"123","nested":[{"cvc":"9876"}]}"#;
+        let logged = scrubbed_body(body);
+        assert!(logged.contains(r#""card_number":"**** **** **** 1111""#));
+        assert!(logged.contains(r#""cvv":"[redacted]""#));
+        assert!(logged.contains(r#""cvc":"[redacted]""#));
+        assert!(!logged.contains("123") && !logged.contains("9876"));
+
+        let form = b"card_number=4111+1111+1111+1111&cvv=123&brand=visa";
+        assert_eq!(
+            scrubbed_body(form),
+            "card_number=****+****+****+1111&cvv=[redacted]&brand=visa"
+        );
+    }
+
+    /// Captures formatted log output.
+    #[derive(Clone, Default)]
+    struct LogBuffer(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for LogBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_log_bodies() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let logs = LogBuffer::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        // The test runtime is single-threaded, so the server logs here too
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let app = Router::new()
+            .route("/cvv/validate", post(|body: String| async move { body }))
+            .layer(middleware::from_fn(log_bodies));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let body = r#"{"card_number":"4111111111111111","cvv":"123","brand":"visa"}"#;
+        let request = format!(
+            "POST /cvv/validate HTTP/1.1\r\nhost: localhost\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let mut socket = tokio::net::TcpStream::connect(addr).await.unwrap();
+        socket.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        socket.read_to_string(&mut response).await.unwrap();
+        // The handler still sees the original body
+        assert!(response.ends_with(body));
+
+        let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        assert!(logs.contains("************1111"), "{}", logs);
+        assert!(logs.contains("[redacted]"), "{}", logs);
+        assert!(!logs.contains("4111111111111111"));
+        assert!(!logs.contains(r#""123""#));
+    }
+
+    #[test]
+    fn test_max_mask_arg() {
+        assert_eq!(max_mask_arg(args(&["server"])), Ok(MaskStyle::Masked));
+        assert_eq!(
+            max_mask_arg(args(&["server", "--max-mask", "last4"])),
+            Ok(MaskStyle::LastFour)
+        );
+        assert_eq!(
+            max_mask_arg(args(&["server", "--max-mask", "with_bin"])),
+            Ok(MaskStyle::MaskedWithBin)
+        );
+        assert!(max_mask_arg(args(&["server", "--max-mask", "full"]))
+            .unwrap_err()
+            .contains("full"));
+        assert!(max_mask_arg(args(&["server", "--max-mask"])).is_err());
+    }
+
+    #[test]
+    fn test_card_response_error_position() {
+        let response = card_response(cc_validator::validate("4111 11x1"), MaskStyle::Masked);
+        assert_eq!(response.error_code.as_deref(), Some("invalid_character"));
+        assert_eq!(response.error_position, Some(7));
+        assert_eq!(response.error_digit_index, Some(6));
+
+        let response = card_response(cc_validator::validate(""), MaskStyle::Masked);
+        assert_eq!(response.error_position, None);
+    }
+}
@@ -0,0 +1,312 @@
+//! Async BIN lookups.
+//!
+//! [`BinDatabase`] lookups block the calling thread, which is fine for
+//! in-memory, memory-mapped and local SQLite databases but stalls an async
+//! runtime worker when the database is across the network. An
+//! [`AsyncBinDatabase`] returns a future instead, so network backends can
+//! await their round trips. `RedisBinDb` implements it natively with the
+//! `bin-redis-async` feature; any sync database can be used where an async
+//! one is expected through [`SyncAdapter`].
+//!
+//! The trait doesn't depend on a particular runtime: lookups return a boxed
+//! [`Future`], so `dyn AsyncBinDatabase` works without extra crates.
+
+use super::{open_database, BinDatabase, BinDbError, BinInfo};
+use crate::ValidatedCard;
+#[cfg(feature = "tokio")]
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// The future returned by [`AsyncBinDatabase::lookup`].
+pub type BinLookup<'a> = Pin<Box<dyn Future<Output = Option<BinInfo>> + Send + 'a>>;
+
+/// A BIN database whose lookups can be awaited.
+///
+/// The async counterpart of [`BinDatabase`], for backends that would block
+/// on the network. `RedisBinDb` implements it with the `bin-redis-async`
+/// feature; wrap any other database in a [`SyncAdapter`], or open one with
+/// [`open_async_database`].
+pub trait AsyncBinDatabase: Send + Sync {
+    /// Looks up BIN information for the given digits (typically the first
+    /// 6-8 digits of a card), like [`BinDatabase::lookup`].
+    fn lookup<'a>(&'a self, bin: &'a [u8]) -> BinLookup<'a>;
+
+    /// Looks up BIN information from a string, like
+    /// [`BinDatabase::lookup_str`].
+    fn lookup_str<'a>(&'a self, bin: &'a str) -> BinLookup<'a> {
+        let digits: Vec<u8> = bin
+            .chars()
+            .filter(|c| c.is_ascii_digit())
+            .map(|c| (c as u8) - b'0')
+            .collect();
+        Box::pin(async move { self.lookup(&digits).await })
+    }
+
+    /// Looks up the BIN of a validated card, like
+    /// [`Validator::lookup_bin`](crate::Validator::lookup_bin).
+    fn lookup_card<'a>(&'a self, card: &'a ValidatedCard) -> BinLookup<'a> {
+        self.lookup(card.bin_lookup_digits())
+    }
+}
+
+/// Serves a sync [`BinDatabase`] as an [`AsyncBinDatabase`].
+///
+/// Lookups run inline when the future is first polled, so only wrap
+/// databases that answer without waiting on the network: memory, mmap,
+/// JSON/CSV files or a local SQLite file. Wrap anything else with
+/// `SyncAdapter::offloaded`, which needs the `tokio` dependency
+/// (enabled by the `server` feature).
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::bin::{AsyncBinDatabase, BinInfo, MemoryBinDbBuilder, SyncAdapter};
+///
+/// let db = MemoryBinDbBuilder::new()
+///     .add("411111", BinInfo::with_bin("411111").issuer("Test Bank"))
+///     .build();
+/// let db = SyncAdapter::new(db);
+/// let lookup = db.lookup_str("41111111");
+/// // Await `lookup` in async code
+/// # drop(lookup);
+/// ```
+pub struct SyncAdapter<D: ?Sized> {
+    db: Arc<D>,
+    #[cfg(feature = "tokio")]
+    offload: bool,
+}
+
+impl<D: BinDatabase> SyncAdapter<D> {
+    /// Wraps `db`.
+    pub fn new(db: D) -> Self {
+        Self::from_arc(Arc::new(db))
+    }
+}
+
+impl<D: BinDatabase + ?Sized> SyncAdapter<D> {
+    /// Wraps a database that is already shared, such as the one returned by
+    /// [`Validator::shared_bin_database`](crate::Validator::shared_bin_database).
+    pub fn from_arc(db: Arc<D>) -> Self {
+        Self {
+            db,
+            #[cfg(feature = "tokio")]
+            offload: false,
+        }
+    }
+
+    /// Wraps a database whose lookups wait on the network, such as a
+    /// `RedisBinDb` without the `bin-redis-async` feature. Each lookup runs
+    /// on tokio's blocking thread pool instead of the polling thread, so it
+    /// must be awaited inside a tokio runtime.
+    #[cfg(feature = "tokio")]
+    pub fn offloaded(db: Arc<D>) -> Self {
+        Self { db, offload: true }
+    }
+
+    /// Returns the wrapped database.
+    #[inline]
+    pub fn inner(&self) -> &Arc<D> {
+        &self.db
+    }
+}
+
+impl<D: BinDatabase + ?Sized + 'static> AsyncBinDatabase for SyncAdapter<D> {
+    fn lookup<'a>(&'a self, bin: &'a [u8]) -> BinLookup<'a> {
+        #[cfg(feature = "tokio")]
+        if self.offload {
+            let (db, bin) = (self.db.clone(), bin.to_vec());
+            return Box::pin(async move {
+                tokio::task::spawn_blocking(move || db.lookup(&bin))
+                    .await
+                    .ok()
+                    .flatten()
+            });
+        }
+        Box::pin(async move { self.db.lookup(bin) })
+    }
+
+    fn lookup_str<'a>(&'a self, bin: &'a str) -> BinLookup<'a> {
+        #[cfg(feature = "tokio")]
+        if self.offload {
+            let (db, bin) = (self.db.clone(), bin.to_string());
+            return Box::pin(async move {
+                tokio::task::spawn_blocking(move || db.lookup_str(&bin))
+                    .await
+                    .ok()
+                    .flatten()
+            });
+        }
+        Box::pin(async move { self.db.lookup_str(bin) })
+    }
+}
+
+impl<D: ?Sized> Clone for SyncAdapter<D> {
+    fn clone(&self) -> Self {
+        Self {
+            db: self.db.clone(),
+            #[cfg(feature = "tokio")]
+            offload: self.offload,
+        }
+    }
+}
+
+impl<D: BinDatabase + ?Sized> std::fmt::Debug for SyncAdapter<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SyncAdapter")
+            .field("entries", &self.db.len())
+            .finish()
+    }
+}
+
+/// Opens a BIN database for async lookups.
+///
+/// Accepts the same paths and URLs as [`open_database`]. With the
+/// `bin-redis-async` feature, `redis://` and `rediss://` URLs get a
+/// `RedisBinDb` that awaits Redis on the async connection; everything
+/// else is opened with [`open_database`] and wrapped in a [`SyncAdapter`].
+/// Without that feature, Redis lookups go through
+/// `SyncAdapter::offloaded`, on tokio's blocking pool, when the `tokio`
+/// dependency is enabled (as by the `server` feature).
+///
+/// # Errors
+///
+/// Returns the same errors as [`open_database`].
+pub fn open_async_database<P: AsRef<Path>>(
+    path: P,
+) -> Result<Arc<dyn AsyncBinDatabase>, BinDbError> {
+    let path = path.as_ref();
+    let redis_url = path
+        .to_str()
+        .filter(|p| p.starts_with("redis://") || p.starts_with("rediss://"));
+    #[cfg(feature = "bin-redis-async")]
+    if let Some(url) = redis_url {
+        return Ok(Arc::new(super::RedisBinDb::open(url)?));
+    }
+    let db: Arc<dyn BinDatabase> = Arc::from(open_database(path)?);
+    #[cfg(all(feature = "bin-redis", feature = "tokio"))]
+    if redis_url.is_some() {
+        return Ok(Arc::new(SyncAdapter::offloaded(db)));
+    }
+    let _ = redis_url;
+    Ok(Arc::new(SyncAdapter::from_arc(db)))
+}
+
+/// Looks up the BINs of a batch of cards, running the lookups concurrently
+/// on the tokio runtime and each distinct BIN only once.
+///
+/// Returns one entry per element of `cards`; `None` elements and BINs the
+/// database doesn't know map to `None`. Must be awaited inside a tokio
+/// runtime.
+#[cfg(feature = "tokio")]
+pub async fn lookup_cards<D>(db: &Arc<D>, cards: &[Option<ValidatedCard>]) -> Vec<Option<BinInfo>>
+where
+    D: AsyncBinDatabase + ?Sized + 'static,
+{
+    let mut lookups = HashMap::new();
+    for card in cards.iter().flatten() {
+        let bin = card.bin_lookup_digits();
+        if !lookups.contains_key(bin) {
+            let (db, key) = (db.clone(), bin.to_vec());
+            let task = tokio::spawn(async move { db.lookup(&key).await });
+            lookups.insert(bin.to_vec(), task);
+        }
+    }
+
+    let mut found = HashMap::new();
+    for (bin, task) in lookups {
+        if let Ok(Some(info)) = task.await {
+            found.insert(bin, info);
+        }
+    }
+    cards
+        .iter()
+        .map(|card| {
+            card.as_ref()
+                .and_then(|card| found.get(card.bin_lookup_digits()).cloned())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bin::{BinInfo, MemoryBinDbBuilder};
+
+    #[tokio::test]
+    async fn test_sync_adapter() {
+        let db = MemoryBinDbBuilder::new()
+            .add("411111", BinInfo::with_bin("411111").issuer("Test Bank"))
+            .build();
+        let db: Arc<dyn AsyncBinDatabase> = Arc::new(SyncAdapter::new(db));
+
+        let info = db.lookup(&[4, 1, 1, 1, 1, 1, 1, 1]).await.unwrap();
+        assert_eq!(info.issuer.as_deref(), Some("Test Bank"));
+        assert!(db.lookup_str("4111-11").await.is_some());
+        assert!(db.lookup_str("550000").await.is_none());
+        let card = crate::validate("4111 1111 1111 1111").unwrap();
+        assert!(db.lookup_card(&card).await.is_some());
+
+        assert!(matches!(
+            open_async_database("bins.xml"),
+            Err(BinDbError::InvalidDatabase(_))
+        ));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_lookup_cards() {
+        let db = MemoryBinDbBuilder::new()
+            .add("411111", BinInfo::with_bin("411111").issuer("Test Bank"))
+            .build();
+        let db = Arc::new(SyncAdapter::new(db));
+        let cards = [
+            crate::validate("4111 1111 1111 1111").ok(),
+            None,
+            crate::validate("5500 0000 0000 0004").ok(),
+            crate::validate("4111 1111 1111 1111").ok(),
+        ];
+
+        let found = lookup_cards(&db, &cards).await;
+        let issuers: Vec<_> = found
+            .iter()
+            .map(|info| info.as_ref().and_then(|i| i.issuer.as_deref()))
+            .collect();
+        assert_eq!(issuers, [Some("Test Bank"), None, None, Some("Test Bank")]);
+    }
+
+    #[cfg(all(
+        feature = "bin-redis",
+        feature = "tokio",
+        not(feature = "bin-redis-async")
+    ))]
+    #[tokio::test]
+    async fn test_redis_lookups_leave_the_runtime_free() {
+        use std::io::{Read, Write};
+        use std::task::Poll;
+
+        // A stand-in server that acknowledges the connection setup, then
+        // answers each lookup late with "not found"
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("redis://{}/", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            stream.write_all(b"+OK\r\n+OK\r\n").unwrap();
+            let mut buf = [0; 1024];
+            while stream.read(&mut buf).is_ok_and(|n| n > 0) {
+                std::thread::sleep(std::time::Duration::from_millis(200));
+                stream.write_all(b"*0\r\n*0\r\n*0\r\n").unwrap();
+            }
+        });
+
+        let db = open_async_database(&url).unwrap();
+        let mut lookup = db.lookup(&[4, 1, 1, 1, 1, 1, 1, 1]);
+        // Run inline, the first poll would block until the reply came in
+        let pending =
+            std::future::poll_fn(|cx| Poll::Ready(lookup.as_mut().poll(cx).is_pending())).await;
+        assert!(pending);
+        assert!(lookup.await.is_none());
+    }
+}
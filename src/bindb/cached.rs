@@ -0,0 +1,270 @@
+//! Caching wrapper for any BIN database.
+//!
+//! Traffic usually concentrates on a few thousand BINs, so a small LRU cache
+//! in front of a SQLite or networked database absorbs most lookups.
+
+use super::lru::LruCache;
+use super::{BinDatabase, BinInfo};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Cache key; `lookup_str` is kept apart from `lookup` since backends may
+/// treat the two differently (e.g. exact match vs. longest prefix).
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Key {
+    Digits(Vec<u8>),
+    Str(String),
+}
+
+struct Entry {
+    info: Option<BinInfo>,
+    inserted: Option<Instant>,
+}
+
+/// Hit and miss counts for a [`CachedBinDb`] or a
+/// [`ValidatorCache`](crate::ValidatorCache).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups answered from the cache.
+    pub hits: u64,
+    /// Lookups forwarded to the wrapped database (or validator).
+    pub misses: u64,
+    /// Entries currently cached.
+    pub entries: usize,
+}
+
+impl CacheStats {
+    /// Returns the fraction of lookups answered from the cache (0.0 when
+    /// there were none).
+    pub fn hit_ratio(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// A [`BinDatabase`] that caches lookups from another one.
+///
+/// Both found and missing BINs are cached, so repeated unknown BINs don't
+/// reach the backend either. Entries are evicted least recently used first
+/// once `capacity` is reached, and optionally expire after a TTL.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::bin::{BinDatabase, BinInfo, CachedBinDb, MemoryBinDbBuilder};
+/// use std::time::Duration;
+///
+/// let backend = MemoryBinDbBuilder::new()
+///     .add("411111", BinInfo::with_bin("411111").issuer("Test Bank"))
+///     .build();
+/// let db = CachedBinDb::new(backend, 10_000).with_ttl(Duration::from_secs(300));
+///
+/// db.lookup_str("411111");
+/// db.lookup_str("411111");
+/// let stats = db.stats();
+/// assert_eq!((stats.hits, stats.misses), (1, 1));
+/// ```
+pub struct CachedBinDb<D: BinDatabase> {
+    inner: D,
+    cache: Mutex<LruCache<Key, Entry>>,
+    ttl: Option<Duration>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<D: BinDatabase> CachedBinDb<D> {
+    /// Wraps `inner` with a cache of at most `capacity` lookups.
+    pub fn new(inner: D, capacity: usize) -> Self {
+        Self {
+            inner,
+            cache: Mutex::new(LruCache::new(capacity)),
+            ttl: None,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Expires cached lookups `ttl` after they were fetched.
+    ///
+    /// Without a TTL entries stay until evicted or [`clear`](Self::clear)ed.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Returns the wrapped database.
+    #[inline]
+    pub fn inner(&self) -> &D {
+        &self.inner
+    }
+
+    /// Consumes the wrapper and returns the wrapped database.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+
+    /// Returns the hit and miss counts since creation or the last
+    /// [`reset_stats`](Self::reset_stats).
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.cache.lock().unwrap().len(),
+        }
+    }
+
+    /// Resets the hit and miss counters.
+    pub fn reset_stats(&self) {
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+
+    /// Drops all cached lookups, e.g. after the backend was updated.
+    pub fn clear(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    fn cached(&self, key: Key, fetch: impl FnOnce() -> Option<BinInfo>) -> Option<BinInfo> {
+        {
+            let mut cache = self.cache.lock().unwrap();
+            if let Some(entry) = cache.get(&key) {
+                let fresh = match (self.ttl, entry.inserted) {
+                    (Some(ttl), Some(inserted)) => inserted.elapsed() < ttl,
+                    _ => true,
+                };
+                if fresh {
+                    self.hits.fetch_add(1, Ordering::Relaxed);
+                    return entry.info.clone();
+                }
+            }
+        }
+
+        // Fetch without holding the lock so slow backends don't serialize lookups
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let info = fetch();
+        let entry = Entry {
+            info: info.clone(),
+            inserted: self.ttl.map(|_| Instant::now()),
+        };
+        self.cache.lock().unwrap().insert(key, entry);
+        info
+    }
+}
+
+impl<D: BinDatabase> BinDatabase for CachedBinDb<D> {
+    fn lookup(&self, bin: &[u8]) -> Option<BinInfo> {
+        self.cached(Key::Digits(bin.to_vec()), || self.inner.lookup(bin))
+    }
+
+    fn lookup_str(&self, bin: &str) -> Option<BinInfo> {
+        self.cached(Key::Str(bin.to_string()), || self.inner.lookup_str(bin))
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<D: BinDatabase + std::fmt::Debug> std::fmt::Debug for CachedBinDb<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedBinDb")
+            .field("inner", &self.inner)
+            .field("ttl", &self.ttl)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bin::{MemoryBinDb, MemoryBinDbBuilder};
+    use std::sync::atomic::AtomicUsize;
+
+    /// Backend that counts the lookups reaching it.
+    struct Counting {
+        db: MemoryBinDb,
+        calls: AtomicUsize,
+    }
+
+    impl BinDatabase for Counting {
+        fn lookup(&self, bin: &[u8]) -> Option<BinInfo> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.db.lookup(bin)
+        }
+
+        fn len(&self) -> usize {
+            self.db.len()
+        }
+    }
+
+    fn backend() -> Counting {
+        Counting {
+            db: MemoryBinDbBuilder::new()
+                .add("411111", BinInfo::with_bin("411111").issuer("Test Bank"))
+                .add("550000", BinInfo::with_bin("550000").issuer("Other Bank"))
+                .build(),
+            calls: AtomicUsize::new(0),
+        }
+    }
+
+    #[test]
+    fn test_hits_and_misses() {
+        let db = CachedBinDb::new(backend(), 16);
+
+        for _ in 0..3 {
+            assert!(db.lookup(&[4, 1, 1, 1, 1, 1]).is_some());
+            assert!(db.lookup(&[9, 9, 9, 9, 9, 9]).is_none());
+        }
+
+        assert_eq!(db.inner().calls.load(Ordering::SeqCst), 2);
+        let stats = db.stats();
+        assert_eq!(stats.hits, 4);
+        assert_eq!(stats.misses, 2);
+        assert_eq!(stats.entries, 2);
+        assert!((stats.hit_ratio() - 4.0 / 6.0).abs() < 1e-9);
+        assert_eq!(db.len(), 2);
+
+        db.reset_stats();
+        assert_eq!(db.stats().hits, 0);
+    }
+
+    #[test]
+    fn test_capacity_evicts() {
+        let db = CachedBinDb::new(backend(), 1);
+        db.lookup(&[4, 1, 1, 1, 1, 1]);
+        db.lookup(&[5, 5, 0, 0, 0, 0]);
+        db.lookup(&[4, 1, 1, 1, 1, 1]);
+
+        assert_eq!(db.inner().calls.load(Ordering::SeqCst), 3);
+        assert_eq!(db.stats().entries, 1);
+    }
+
+    #[test]
+    fn test_ttl_expires() {
+        let db = CachedBinDb::new(backend(), 16).with_ttl(Duration::ZERO);
+        db.lookup(&[4, 1, 1, 1, 1, 1]);
+        db.lookup(&[4, 1, 1, 1, 1, 1]);
+        assert_eq!(db.inner().calls.load(Ordering::SeqCst), 2);
+
+        let db = CachedBinDb::new(backend(), 16).with_ttl(Duration::from_secs(60));
+        db.lookup(&[4, 1, 1, 1, 1, 1]);
+        db.lookup(&[4, 1, 1, 1, 1, 1]);
+        assert_eq!(db.inner().calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_clear() {
+        let db = CachedBinDb::new(backend(), 16);
+        db.lookup_str("411111");
+        db.clear();
+        assert_eq!(db.stats().entries, 0);
+        db.lookup_str("411111");
+        assert_eq!(db.into_inner().calls.load(Ordering::SeqCst), 2);
+    }
+}
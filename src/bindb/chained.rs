@@ -0,0 +1,199 @@
+//! Layered BIN databases.
+//!
+//! Enterprises typically keep a small corrections file on top of a vendor
+//! dataset. [`ChainedBinDb`] queries such layers in order and merges their
+//! answers.
+
+use super::{BinDatabase, BinInfo};
+
+/// A [`BinDatabase`] that queries an ordered list of databases.
+///
+/// The first database that knows the BIN supplies the entry; fields it
+/// leaves empty are filled from later databases (see
+/// [`BinInfo::fill_missing`]). Lookups stop as soon as the entry is
+/// complete, so put the cheapest and most authoritative sources first, e.g.
+/// local overrides, then a memory database, then SQLite or a network store.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::bin::{BinDatabase, BinInfo, CardType, ChainedBinDb, MemoryBinDbBuilder};
+///
+/// let overrides = MemoryBinDbBuilder::new()
+///     .add("411111", BinInfo::with_bin("411111").issuer("Corrected Bank"))
+///     .build();
+/// let vendor = MemoryBinDbBuilder::new()
+///     .add("411111", BinInfo::with_bin("411111").issuer("Vendor Bank").card_type(CardType::Credit))
+///     .build();
+///
+/// let db = ChainedBinDb::new().with(overrides).with(vendor);
+/// let info = db.lookup_str("411111").unwrap();
+/// assert_eq!(info.issuer.as_deref(), Some("Corrected Bank"));
+/// assert_eq!(info.card_type, Some(CardType::Credit));
+/// ```
+#[derive(Default)]
+pub struct ChainedBinDb {
+    sources: Vec<Box<dyn BinDatabase>>,
+}
+
+impl ChainedBinDb {
+    /// Creates an empty chain.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a database, queried after all earlier ones.
+    pub fn with<D: BinDatabase + 'static>(self, db: D) -> Self {
+        self.with_boxed(Box::new(db))
+    }
+
+    /// Appends an already boxed database, such as one returned by
+    /// [`open_database`](super::open_database).
+    pub fn with_boxed(mut self, db: Box<dyn BinDatabase>) -> Self {
+        self.push(db);
+        self
+    }
+
+    /// Appends a database in place.
+    pub fn push(&mut self, db: Box<dyn BinDatabase>) {
+        self.sources.push(db);
+    }
+
+    /// Returns the number of databases in the chain.
+    pub fn sources(&self) -> usize {
+        self.sources.len()
+    }
+
+    fn merge(
+        &self,
+        mut lookup: impl FnMut(&dyn BinDatabase) -> Option<BinInfo>,
+    ) -> Option<BinInfo> {
+        let mut merged: Option<BinInfo> = None;
+        for source in &self.sources {
+            if merged.as_ref().is_some_and(BinInfo::is_complete) {
+                break;
+            }
+            if let Some(info) = lookup(source.as_ref()) {
+                match merged.as_mut() {
+                    Some(merged) => merged.fill_missing(&info),
+                    None => merged = Some(info),
+                }
+            }
+        }
+        merged
+    }
+}
+
+impl BinDatabase for ChainedBinDb {
+    fn lookup(&self, bin: &[u8]) -> Option<BinInfo> {
+        self.merge(|db| db.lookup(bin))
+    }
+
+    fn lookup_str(&self, bin: &str) -> Option<BinInfo> {
+        self.merge(|db| db.lookup_str(bin))
+    }
+
+    /// Returns the size of the largest database in the chain.
+    ///
+    /// Entries usually overlap between layers, so this is a lower bound on
+    /// the number of distinct BINs.
+    fn len(&self) -> usize {
+        self.sources.iter().map(|db| db.len()).max().unwrap_or(0)
+    }
+}
+
+impl std::fmt::Debug for ChainedBinDb {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChainedBinDb")
+            .field("sources", &self.sources.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bin::{CardLevel, CardType, MemoryBinDb, MemoryBinDbBuilder};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Backend that counts the lookups reaching it.
+    struct Counting(MemoryBinDb, Arc<AtomicUsize>);
+
+    impl BinDatabase for Counting {
+        fn lookup(&self, bin: &[u8]) -> Option<BinInfo> {
+            self.1.fetch_add(1, Ordering::SeqCst);
+            self.0.lookup(bin)
+        }
+
+        fn len(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    fn complete(bin: &str, issuer: &str) -> BinInfo {
+        let mut info = BinInfo::with_bin(bin)
+            .issuer(issuer)
+            .card_type(CardType::Credit)
+            .card_level(CardLevel::Gold)
+            .country("US");
+        info.country_name = Some("United States".to_string());
+        info.brand = Some("Visa".to_string());
+        info.bank_phone = Some("+1 800 000 0000".to_string());
+        info.bank_url = Some("https://bank.example".to_string());
+        info
+    }
+
+    #[test]
+    fn test_earlier_sources_win() {
+        let overrides = MemoryBinDbBuilder::new()
+            .add("411111", BinInfo::with_bin("411111").country("CA"))
+            .build();
+        let vendor = MemoryBinDbBuilder::new()
+            .add("411111", complete("411111", "Vendor Bank"))
+            .add("550000", BinInfo::with_bin("550000").issuer("Other Bank"))
+            .build();
+        let db = ChainedBinDb::new().with(overrides).with(vendor);
+
+        let info = db.lookup(&[4, 1, 1, 1, 1, 1]).unwrap();
+        assert_eq!(info.country, Some("CA".to_string()));
+        assert_eq!(info.issuer, Some("Vendor Bank".to_string()));
+        assert_eq!(info.card_level, Some(CardLevel::Gold));
+
+        // Found only in a later source
+        let info = db.lookup_str("550000").unwrap();
+        assert_eq!(info.issuer, Some("Other Bank".to_string()));
+
+        assert!(db.lookup_str("999999").is_none());
+        assert_eq!(db.sources(), 2);
+        assert_eq!(db.len(), 2);
+    }
+
+    #[test]
+    fn test_stops_once_complete() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let first = MemoryBinDbBuilder::new()
+            .add("411111", complete("411111", "First Bank"))
+            .build();
+        let second = MemoryBinDbBuilder::new()
+            .add("411111", BinInfo::with_bin("411111").issuer("Second Bank"))
+            .build();
+        let db = ChainedBinDb::new()
+            .with(first)
+            .with(Counting(second, Arc::clone(&calls)));
+
+        let info = db.lookup(&[4, 1, 1, 1, 1, 1]).unwrap();
+        assert_eq!(info.issuer, Some("First Bank".to_string()));
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        db.lookup(&[5, 5, 0, 0, 0, 0]);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_empty_chain() {
+        let db = ChainedBinDb::new();
+        assert!(db.lookup_str("411111").is_none());
+        assert!(db.is_empty());
+    }
+}
@@ -16,7 +16,7 @@
 //! Column order doesn't matter as long as headers are present.
 //! Only the `bin` column is required.
 
-use super::{BinDbError, BinInfo, CardLevel, CardType, MemoryBinDb};
+use super::{check_bin, BinDbError, BinInfo, CardLevel, CardType, MemoryBinDb};
 use std::fs::File;
 use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
@@ -35,10 +35,15 @@ impl CsvBinLoader {
     ///
     /// # Example
     ///
-    /// ```rust,ignore
-    /// use cc_validator::bin::CsvBinLoader;
+    /// ```
+    /// use cc_validator::bin::{BinDatabase, CsvBinLoader};
     ///
-    /// let db = CsvBinLoader::from_file("bins.csv")?;
+    /// let path = std::env::temp_dir().join("cc_validator_doc_bins.csv");
+    /// std::fs::write(&path, "bin,issuer\n411111,Test Bank\n")?;
+    /// let db = CsvBinLoader::from_file(&path)?;
+    /// assert_eq!(db.len(), 1);
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok::<(), cc_validator::bin::BinDbError>(())
     /// ```
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<MemoryBinDb, BinDbError> {
         let file = File::open(path)?;
@@ -46,33 +51,21 @@ impl CsvBinLoader {
     }
 
     /// Loads a BIN database from a reader.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::bin::{BinDatabase, CsvBinLoader};
+    /// use std::io::Cursor;
+    ///
+    /// let csv = "bin,issuer,country\n411111,Test Bank,US\n";
+    /// let db = CsvBinLoader::from_reader(Cursor::new(csv))?;
+    /// let info = db.lookup_str("411111").unwrap();
+    /// assert_eq!(info.issuer.as_deref(), Some("Test Bank"));
+    /// # Ok::<(), cc_validator::bin::BinDbError>(())
+    /// ```
     pub fn from_reader<R: Read>(reader: R) -> Result<MemoryBinDb, BinDbError> {
-        let mut csv_reader = csv::ReaderBuilder::new()
-            .flexible(true)
-            .trim(csv::Trim::All)
-            .from_reader(reader);
-
-        let headers = csv_reader
-            .headers()
-            .map_err(|e| BinDbError::ParseError(format!("Failed to read CSV headers: {}", e)))?
-            .clone();
-
-        // Map header names to column indices
-        let col_map = ColumnMap::from_headers(&headers)?;
-
-        let mut db = MemoryBinDb::new();
-
-        for result in csv_reader.records() {
-            let record =
-                result.map_err(|e| BinDbError::ParseError(format!("CSV parse error: {}", e)))?;
-
-            if let Some(info) = col_map.parse_record(&record) {
-                let bin = info.bin.clone();
-                db.insert(&bin, info);
-            }
-        }
-
-        Ok(db)
+        Self::load(reader, CsvLoadOptions::new()).map(|(db, _)| db)
     }
 
     /// Loads a BIN database from a CSV string.
@@ -94,31 +87,207 @@ impl CsvBinLoader {
         reader: R,
         delimiter: u8,
     ) -> Result<MemoryBinDb, BinDbError> {
+        Self::load(reader, CsvLoadOptions::new().delimiter(delimiter)).map(|(db, _)| db)
+    }
+
+    /// Loads a BIN database from a CSV file with the given options.
+    pub fn load_file<P: AsRef<Path>>(
+        path: P,
+        options: CsvLoadOptions<'_>,
+    ) -> Result<(MemoryBinDb, CsvLoadReport), BinDbError> {
+        let file = File::open(path)?;
+        Self::load(file, options)
+    }
+
+    /// Loads a BIN database from a reader with the given options.
+    ///
+    /// Records are streamed from the reader one at a time. With
+    /// [`ErrorTolerance::Skip`], malformed rows are recorded in the returned
+    /// [`CsvLoadReport`] instead of aborting the load.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the header row cannot be read or has no `bin`
+    /// column, on I/O failure, or on the first malformed row when using
+    /// [`ErrorTolerance::Abort`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::bin::{BinDatabase, CsvBinLoader, CsvLoadOptions, ErrorTolerance};
+    /// use std::io::Cursor;
+    ///
+    /// let csv = "bin,issuer\n411111,Test Bank\n41x111,Broken\n550000,Other Bank\n";
+    /// let options = CsvLoadOptions::new()
+    ///     .error_tolerance(ErrorTolerance::Skip)
+    ///     .on_progress(|rows| eprintln!("{} rows", rows));
+    /// let (db, report) = CsvBinLoader::load(Cursor::new(csv), options)?;
+    /// for err in report.errors() {
+    ///     eprintln!("skipped: {}", err);
+    /// }
+    /// assert_eq!(db.len(), 2);
+    /// assert_eq!(report.skipped(), 1);
+    /// # Ok::<(), cc_validator::bin::BinDbError>(())
+    /// ```
+    pub fn load<R: Read>(
+        reader: R,
+        mut options: CsvLoadOptions<'_>,
+    ) -> Result<(MemoryBinDb, CsvLoadReport), BinDbError> {
         let mut csv_reader = csv::ReaderBuilder::new()
-            .delimiter(delimiter)
+            .delimiter(options.delimiter)
             .flexible(true)
             .trim(csv::Trim::All)
             .from_reader(reader);
 
-        let headers = csv_reader
-            .headers()
-            .map_err(|e| BinDbError::ParseError(format!("Failed to read CSV headers: {}", e)))?
-            .clone();
+        let headers = csv_reader.headers()?.clone();
 
+        // Map header names to column indices
         let col_map = ColumnMap::from_headers(&headers)?;
+
         let mut db = MemoryBinDb::new();
+        let mut report = CsvLoadReport::default();
 
         for result in csv_reader.records() {
-            let record =
-                result.map_err(|e| BinDbError::ParseError(format!("CSV parse error: {}", e)))?;
+            if options.row_limit.is_some_and(|limit| report.rows >= limit) {
+                break;
+            }
+            report.rows += 1;
+
+            match result
+                .map_err(BinDbError::from)
+                .and_then(|record| col_map.parse_record(&record))
+            {
+                Ok(Some(info)) => {
+                    let bin = info.bin.clone();
+                    db.insert(&bin, info);
+                    report.loaded += 1;
+                }
+                Ok(None) => {}
+                Err(err @ BinDbError::IoError(_)) => return Err(err),
+                Err(err) => match options.tolerance {
+                    ErrorTolerance::Abort => return Err(err),
+                    ErrorTolerance::Skip => report.errors.push(err),
+                },
+            }
 
-            if let Some(info) = col_map.parse_record(&record) {
-                let bin = info.bin.clone();
-                db.insert(&bin, info);
+            if report.rows % options.progress_interval == 0 {
+                if let Some(progress) = options.progress.as_mut() {
+                    progress(report.rows);
+                }
             }
         }
 
-        Ok(db)
+        if report.rows % options.progress_interval != 0 {
+            if let Some(progress) = options.progress.as_mut() {
+                progress(report.rows);
+            }
+        }
+
+        db.ensure_sorted();
+        Ok((db, report))
+    }
+}
+
+/// How the CSV loader handles malformed rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorTolerance {
+    /// Fail the whole load on the first malformed row.
+    #[default]
+    Abort,
+    /// Skip malformed rows and record them in the [`CsvLoadReport`].
+    Skip,
+}
+
+/// Options for [`CsvBinLoader::load`].
+///
+/// Built with [`CsvLoadOptions::new`] and the builder methods.
+pub struct CsvLoadOptions<'a> {
+    delimiter: u8,
+    tolerance: ErrorTolerance,
+    row_limit: Option<usize>,
+    progress_interval: usize,
+    progress: Option<Box<dyn FnMut(usize) + 'a>>,
+}
+
+impl<'a> CsvLoadOptions<'a> {
+    /// Creates options for a comma-delimited file that aborts on errors.
+    pub fn new() -> Self {
+        Self {
+            delimiter: b',',
+            tolerance: ErrorTolerance::Abort,
+            row_limit: None,
+            progress_interval: 10_000,
+            progress: None,
+        }
+    }
+
+    /// Sets the field delimiter.
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets how malformed rows are handled.
+    pub fn error_tolerance(mut self, tolerance: ErrorTolerance) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Stops after reading `limit` data rows, for sampling large files.
+    pub fn row_limit(mut self, limit: usize) -> Self {
+        self.row_limit = Some(limit);
+        self
+    }
+
+    /// Registers a callback that receives the number of rows processed.
+    ///
+    /// Called every [`progress_interval`](Self::progress_interval) rows and
+    /// once more when loading finishes.
+    pub fn on_progress<F: FnMut(usize) + 'a>(mut self, callback: F) -> Self {
+        self.progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Sets how many rows pass between progress callbacks (default 10,000).
+    pub fn progress_interval(mut self, rows: usize) -> Self {
+        self.progress_interval = rows.max(1);
+        self
+    }
+}
+
+impl Default for CsvLoadOptions<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Summary of a CSV load.
+#[derive(Debug, Default)]
+pub struct CsvLoadReport {
+    rows: usize,
+    loaded: usize,
+    errors: Vec<BinDbError>,
+}
+
+impl CsvLoadReport {
+    /// Returns the number of data rows read (excluding the header).
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of rows inserted into the database.
+    pub fn loaded(&self) -> usize {
+        self.loaded
+    }
+
+    /// Returns the errors for rows that were skipped.
+    pub fn errors(&self) -> &[BinDbError] {
+        &self.errors
+    }
+
+    /// Returns the number of rows that were skipped as malformed.
+    pub fn skipped(&self) -> usize {
+        self.errors.len()
     }
 }
 
@@ -162,8 +331,7 @@ impl ColumnMap {
             }
         }
 
-        let bin = bin_col
-            .ok_or_else(|| BinDbError::ParseError("Missing required 'bin' column".to_string()))?;
+        let bin = bin_col.ok_or_else(|| BinDbError::MissingColumn("bin".to_string()))?;
 
         Ok(Self {
             bin,
@@ -178,11 +346,12 @@ impl ColumnMap {
         })
     }
 
-    fn parse_record(&self, record: &csv::StringRecord) -> Option<BinInfo> {
-        let bin = record.get(self.bin)?.trim();
-        if bin.is_empty() {
-            return None;
-        }
+    fn parse_record(&self, record: &csv::StringRecord) -> Result<Option<BinInfo>, BinDbError> {
+        let bin = match record.get(self.bin).map(str::trim) {
+            Some(bin) if !bin.is_empty() => bin,
+            _ => return Ok(None),
+        };
+        check_bin(bin, record.position().map(|p| p.line()))?;
 
         let get_field = |idx: Option<usize>| -> Option<String> {
             idx.and_then(|i| record.get(i))
@@ -191,7 +360,7 @@ impl ColumnMap {
                 .map(|s| s.to_string())
         };
 
-        Some(BinInfo {
+        Ok(Some(BinInfo {
             bin: bin.to_string(),
             issuer: get_field(self.issuer),
             card_type: self
@@ -207,7 +376,7 @@ impl ColumnMap {
             brand: get_field(self.brand),
             bank_phone: get_field(self.bank_phone),
             bank_url: get_field(self.bank_url),
-        })
+        }))
     }
 }
 
@@ -267,7 +436,7 @@ impl SimpleCsvLoader {
         let bin_idx = headers
             .iter()
             .position(|h| h.eq_ignore_ascii_case("bin"))
-            .ok_or_else(|| BinDbError::ParseError("Missing 'bin' column".to_string()))?;
+            .ok_or_else(|| BinDbError::MissingColumn("bin".to_string()))?;
 
         let issuer_idx = headers
             .iter()
@@ -307,6 +476,7 @@ impl SimpleCsvLoader {
             }
         }
 
+        db.ensure_sorted();
         Ok(db)
     }
 }
@@ -366,6 +536,28 @@ Test Bank,US"#;
 
         let result = CsvBinLoader::parse(csv);
         assert!(result.is_err());
+        assert_eq!(result.unwrap_err().code(), "missing_column");
+    }
+
+    #[test]
+    fn test_invalid_bin_line() {
+        let csv = "bin,issuer\n411111,Good Bank\n41x111,Bad Bank\n";
+
+        match CsvBinLoader::parse(csv).unwrap_err() {
+            BinDbError::InvalidBin { bin, line } => {
+                assert_eq!(bin, "41x111");
+                assert_eq!(line, Some(3));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_invalid_utf8_line() {
+        let err =
+            CsvBinLoader::from_reader(&b"bin,issuer\n411111,Bank\n550000,\xff\n"[..]).unwrap_err();
+        assert_eq!(err.code(), "csv_syntax");
+        assert_eq!(err.line(), Some(3));
     }
 
     #[test]
@@ -385,6 +577,44 @@ Test Bank,US"#;
         assert!(info2.country.is_none());
     }
 
+    #[test]
+    fn test_skip_malformed_rows() {
+        let csv = "bin,issuer\n411111,Good\n41x111,Bad\n\n550000,Also Good\n";
+
+        assert!(CsvBinLoader::parse(csv).is_err());
+
+        let options = CsvLoadOptions::new().error_tolerance(ErrorTolerance::Skip);
+        let (db, report) = CsvBinLoader::load(csv.as_bytes(), options).unwrap();
+        assert_eq!(db.len(), 2);
+        assert_eq!(report.loaded(), 2);
+        assert_eq!(report.skipped(), 1);
+        assert_eq!(report.errors()[0].line(), Some(3));
+    }
+
+    #[test]
+    fn test_row_limit() {
+        let csv = "bin\n411111\n422222\n433333\n444444\n";
+
+        let (db, report) =
+            CsvBinLoader::load(csv.as_bytes(), CsvLoadOptions::new().row_limit(2)).unwrap();
+        assert_eq!(db.len(), 2);
+        assert_eq!(report.rows(), 2);
+        assert!(db.lookup_str("433333").is_none());
+    }
+
+    #[test]
+    fn test_progress_callback() {
+        let csv = "bin\n411111\n422222\n433333\n444444\n455555\n";
+        let mut calls = Vec::new();
+
+        let options = CsvLoadOptions::new()
+            .progress_interval(2)
+            .on_progress(|rows| calls.push(rows));
+        CsvBinLoader::load(csv.as_bytes(), options).unwrap();
+
+        assert_eq!(calls, vec![2, 4, 5]);
+    }
+
     #[test]
     fn test_simple_csv_loader() {
         let lines = vec![
@@ -0,0 +1,325 @@
+//! Currency inference from the issuing country.
+//!
+//! Checkout flows can preselect a currency from the card's BIN: look up the
+//! [`BinInfo`], then map its issuer country to that country's ISO 4217
+//! currency with [`infer_currency`]. The table is static, so treat the result
+//! as a default rather than a guarantee; cards are often used abroad.
+
+use super::BinInfo;
+
+/// ISO 3166-1 alpha-2 country codes and their ISO 4217 currency, sorted by
+/// country for binary search.
+///
+/// Countries with more than one legal tender map to the one most used for
+/// card payments (e.g. Panama to USD).
+static CURRENCIES: &[(&str, &str)] = &[
+    ("AD", "EUR"),
+    ("AE", "AED"),
+    ("AF", "AFN"),
+    ("AG", "XCD"),
+    ("AI", "XCD"),
+    ("AL", "ALL"),
+    ("AM", "AMD"),
+    ("AO", "AOA"),
+    ("AR", "ARS"),
+    ("AS", "USD"),
+    ("AT", "EUR"),
+    ("AU", "AUD"),
+    ("AW", "AWG"),
+    ("AX", "EUR"),
+    ("AZ", "AZN"),
+    ("BA", "BAM"),
+    ("BB", "BBD"),
+    ("BD", "BDT"),
+    ("BE", "EUR"),
+    ("BF", "XOF"),
+    ("BG", "EUR"),
+    ("BH", "BHD"),
+    ("BI", "BIF"),
+    ("BJ", "XOF"),
+    ("BL", "EUR"),
+    ("BM", "BMD"),
+    ("BN", "BND"),
+    ("BO", "BOB"),
+    ("BQ", "USD"),
+    ("BR", "BRL"),
+    ("BS", "BSD"),
+    ("BT", "BTN"),
+    ("BV", "NOK"),
+    ("BW", "BWP"),
+    ("BY", "BYN"),
+    ("BZ", "BZD"),
+    ("CA", "CAD"),
+    ("CC", "AUD"),
+    ("CD", "CDF"),
+    ("CF", "XAF"),
+    ("CG", "XAF"),
+    ("CH", "CHF"),
+    ("CI", "XOF"),
+    ("CK", "NZD"),
+    ("CL", "CLP"),
+    ("CM", "XAF"),
+    ("CN", "CNY"),
+    ("CO", "COP"),
+    ("CR", "CRC"),
+    ("CU", "CUP"),
+    ("CV", "CVE"),
+    ("CW", "XCG"),
+    ("CX", "AUD"),
+    ("CY", "EUR"),
+    ("CZ", "CZK"),
+    ("DE", "EUR"),
+    ("DJ", "DJF"),
+    ("DK", "DKK"),
+    ("DM", "XCD"),
+    ("DO", "DOP"),
+    ("DZ", "DZD"),
+    ("EC", "USD"),
+    ("EE", "EUR"),
+    ("EG", "EGP"),
+    ("EH", "MAD"),
+    ("ER", "ERN"),
+    ("ES", "EUR"),
+    ("ET", "ETB"),
+    ("FI", "EUR"),
+    ("FJ", "FJD"),
+    ("FK", "FKP"),
+    ("FM", "USD"),
+    ("FO", "DKK"),
+    ("FR", "EUR"),
+    ("GA", "XAF"),
+    ("GB", "GBP"),
+    ("GD", "XCD"),
+    ("GE", "GEL"),
+    ("GF", "EUR"),
+    ("GG", "GBP"),
+    ("GH", "GHS"),
+    ("GI", "GIP"),
+    ("GL", "DKK"),
+    ("GM", "GMD"),
+    ("GN", "GNF"),
+    ("GP", "EUR"),
+    ("GQ", "XAF"),
+    ("GR", "EUR"),
+    ("GS", "GBP"),
+    ("GT", "GTQ"),
+    ("GU", "USD"),
+    ("GW", "XOF"),
+    ("GY", "GYD"),
+    ("HK", "HKD"),
+    ("HM", "AUD"),
+    ("HN", "HNL"),
+    ("HR", "EUR"),
+    ("HT", "HTG"),
+    ("HU", "HUF"),
+    ("ID", "IDR"),
+    ("IE", "EUR"),
+    ("IL", "ILS"),
+    ("IM", "GBP"),
+    ("IN", "INR"),
+    ("IO", "USD"),
+    ("IQ", "IQD"),
+    ("IR", "IRR"),
+    ("IS", "ISK"),
+    ("IT", "EUR"),
+    ("JE", "GBP"),
+    ("JM", "JMD"),
+    ("JO", "JOD"),
+    ("JP", "JPY"),
+    ("KE", "KES"),
+    ("KG", "KGS"),
+    ("KH", "KHR"),
+    ("KI", "AUD"),
+    ("KM", "KMF"),
+    ("KN", "XCD"),
+    ("KP", "KPW"),
+    ("KR", "KRW"),
+    ("KW", "KWD"),
+    ("KY", "KYD"),
+    ("KZ", "KZT"),
+    ("LA", "LAK"),
+    ("LB", "LBP"),
+    ("LC", "XCD"),
+    ("LI", "CHF"),
+    ("LK", "LKR"),
+    ("LR", "LRD"),
+    ("LS", "LSL"),
+    ("LT", "EUR"),
+    ("LU", "EUR"),
+    ("LV", "EUR"),
+    ("LY", "LYD"),
+    ("MA", "MAD"),
+    ("MC", "EUR"),
+    ("MD", "MDL"),
+    ("ME", "EUR"),
+    ("MF", "EUR"),
+    ("MG", "MGA"),
+    ("MH", "USD"),
+    ("MK", "MKD"),
+    ("ML", "XOF"),
+    ("MM", "MMK"),
+    ("MN", "MNT"),
+    ("MO", "MOP"),
+    ("MP", "USD"),
+    ("MQ", "EUR"),
+    ("MR", "MRU"),
+    ("MS", "XCD"),
+    ("MT", "EUR"),
+    ("MU", "MUR"),
+    ("MV", "MVR"),
+    ("MW", "MWK"),
+    ("MX", "MXN"),
+    ("MY", "MYR"),
+    ("MZ", "MZN"),
+    ("NA", "NAD"),
+    ("NC", "XPF"),
+    ("NE", "XOF"),
+    ("NF", "AUD"),
+    ("NG", "NGN"),
+    ("NI", "NIO"),
+    ("NL", "EUR"),
+    ("NO", "NOK"),
+    ("NP", "NPR"),
+    ("NR", "AUD"),
+    ("NU", "NZD"),
+    ("NZ", "NZD"),
+    ("OM", "OMR"),
+    ("PA", "USD"),
+    ("PE", "PEN"),
+    ("PF", "XPF"),
+    ("PG", "PGK"),
+    ("PH", "PHP"),
+    ("PK", "PKR"),
+    ("PL", "PLN"),
+    ("PM", "EUR"),
+    ("PN", "NZD"),
+    ("PR", "USD"),
+    ("PS", "ILS"),
+    ("PT", "EUR"),
+    ("PW", "USD"),
+    ("PY", "PYG"),
+    ("QA", "QAR"),
+    ("RE", "EUR"),
+    ("RO", "RON"),
+    ("RS", "RSD"),
+    ("RU", "RUB"),
+    ("RW", "RWF"),
+    ("SA", "SAR"),
+    ("SB", "SBD"),
+    ("SC", "SCR"),
+    ("SD", "SDG"),
+    ("SE", "SEK"),
+    ("SG", "SGD"),
+    ("SH", "SHP"),
+    ("SI", "EUR"),
+    ("SJ", "NOK"),
+    ("SK", "EUR"),
+    ("SL", "SLE"),
+    ("SM", "EUR"),
+    ("SN", "XOF"),
+    ("SO", "SOS"),
+    ("SR", "SRD"),
+    ("SS", "SSP"),
+    ("ST", "STN"),
+    ("SV", "USD"),
+    ("SX", "XCG"),
+    ("SY", "SYP"),
+    ("SZ", "SZL"),
+    ("TC", "USD"),
+    ("TD", "XAF"),
+    ("TF", "EUR"),
+    ("TG", "XOF"),
+    ("TH", "THB"),
+    ("TJ", "TJS"),
+    ("TK", "NZD"),
+    ("TL", "USD"),
+    ("TM", "TMT"),
+    ("TN", "TND"),
+    ("TO", "TOP"),
+    ("TR", "TRY"),
+    ("TT", "TTD"),
+    ("TV", "AUD"),
+    ("TW", "TWD"),
+    ("TZ", "TZS"),
+    ("UA", "UAH"),
+    ("UG", "UGX"),
+    ("UM", "USD"),
+    ("US", "USD"),
+    ("UY", "UYU"),
+    ("UZ", "UZS"),
+    ("VA", "EUR"),
+    ("VC", "XCD"),
+    ("VE", "VES"),
+    ("VG", "USD"),
+    ("VI", "USD"),
+    ("VN", "VND"),
+    ("VU", "VUV"),
+    ("WF", "XPF"),
+    ("WS", "WST"),
+    ("XK", "EUR"),
+    ("YE", "YER"),
+    ("YT", "EUR"),
+    ("ZA", "ZAR"),
+    ("ZM", "ZMW"),
+    ("ZW", "ZWG"),
+];
+
+/// Returns the ISO 4217 currency of the card's issuer country.
+///
+/// Reads [`BinInfo::country`] as an ISO 3166-1 alpha-2 code (either case).
+/// Returns `None` if the country is missing or unknown.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::bin::{infer_currency, BinInfo};
+///
+/// let info = BinInfo::with_bin("457173").country("DK");
+/// assert_eq!(infer_currency(&info), Some("DKK"));
+/// assert_eq!(infer_currency(&BinInfo::with_bin("411111")), None);
+/// ```
+pub fn infer_currency(info: &BinInfo) -> Option<&'static str> {
+    currency_for_country(info.country.as_deref()?)
+}
+
+/// Returns the ISO 4217 currency of an ISO 3166-1 alpha-2 country code.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::bin::currency_for_country;
+///
+/// assert_eq!(currency_for_country("de"), Some("EUR"));
+/// assert_eq!(currency_for_country("GB"), Some("GBP"));
+/// assert_eq!(currency_for_country("XX"), None);
+/// ```
+pub fn currency_for_country(code: &str) -> Option<&'static str> {
+    let code = code.trim().to_ascii_uppercase();
+    CURRENCIES
+        .binary_search_by(|(country, _)| (*country).cmp(code.as_str()))
+        .ok()
+        .map(|i| CURRENCIES[i].1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_is_sorted() {
+        assert!(CURRENCIES.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(CURRENCIES
+            .iter()
+            .all(|(country, currency)| country.len() == 2 && currency.len() == 3));
+    }
+
+    #[test]
+    fn test_infer_currency() {
+        let info = BinInfo::with_bin("411111").country("us");
+        assert_eq!(infer_currency(&info), Some("USD"));
+        assert_eq!(info.currency(), Some("USD"));
+        assert_eq!(currency_for_country(" JP "), Some("JPY"));
+        assert_eq!(currency_for_country("USA"), None);
+        assert_eq!(currency_for_country(""), None);
+    }
+}
@@ -0,0 +1,159 @@
+//! Interchange fee tier hints from BIN data.
+//!
+//! Networks charge different interchange rates for debit, everyday credit,
+//! rewards credit and business cards. [`fee_tier`] combines a [`BinInfo`]'s
+//! card type and level into a coarse [`FeeTier`] for surcharging decisions
+//! and cost estimates:
+//!
+//! | Card type | Card level | Tier |
+//! |-----------|------------|------|
+//! | `Corporate` | any | `Commercial` |
+//! | any | `Business`, `Corporate` | `Commercial` |
+//! | `Debit` | other | `ConsumerDebit` |
+//! | `Prepaid` | other | `Prepaid` |
+//! | `Credit`, `Charge` | `Platinum`, `Signature`, `Infinite`, `World` | `PremiumCredit` |
+//! | `Credit`, `Charge` | `Standard`, `Gold`, unknown | `ConsumerCredit` |
+//! | unknown | other | none |
+//!
+//! The tier is only a hint: actual rates depend on the network, region,
+//! merchant category and how the card is presented.
+
+use super::{BinInfo, CardLevel, CardType};
+use std::fmt;
+
+/// Coarse interchange tier of a card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeeTier {
+    /// Consumer debit card (often rate-capped, e.g. Durbin or EU IFR).
+    ConsumerDebit,
+    /// Prepaid card.
+    Prepaid,
+    /// Consumer credit or charge card without premium rewards.
+    ConsumerCredit,
+    /// Rewards or premium consumer credit card.
+    PremiumCredit,
+    /// Business, corporate or purchasing card.
+    Commercial,
+}
+
+impl FeeTier {
+    /// Returns a stable snake_case name (e.g. `"premium_credit"`).
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::ConsumerDebit => "consumer_debit",
+            Self::Prepaid => "prepaid",
+            Self::ConsumerCredit => "consumer_credit",
+            Self::PremiumCredit => "premium_credit",
+            Self::Commercial => "commercial",
+        }
+    }
+}
+
+impl fmt::Display for FeeTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Returns the interchange tier hint for a BIN entry.
+///
+/// Returns `None` if the card type is missing or unknown and the level
+/// doesn't mark a business card. See the module docs for the
+/// mapping.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::bin::{fee_tier, BinInfo, CardLevel, CardType, FeeTier};
+///
+/// let info = BinInfo::with_bin("411111")
+///     .card_type(CardType::Credit)
+///     .card_level(CardLevel::Platinum);
+/// assert_eq!(fee_tier(&info), Some(FeeTier::PremiumCredit));
+///
+/// let info = BinInfo::with_bin("411111").card_type(CardType::Debit);
+/// assert_eq!(info.fee_tier(), Some(FeeTier::ConsumerDebit));
+/// assert_eq!(fee_tier(&BinInfo::with_bin("411111")), None);
+/// ```
+pub fn fee_tier(info: &BinInfo) -> Option<FeeTier> {
+    let level = info.card_level.unwrap_or(CardLevel::Unknown);
+    if info.card_type == Some(CardType::Corporate)
+        || matches!(level, CardLevel::Business | CardLevel::Corporate)
+    {
+        return Some(FeeTier::Commercial);
+    }
+
+    match info.card_type? {
+        CardType::Debit => Some(FeeTier::ConsumerDebit),
+        CardType::Prepaid => Some(FeeTier::Prepaid),
+        CardType::Credit | CardType::Charge => match level {
+            CardLevel::Platinum | CardLevel::Signature | CardLevel::Infinite | CardLevel::World => {
+                Some(FeeTier::PremiumCredit)
+            }
+            _ => Some(FeeTier::ConsumerCredit),
+        },
+        CardType::Corporate | CardType::Unknown => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tier(card_type: Option<CardType>, level: Option<CardLevel>) -> Option<FeeTier> {
+        fee_tier(&BinInfo {
+            card_type,
+            card_level: level,
+            ..BinInfo::default()
+        })
+    }
+
+    #[test]
+    fn test_mapping() {
+        use CardLevel as L;
+        use CardType as T;
+
+        let cases = [
+            (
+                Some(T::Corporate),
+                Some(L::Platinum),
+                Some(FeeTier::Commercial),
+            ),
+            (
+                Some(T::Credit),
+                Some(L::Business),
+                Some(FeeTier::Commercial),
+            ),
+            (None, Some(L::Corporate), Some(FeeTier::Commercial)),
+            (
+                Some(T::Debit),
+                Some(L::Platinum),
+                Some(FeeTier::ConsumerDebit),
+            ),
+            (Some(T::Prepaid), None, Some(FeeTier::Prepaid)),
+            (
+                Some(T::Charge),
+                Some(L::Infinite),
+                Some(FeeTier::PremiumCredit),
+            ),
+            (
+                Some(T::Credit),
+                Some(L::Gold),
+                Some(FeeTier::ConsumerCredit),
+            ),
+            (Some(T::Credit), None, Some(FeeTier::ConsumerCredit)),
+            (Some(T::Unknown), Some(L::World), None),
+            (None, None, None),
+        ];
+        for (card_type, level, expected) in cases {
+            assert_eq!(
+                tier(card_type, level),
+                expected,
+                "{:?} {:?}",
+                card_type,
+                level
+            );
+        }
+        assert_eq!(FeeTier::PremiumCredit.to_string(), "premium_credit");
+    }
+}
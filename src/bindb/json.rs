@@ -31,14 +31,40 @@
 //!   }
 //! }
 //! ```
+//!
+//! ## Public dataset schemas
+//!
+//! Entries in the binlist schema, with nested `bank` and `country`
+//! objects, load without preprocessing:
+//!
+//! ```json
+//! {
+//!   "457173": {
+//!     "scheme": "visa",
+//!     "type": "debit",
+//!     "brand": "Visa/Dankort",
+//!     "prepaid": false,
+//!     "country": { "alpha2": "DK", "name": "Denmark" },
+//!     "bank": { "name": "Jyske Bank", "url": "www.jyskebank.dk", "phone": "+4589893300" }
+//!   }
+//! }
+//! ```
+//!
+//! binbase-style field names (`category`, `isoCode2`, `countryName`) and
+//! numeric `bin` values are accepted as well. Files compressed with gzip
+//! (`.json.gz`) are decompressed transparently.
 
-use super::{BinDbError, BinInfo, CardLevel, CardType, MemoryBinDb};
-use serde::Deserialize;
+use super::{check_bin, BinDbError, BinInfo, CardLevel, CardType, MemoryBinDb};
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Deserializer};
 use std::collections::HashMap;
-use std::fs;
-use std::io::Read;
+use std::fs::File;
+use std::io::{self, Read};
 use std::path::Path;
 
+/// Leading bytes of a gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
 /// JSON BIN database loader.
 ///
 /// Supports multiple JSON formats commonly used for BIN data.
@@ -47,7 +73,8 @@ pub struct JsonBinLoader;
 impl JsonBinLoader {
     /// Loads a BIN database from a JSON file.
     ///
-    /// Automatically detects the JSON format (array or object).
+    /// Automatically detects the JSON format (array or object) and
+    /// decompresses `.json.gz` files.
     ///
     /// # Arguments
     ///
@@ -61,14 +88,26 @@ impl JsonBinLoader {
     /// let db = JsonBinLoader::from_file("bins.json")?;
     /// ```
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<MemoryBinDb, BinDbError> {
-        let content = fs::read_to_string(path)?;
-        Self::parse(&content)
+        let file = File::open(path)?;
+        Self::from_reader(file)
     }
 
     /// Loads a BIN database from a reader.
+    ///
+    /// Gzip-compressed input is detected from its magic bytes and
+    /// decompressed transparently.
     pub fn from_reader<R: Read>(mut reader: R) -> Result<MemoryBinDb, BinDbError> {
-        let mut content = String::new();
-        reader.read_to_string(&mut content)?;
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        let content = if bytes.starts_with(&GZIP_MAGIC) {
+            let mut content = String::new();
+            GzDecoder::new(bytes.as_slice()).read_to_string(&mut content)?;
+            content
+        } else {
+            String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+        };
+
         Self::parse(&content)
     }
 
@@ -83,8 +122,10 @@ impl JsonBinLoader {
         } else if trimmed.starts_with('{') {
             // Could be object keyed by BIN or single entry
             // Try object format first
-            if let Ok(db) = Self::parse_object(json) {
-                return Ok(db);
+            match Self::parse_object(json) {
+                Ok(db) => return Ok(db),
+                Err(err @ BinDbError::InvalidBin { .. }) => return Err(err),
+                Err(_) => {}
             }
             // Fall back to array of one
             Self::parse_array(json)
@@ -97,28 +138,32 @@ impl JsonBinLoader {
 
     /// Parses JSON array format.
     fn parse_array(json: &str) -> Result<MemoryBinDb, BinDbError> {
-        let entries: Vec<JsonBinEntry> = serde_json::from_str(json)
-            .map_err(|e| BinDbError::ParseError(format!("JSON parse error: {}", e)))?;
+        let entries: Vec<JsonBinEntry> = serde_json::from_str(json)?;
 
         let mut db = MemoryBinDb::with_capacity(entries.len());
 
         for entry in entries {
+            if entry.bin.is_empty() {
+                continue;
+            }
+            check_bin(&entry.bin, None)?;
             let bin = entry.bin.clone();
             let info = entry.into_bin_info();
             db.insert(&bin, info);
         }
 
+        db.ensure_sorted();
         Ok(db)
     }
 
     /// Parses JSON object format (keyed by BIN).
     fn parse_object(json: &str) -> Result<MemoryBinDb, BinDbError> {
-        let map: HashMap<String, JsonBinEntry> = serde_json::from_str(json)
-            .map_err(|e| BinDbError::ParseError(format!("JSON parse error: {}", e)))?;
+        let map: HashMap<String, JsonBinEntry> = serde_json::from_str(json)?;
 
         let mut db = MemoryBinDb::with_capacity(map.len());
 
         for (bin, mut entry) in map {
+            check_bin(&bin, None)?;
             // Use the key as the BIN if entry doesn't have one
             if entry.bin.is_empty() {
                 entry.bin = bin.clone();
@@ -127,32 +172,49 @@ impl JsonBinLoader {
             db.insert(&bin, info);
         }
 
+        db.ensure_sorted();
         Ok(db)
     }
 }
 
 /// Internal structure for deserializing JSON BIN entries.
+///
+/// Field names and aliases cover the flat format above as well as the
+/// binlist and binbase schemas; nested `bank` and `country` objects are
+/// flattened into the matching [`BinInfo`] fields.
 #[derive(Debug, Deserialize, Default)]
 struct JsonBinEntry {
-    #[serde(default)]
+    #[serde(default, alias = "iin", deserialize_with = "deserialize_bin")]
     bin: String,
 
-    #[serde(default, alias = "bank", alias = "bank_name")]
-    issuer: Option<String>,
+    #[serde(default, alias = "bank", alias = "bank_name", alias = "issuer_name")]
+    issuer: Option<Issuer>,
 
     #[serde(default, alias = "type")]
     card_type: Option<String>,
 
-    #[serde(default, alias = "level", alias = "tier")]
+    #[serde(default)]
+    prepaid: Option<bool>,
+
+    #[serde(default, alias = "level", alias = "tier", alias = "category")]
     card_level: Option<String>,
 
-    #[serde(default, alias = "country_code")]
-    country: Option<String>,
+    #[serde(
+        default,
+        alias = "country_code",
+        alias = "alpha_2",
+        alias = "isoCode2",
+        alias = "iso_code2"
+    )]
+    country: Option<Country>,
 
-    #[serde(default)]
+    #[serde(default, alias = "countryName")]
     country_name: Option<String>,
 
-    #[serde(default, alias = "scheme", alias = "network")]
+    #[serde(default, alias = "network")]
+    scheme: Option<String>,
+
+    #[serde(default)]
     brand: Option<String>,
 
     #[serde(default, alias = "phone")]
@@ -162,18 +224,77 @@ struct JsonBinEntry {
     bank_url: Option<String>,
 }
 
+/// An issuer given either by name or as a binlist-style `bank` object.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Issuer {
+    Name(String),
+    Bank {
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default)]
+        phone: Option<String>,
+        #[serde(default)]
+        url: Option<String>,
+    },
+}
+
+/// A country given either as a code or as a binlist-style object.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Country {
+    Code(String),
+    Details {
+        #[serde(default, alias = "alpha_2", alias = "code")]
+        alpha2: Option<String>,
+        #[serde(default)]
+        name: Option<String>,
+    },
+}
+
+/// Accepts a BIN written either as a string or as a bare number.
+fn deserialize_bin<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Bin {
+        Text(String),
+        Number(u64),
+    }
+
+    Ok(match Bin::deserialize(deserializer)? {
+        Bin::Text(s) => s,
+        Bin::Number(n) => n.to_string(),
+    })
+}
+
 impl JsonBinEntry {
     fn into_bin_info(self) -> BinInfo {
+        let (issuer, phone, url) = match self.issuer {
+            Some(Issuer::Name(name)) => (Some(name), None, None),
+            Some(Issuer::Bank { name, phone, url }) => (name, phone, url),
+            None => (None, None, None),
+        };
+        let (country, country_name) = match self.country {
+            Some(Country::Code(code)) => (Some(code), None),
+            Some(Country::Details { alpha2, name }) => (alpha2, name),
+            None => (None, None),
+        };
+        let card_type = match self.prepaid {
+            Some(true) => Some(CardType::Prepaid),
+            _ => self.card_type.as_ref().map(|s| parse_card_type(s)),
+        };
+
         BinInfo {
             bin: self.bin,
-            issuer: self.issuer,
-            card_type: self.card_type.as_ref().map(|s| parse_card_type(s)),
+            issuer,
+            card_type,
             card_level: self.card_level.as_ref().map(|s| parse_card_level(s)),
-            country: self.country,
-            country_name: self.country_name,
-            brand: self.brand,
-            bank_phone: self.bank_phone,
-            bank_url: self.bank_url,
+            country,
+            country_name: self.country_name.or(country_name),
+            // binlist uses `scheme` for the network and `brand` for the product
+            brand: self.scheme.or(self.brand),
+            bank_phone: self.bank_phone.or(phone),
+            bank_url: self.bank_url.or(url),
         }
     }
 }
@@ -278,6 +399,73 @@ mod tests {
         assert_eq!(info.brand, Some("Visa".to_string()));
     }
 
+    #[test]
+    fn test_binlist_schema() {
+        let json = r#"{
+            "457173": {
+                "number": { "length": 16, "luhn": true },
+                "scheme": "visa",
+                "type": "debit",
+                "brand": "Visa/Dankort",
+                "prepaid": false,
+                "country": { "numeric": "208", "alpha2": "DK", "name": "Denmark", "currency": "DKK" },
+                "bank": { "name": "Jyske Bank", "url": "www.jyskebank.dk", "phone": "+4589893300", "city": "Hjørring" }
+            }
+        }"#;
+
+        let db = JsonBinLoader::parse(json).unwrap();
+        let info = db.lookup_str("457173").unwrap();
+        assert_eq!(info.issuer, Some("Jyske Bank".to_string()));
+        assert_eq!(info.card_type, Some(CardType::Debit));
+        assert_eq!(info.country, Some("DK".to_string()));
+        assert_eq!(info.country_name, Some("Denmark".to_string()));
+        assert_eq!(info.brand, Some("visa".to_string()));
+        assert_eq!(info.bank_phone, Some("+4589893300".to_string()));
+        assert_eq!(info.bank_url, Some("www.jyskebank.dk".to_string()));
+    }
+
+    #[test]
+    fn test_binbase_schema() {
+        let json = r#"[
+            {
+                "bin": 411111,
+                "brand": "VISA",
+                "issuer": "Test Bank",
+                "type": "CREDIT",
+                "category": "PLATINUM",
+                "isoCode2": "US",
+                "countryName": "UNITED STATES",
+                "prepaid": true
+            }
+        ]"#;
+
+        let db = JsonBinLoader::parse(json).unwrap();
+        let info = db.lookup_str("411111").unwrap();
+        assert_eq!(info.bin, "411111");
+        assert_eq!(info.card_type, Some(CardType::Prepaid));
+        assert_eq!(info.card_level, Some(CardLevel::Platinum));
+        assert_eq!(info.country, Some("US".to_string()));
+        assert_eq!(info.country_name, Some("UNITED STATES".to_string()));
+        assert_eq!(info.brand, Some("VISA".to_string()));
+    }
+
+    #[test]
+    fn test_gzip_reader() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(br#"[{"bin": "411111", "issuer": "Gzip Bank"}]"#)
+            .unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let db = JsonBinLoader::from_reader(compressed.as_slice()).unwrap();
+        let info = db.lookup_str("411111").unwrap();
+        assert_eq!(info.issuer, Some("Gzip Bank".to_string()));
+    }
+
     #[test]
     fn test_card_type_parsing() {
         assert_eq!(parse_card_type("Credit"), CardType::Credit);
@@ -307,4 +495,26 @@ mod tests {
         let result = JsonBinLoader::parse("not valid json");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_json_syntax_error_position() {
+        let err = JsonBinLoader::parse("[\n  {\"bin\": \"411111\",}\n]").unwrap_err();
+        assert_eq!(err.code(), "json_syntax");
+        match err {
+            BinDbError::Json { line, column, .. } => {
+                assert_eq!(line, 2);
+                assert!(column > 0);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_invalid_bin_reported() {
+        let err = JsonBinLoader::parse(r#"[{"bin": "41x111"}]"#).unwrap_err();
+        assert!(matches!(err, BinDbError::InvalidBin { ref bin, .. } if bin == "41x111"));
+
+        let err = JsonBinLoader::parse(r#"{"abc": {"issuer": "Bank"}}"#).unwrap_err();
+        assert_eq!(err.code(), "invalid_bin");
+    }
 }
@@ -2,6 +2,9 @@
 //!
 //! Provides a fast, in-memory BIN lookup using a sorted vector
 //! with binary search for O(log n) lookups.
+//!
+//! BINs and ranges of any length are stored in the canonical 8-digit space
+//! described on [`BinRange`], so 6- and 8-digit data can be mixed freely.
 
 use super::{BinDatabase, BinInfo, BinRange};
 use std::collections::HashMap;
@@ -13,6 +16,10 @@ use std::collections::HashMap;
 /// - Memory efficiency: Compact representation
 /// - Range support: Can match BIN ranges, not just exact values
 ///
+/// When ranges overlap, the narrowest one containing the card's BIN wins
+/// (the one inserted last among equally narrow ones), so an 8-digit entry
+/// overrides a 6-digit one.
+///
 /// # Example
 ///
 /// ```
@@ -34,6 +41,9 @@ use std::collections::HashMap;
 pub struct MemoryBinDb {
     /// Entries sorted by BIN range start for binary search.
     entries: Vec<(BinRange, BinInfo)>,
+    /// Largest range end among `entries[..=i]`, bounding the backward scan
+    /// for overlapping ranges. Valid only while `sorted`.
+    max_end: Vec<u64>,
     /// Optional exact-match index for faster single-BIN lookups.
     exact_index: Option<HashMap<u64, usize>>,
     /// Whether the entries are sorted (for lazy sorting).
@@ -45,6 +55,7 @@ impl MemoryBinDb {
     pub fn new() -> Self {
         Self {
             entries: Vec::new(),
+            max_end: Vec::new(),
             exact_index: None,
             sorted: true,
         }
@@ -54,35 +65,49 @@ impl MemoryBinDb {
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             entries: Vec::with_capacity(capacity),
+            max_end: Vec::new(),
             exact_index: None,
             sorted: true,
         }
     }
 
-    /// Inserts a single BIN entry.
+    /// Inserts a BIN prefix of any length (see [`BinRange::from_prefix`]).
     pub fn insert(&mut self, bin: &str, info: BinInfo) {
-        if let Some(bin_num) = BinRange::parse_bin(bin) {
-            self.entries.push((BinRange::single(bin_num), info));
-            self.sorted = false;
-            self.exact_index = None; // Invalidate index
+        if let Some(range) = BinRange::from_prefix(bin) {
+            self.push(range, info);
         }
     }
 
-    /// Inserts a BIN range entry.
+    /// Inserts a BIN range entry (see [`BinRange::from_bounds`]).
     pub fn insert_range(&mut self, start: &str, end: &str, info: BinInfo) {
-        if let (Some(start_num), Some(end_num)) =
-            (BinRange::parse_bin(start), BinRange::parse_bin(end))
-        {
-            self.entries.push((BinRange::new(start_num, end_num), info));
-            self.sorted = false;
-            self.exact_index = None;
+        if let Some(range) = BinRange::from_bounds(start, end) {
+            self.push(range, info);
         }
     }
 
-    /// Ensures entries are sorted for binary search.
-    fn ensure_sorted(&mut self) {
+    fn push(&mut self, range: BinRange, info: BinInfo) {
+        self.entries.push((range, info));
+        self.sorted = false;
+        self.exact_index = None; // Invalidate index
+    }
+
+    /// Sorts the entries for binary search.
+    ///
+    /// Lookups on an unsorted database fall back to a linear scan; loaders
+    /// call this once they have inserted everything.
+    pub(crate) fn ensure_sorted(&mut self) {
         if !self.sorted {
-            self.entries.sort_by(|a, b| a.0.cmp(&b.0));
+            // Stable, so equal ranges keep their insertion order
+            self.entries
+                .sort_by_key(|(range, _)| (range.start, range.end));
+            self.max_end = self
+                .entries
+                .iter()
+                .scan(0, |max, (range, _)| {
+                    *max = range.end.max(*max);
+                    Some(*max)
+                })
+                .collect();
             self.sorted = true;
         }
     }
@@ -115,40 +140,148 @@ impl MemoryBinDb {
             }
         }
 
-        // Binary search for range containing this BIN
-        let result = self.entries.binary_search_by(|(range, _)| {
-            if bin < range.start {
-                std::cmp::Ordering::Greater
-            } else if bin > range.end {
-                std::cmp::Ordering::Less
-            } else {
-                std::cmp::Ordering::Equal
-            }
-        });
-
-        match result {
-            Ok(idx) => Some(&self.entries[idx].1),
-            Err(_) => None,
+        if !self.sorted {
+            return narrowest(self.entries.iter().rev(), bin);
         }
-    }
 
-    /// Converts digit slice to u64 for lookup.
-    fn digits_to_u64(digits: &[u8]) -> u64 {
-        let mut result: u64 = 0;
-        for &d in digits.iter().take(8) {
-            result = result * 10 + (d as u64);
-        }
-        result
+        // Ranges starting at or before the BIN, scanned back until none
+        // further back can reach it
+        let end = self
+            .entries
+            .partition_point(|(range, _)| range.start <= bin);
+        let reachable = self.max_end[..end]
+            .iter()
+            .rev()
+            .take_while(|&&max| max >= bin)
+            .count();
+        narrowest(self.entries[end - reachable..end].iter().rev(), bin)
     }
 
-    /// Returns an iterator over all entries.
+    /// Returns an iterator over all entries in storage order.
+    ///
+    /// Storage order is insertion order until the database is sorted (by
+    /// [`MemoryBinDbBuilder::build`], [`from_entries`](Self::from_entries)
+    /// or [`build_index`](Self::build_index)); use
+    /// [`iter_ordered`](Self::iter_ordered) for an order that doesn't
+    /// depend on that.
     pub fn iter(&self) -> impl Iterator<Item = &(BinRange, BinInfo)> {
         self.entries.iter()
     }
 
+    /// Returns an iterator over all entries ordered by BIN: by range start,
+    /// then range end, then insertion order.
+    ///
+    /// The order is the same whether or not the database has been sorted
+    /// or indexed. An unsorted database is ordered on each call, in
+    /// O(n log n).
+    pub fn iter_ordered(&self) -> impl Iterator<Item = &(BinRange, BinInfo)> {
+        let (sorted, unsorted) = if self.sorted {
+            (Some(self.entries.iter()), None)
+        } else {
+            let mut entries: Vec<_> = self.entries.iter().collect();
+            entries.sort_by_key(|(range, _)| (range.start, range.end));
+            (None, Some(entries))
+        };
+        sorted
+            .into_iter()
+            .flatten()
+            .chain(unsorted.into_iter().flatten())
+    }
+
+    /// Returns the entries whose range overlaps the BINs from `start` to
+    /// `end` (inclusive, of any length; see [`BinRange::from_bounds`]),
+    /// ordered like [`iter_ordered`](Self::iter_ordered).
+    ///
+    /// Returns nothing if the bounds have no digits or `start` is after
+    /// `end`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::bin::{BinInfo, MemoryBinDbBuilder};
+    ///
+    /// let db = MemoryBinDbBuilder::new()
+    ///     .add("411111", BinInfo::with_bin("411111"))
+    ///     .add("550000", BinInfo::with_bin("550000"))
+    ///     .add_range("400000", "419999", BinInfo::with_bin("400000-419999"))
+    ///     .build();
+    ///
+    /// let bins: Vec<_> = db
+    ///     .entries_between("41", "49")
+    ///     .map(|(_, info)| info.bin.as_str())
+    ///     .collect();
+    /// assert_eq!(bins, ["400000-419999", "411111"]);
+    /// ```
+    pub fn entries_between(
+        &self,
+        start: &str,
+        end: &str,
+    ) -> impl Iterator<Item = &(BinRange, BinInfo)> {
+        let bounds = BinRange::from_bounds(start, end).map(|b| (b.start, b.end));
+        let candidates = match bounds {
+            // Ranges starting before the end whose running maximum end
+            // reaches the start
+            Some((start, end)) if self.sorted => {
+                let first = self.max_end.partition_point(|&max| max < start);
+                let last = self
+                    .entries
+                    .partition_point(|(range, _)| range.start <= end);
+                &self.entries[first..last.max(first)]
+            }
+            _ => &self.entries[..0],
+        };
+        let unsorted = bounds.filter(|_| !self.sorted).map(|_| self.iter_ordered());
+        candidates
+            .iter()
+            .chain(unsorted.into_iter().flatten())
+            .filter(move |(range, _)| {
+                bounds.is_some_and(|(start, end)| range.start <= end && range.end >= start)
+            })
+    }
+
+    /// Returns up to `limit` entries starting at `offset` in
+    /// [`iter_ordered`](Self::iter_ordered) order, for browsing large
+    /// databases a page at a time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::bin::{BinInfo, MemoryBinDb};
+    ///
+    /// let bins = ["550000", "411111", "370000"];
+    /// let db = MemoryBinDb::from_entries(
+    ///     bins.iter().map(|b| (b.to_string(), BinInfo::with_bin(*b))).collect(),
+    /// );
+    ///
+    /// let page = db.page(0, 2);
+    /// assert_eq!(page.entries[0].1.bin, "370000");
+    /// assert_eq!((page.total, page.next_offset()), (3, Some(2)));
+    /// assert_eq!(db.page(2, 2).next_offset(), None);
+    /// ```
+    pub fn page(&self, offset: usize, limit: usize) -> BinPage<'_> {
+        if self.sorted {
+            let start = offset.min(self.entries.len());
+            let end = offset.saturating_add(limit).min(self.entries.len());
+            BinPage {
+                entries: self.entries[start..end].iter().collect(),
+                offset,
+                total: self.entries.len(),
+            }
+        } else {
+            BinPage::of(self.iter_ordered(), offset, limit)
+        }
+    }
+
+    /// Returns a page of [`entries_between`](Self::entries_between)
+    /// `start` and `end`, like [`page`](Self::page).
+    pub fn page_between(&self, start: &str, end: &str, offset: usize, limit: usize) -> BinPage<'_> {
+        BinPage::of(self.entries_between(start, end), offset, limit)
+    }
+
     /// Clears all entries from the database.
     pub fn clear(&mut self) {
         self.entries.clear();
+        self.max_end.clear();
         self.exact_index = None;
         self.sorted = true;
     }
@@ -170,18 +303,7 @@ impl BinDatabase for MemoryBinDb {
             return None;
         }
 
-        // Try progressively shorter BIN lengths (8, 7, 6)
-        // This handles both 8-digit and 6-digit BIN databases
-        for len in (6..=8).rev() {
-            if bin.len() >= len {
-                let bin_num = Self::digits_to_u64(&bin[..len]);
-                if let Some(info) = self.lookup_bin(bin_num) {
-                    return Some(info.clone());
-                }
-            }
-        }
-
-        None
+        self.lookup_bin(BinRange::canonical_bin(bin)).cloned()
     }
 
     fn len(&self) -> usize {
@@ -189,6 +311,47 @@ impl BinDatabase for MemoryBinDb {
     }
 }
 
+/// One page of a [`MemoryBinDb`], from [`MemoryBinDb::page`] or
+/// [`MemoryBinDb::page_between`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinPage<'a> {
+    /// The entries on this page, in BIN order.
+    pub entries: Vec<&'a (BinRange, BinInfo)>,
+    /// Position of the first entry among all matching entries.
+    pub offset: usize,
+    /// Number of matching entries across all pages.
+    pub total: usize,
+}
+
+impl<'a> BinPage<'a> {
+    /// Collects the page at `offset` from `entries`, counting the rest.
+    fn of(
+        entries: impl Iterator<Item = &'a (BinRange, BinInfo)>,
+        offset: usize,
+        limit: usize,
+    ) -> Self {
+        let mut total = 0;
+        let mut page = Vec::new();
+        for entry in entries {
+            if total >= offset && page.len() < limit {
+                page.push(entry);
+            }
+            total += 1;
+        }
+        Self {
+            entries: page,
+            offset,
+            total,
+        }
+    }
+
+    /// Returns the offset of the next page, or `None` on the last page.
+    pub fn next_offset(&self) -> Option<usize> {
+        let next = self.offset.saturating_add(self.entries.len());
+        (!self.entries.is_empty() && next < self.total).then_some(next)
+    }
+}
+
 /// Builder for creating MemoryBinDb instances.
 #[derive(Debug, Default)]
 pub struct MemoryBinDbBuilder {
@@ -201,33 +364,49 @@ impl MemoryBinDbBuilder {
         Self::default()
     }
 
-    /// Adds a single BIN entry.
+    /// Adds a BIN prefix of any length.
     pub fn add(mut self, bin: &str, info: BinInfo) -> Self {
-        if let Some(bin_num) = BinRange::parse_bin(bin) {
-            self.entries.push((BinRange::single(bin_num), info));
+        if let Some(range) = BinRange::from_prefix(bin) {
+            self.entries.push((range, info));
         }
         self
     }
 
     /// Adds a BIN range entry.
     pub fn add_range(mut self, start: &str, end: &str, info: BinInfo) -> Self {
-        if let (Some(start_num), Some(end_num)) =
-            (BinRange::parse_bin(start), BinRange::parse_bin(end))
-        {
-            self.entries.push((BinRange::new(start_num, end_num), info));
+        if let Some(range) = BinRange::from_bounds(start, end) {
+            self.entries.push((range, info));
         }
         self
     }
 
     /// Builds the MemoryBinDb.
-    pub fn build(mut self) -> MemoryBinDb {
-        self.entries.sort_by(|a, b| a.0.cmp(&b.0));
-        MemoryBinDb {
+    pub fn build(self) -> MemoryBinDb {
+        let mut db = MemoryBinDb {
             entries: self.entries,
+            max_end: Vec::new(),
             exact_index: None,
-            sorted: true,
+            sorted: false,
+        };
+        db.ensure_sorted();
+        db
+    }
+}
+
+/// Returns the info of the narrowest range containing `bin`, preferring
+/// the first one seen among equally narrow ranges.
+fn narrowest<'a>(
+    entries: impl Iterator<Item = &'a (BinRange, BinInfo)>,
+    bin: u64,
+) -> Option<&'a BinInfo> {
+    let mut best: Option<&(BinRange, BinInfo)> = None;
+    for entry in entries.filter(|(range, _)| range.contains(bin)) {
+        let width = entry.0.end - entry.0.start;
+        if best.is_none_or(|(range, _)| width < range.end - range.start) {
+            best = Some(entry);
         }
     }
+    best.map(|(_, info)| info)
 }
 
 #[cfg(test)]
@@ -340,9 +519,89 @@ mod tests {
         assert!(db.is_empty());
     }
 
+    #[test]
+    fn test_mixed_length_entries() {
+        let mut db = MemoryBinDb::new();
+        db.insert("4", BinInfo::with_bin("4").issuer("Visa"));
+        db.insert_range(
+            "411111",
+            "41119999",
+            BinInfo::with_bin("411111").issuer("Range"),
+        );
+        db.insert("41111111", BinInfo::with_bin("41111111").issuer("Eight"));
+        db.insert("550000", BinInfo::with_bin("550000").issuer("Six"));
+        db.insert("55000012", BinInfo::with_bin("55000012").issuer("Eight"));
+
+        // Unsorted lookups scan linearly, sorted ones binary search
+        for sorted in [false, true] {
+            if sorted {
+                db.ensure_sorted();
+            }
+            let issuer = |bin: &str| db.lookup_str(bin).and_then(|info| info.issuer);
+            assert_eq!(issuer("4111111111111111").as_deref(), Some("Eight"));
+            assert_eq!(issuer("4111 1122 0000 0000").as_deref(), Some("Range"));
+            assert_eq!(issuer("4999999999999999").as_deref(), Some("Visa"));
+            assert_eq!(issuer("5500001200000000").as_deref(), Some("Eight"));
+            assert_eq!(issuer("5500009900000000").as_deref(), Some("Six"));
+            assert_eq!(issuer("550000").as_deref(), Some("Six"));
+            // A 6-digit range must not swallow unrelated 8-digit BINs
+            assert!(db.lookup_str("5000000000000000").is_none());
+        }
+    }
+
     #[test]
     fn test_empty_lookup() {
         let db = sample_db();
         assert!(db.lookup(&[]).is_none());
     }
+
+    #[test]
+    fn test_ordered_iteration() {
+        let mut db = MemoryBinDb::new();
+        db.insert("550000", BinInfo::with_bin("550000"));
+        db.insert_range("4", "4", BinInfo::with_bin("4"));
+        db.insert("41111111", BinInfo::with_bin("41111111"));
+        db.insert("411111", BinInfo::with_bin("411111"));
+        db.insert("370000", BinInfo::with_bin("370000"));
+
+        let bins = |entries: Vec<&(BinRange, BinInfo)>| -> Vec<String> {
+            entries
+                .into_iter()
+                .map(|(_, info)| info.bin.clone())
+                .collect()
+        };
+        // Same order before and after sorting and indexing
+        for sorted in [false, true] {
+            if sorted {
+                db.build_index();
+            }
+            assert_eq!(
+                bins(db.iter_ordered().collect()),
+                ["370000", "4", "411111", "41111111", "550000"]
+            );
+            assert_eq!(
+                bins(db.entries_between("411111", "411111").collect()),
+                ["4", "411111", "41111111"]
+            );
+            assert_eq!(
+                bins(db.entries_between("42", "5500").collect()),
+                ["4", "550000"]
+            );
+            assert_eq!(db.entries_between("5", "4").count(), 0);
+            assert_eq!(db.entries_between("", "9").count(), 0);
+
+            let page = db.page(1, 2);
+            assert_eq!(bins(page.entries.clone()), ["4", "411111"]);
+            assert_eq!(
+                (page.offset, page.total, page.next_offset()),
+                (1, 5, Some(3))
+            );
+            assert_eq!(db.page(3, 2).next_offset(), None);
+            assert!(db.page(9, 2).entries.is_empty());
+
+            let page = db.page_between("4", "4", 2, 10);
+            assert_eq!(bins(page.entries.clone()), ["41111111"]);
+            assert_eq!((page.total, page.next_offset()), (3, None));
+        }
+    }
 }
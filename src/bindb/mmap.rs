@@ -0,0 +1,434 @@
+//! Memory-mapped, read-only BIN database.
+//!
+//! [`MmapBinDb`] maps a compiled BIN file and binary-searches it in place.
+//! Opening only checks the header, with no read or deserialization pass, so
+//! startup cost does not grow with the size of the dataset, which suits
+//! serverless and other cold-start-sensitive deployments. Pages are loaded
+//! by the OS as lookups touch them. Compile the file ahead of time with
+//! [`MmapBinDb::compile`] from any [`MemoryBinDb`] (e.g. one loaded from
+//! JSON or CSV).
+//!
+//! # Feature
+//!
+//! Requires the `bin-mmap` feature.
+//!
+//! # File format
+//!
+//! All integers are little-endian.
+//!
+//! | Section | Layout |
+//! |---------|--------|
+//! | Header (16 bytes) | magic `CCVBIN02`, entry count (u32), reserved (u32) |
+//! | Index (32 bytes per entry, sorted by range start) | range start (u64), range end (u64), largest range end up to this entry (u64), record offset (u32), record length (u32) |
+//! | Records | card type (u8), card level (u8), then `bin`, `issuer`, `country`, `country_name`, `brand`, `bank_phone`, `bank_url` as u16 length + UTF-8 bytes (`0xFFFF` for none) |
+//!
+//! Ranges are in the canonical 8-digit space described on [`BinRange`].
+//! Record offsets are relative to the start of the records section. Type
+//! and level are 0 for none, otherwise the variant's position plus one.
+
+use super::{BinDatabase, BinDbError, BinInfo, BinRange, CardLevel, CardType, MemoryBinDb};
+use memmap2::Mmap;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+const MAGIC: &[u8; 8] = b"CCVBIN02";
+const HEADER_LEN: usize = 16;
+const INDEX_ENTRY_LEN: usize = 32;
+const NONE_LEN: u16 = u16::MAX;
+
+const CARD_TYPES: [CardType; 6] = [
+    CardType::Credit,
+    CardType::Debit,
+    CardType::Prepaid,
+    CardType::Charge,
+    CardType::Corporate,
+    CardType::Unknown,
+];
+
+const CARD_LEVELS: [CardLevel; 9] = [
+    CardLevel::Standard,
+    CardLevel::Gold,
+    CardLevel::Platinum,
+    CardLevel::Signature,
+    CardLevel::Infinite,
+    CardLevel::Business,
+    CardLevel::Corporate,
+    CardLevel::World,
+    CardLevel::Unknown,
+];
+
+/// A read-only BIN database backed by a memory-mapped compiled file.
+///
+/// Lookups match [`MemoryBinDb`]: the narrowest stored range containing
+/// the card's canonical BIN wins. A corrupt record makes its lookup return
+/// `None` rather than panic.
+///
+/// The file must not be modified while mapped; replace it by writing a new
+/// file and renaming it over the old one, then reopen.
+///
+/// # Example
+///
+/// ```no_run
+/// use cc_validator::bin::{BinDatabase, BinInfo, MmapBinDb, MemoryBinDb};
+///
+/// # fn main() -> Result<(), cc_validator::bin::BinDbError> {
+/// let mut source = MemoryBinDb::new();
+/// source.insert("411111", BinInfo::with_bin("411111").issuer("Test Bank"));
+/// MmapBinDb::compile(&source, "bins.ccbin")?;
+///
+/// let db = MmapBinDb::open("bins.ccbin")?;
+/// assert_eq!(db.lookup_str("411111").unwrap().issuer.as_deref(), Some("Test Bank"));
+/// # Ok(())
+/// # }
+/// ```
+pub struct MmapBinDb {
+    data: Mmap,
+    count: usize,
+}
+
+impl MmapBinDb {
+    /// Maps a compiled BIN file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BinDbError::IoError`] if the file can't be opened or mapped
+    /// and [`BinDbError::InvalidDatabase`] if the header is wrong or the
+    /// file is too short for its index.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, BinDbError> {
+        let data = map_file(&File::open(path)?)?;
+        if data.len() < HEADER_LEN || &data[..MAGIC.len()] != MAGIC {
+            return Err(BinDbError::InvalidDatabase(
+                "not a compiled BIN database".to_string(),
+            ));
+        }
+        let count = read_u32(&data, 8).unwrap_or(0) as usize;
+        let index_end = count
+            .checked_mul(INDEX_ENTRY_LEN)
+            .and_then(|len| len.checked_add(HEADER_LEN));
+        if index_end.is_none_or(|end| data.len() < end) {
+            return Err(BinDbError::InvalidDatabase(format!(
+                "index of {} entries is truncated",
+                count
+            )));
+        }
+
+        Ok(Self { data, count })
+    }
+
+    /// Writes `db` in the compiled format to `path`.
+    pub fn compile<P: AsRef<Path>>(db: &MemoryBinDb, path: P) -> Result<(), BinDbError> {
+        let mut file = std::io::BufWriter::new(File::create(path)?);
+        Self::write(db, &mut file)?;
+        file.flush()?;
+        Ok(())
+    }
+
+    /// Writes `db` in the compiled format to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BinDbError::InvalidDatabase`] if a string field is longer
+    /// than 65534 bytes or the records exceed 4 GiB.
+    pub fn write<W: Write>(db: &MemoryBinDb, mut writer: W) -> Result<(), BinDbError> {
+        let mut entries: Vec<&(BinRange, BinInfo)> = db.iter().collect();
+        entries.sort_by_key(|(range, _)| range.start);
+
+        let mut index = Vec::with_capacity(entries.len() * INDEX_ENTRY_LEN);
+        let mut records = Vec::new();
+        let mut max_end = 0;
+        for (range, info) in entries {
+            max_end = range.end.max(max_end);
+            let offset = records.len();
+            encode_record(info, &mut records)?;
+            let (Ok(offset), Ok(len)) =
+                (u32::try_from(offset), u32::try_from(records.len() - offset))
+            else {
+                return Err(BinDbError::InvalidDatabase(
+                    "records exceed 4 GiB".to_string(),
+                ));
+            };
+            index.extend_from_slice(&range.start.to_le_bytes());
+            index.extend_from_slice(&range.end.to_le_bytes());
+            index.extend_from_slice(&max_end.to_le_bytes());
+            index.extend_from_slice(&offset.to_le_bytes());
+            index.extend_from_slice(&len.to_le_bytes());
+        }
+
+        let count = u32::try_from(index.len() / INDEX_ENTRY_LEN)
+            .map_err(|_| BinDbError::InvalidDatabase("too many entries".to_string()))?;
+        writer.write_all(MAGIC)?;
+        writer.write_all(&count.to_le_bytes())?;
+        writer.write_all(&0u32.to_le_bytes())?;
+        writer.write_all(&index)?;
+        writer.write_all(&records)?;
+        Ok(())
+    }
+
+    /// Returns the `(start, end, max_end)` fields of the `i`th index entry.
+    fn range(&self, i: usize) -> (u64, u64, u64) {
+        let at = HEADER_LEN + i * INDEX_ENTRY_LEN;
+        (
+            read_u64(&self.data, at).unwrap_or(0),
+            read_u64(&self.data, at + 8).unwrap_or(0),
+            read_u64(&self.data, at + 16).unwrap_or(0),
+        )
+    }
+
+    /// Decodes the record of the `i`th index entry.
+    fn record(&self, i: usize) -> Option<BinInfo> {
+        let at = HEADER_LEN + i * INDEX_ENTRY_LEN + 24;
+        let offset = read_u32(&self.data, at)? as usize;
+        let len = read_u32(&self.data, at + 4)? as usize;
+        let start = HEADER_LEN + self.count * INDEX_ENTRY_LEN + offset;
+        decode_record(self.data.get(start..start.checked_add(len)?)?)
+    }
+
+    fn lookup_bin(&self, bin: u64) -> Option<BinInfo> {
+        // First entry starting after the BIN
+        let (mut lo, mut hi) = (0, self.count);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.range(mid).0 <= bin {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+
+        // Scan back while an earlier range can still reach the BIN, keeping
+        // the narrowest (the last inserted among equals, as in MemoryBinDb)
+        let mut best: Option<(usize, u64)> = None;
+        for i in (0..lo).rev() {
+            let (start, end, max_end) = self.range(i);
+            if max_end < bin {
+                break;
+            }
+            if end < bin {
+                continue;
+            }
+            let width = end.saturating_sub(start);
+            if best.is_none_or(|(_, best)| width < best) {
+                best = Some((i, width));
+            }
+        }
+        self.record(best?.0)
+    }
+}
+
+impl BinDatabase for MmapBinDb {
+    fn lookup(&self, bin: &[u8]) -> Option<BinInfo> {
+        if bin.is_empty() {
+            return None;
+        }
+        self.lookup_bin(BinRange::canonical_bin(bin))
+    }
+
+    fn len(&self) -> usize {
+        self.count
+    }
+}
+
+impl std::fmt::Debug for MmapBinDb {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MmapBinDb")
+            .field("entries", &self.count)
+            .field("bytes", &self.data.len())
+            .finish()
+    }
+}
+
+/// Maps `file` read-only.
+#[allow(unsafe_code)]
+fn map_file(file: &File) -> Result<Mmap, BinDbError> {
+    // SAFETY: the mapping is read-only and every access goes through
+    // bounds-checked slicing. Truncating or rewriting the file while it is
+    // mapped is documented as unsupported on `MmapBinDb`.
+    Ok(unsafe { Mmap::map(file) }?)
+}
+
+fn read_u32(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn read_u64(data: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(data.get(at..at + 8)?.try_into().ok()?))
+}
+
+fn encode_record(info: &BinInfo, out: &mut Vec<u8>) -> Result<(), BinDbError> {
+    let type_code = info
+        .card_type
+        .and_then(|t| CARD_TYPES.iter().position(|&c| c == t))
+        .map_or(0, |i| i as u8 + 1);
+    let level_code = info
+        .card_level
+        .and_then(|l| CARD_LEVELS.iter().position(|&c| c == l))
+        .map_or(0, |i| i as u8 + 1);
+    out.push(type_code);
+    out.push(level_code);
+
+    let fields = [
+        Some(&info.bin),
+        info.issuer.as_ref(),
+        info.country.as_ref(),
+        info.country_name.as_ref(),
+        info.brand.as_ref(),
+        info.bank_phone.as_ref(),
+        info.bank_url.as_ref(),
+    ];
+    for field in fields {
+        match field {
+            Some(s) => {
+                let len = u16::try_from(s.len())
+                    .ok()
+                    .filter(|&len| len != NONE_LEN)
+                    .ok_or_else(|| {
+                        BinDbError::InvalidDatabase(format!(
+                            "field too long for BIN {}: {} bytes",
+                            info.bin,
+                            s.len()
+                        ))
+                    })?;
+                out.extend_from_slice(&len.to_le_bytes());
+                out.extend_from_slice(s.as_bytes());
+            }
+            None => out.extend_from_slice(&NONE_LEN.to_le_bytes()),
+        }
+    }
+    Ok(())
+}
+
+fn decode_record(mut data: &[u8]) -> Option<BinInfo> {
+    fn field(data: &mut &[u8]) -> Option<Option<String>> {
+        let len = u16::from_le_bytes(data.get(..2)?.try_into().ok()?);
+        *data = &data[2..];
+        if len == NONE_LEN {
+            return Some(None);
+        }
+        let (bytes, rest) = data.split_at_checked(len as usize)?;
+        *data = rest;
+        Some(Some(std::str::from_utf8(bytes).ok()?.to_string()))
+    }
+
+    let (&type_code, rest) = data.split_first()?;
+    let (&level_code, rest) = rest.split_first()?;
+    data = rest;
+
+    let card_type = match type_code {
+        0 => None,
+        code => Some(*CARD_TYPES.get(code as usize - 1)?),
+    };
+    let card_level = match level_code {
+        0 => None,
+        code => Some(*CARD_LEVELS.get(code as usize - 1)?),
+    };
+
+    Some(BinInfo {
+        bin: field(&mut data)?.unwrap_or_default(),
+        issuer: field(&mut data)?,
+        card_type,
+        card_level,
+        country: field(&mut data)?,
+        country_name: field(&mut data)?,
+        brand: field(&mut data)?,
+        bank_phone: field(&mut data)?,
+        bank_url: field(&mut data)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("compiled_{}_{}.ccbin", name, std::process::id()))
+    }
+
+    fn source() -> MemoryBinDb {
+        let mut db = MemoryBinDb::new();
+        let mut full = BinInfo::with_bin("411111")
+            .issuer("Test Bank")
+            .card_type(CardType::Credit)
+            .card_level(CardLevel::Platinum)
+            .country("US");
+        full.country_name = Some("United States".to_string());
+        full.bank_url = Some("https://bank.example".to_string());
+        db.insert("411111", full);
+        db.insert(
+            "55000000",
+            BinInfo::with_bin("55000000").issuer("Eight Digit"),
+        );
+        db.insert_range(
+            "510000",
+            "519999",
+            BinInfo::with_bin("51").issuer("Range Bank"),
+        );
+        db.insert("41111199", BinInfo::with_bin("41111199").issuer("Nested"));
+        db.build_index();
+        db
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let path = temp_path("round_trip");
+        let source = source();
+        MmapBinDb::compile(&source, &path).unwrap();
+        let db = MmapBinDb::open(&path).unwrap();
+
+        assert_eq!(db.len(), 4);
+        assert_eq!(
+            db.lookup_str("4111119900000000").unwrap().issuer.as_deref(),
+            Some("Nested")
+        );
+        assert_eq!(
+            db.lookup_str("4111110000000000").unwrap().issuer.as_deref(),
+            Some("Test Bank")
+        );
+        for bin in [
+            "411111",
+            "4111111111111111",
+            "4111119900000000",
+            "5500000000000004",
+            "5500000100000000",
+            "515151",
+        ] {
+            assert_eq!(db.lookup_str(bin), source.lookup_str(bin), "{}", bin);
+        }
+        assert!(db.lookup_str("999999").is_none());
+        assert!(db.lookup_str("4111").is_none());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_bad_files() {
+        let path = temp_path("bad");
+        std::fs::write(&path, b"not a BIN database").unwrap();
+        let err = MmapBinDb::open(&path).unwrap_err();
+        assert_eq!(err.code(), "invalid_database");
+
+        // Header claiming more entries than the file holds
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&5u32.to_le_bytes());
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(MmapBinDb::open(&path).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(MmapBinDb::open(&path).unwrap_err().code(), "io_error");
+    }
+
+    #[test]
+    fn test_corrupt_record_is_not_found() {
+        let mut bytes = Vec::new();
+        MmapBinDb::write(&source(), &mut bytes).unwrap();
+        // Cut the records section short
+        bytes.truncate(bytes.len() - 10);
+        let path = temp_path("corrupt");
+        std::fs::write(&path, &bytes).unwrap();
+
+        let db = MmapBinDb::open(&path).unwrap();
+        assert!(db.lookup_str("510000").is_some());
+        assert!(db.lookup_str("55000000").is_none());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -7,9 +7,12 @@
 //!
 //! BIN database support is optional and requires feature flags:
 //!
-//! - `bin-json` - JSON file loader
+//! - `bin-json` - JSON file loader (plain or gzip-compressed)
 //! - `bin-csv` - CSV file loader
 //! - `bin-sqlite` - SQLite database loader
+//! - `bin-redis` - Redis-backed database shared between processes
+//! - `bin-redis-async` - Async Redis lookups through [`AsyncBinDatabase`]
+//! - `bin-mmap` - Memory-mapped compiled database for fast cold starts
 //!
 //! # Example
 //!
@@ -26,6 +29,12 @@
 //! }
 //! ```
 
+mod async_db;
+mod cached;
+mod chained;
+mod currency;
+mod fee_tier;
+pub(crate) mod lru;
 mod memory;
 
 #[cfg(feature = "bin-json")]
@@ -37,17 +46,36 @@ mod csv;
 #[cfg(feature = "bin-sqlite")]
 mod sqlite;
 
-pub use memory::{MemoryBinDb, MemoryBinDbBuilder};
+#[cfg(feature = "bin-redis")]
+mod redis;
+
+#[cfg(feature = "bin-mmap")]
+mod mmap;
+
+#[cfg(feature = "tokio")]
+pub use async_db::lookup_cards;
+pub use async_db::{open_async_database, AsyncBinDatabase, BinLookup, SyncAdapter};
+pub use cached::{CacheStats, CachedBinDb};
+pub use chained::ChainedBinDb;
+pub use currency::{currency_for_country, infer_currency};
+pub use fee_tier::{fee_tier, FeeTier};
+pub use memory::{BinPage, MemoryBinDb, MemoryBinDbBuilder};
 
 #[cfg(feature = "bin-json")]
 pub use json::JsonBinLoader;
 
 #[cfg(feature = "bin-csv")]
-pub use csv::CsvBinLoader;
+pub use csv::{CsvBinLoader, CsvLoadOptions, CsvLoadReport, ErrorTolerance};
 
 #[cfg(feature = "bin-sqlite")]
 pub use sqlite::SqliteBinDb;
 
+#[cfg(feature = "bin-redis")]
+pub use self::redis::{RedisBinDb, DEFAULT_PREFIX as REDIS_DEFAULT_PREFIX};
+
+#[cfg(feature = "bin-mmap")]
+pub use mmap::MmapBinDb;
+
 use std::fmt;
 
 /// Trait for BIN database implementations.
@@ -57,6 +85,12 @@ use std::fmt;
 ///
 /// - `MemoryBinDb` - In-memory database using sorted vector + binary search
 /// - `SqliteBinDb` - SQLite-backed database (requires `bin-sqlite` feature)
+/// - `RedisBinDb` - Redis-backed database (requires `bin-redis` feature)
+/// - `MmapBinDb` - Memory-mapped compiled file (requires `bin-mmap` feature)
+///
+/// Wrap a slow backend in [`CachedBinDb`] to cache its lookups, and layer
+/// several databases with [`ChainedBinDb`]. Async code that shouldn't block
+/// on lookups uses [`AsyncBinDatabase`] instead.
 pub trait BinDatabase: Send + Sync {
     /// Looks up BIN information for the given digits.
     ///
@@ -171,6 +205,63 @@ impl BinInfo {
         self.country = Some(country.into());
         self
     }
+
+    /// Fills fields that are missing here from `other`.
+    ///
+    /// `Unknown` card types and levels count as missing. The BIN is taken
+    /// from `other` only if this entry has none.
+    pub fn fill_missing(&mut self, other: &BinInfo) {
+        fn fill<T: Clone>(field: &mut Option<T>, other: &Option<T>) {
+            if field.is_none() {
+                field.clone_from(other);
+            }
+        }
+
+        if self.bin.is_empty() {
+            self.bin.clone_from(&other.bin);
+        }
+        fill(&mut self.issuer, &other.issuer);
+        if matches!(self.card_type, None | Some(CardType::Unknown)) && other.card_type.is_some() {
+            self.card_type = other.card_type;
+        }
+        if matches!(self.card_level, None | Some(CardLevel::Unknown)) && other.card_level.is_some()
+        {
+            self.card_level = other.card_level;
+        }
+        fill(&mut self.country, &other.country);
+        fill(&mut self.country_name, &other.country_name);
+        fill(&mut self.brand, &other.brand);
+        fill(&mut self.bank_phone, &other.bank_phone);
+        fill(&mut self.bank_url, &other.bank_url);
+    }
+
+    /// Returns the ISO 4217 currency of the issuer country, if known.
+    ///
+    /// See [`infer_currency`].
+    pub fn currency(&self) -> Option<&'static str> {
+        infer_currency(self)
+    }
+
+    /// Returns the interchange fee tier hint, if the card type is known.
+    ///
+    /// See [`fee_tier`].
+    pub fn fee_tier(&self) -> Option<FeeTier> {
+        fee_tier(self)
+    }
+
+    /// Returns `true` if every field is set (and the card type and level
+    /// are known).
+    pub fn is_complete(&self) -> bool {
+        !self.bin.is_empty()
+            && self.issuer.is_some()
+            && !matches!(self.card_type, None | Some(CardType::Unknown))
+            && !matches!(self.card_level, None | Some(CardLevel::Unknown))
+            && self.country.is_some()
+            && self.country_name.is_some()
+            && self.brand.is_some()
+            && self.bank_phone.is_some()
+            && self.bank_url.is_some()
+    }
 }
 
 /// Type of payment card.
@@ -248,9 +339,24 @@ impl fmt::Display for CardLevel {
     }
 }
 
+/// Number of digits in the canonical BIN space (see [`BinRange`]).
+pub const BIN_SPACE_DIGITS: usize = 8;
+
 /// A range of BIN numbers.
 ///
 /// Used for efficient lookup when BINs are assigned in ranges.
+///
+/// # Canonical BIN space
+///
+/// BIN databases mix 6- and 8-digit entries (and sometimes shorter or
+/// longer prefixes), and `"4111"` and `"41110000"` parse to numbers of
+/// different magnitudes. [`MemoryBinDb`] and `MmapBinDb` therefore store
+/// every range in a canonical 8-digit space built with
+/// [`from_prefix`](Self::from_prefix) and [`from_bounds`](Self::from_bounds):
+/// a prefix stands for every 8-digit BIN it starts, so `"411111"` becomes
+/// `41111100..=41111199`. Lookups use [`canonical_bin`](Self::canonical_bin)
+/// and the narrowest range containing it wins, so an 8-digit entry overrides
+/// the 6-digit range around it.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BinRange {
     /// Start of the range (inclusive).
@@ -280,6 +386,9 @@ impl BinRange {
     }
 
     /// Parses a BIN string into a u64.
+    ///
+    /// The value keeps the string's magnitude; use
+    /// [`from_prefix`](Self::from_prefix) for the canonical range.
     pub fn parse_bin(bin: &str) -> Option<u64> {
         bin.chars()
             .filter(|c| c.is_ascii_digit())
@@ -287,6 +396,84 @@ impl BinRange {
             .parse()
             .ok()
     }
+
+    /// Returns the canonical range of every 8-digit BIN starting with
+    /// `prefix`.
+    ///
+    /// Separators are ignored and digits past the eighth are dropped.
+    /// Returns `None` if `prefix` has no digits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::bin::BinRange;
+    ///
+    /// assert_eq!(BinRange::from_prefix("411111"), Some(BinRange::new(41111100, 41111199)));
+    /// assert_eq!(BinRange::from_prefix("4111-1111"), Some(BinRange::single(41111111)));
+    /// ```
+    pub fn from_prefix(prefix: &str) -> Option<Self> {
+        Self::from_bounds(prefix, prefix)
+    }
+
+    /// Returns the canonical range from the first BIN starting with `start`
+    /// to the last one starting with `end`.
+    ///
+    /// `start` is padded with zeros and `end` with nines, so bounds of
+    /// different lengths (`"411111"` to `"41119999"`) compare correctly.
+    /// Returns `None` if either bound has no digits or `start` is after
+    /// `end`.
+    pub fn from_bounds(start: &str, end: &str) -> Option<Self> {
+        let start = canonical(start, 0)?;
+        let end = canonical(end, 9)?;
+        (start <= end).then(|| Self::new(start, end))
+    }
+
+    /// Returns the canonical value of a card number's leading digits: the
+    /// first eight, padded with zeros if there are fewer.
+    #[inline]
+    pub fn canonical_bin(digits: &[u8]) -> u64 {
+        (0..BIN_SPACE_DIGITS).fold(0, |n, i| {
+            n * 10 + u64::from(digits.get(i).copied().unwrap_or(0))
+        })
+    }
+
+    /// Returns the prefix this canonical range stands for, if it covers
+    /// exactly the BINs starting with some prefix.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::bin::BinRange;
+    ///
+    /// assert_eq!(BinRange::from_prefix("411111").unwrap().prefix().as_deref(), Some("411111"));
+    /// assert_eq!(BinRange::from_bounds("400000", "400050").unwrap().prefix(), None);
+    /// ```
+    pub fn prefix(&self) -> Option<String> {
+        // Drop trailing digits while the range spans all their values
+        let span = self.end.checked_sub(self.start)?;
+        let mut len = BIN_SPACE_DIGITS;
+        let mut width = 1;
+        while len > 1 && self.start.is_multiple_of(width * 10) && span >= width * 10 - 1 {
+            width *= 10;
+            len -= 1;
+        }
+        (span == width - 1).then(|| format!("{:0len$}", self.start / width))
+    }
+}
+
+/// Maps a BIN string into the canonical space, padding with `fill`.
+fn canonical(bin: &str, fill: u8) -> Option<u64> {
+    let mut digits = bin
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .map(|b| b - b'0')
+        .take(BIN_SPACE_DIGITS)
+        .peekable();
+    digits.peek()?;
+    let digits: Vec<u8> = digits.collect();
+    Some((0..BIN_SPACE_DIGITS).fold(0, |n, i| {
+        n * 10 + u64::from(digits.get(i).copied().unwrap_or(fill))
+    }))
 }
 
 impl PartialOrd for BinRange {
@@ -312,6 +499,59 @@ pub enum BinDbError {
     InvalidDatabase(String),
     /// Feature not available.
     FeatureNotEnabled(String),
+    /// Malformed JSON at the given position (1-based).
+    Json {
+        /// Line of the offending input.
+        line: usize,
+        /// Column of the offending input.
+        column: usize,
+        /// Description of the problem.
+        message: String,
+    },
+    /// Malformed CSV record.
+    Csv {
+        /// Line of the offending record, if known.
+        line: Option<u64>,
+        /// Description of the problem.
+        message: String,
+    },
+    /// An entry's BIN is not a digit string.
+    InvalidBin {
+        /// The offending BIN as it appeared in the source.
+        bin: String,
+        /// Line of the offending entry, if known.
+        line: Option<u64>,
+    },
+    /// A required column is missing from the header row.
+    MissingColumn(String),
+}
+
+impl BinDbError {
+    /// Returns a stable, machine-readable code for this error.
+    ///
+    /// Codes are snake_case and do not change between releases, so they are
+    /// suitable for logs, metrics, and programmatic handling.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::IoError(_) => "io_error",
+            Self::ParseError(_) => "parse_error",
+            Self::InvalidDatabase(_) => "invalid_database",
+            Self::FeatureNotEnabled(_) => "feature_not_enabled",
+            Self::Json { .. } => "json_syntax",
+            Self::Csv { .. } => "csv_syntax",
+            Self::InvalidBin { .. } => "invalid_bin",
+            Self::MissingColumn(_) => "missing_column",
+        }
+    }
+
+    /// Returns the line of the input that caused the error, if known.
+    pub fn line(&self) -> Option<u64> {
+        match self {
+            Self::Json { line, .. } => Some(*line as u64),
+            Self::Csv { line, .. } | Self::InvalidBin { line, .. } => *line,
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for BinDbError {
@@ -321,6 +561,29 @@ impl fmt::Display for BinDbError {
             Self::ParseError(s) => write!(f, "Parse error: {}", s),
             Self::InvalidDatabase(s) => write!(f, "Invalid database: {}", s),
             Self::FeatureNotEnabled(s) => write!(f, "Feature not enabled: {}", s),
+            Self::Json {
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "JSON error at line {} column {}: {}",
+                line, column, message
+            ),
+            Self::Csv {
+                line: Some(line),
+                message,
+            } => write!(f, "CSV error at line {}: {}", line, message),
+            Self::Csv {
+                line: None,
+                message,
+            } => write!(f, "CSV error: {}", message),
+            Self::InvalidBin {
+                bin,
+                line: Some(line),
+            } => write!(f, "Invalid BIN {:?} at line {}", bin, line),
+            Self::InvalidBin { bin, line: None } => write!(f, "Invalid BIN {:?}", bin),
+            Self::MissingColumn(s) => write!(f, "Missing required '{}' column", s),
         }
     }
 }
@@ -340,6 +603,146 @@ impl From<std::io::Error> for BinDbError {
     }
 }
 
+#[cfg(feature = "bin-redis")]
+impl From<::redis::RedisError> for BinDbError {
+    fn from(err: ::redis::RedisError) -> Self {
+        if err.is_io_error() || err.is_connection_refusal() {
+            Self::IoError(std::io::Error::other(err.to_string()))
+        } else {
+            Self::InvalidDatabase(err.to_string())
+        }
+    }
+}
+
+#[cfg(feature = "bin-json")]
+impl From<serde_json::Error> for BinDbError {
+    fn from(err: serde_json::Error) -> Self {
+        if err.is_io() {
+            return Self::IoError(err.into());
+        }
+        // serde_json appends " at line N column M" to its Display output
+        let message = err.to_string();
+        let message = match message.rfind(" at line ") {
+            Some(pos) => message[..pos].to_string(),
+            None => message,
+        };
+        Self::Json {
+            line: err.line(),
+            column: err.column(),
+            message,
+        }
+    }
+}
+
+#[cfg(feature = "bin-csv")]
+impl From<::csv::Error> for BinDbError {
+    fn from(err: ::csv::Error) -> Self {
+        let line = err.position().map(|p| p.line());
+        let message = match err.kind() {
+            ::csv::ErrorKind::UnequalLengths {
+                expected_len, len, ..
+            } => format!("expected {} fields, found {}", expected_len, len),
+            _ => err.to_string(),
+        };
+        match err.into_kind() {
+            ::csv::ErrorKind::Io(e) => Self::IoError(e),
+            _ => Self::Csv { line, message },
+        }
+    }
+}
+
+/// Checks that a BIN read from a data file is a usable digit string.
+///
+/// Spaces and dashes are tolerated as separators, matching
+/// [`BinRange::parse_bin`]; anything else is reported as
+/// [`BinDbError::InvalidBin`].
+#[cfg_attr(not(any(feature = "bin-json", feature = "bin-csv")), allow(dead_code))]
+pub(crate) fn check_bin(bin: &str, line: Option<u64>) -> Result<(), BinDbError> {
+    let digits = bin.chars().filter(|c| c.is_ascii_digit()).count();
+    let separators_only = bin
+        .chars()
+        .all(|c| c.is_ascii_digit() || c == ' ' || c == '-');
+
+    if separators_only && (1..=19).contains(&digits) {
+        Ok(())
+    } else {
+        Err(BinDbError::InvalidBin {
+            bin: bin.to_string(),
+            line,
+        })
+    }
+}
+
+/// Opens a BIN database file, choosing the loader from its extension.
+///
+/// `.json` and `.json.gz` files use [`JsonBinLoader`], `.csv` files use [`CsvBinLoader`],
+/// `.db`/`.sqlite`/`.sqlite3` files use [`SqliteBinDb`], and `.ccbin` files use
+/// `MmapBinDb`; each requires the matching feature. A `redis://` or `rediss://` URL connects
+/// a `RedisBinDb` (feature `bin-redis`) with the default key prefix.
+///
+/// # Errors
+///
+/// Returns [`BinDbError::FeatureNotEnabled`] if the loader for the extension
+/// was not compiled in, [`BinDbError::InvalidDatabase`] for an unknown
+/// extension, or any error from the loader itself.
+pub fn open_database<P: AsRef<std::path::Path>>(
+    path: P,
+) -> Result<Box<dyn BinDatabase>, BinDbError> {
+    let path = path.as_ref();
+    if let Some(url) = path
+        .to_str()
+        .filter(|p| p.starts_with("redis://") || p.starts_with("rediss://"))
+    {
+        #[cfg(feature = "bin-redis")]
+        return Ok(Box::new(RedisBinDb::open(url)?));
+        #[cfg(not(feature = "bin-redis"))]
+        {
+            let _ = url;
+            return Err(BinDbError::FeatureNotEnabled("bin-redis".to_string()));
+        }
+    }
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(|n| n.to_ascii_lowercase())
+        .unwrap_or_default();
+    let (stem, gzipped) = match name.strip_suffix(".gz") {
+        Some(stem) => (stem, true),
+        None => (name.as_str(), false),
+    };
+    let ext = stem.rsplit_once('.').map_or("", |(_, ext)| ext);
+
+    if gzipped && ext != "json" {
+        return Err(BinDbError::InvalidDatabase(format!(
+            "gzip compression is only supported for JSON BIN databases: {}",
+            path.display()
+        )));
+    }
+
+    match ext {
+        #[cfg(feature = "bin-json")]
+        "json" => Ok(Box::new(JsonBinLoader::from_file(path)?)),
+        #[cfg(feature = "bin-csv")]
+        "csv" => Ok(Box::new(CsvBinLoader::from_file(path)?)),
+        #[cfg(feature = "bin-sqlite")]
+        "db" | "sqlite" | "sqlite3" => Ok(Box::new(SqliteBinDb::open(path)?)),
+        #[cfg(feature = "bin-mmap")]
+        "ccbin" => Ok(Box::new(MmapBinDb::open(path)?)),
+        #[cfg(not(feature = "bin-json"))]
+        "json" => Err(BinDbError::FeatureNotEnabled("bin-json".to_string())),
+        #[cfg(not(feature = "bin-csv"))]
+        "csv" => Err(BinDbError::FeatureNotEnabled("bin-csv".to_string())),
+        #[cfg(not(feature = "bin-sqlite"))]
+        "db" | "sqlite" | "sqlite3" => Err(BinDbError::FeatureNotEnabled("bin-sqlite".to_string())),
+        #[cfg(not(feature = "bin-mmap"))]
+        "ccbin" => Err(BinDbError::FeatureNotEnabled("bin-mmap".to_string())),
+        _ => Err(BinDbError::InvalidDatabase(format!(
+            "unsupported BIN database extension: {}",
+            path.display()
+        ))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -359,6 +762,26 @@ mod tests {
         assert_eq!(info.country, Some("US".to_string()));
     }
 
+    #[test]
+    fn test_bin_info_fill_missing() {
+        let mut info = BinInfo::with_bin("411111")
+            .issuer("Override Bank")
+            .card_type(CardType::Unknown);
+        info.fill_missing(
+            &BinInfo::with_bin("4111")
+                .issuer("Vendor Bank")
+                .card_type(CardType::Debit)
+                .country("US"),
+        );
+
+        assert_eq!(info.bin, "411111");
+        assert_eq!(info.issuer, Some("Override Bank".to_string()));
+        assert_eq!(info.card_type, Some(CardType::Debit));
+        assert_eq!(info.country, Some("US".to_string()));
+        assert!(info.card_level.is_none());
+        assert!(!info.is_complete());
+    }
+
     #[test]
     fn test_bin_range() {
         let range = BinRange::new(411111, 411199);
@@ -383,6 +806,33 @@ mod tests {
         assert_eq!(BinRange::parse_bin(""), None);
     }
 
+    #[test]
+    fn test_canonical_ranges() {
+        // Mixed-length bounds land in the same 8-digit space
+        let range = BinRange::from_bounds("411111", "41119999").unwrap();
+        assert_eq!(range, BinRange::new(41111100, 41119999));
+        assert!(!range.contains(BinRange::canonical_bin(&[5, 0, 0, 0, 0, 0])));
+        assert_eq!(
+            BinRange::from_prefix("4"),
+            Some(BinRange::new(40000000, 49999999))
+        );
+        assert_eq!(
+            BinRange::from_prefix("41111111234").unwrap().start,
+            41111111
+        );
+        assert_eq!(BinRange::from_prefix(""), None);
+        assert_eq!(BinRange::from_bounds("42", "41"), None);
+
+        assert_eq!(BinRange::canonical_bin(&[4, 1, 1, 1]), 41110000);
+        assert_eq!(BinRange::canonical_bin(&[4; 16]), 44444444);
+
+        for prefix in ["4", "4111", "411111", "41111111", "0123"] {
+            let range = BinRange::from_prefix(prefix).unwrap();
+            assert_eq!(range.prefix().as_deref(), Some(prefix));
+        }
+        assert_eq!(BinRange::new(41111100, 41111198).prefix(), None);
+    }
+
     #[test]
     fn test_card_type_display() {
         assert_eq!(CardType::Credit.to_string(), "Credit");
@@ -396,4 +846,45 @@ mod tests {
         assert_eq!(CardLevel::Platinum.to_string(), "Platinum");
         assert_eq!(CardLevel::Infinite.to_string(), "Infinite");
     }
+
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(BinDbError::ParseError(String::new()).code(), "parse_error");
+        assert_eq!(
+            BinDbError::MissingColumn("bin".to_string()).code(),
+            "missing_column"
+        );
+        let err = BinDbError::InvalidBin {
+            bin: "41x111".to_string(),
+            line: Some(3),
+        };
+        assert_eq!(err.code(), "invalid_bin");
+        assert_eq!(err.line(), Some(3));
+        assert_eq!(err.to_string(), "Invalid BIN \"41x111\" at line 3");
+    }
+
+    #[test]
+    fn test_check_bin() {
+        assert!(check_bin("411111", None).is_ok());
+        assert!(check_bin("4111-11", None).is_ok());
+        assert!(check_bin("", None).is_err());
+        assert!(check_bin("41x111", None).is_err());
+        assert!(check_bin("12345678901234567890", None).is_err());
+    }
+
+    #[test]
+    fn test_open_database_unknown_extension() {
+        let err = open_database("bins.txt").err().unwrap();
+        assert_eq!(err.code(), "invalid_database");
+
+        let err = open_database("bins.csv.gz").err().unwrap();
+        assert_eq!(err.code(), "invalid_database");
+    }
+
+    #[cfg(not(feature = "bin-redis"))]
+    #[test]
+    fn test_open_database_redis_needs_feature() {
+        let err = open_database("redis://127.0.0.1/").err().unwrap();
+        assert_eq!(err.code(), "feature_not_enabled");
+    }
 }
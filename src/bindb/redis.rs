@@ -0,0 +1,722 @@
+//! Redis BIN database implementation.
+//!
+//! Stores each BIN as a Redis hash so several server instances can share one
+//! centrally updated BIN store.
+//!
+//! # Feature
+//!
+//! Requires the `bin-redis` feature.
+//!
+//! # Key Layout
+//!
+//! With the default `bin:` prefix, BIN `411111` is stored as:
+//!
+//! ```text
+//! HSET bin:411111 issuer "Test Bank" card_type Credit country US ...
+//! SADD bin:index 411111
+//! ```
+//!
+//! Hash fields match the SQLite columns: `issuer`, `card_type`, `card_level`,
+//! `country`, `country_name`, `brand`, `bank_phone`, `bank_url`. Absent
+//! fields are left out of the hash. The `index` set lists every stored BIN
+//! and backs [`BinDatabase::len`].
+//!
+//! # Async Lookups
+//!
+//! With the `bin-redis-async` feature, `RedisBinDb` also implements
+//! [`AsyncBinDatabase`](super::AsyncBinDatabase) over a multiplexed async
+//! connection (tokio), opened on the first async lookup. Writes and
+//! [`len`](BinDatabase::len) stay on the blocking connection.
+
+use super::lru::LruCache;
+#[cfg(any(feature = "bin-json", feature = "bin-csv"))]
+use super::MemoryBinDb;
+use super::{BinDatabase, BinDbError, BinInfo, CardLevel, CardType};
+#[cfg(feature = "bin-redis-async")]
+use redis::aio::MultiplexedConnection;
+use redis::{Client, Commands, Connection};
+use std::collections::HashMap;
+#[cfg(any(feature = "bin-json", feature = "bin-csv"))]
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Default key prefix.
+pub const DEFAULT_PREFIX: &str = "bin:";
+
+/// Redis-backed BIN database.
+///
+/// Each lookup is one round trip to Redis. Enable the local cache with
+/// [`with_cache`](Self::with_cache) to serve repeated BINs (including misses)
+/// from memory.
+///
+/// # Thread Safety
+///
+/// The connection is wrapped in a Mutex to allow sharing across threads. A
+/// connection that fails is dropped and re-established on the next lookup.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use cc_validator::bin::{BinDatabase, RedisBinDb};
+///
+/// let db = RedisBinDb::open("redis://127.0.0.1/")?
+///     .with_prefix("cards:bin:")
+///     .with_cache(10_000);
+/// let info = db.lookup_str("411111");
+/// ```
+pub struct RedisBinDb {
+    client: Client,
+    conn: Mutex<Option<Connection>>,
+    #[cfg(feature = "bin-redis-async")]
+    async_conn: Mutex<Option<MultiplexedConnection>>,
+    prefix: String,
+    cache: Option<Mutex<LruCache<String, Option<BinInfo>>>>,
+}
+
+impl RedisBinDb {
+    /// Connects to the Redis server at `url` (e.g. `redis://127.0.0.1/`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL is invalid or the server is unreachable.
+    pub fn open(url: &str) -> Result<Self, BinDbError> {
+        let client = Client::open(url)?;
+        let conn = client.get_connection()?;
+        Ok(Self {
+            client,
+            conn: Mutex::new(Some(conn)),
+            #[cfg(feature = "bin-redis-async")]
+            async_conn: Mutex::new(None),
+            prefix: DEFAULT_PREFIX.to_string(),
+            cache: None,
+        })
+    }
+
+    /// Sets the prefix prepended to every key (default: `bin:`).
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self.clear_cache();
+        self
+    }
+
+    /// Caches up to `capacity` lookups locally, evicting the least recently
+    /// used.
+    ///
+    /// Cached entries are not refreshed when Redis changes; call
+    /// [`clear_cache`](Self::clear_cache) after updating the store.
+    pub fn with_cache(mut self, capacity: usize) -> Self {
+        self.cache = Some(Mutex::new(LruCache::new(capacity)));
+        self
+    }
+
+    /// Returns the key prefix.
+    #[inline]
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Drops all locally cached lookups.
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.lock().unwrap().clear();
+        }
+    }
+
+    /// Returns the number of locally cached lookups (0 without a cache).
+    pub fn cache_len(&self) -> usize {
+        self.cache
+            .as_ref()
+            .map_or(0, |cache| cache.lock().unwrap().len())
+    }
+
+    /// Stores a BIN entry, replacing any existing one.
+    pub fn insert(&self, info: &BinInfo) -> Result<(), BinDbError> {
+        self.insert_many(std::slice::from_ref(info))
+    }
+
+    /// Stores multiple BIN entries in a single pipeline.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BinDbError::InvalidBin`] if an entry's BIN is not 1-19
+    /// digits; nothing is written in that case.
+    pub fn insert_many(&self, entries: &[BinInfo]) -> Result<(), BinDbError> {
+        for info in entries {
+            super::check_bin(&info.bin, None)?;
+        }
+
+        let mut pipe = redis::pipe();
+        for info in entries {
+            let key = self.key(&info.bin);
+            pipe.del(&key).ignore();
+            let fields = to_fields(info);
+            if !fields.is_empty() {
+                pipe.hset_multiple(&key, &fields).ignore();
+            } else {
+                // An empty hash doesn't exist in Redis; keep a marker field
+                pipe.hset(&key, "bin", &info.bin).ignore();
+            }
+            pipe.sadd(self.index_key(), &info.bin).ignore();
+        }
+
+        self.with_conn(|conn| pipe.query::<()>(conn))?;
+        self.clear_cache();
+        Ok(())
+    }
+
+    /// Loads a JSON or CSV BIN file and stores its entries.
+    ///
+    /// The format is chosen by extension as in
+    /// [`open_database`](super::open_database). Ranges in the source are
+    /// skipped since Redis entries are keyed by exact BIN. Returns the
+    /// number of entries stored.
+    ///
+    /// Requires the `bin-json` or `bin-csv` feature for the matching format.
+    #[cfg(any(feature = "bin-json", feature = "bin-csv"))]
+    pub fn import_file<P: AsRef<Path>>(&self, path: P) -> Result<usize, BinDbError> {
+        let path = path.as_ref();
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| n.to_ascii_lowercase())
+            .unwrap_or_default();
+        let ext = name.strip_suffix(".gz").unwrap_or(&name);
+        let ext = ext.rsplit_once('.').map_or("", |(_, ext)| ext);
+
+        let db: MemoryBinDb = match ext {
+            #[cfg(feature = "bin-json")]
+            "json" => super::JsonBinLoader::from_file(path)?,
+            #[cfg(feature = "bin-csv")]
+            "csv" => super::CsvBinLoader::from_file(path)?,
+            #[cfg(not(feature = "bin-json"))]
+            "json" => return Err(BinDbError::FeatureNotEnabled("bin-json".to_string())),
+            #[cfg(not(feature = "bin-csv"))]
+            "csv" => return Err(BinDbError::FeatureNotEnabled("bin-csv".to_string())),
+            _ => {
+                return Err(BinDbError::InvalidDatabase(format!(
+                    "unsupported BIN file for Redis import: {}",
+                    path.display()
+                )))
+            }
+        };
+
+        // Only entries covering exactly one 6-8 digit BIN map to a key
+        let entries: Vec<BinInfo> = db
+            .iter()
+            .filter_map(|(range, info)| {
+                let prefix = range.prefix().filter(|p| (6..=8).contains(&p.len()))?;
+                let mut info = info.clone();
+                if info.bin.is_empty() {
+                    info.bin = prefix;
+                }
+                Some(info)
+            })
+            .collect();
+        self.insert_many(&entries)?;
+        Ok(entries.len())
+    }
+
+    fn key(&self, bin: &str) -> String {
+        format!("{}{}", self.prefix, bin)
+    }
+
+    fn index_key(&self) -> String {
+        format!("{}index", self.prefix)
+    }
+
+    /// Runs `f` on the connection, reconnecting first if the previous
+    /// connection failed.
+    fn with_conn<T>(
+        &self,
+        f: impl FnOnce(&mut Connection) -> redis::RedisResult<T>,
+    ) -> Result<T, BinDbError> {
+        let mut guard = self.conn.lock().unwrap();
+        let conn = match guard.as_mut() {
+            Some(conn) => conn,
+            None => guard.insert(self.client.get_connection()?),
+        };
+        let result = f(conn);
+        if result.as_ref().is_err_and(|e| e.is_io_error()) {
+            *guard = None;
+        }
+        Ok(result?)
+    }
+
+    /// Fetches the longest stored BIN (8 down to 6 digits) for `bin_str`.
+    fn fetch(&self, bin_str: &str) -> Option<BinInfo> {
+        let candidates = candidates(bin_str);
+        if candidates.is_empty() {
+            return self.fetch_exact(bin_str);
+        }
+
+        let pipe = self.hgetall_pipe(&candidates);
+        let hashes: Vec<HashMap<String, String>> = self.with_conn(|c| pipe.query(c)).ok()?;
+        first_found(&candidates, hashes)
+    }
+
+    fn hgetall_pipe(&self, bins: &[&str]) -> redis::Pipeline {
+        let mut pipe = redis::pipe();
+        for bin in bins {
+            pipe.hgetall(self.key(bin));
+        }
+        pipe
+    }
+
+    fn fetch_exact(&self, bin: &str) -> Option<BinInfo> {
+        let fields: HashMap<String, String> = self.with_conn(|c| c.hgetall(self.key(bin))).ok()?;
+        (!fields.is_empty()).then(|| from_fields(bin, fields))
+    }
+
+    fn cached(&self, key: &str, fetch: impl FnOnce() -> Option<BinInfo>) -> Option<BinInfo> {
+        let Some(cache) = &self.cache else {
+            return fetch();
+        };
+        if let Some(hit) = cache.lock().unwrap().get(&key.to_string()) {
+            return hit.clone();
+        }
+        let info = fetch();
+        cache.lock().unwrap().insert(key.to_string(), info.clone());
+        info
+    }
+}
+
+#[cfg(feature = "bin-redis-async")]
+impl RedisBinDb {
+    /// Returns the async connection, connecting first if there is none or
+    /// the previous one failed.
+    async fn async_conn(&self) -> Result<MultiplexedConnection, BinDbError> {
+        let existing = self.async_conn.lock().unwrap().clone();
+        if let Some(conn) = existing {
+            return Ok(conn);
+        }
+        let conn = self.client.get_multiplexed_async_connection().await?;
+        *self.async_conn.lock().unwrap() = Some(conn.clone());
+        Ok(conn)
+    }
+
+    /// Like [`fetch`](Self::fetch) (or `fetch_exact` if `exact`), awaiting
+    /// Redis on the async connection.
+    async fn fetch_async(&self, bin_str: &str, exact: bool) -> Option<BinInfo> {
+        let mut candidates = if exact {
+            Vec::new()
+        } else {
+            candidates(bin_str)
+        };
+        if candidates.is_empty() {
+            candidates.push(bin_str);
+        }
+
+        let pipe = self.hgetall_pipe(&candidates);
+        let mut conn = self.async_conn().await.ok()?;
+        let result: redis::RedisResult<Vec<HashMap<String, String>>> =
+            pipe.query_async(&mut conn).await;
+        if result.as_ref().is_err_and(|e| e.is_io_error()) {
+            *self.async_conn.lock().unwrap() = None;
+        }
+        first_found(&candidates, result.ok()?)
+    }
+
+    async fn cached_async(
+        &self,
+        key: String,
+        fetch: impl std::future::Future<Output = Option<BinInfo>>,
+    ) -> Option<BinInfo> {
+        let Some(cache) = &self.cache else {
+            return fetch.await;
+        };
+        let hit = cache.lock().unwrap().get(&key).cloned();
+        if let Some(hit) = hit {
+            return hit;
+        }
+        let info = fetch.await;
+        cache.lock().unwrap().insert(key, info.clone());
+        info
+    }
+}
+
+#[cfg(feature = "bin-redis-async")]
+impl super::AsyncBinDatabase for RedisBinDb {
+    fn lookup<'a>(&'a self, bin: &'a [u8]) -> super::BinLookup<'a> {
+        Box::pin(async move {
+            if bin.is_empty() {
+                return None;
+            }
+            let bin_str: String = bin.iter().take(8).map(|&d| (b'0' + d) as char).collect();
+            self.cached_async(bin_str.clone(), self.fetch_async(&bin_str, false))
+                .await
+        })
+    }
+
+    fn lookup_str<'a>(&'a self, bin: &'a str) -> super::BinLookup<'a> {
+        Box::pin(self.cached_async(format!("={}", bin), self.fetch_async(bin, true)))
+    }
+}
+
+impl BinDatabase for RedisBinDb {
+    fn lookup(&self, bin: &[u8]) -> Option<BinInfo> {
+        if bin.is_empty() {
+            return None;
+        }
+
+        // Convert digits to string
+        let bin_str: String = bin.iter().take(8).map(|&d| (b'0' + d) as char).collect();
+        self.cached(&bin_str, || self.fetch(&bin_str))
+    }
+
+    fn lookup_str(&self, bin: &str) -> Option<BinInfo> {
+        self.cached(&format!("={}", bin), || self.fetch_exact(bin))
+    }
+
+    fn len(&self) -> usize {
+        self.with_conn(|c| c.scard(self.index_key())).unwrap_or(0)
+    }
+}
+
+impl std::fmt::Debug for RedisBinDb {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisBinDb")
+            .field("server", &self.client.get_connection_info().addr)
+            .field("prefix", &self.prefix)
+            .field("cached", &self.cache.is_some())
+            .finish()
+    }
+}
+
+/// Returns the 8, 7 and 6 digit prefixes of `bin_str` that it is long
+/// enough for, longest first.
+fn candidates(bin_str: &str) -> Vec<&str> {
+    (6..=8)
+        .rev()
+        .filter(|&len| bin_str.len() >= len)
+        .map(|len| &bin_str[..len])
+        .collect()
+}
+
+/// Builds the entry for the first candidate whose hash exists.
+fn first_found(candidates: &[&str], hashes: Vec<HashMap<String, String>>) -> Option<BinInfo> {
+    candidates
+        .iter()
+        .zip(hashes)
+        .find(|(_, fields)| !fields.is_empty())
+        .map(|(bin, fields)| from_fields(bin, fields))
+}
+
+/// Converts an entry to hash fields, leaving out absent values.
+fn to_fields(info: &BinInfo) -> Vec<(&'static str, String)> {
+    [
+        ("issuer", info.issuer.clone()),
+        ("card_type", info.card_type.map(|t| format!("{:?}", t))),
+        ("card_level", info.card_level.map(|l| format!("{:?}", l))),
+        ("country", info.country.clone()),
+        ("country_name", info.country_name.clone()),
+        ("brand", info.brand.clone()),
+        ("bank_phone", info.bank_phone.clone()),
+        ("bank_url", info.bank_url.clone()),
+    ]
+    .into_iter()
+    .filter_map(|(field, value)| value.map(|v| (field, v)))
+    .collect()
+}
+
+/// Builds an entry from hash fields.
+fn from_fields(bin: &str, mut fields: HashMap<String, String>) -> BinInfo {
+    BinInfo {
+        bin: bin.to_string(),
+        issuer: fields.remove("issuer"),
+        card_type: fields.remove("card_type").map(|s| parse_card_type(&s)),
+        card_level: fields.remove("card_level").map(|s| parse_card_level(&s)),
+        country: fields.remove("country"),
+        country_name: fields.remove("country_name"),
+        brand: fields.remove("brand"),
+        bank_phone: fields.remove("bank_phone"),
+        bank_url: fields.remove("bank_url"),
+    }
+}
+
+/// Parses a card type string into CardType enum.
+fn parse_card_type(s: &str) -> CardType {
+    match s.to_lowercase().as_str() {
+        "credit" => CardType::Credit,
+        "debit" => CardType::Debit,
+        "prepaid" => CardType::Prepaid,
+        "charge" => CardType::Charge,
+        "corporate" | "business" => CardType::Corporate,
+        _ => CardType::Unknown,
+    }
+}
+
+/// Parses a card level string into CardLevel enum.
+fn parse_card_level(s: &str) -> CardLevel {
+    match s.to_lowercase().as_str() {
+        "standard" | "classic" => CardLevel::Standard,
+        "gold" => CardLevel::Gold,
+        "platinum" => CardLevel::Platinum,
+        "signature" | "premium" => CardLevel::Signature,
+        "infinite" | "black" => CardLevel::Infinite,
+        "business" => CardLevel::Business,
+        "corporate" => CardLevel::Corporate,
+        "world" | "world elite" => CardLevel::World,
+        _ => CardLevel::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{HashMap, HashSet};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// In-process stand-in for a Redis server, supporting the handful of
+    /// commands the database issues.
+    #[derive(Default)]
+    struct MockRedis {
+        hashes: Mutex<HashMap<String, HashMap<String, String>>>,
+        sets: Mutex<HashMap<String, HashSet<String>>>,
+        hgetall_calls: AtomicUsize,
+    }
+
+    impl MockRedis {
+        fn start() -> (String, Arc<Self>) {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("redis://{}/", listener.local_addr().unwrap());
+            let mock = Arc::new(Self::default());
+            let server = Arc::clone(&mock);
+            std::thread::spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    let server = Arc::clone(&server);
+                    std::thread::spawn(move || server.serve(stream));
+                }
+            });
+            (url, mock)
+        }
+
+        fn serve(&self, stream: TcpStream) {
+            let mut writer = stream.try_clone().unwrap();
+            let mut reader = BufReader::new(stream);
+            while let Some(args) = read_command(&mut reader) {
+                let reply = self.execute(&args);
+                if writer.write_all(reply.as_bytes()).is_err() {
+                    return;
+                }
+            }
+        }
+
+        fn execute(&self, args: &[String]) -> String {
+            match args[0].to_uppercase().as_str() {
+                "HSET" | "HMSET" => {
+                    let mut hashes = self.hashes.lock().unwrap();
+                    let hash = hashes.entry(args[1].clone()).or_default();
+                    for pair in args[2..].chunks(2) {
+                        hash.insert(pair[0].clone(), pair[1].clone());
+                    }
+                    ":1\r\n".to_string()
+                }
+                "HGETALL" => {
+                    self.hgetall_calls.fetch_add(1, Ordering::SeqCst);
+                    let hashes = self.hashes.lock().unwrap();
+                    let fields = hashes.get(&args[1]).cloned().unwrap_or_default();
+                    let mut reply = format!("*{}\r\n", fields.len() * 2);
+                    for (k, v) in fields {
+                        reply += &bulk(&k);
+                        reply += &bulk(&v);
+                    }
+                    reply
+                }
+                "DEL" => {
+                    self.hashes.lock().unwrap().remove(&args[1]);
+                    ":1\r\n".to_string()
+                }
+                "SADD" => {
+                    let mut sets = self.sets.lock().unwrap();
+                    let set = sets.entry(args[1].clone()).or_default();
+                    set.extend(args[2..].iter().cloned());
+                    ":1\r\n".to_string()
+                }
+                "SCARD" => {
+                    let sets = self.sets.lock().unwrap();
+                    format!(":{}\r\n", sets.get(&args[1]).map_or(0, |s| s.len()))
+                }
+                _ => "+OK\r\n".to_string(),
+            }
+        }
+    }
+
+    fn bulk(s: &str) -> String {
+        format!("${}\r\n{}\r\n", s.len(), s)
+    }
+
+    fn read_command(reader: &mut impl BufRead) -> Option<Vec<String>> {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let count: usize = line.trim().strip_prefix('*')?.parse().ok()?;
+        let mut args = Vec::with_capacity(count);
+        for _ in 0..count {
+            line.clear();
+            reader.read_line(&mut line).ok()?;
+            let len: usize = line.trim().strip_prefix('$')?.parse().ok()?;
+            let mut buf = vec![0u8; len + 2];
+            reader.read_exact(&mut buf).ok()?;
+            buf.truncate(len);
+            args.push(String::from_utf8(buf).ok()?);
+        }
+        Some(args)
+    }
+
+    fn create_test_db() -> (RedisBinDb, Arc<MockRedis>) {
+        let (url, mock) = MockRedis::start();
+        let db = RedisBinDb::open(&url).unwrap();
+        db.insert_many(&[
+            BinInfo::with_bin("411111")
+                .issuer("Test Bank")
+                .card_type(CardType::Credit)
+                .country("US"),
+            BinInfo::with_bin("41111122").issuer("Test Bank Platinum"),
+            BinInfo::with_bin("550000"),
+        ])
+        .unwrap();
+        (db, mock)
+    }
+
+    #[test]
+    fn test_lookup() {
+        let (db, _mock) = create_test_db();
+
+        let info = db.lookup_str("411111").unwrap();
+        assert_eq!(info.bin, "411111");
+        assert_eq!(info.issuer, Some("Test Bank".to_string()));
+        assert_eq!(info.card_type, Some(CardType::Credit));
+        assert_eq!(info.country, Some("US".to_string()));
+        assert!(info.card_level.is_none());
+
+        assert!(db.lookup_str("999999").is_none());
+        assert_eq!(db.len(), 3);
+    }
+
+    #[test]
+    fn test_lookup_prefers_longest_bin() {
+        let (db, _mock) = create_test_db();
+
+        let info = db.lookup(&[4, 1, 1, 1, 1, 1, 2, 2, 3, 3]).unwrap();
+        assert_eq!(info.issuer, Some("Test Bank Platinum".to_string()));
+
+        let info = db.lookup(&[4, 1, 1, 1, 1, 1, 9, 9]).unwrap();
+        assert_eq!(info.issuer, Some("Test Bank".to_string()));
+
+        // An entry with no fields is still found
+        assert_eq!(db.lookup(&[5, 5, 0, 0, 0, 0]).unwrap().bin, "550000");
+    }
+
+    #[test]
+    fn test_prefix() {
+        let (url, mock) = MockRedis::start();
+        let db = RedisBinDb::open(&url).unwrap().with_prefix("cards:");
+        db.insert(&BinInfo::with_bin("411111").issuer("Test Bank"))
+            .unwrap();
+
+        assert_eq!(db.prefix(), "cards:");
+        assert!(mock.hashes.lock().unwrap().contains_key("cards:411111"));
+        assert!(mock.sets.lock().unwrap().contains_key("cards:index"));
+        assert!(db.lookup_str("411111").is_some());
+    }
+
+    #[test]
+    fn test_cache() {
+        let (db, mock) = create_test_db();
+        let db = db.with_cache(16);
+
+        for _ in 0..3 {
+            assert!(db.lookup(&[4, 1, 1, 1, 1, 1]).is_some());
+            assert!(db.lookup(&[9, 9, 9, 9, 9, 9]).is_none());
+        }
+        assert_eq!(mock.hgetall_calls.load(Ordering::SeqCst), 2);
+        assert_eq!(db.cache_len(), 2);
+
+        // Writes through this handle invalidate the cache
+        db.insert(&BinInfo::with_bin("999999").issuer("New Bank"))
+            .unwrap();
+        assert!(db.lookup(&[9, 9, 9, 9, 9, 9]).is_some());
+    }
+
+    #[cfg(feature = "bin-redis-async")]
+    #[tokio::test]
+    async fn test_async_lookup() {
+        use crate::bin::AsyncBinDatabase;
+
+        let (db, mock) = create_test_db();
+        let db = db.with_cache(16);
+
+        let info = AsyncBinDatabase::lookup(&db, &[4, 1, 1, 1, 1, 1, 2, 2, 3])
+            .await
+            .unwrap();
+        assert_eq!(info.issuer, Some("Test Bank Platinum".to_string()));
+        let info = AsyncBinDatabase::lookup_str(&db, "411111").await.unwrap();
+        assert_eq!(info.country, Some("US".to_string()));
+        assert!(AsyncBinDatabase::lookup_str(&db, "41111122")
+            .await
+            .is_some());
+        assert!(AsyncBinDatabase::lookup(&db, &[9, 9, 9, 9, 9, 9])
+            .await
+            .is_none());
+
+        let calls = mock.hgetall_calls.load(Ordering::SeqCst);
+        assert!(AsyncBinDatabase::lookup(&db, &[9, 9, 9, 9, 9, 9])
+            .await
+            .is_none());
+        assert_eq!(mock.hgetall_calls.load(Ordering::SeqCst), calls);
+    }
+
+    #[test]
+    fn test_invalid_bin_is_rejected() {
+        let (url, mock) = MockRedis::start();
+        let db = RedisBinDb::open(&url).unwrap();
+        let err = db
+            .insert_many(&[BinInfo::with_bin("411111"), BinInfo::with_bin("41x1")])
+            .unwrap_err();
+        assert_eq!(err.code(), "invalid_bin");
+        assert!(mock.hashes.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_unreachable_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("redis://{}/", listener.local_addr().unwrap());
+        drop(listener);
+
+        let err = RedisBinDb::open(&url).unwrap_err();
+        assert_eq!(err.code(), "io_error");
+    }
+
+    #[cfg(feature = "bin-csv")]
+    #[test]
+    fn test_import_csv() {
+        let (url, _mock) = MockRedis::start();
+        let db = RedisBinDb::open(&url).unwrap();
+
+        let path = std::env::temp_dir().join(format!("redis_import_{}.csv", std::process::id()));
+        std::fs::write(
+            &path,
+            "bin,issuer,country\n411111,Test Bank,US\n550000,Other,GB\n",
+        )
+        .unwrap();
+        let imported = db.import_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(imported.unwrap(), 2);
+        assert_eq!(
+            db.lookup_str("550000").unwrap().country,
+            Some("GB".to_string())
+        );
+    }
+
+    #[cfg(any(feature = "bin-json", feature = "bin-csv"))]
+    #[test]
+    fn test_import_unsupported_extension() {
+        let (url, _mock) = MockRedis::start();
+        let db = RedisBinDb::open(&url).unwrap();
+        let err = db.import_file("bins.xml").unwrap_err();
+        assert_eq!(err.code(), "invalid_database");
+    }
+}
@@ -227,6 +227,7 @@ impl SqliteBinDb {
             db.insert(&bin, info);
         }
 
+        db.ensure_sorted();
         Ok(db)
     }
 }
@@ -0,0 +1,196 @@
+//! Memoized validation for hot, repeated card numbers.
+//!
+//! Checkout and tokenization APIs see the same cards over and over: retries,
+//! saved cards, subscription renewals. A [`ValidatorCache`] wraps a
+//! [`Validator`] and remembers the outcome for each number (its brand, or
+//! the error it failed with), so a repeat skips the Luhn check, brand
+//! detection and policy checks.
+//!
+//! Numbers are never stored. Each entry is keyed by a 128-bit keyed hash
+//! (SipHash under two random keys drawn when the cache is created) of the
+//! digits, so the cache contents can't be turned back into PANs, and two
+//! caches never share keys.
+//!
+//! Only the outcome after digit extraction is cached: malformed input
+//! (empty, invalid characters, too few or too many digits) is reported from
+//! the input every time.
+//!
+//! # Example
+//!
+//! ```
+//! use cc_validator::{Validator, ValidatorCache};
+//!
+//! let cache = ValidatorCache::new(Validator::new(), 10_000);
+//! assert!(cache.validate("4111 1111 1111 1111").is_ok());
+//! assert!(cache.validate("4111-1111-1111-1111").is_ok());
+//! let stats = cache.stats();
+//! assert_eq!((stats.hits, stats.misses), (1, 1));
+//! ```
+
+use crate::bin::lru::LruCache;
+use crate::bin::CacheStats;
+use crate::metrics::recorded;
+use crate::sanitize::sanitize_ocr;
+use crate::validator::Validator;
+use crate::{CardBrand, ValidatedCard, ValidationError, Workspace};
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// A [`Validator`] that remembers outcomes by keyed hash of the digits.
+///
+/// Entries are evicted least recently used first once `capacity` is
+/// reached. Safe to share between threads.
+pub struct ValidatorCache {
+    validator: Validator,
+    keys: [RandomState; 2],
+    cache: Mutex<LruCache<u128, Result<CardBrand, ValidationError>>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ValidatorCache {
+    /// Wraps `validator` with a cache of at most `capacity` outcomes.
+    pub fn new(validator: Validator, capacity: usize) -> Self {
+        Self {
+            validator,
+            keys: [RandomState::new(), RandomState::new()],
+            cache: Mutex::new(LruCache::new(capacity)),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the wrapped validator.
+    #[inline]
+    pub fn validator(&self) -> &Validator {
+        &self.validator
+    }
+
+    /// Validates a card number like [`Validator::validate`], answering
+    /// repeats from the cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Validator::validate`].
+    pub fn validate(&self, input: &str) -> Result<ValidatedCard, ValidationError> {
+        let sanitized;
+        let input = if self.validator.ocr_correction() {
+            sanitized = sanitize_ocr(input);
+            sanitized.as_str()
+        } else {
+            input
+        };
+
+        let metrics = self.validator.metrics();
+        let mut workspace = Workspace::new();
+        if let Err(e) = workspace.extract(input, self.validator.digit_limits()) {
+            return recorded(metrics, Err(e));
+        }
+        let key = self.key(workspace.digits());
+
+        let cached = self.cache.lock().unwrap().get(&key).cloned();
+        if let Some(outcome) = cached {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return recorded(metrics, outcome.map(|brand| workspace.card(brand)));
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let result = self.validator.validate_with(&mut workspace, input);
+        let outcome = result
+            .as_ref()
+            .map(ValidatedCard::brand)
+            .map_err(Clone::clone);
+        self.cache.lock().unwrap().insert(key, outcome);
+        result
+    }
+
+    /// Returns `true` if the card number passes the wrapped validator.
+    pub fn is_valid(&self, input: &str) -> bool {
+        self.validate(input).is_ok()
+    }
+
+    /// Returns the hit and miss counts since creation or the last
+    /// [`reset_stats`](Self::reset_stats).
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.cache.lock().unwrap().len(),
+        }
+    }
+
+    /// Resets the hit and miss counters.
+    pub fn reset_stats(&self) {
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+
+    /// Drops all cached outcomes.
+    pub fn clear(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// Hashes `digits` under both keys.
+    fn key(&self, digits: &[u8]) -> u128 {
+        let [high, low] = self.keys.each_ref().map(|keys| {
+            let mut hasher = keys.build_hasher();
+            hasher.write(digits);
+            hasher.finish()
+        });
+        u128::from(high) << 64 | u128::from(low)
+    }
+}
+
+impl fmt::Debug for ValidatorCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValidatorCache")
+            .field("validator", &self.validator)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validator_cache() {
+        let validator = Validator::new().with_allowed_brands([CardBrand::Visa]);
+        let cache = ValidatorCache::new(validator, 2);
+
+        for _ in 0..2 {
+            let card = cache.validate("4111-1111-1111-1111").unwrap();
+            assert_eq!(card.brand(), CardBrand::Visa);
+            assert_eq!(card.last_four(), "1111");
+            assert_eq!(
+                cache.validate("5500 0000 0000 0004"),
+                Err(ValidationError::BrandNotAllowed {
+                    brand: CardBrand::Mastercard
+                })
+            );
+            assert_eq!(
+                cache.validate("4111111111111112"),
+                Err(ValidationError::InvalidChecksum)
+            );
+        }
+        // Malformed input never reaches the cache
+        assert_eq!(cache.validate(""), Err(ValidationError::Empty));
+        assert_eq!(cache.validate("4111"), cache.validator().validate("4111"));
+
+        let stats = cache.stats();
+        // Capacity 2 evicts each number before its repeat comes around
+        assert_eq!((stats.hits, stats.misses, stats.entries), (0, 6, 2));
+
+        cache.reset_stats();
+        cache.clear();
+        assert!(cache.is_valid("4111111111111111"));
+        assert!(cache.is_valid("4111111111111111"));
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+        assert_eq!(stats.hit_ratio(), 0.5);
+    }
+}
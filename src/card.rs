@@ -3,14 +3,18 @@
 //! This module provides the `CardBrand` enum for identifying card networks
 //! and the `ValidatedCard` struct for holding validated card data securely.
 
+use crate::error::ValidationError;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use zeroize::Zeroize;
 
 /// Supported credit card brands/networks.
 ///
 /// Each variant represents a major payment network with its own BIN ranges
-/// and validation rules.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// and validation rules. Brands are ordered by their
+/// [`numeric_id`](Self::numeric_id).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
 pub enum CardBrand {
     /// Visa - Prefix 4, lengths 13, 16, 19
     Visa,
@@ -40,9 +44,80 @@ pub enum CardBrand {
     Troy,
     /// BC Card - South Korean payment system, Prefix 94, length 16
     BcCard,
+    /// Interac - Canadian debit, Prefix 4506, 4519, 4536, 4724, length 16
+    Interac,
+    /// Dankort - Danish debit, Prefix 5019, 4571 (Visa/Dankort), length 16
+    Dankort,
+    /// Bancontact - Belgian debit, Prefix 6703, length 16-19
+    Bancontact,
+    /// UATP - Airline travel cards, Prefix 1, length 15, no CVV
+    Uatp,
+    /// WEX - Fleet/fuel cards, Prefix 690046, 707138, length 13, 16, 19, no CVV
+    Wex,
+    /// Voyager - Fleet/fuel cards, Prefix 7088, length 16, 19, no CVV
+    Voyager,
 }
 
+/// Every supported brand, in detection-table order.
+const ALL_BRANDS: [CardBrand; 20] = [
+    CardBrand::Visa,
+    CardBrand::Mastercard,
+    CardBrand::Amex,
+    CardBrand::Discover,
+    CardBrand::DinersClub,
+    CardBrand::Jcb,
+    CardBrand::UnionPay,
+    CardBrand::Maestro,
+    CardBrand::Mir,
+    CardBrand::RuPay,
+    CardBrand::Verve,
+    CardBrand::Elo,
+    CardBrand::Troy,
+    CardBrand::BcCard,
+    CardBrand::Interac,
+    CardBrand::Dankort,
+    CardBrand::Bancontact,
+    CardBrand::Uatp,
+    CardBrand::Wex,
+    CardBrand::Voyager,
+];
+
 impl CardBrand {
+    /// Returns every supported card brand.
+    ///
+    /// Use this instead of maintaining a separate brand list; the README
+    /// brand table is checked against it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::{cvv, CardBrand};
+    ///
+    /// let readme = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"));
+    /// for brand in CardBrand::all() {
+    ///     let row = readme
+    ///         .lines()
+    ///         .find(|l| l.starts_with(&format!("| {} |", brand.name())))
+    ///         .expect("brand missing from README table");
+    ///     let cells: Vec<&str> = row.split('|').map(str::trim).collect();
+    ///
+    ///     let lengths: Vec<u8> = cells[3]
+    ///         .split(", ")
+    ///         .flat_map(|part| match part.split_once('-') {
+    ///             Some((lo, hi)) => (lo.parse().unwrap()..=hi.parse().unwrap()).collect(),
+    ///             None => vec![part.parse().unwrap()],
+    ///         })
+    ///         .collect();
+    ///     assert_eq!(lengths, brand.valid_lengths(), "{}", brand);
+    ///     let cvv = if brand.has_cvv() { brand.cvv_length().to_string() } else { "none".into() };
+    ///     assert_eq!(cells[4], cvv, "{}", brand);
+    /// }
+    /// ```
+    #[inline]
+    pub const fn all() -> &'static [CardBrand] {
+        &ALL_BRANDS
+    }
+
     /// Returns the valid lengths for this card brand.
     #[inline]
     pub const fn valid_lengths(&self) -> &'static [u8] {
@@ -61,6 +136,26 @@ impl CardBrand {
             Self::Elo => &[16],
             Self::Troy => &[16],
             Self::BcCard => &[16],
+            Self::Interac => &[16],
+            Self::Dankort => &[16],
+            Self::Bancontact => &[16, 17, 18, 19],
+            Self::Uatp => &[15],
+            Self::Wex => &[13, 16, 19],
+            Self::Voyager => &[16, 19],
+        }
+    }
+
+    /// Returns the lengths most cards of this brand are issued with.
+    ///
+    /// The other [`valid_lengths`](Self::valid_lengths) are legal but rare,
+    /// so a number of such a length is more likely mistyped.
+    #[inline]
+    pub const fn common_lengths(&self) -> &'static [u8] {
+        match self {
+            Self::Amex | Self::Uatp => &[15],
+            Self::DinersClub => &[14, 16],
+            Self::UnionPay | Self::Maestro | Self::Verve | Self::Voyager => &[16, 19],
+            _ => &[16],
         }
     }
 
@@ -96,8 +191,280 @@ impl CardBrand {
             Self::Elo => "Elo",
             Self::Troy => "Troy",
             Self::BcCard => "BC Card",
+            Self::Interac => "Interac",
+            Self::Dankort => "Dankort",
+            Self::Bancontact => "Bancontact",
+            Self::Uatp => "UATP",
+            Self::Wex => "WEX",
+            Self::Voyager => "Voyager",
+        }
+    }
+
+    /// Returns a short lowercase identifier for the brand (e.g. `"amex"`).
+    ///
+    /// Identifiers are stable and suitable for CLI arguments, query
+    /// parameters, and configuration files.
+    #[inline]
+    pub const fn id(&self) -> &'static str {
+        match self {
+            Self::Visa => "visa",
+            Self::Mastercard => "mastercard",
+            Self::Amex => "amex",
+            Self::Discover => "discover",
+            Self::DinersClub => "diners",
+            Self::Jcb => "jcb",
+            Self::UnionPay => "unionpay",
+            Self::Maestro => "maestro",
+            Self::Mir => "mir",
+            Self::RuPay => "rupay",
+            Self::Verve => "verve",
+            Self::Elo => "elo",
+            Self::Troy => "troy",
+            Self::BcCard => "bccard",
+            Self::Interac => "interac",
+            Self::Dankort => "dankort",
+            Self::Bancontact => "bancontact",
+            Self::Uatp => "uatp",
+            Self::Wex => "wex",
+            Self::Voyager => "voyager",
+        }
+    }
+
+    /// Returns a stable numeric identifier for the brand, from 1 (Visa) up.
+    ///
+    /// Ids are never reused or renumbered, and new brands get the next free
+    /// one, so they can be stored in a byte. `0` is left for "no brand", as
+    /// in the [`validate_compact`](crate::batch::validate_compact) format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::CardBrand;
+    ///
+    /// assert_eq!(CardBrand::Amex.numeric_id(), 3);
+    /// assert_eq!(CardBrand::try_from(3), Ok(CardBrand::Amex));
+    /// assert!(CardBrand::try_from(0).is_err());
+    /// ```
+    #[inline]
+    pub const fn numeric_id(&self) -> u8 {
+        match self {
+            Self::Visa => 1,
+            Self::Mastercard => 2,
+            Self::Amex => 3,
+            Self::Discover => 4,
+            Self::DinersClub => 5,
+            Self::Jcb => 6,
+            Self::UnionPay => 7,
+            Self::Maestro => 8,
+            Self::Mir => 9,
+            Self::RuPay => 10,
+            Self::Verve => 11,
+            Self::Elo => 12,
+            Self::Troy => 13,
+            Self::BcCard => 14,
+            Self::Interac => 15,
+            Self::Dankort => 16,
+            Self::Bancontact => 17,
+            Self::Uatp => 18,
+            Self::Wex => 19,
+            Self::Voyager => 20,
+        }
+    }
+
+    /// Returns the brand with the given [`numeric_id`](Self::numeric_id).
+    pub const fn from_numeric_id(id: u8) -> Option<Self> {
+        Some(match id {
+            1 => Self::Visa,
+            2 => Self::Mastercard,
+            3 => Self::Amex,
+            4 => Self::Discover,
+            5 => Self::DinersClub,
+            6 => Self::Jcb,
+            7 => Self::UnionPay,
+            8 => Self::Maestro,
+            9 => Self::Mir,
+            10 => Self::RuPay,
+            11 => Self::Verve,
+            12 => Self::Elo,
+            13 => Self::Troy,
+            14 => Self::BcCard,
+            15 => Self::Interac,
+            16 => Self::Dankort,
+            17 => Self::Bancontact,
+            18 => Self::Uatp,
+            19 => Self::Wex,
+            20 => Self::Voyager,
+            _ => return None,
+        })
+    }
+
+    /// Returns the CVV length for this brand (4 for Amex, 0 for UATP and
+    /// fleet cards, which have none, 3 otherwise).
+    #[inline]
+    pub const fn cvv_length(&self) -> usize {
+        match self {
+            Self::Amex => 4,
+            Self::Uatp | Self::Wex | Self::Voyager => 0,
+            _ => 3,
+        }
+    }
+
+    /// Returns true if cards of this brand carry a CVV (all but UATP and
+    /// fleet cards).
+    #[inline]
+    pub const fn has_cvv(&self) -> bool {
+        self.cvv_length() > 0
+    }
+
+    /// Returns true for fleet and fuel cards (WEX, Voyager).
+    ///
+    /// Fleet cards are private-label: they are accepted only at enrolled
+    /// fuel and maintenance merchants and prompt for a driver ID or odometer
+    /// reading instead of a CVV.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::{validate, CardBrand};
+    ///
+    /// assert!(CardBrand::Voyager.is_fleet());
+    /// assert!(!CardBrand::Visa.is_fleet());
+    ///
+    /// let card = validate("6900460000000000008").unwrap();
+    /// assert_eq!(card.brand(), CardBrand::Wex);
+    /// assert!(card.brand().is_fleet());
+    /// ```
+    #[inline]
+    pub const fn is_fleet(&self) -> bool {
+        matches!(self, Self::Wex | Self::Voyager)
+    }
+
+    /// Returns the home country of a domestic-only scheme as an ISO 3166-1
+    /// alpha-2 code, or `None` for networks accepted internationally.
+    ///
+    /// Mir, RuPay, Verve, Elo, Troy, and Interac cards are generally only
+    /// accepted by acquirers in their home country (co-badged cards carry a
+    /// second, international brand). Dankort and Bancontact are not listed:
+    /// their cards are mostly co-badged with Visa and Maestro.
+    #[inline]
+    pub const fn home_country(&self) -> Option<&'static str> {
+        match self {
+            Self::Mir => Some("RU"),
+            Self::RuPay => Some("IN"),
+            Self::Verve => Some("NG"),
+            Self::Elo => Some("BR"),
+            Self::Troy => Some("TR"),
+            Self::Interac => Some("CA"),
+            _ => None,
         }
     }
+
+    /// Returns true if this is a domestic-only scheme (see
+    /// [`home_country`](Self::home_country)).
+    #[inline]
+    pub const fn is_domestic_only(&self) -> bool {
+        self.home_country().is_some()
+    }
+
+    /// Returns true if cards of this brand are accepted in the given
+    /// country (ISO 3166-1 alpha-2, either case).
+    ///
+    /// Uses the default rules: international networks are accepted
+    /// everywhere, domestic-only schemes only in their home country. Use an
+    /// [`AcceptanceMatrix`](crate::acceptance::AcceptanceMatrix) to
+    /// override them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::CardBrand;
+    ///
+    /// assert!(CardBrand::Visa.is_accepted_in("DE"));
+    /// assert!(CardBrand::Mir.is_accepted_in("ru"));
+    /// assert!(!CardBrand::Mir.is_accepted_in("US"));
+    /// ```
+    pub fn is_accepted_in(&self, country_code: &str) -> bool {
+        match self.home_country() {
+            Some(home) => home.eq_ignore_ascii_case(country_code.trim()),
+            None => true,
+        }
+    }
+
+    /// Returns a regex matching this brand's card numbers, for the HTML
+    /// `pattern` attribute or a JavaScript `RegExp`.
+    ///
+    /// The pattern is built from the same prefix table as
+    /// [`detect_brand`](crate::detect::detect_brand) and the brand's valid
+    /// lengths, so a digits-only input matches exactly when detection and
+    /// the length check would accept it. It doesn't check the Luhn digit,
+    /// accept separators, or include `^`/`$` anchors (the `pattern`
+    /// attribute adds them; wrap it in `^...$` for `RegExp`). The six-digit
+    /// WEX ranges are also matched by Maestro's pattern.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::CardBrand;
+    ///
+    /// assert_eq!(CardBrand::Troy.input_pattern(), r"9792\d{12}");
+    /// assert_eq!(CardBrand::Amex.input_pattern(), r"3[47]\d{13}");
+    /// ```
+    pub fn input_pattern(&self) -> String {
+        crate::detect::input_pattern(*self)
+    }
+
+    /// Returns a regex matching card numbers of any supported brand.
+    ///
+    /// This is the alternation of every [`input_pattern`](Self::input_pattern),
+    /// with the same caveats.
+    ///
+    /// ```html
+    /// <input inputmode="numeric" autocomplete="cc-number" pattern="...">
+    /// ```
+    pub fn any_input_pattern() -> String {
+        let patterns: Vec<String> = Self::all().iter().map(|b| b.input_pattern()).collect();
+        format!("(?:{})", patterns.join("|"))
+    }
+
+    /// Looks up a brand by identifier or display name.
+    ///
+    /// Matching ignores case, spaces, hyphens, and underscores, so `"amex"`,
+    /// `"American Express"`, and `"diners-club"` all resolve. `"mc"` is
+    /// accepted for Mastercard, `"bcmc"` and `"mistercash"` for Bancontact,
+    /// and `"visa-dankort"` for Dankort.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::CardBrand;
+    ///
+    /// assert_eq!(CardBrand::from_name("amex"), Some(CardBrand::Amex));
+    /// assert_eq!(CardBrand::from_name("Diners Club"), Some(CardBrand::DinersClub));
+    /// assert_eq!(CardBrand::from_name("bc-card"), Some(CardBrand::BcCard));
+    /// assert_eq!(CardBrand::from_name("BCMC"), Some(CardBrand::Bancontact));
+    /// assert_eq!(CardBrand::from_name("unknown"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        fn normalize(s: &str) -> String {
+            s.chars()
+                .filter(|c| !matches!(c, ' ' | '-' | '_'))
+                .map(|c| c.to_ascii_lowercase())
+                .collect()
+        }
+
+        let key = normalize(name);
+        match key.as_str() {
+            "mc" => return Some(Self::Mastercard),
+            "bcmc" | "mistercash" => return Some(Self::Bancontact),
+            "visadankort" => return Some(Self::Dankort),
+            _ => {}
+        }
+
+        Self::all()
+            .iter()
+            .copied()
+            .find(|b| b.id() == key || normalize(b.name()) == key)
+    }
 }
 
 impl fmt::Display for CardBrand {
@@ -106,12 +473,71 @@ impl fmt::Display for CardBrand {
     }
 }
 
+impl From<CardBrand> for u8 {
+    fn from(brand: CardBrand) -> Self {
+        brand.numeric_id()
+    }
+}
+
+impl TryFrom<u8> for CardBrand {
+    type Error = UnknownBrandId;
+
+    fn try_from(id: u8) -> Result<Self, Self::Error> {
+        Self::from_numeric_id(id).ok_or(UnknownBrandId(id))
+    }
+}
+
+/// A byte that is not the [`numeric_id`](CardBrand::numeric_id) of any
+/// brand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownBrandId(pub u8);
+
+impl fmt::Display for UnknownBrandId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown card brand id {}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownBrandId {}
+
+/// A BIN longer than PCI DSS truncation allows for the card's length,
+/// from [`ValidatedCard::bin_checked`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BinExposureError {
+    /// The number of leading digits asked for.
+    pub requested: usize,
+    /// The most leading digits that may be shown.
+    pub allowed: usize,
+    /// The number of digits in the card number.
+    pub pan_length: usize,
+}
+
+impl fmt::Display for BinExposureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "a {}-digit BIN exposes too much of a {}-digit card number (at most {} digits)",
+            self.requested, self.pan_length, self.allowed
+        )
+    }
+}
+
+impl std::error::Error for BinExposureError {}
+
 /// Maximum number of digits in a credit card number.
 pub const MAX_CARD_DIGITS: usize = 19;
 
 /// Minimum number of digits in a credit card number.
 pub const MIN_CARD_DIGITS: usize = 12;
 
+/// Most digits a [`Validator`](crate::Validator) can be configured to accept,
+/// for closed-loop cards longer than [`MAX_CARD_DIGITS`].
+pub const MAX_SUPPORTED_DIGITS: usize = 23;
+
+/// Fewest digits a [`Validator`](crate::Validator) can be configured to
+/// accept (a full 8-digit BIN).
+pub const MIN_SUPPORTED_DIGITS: usize = 8;
+
 /// A validated credit card with secure memory handling.
 ///
 /// The card number is stored in a fixed-size array that is automatically
@@ -129,7 +555,7 @@ pub struct ValidatedCard {
     /// The detected card brand.
     brand: CardBrand,
     /// The full card number as digits (0-9).
-    digits: [u8; MAX_CARD_DIGITS],
+    digits: [u8; MAX_SUPPORTED_DIGITS],
     /// Number of actual digits in the card.
     digit_count: u8,
 }
@@ -141,7 +567,11 @@ impl ValidatedCard {
     ///
     /// This is an internal constructor. Use `validate()` to create instances.
     #[inline]
-    pub(crate) fn new(brand: CardBrand, digits: [u8; MAX_CARD_DIGITS], digit_count: u8) -> Self {
+    pub(crate) fn new(
+        brand: CardBrand,
+        digits: [u8; MAX_SUPPORTED_DIGITS],
+        digit_count: u8,
+    ) -> Self {
         Self {
             brand,
             digits,
@@ -161,6 +591,45 @@ impl ValidatedCard {
         self.digit_count as usize
     }
 
+    /// Returns the digit (0-9) at `index`, counting from the left, or `None`
+    /// past the end.
+    ///
+    /// Not available with the `no-pan-exposure` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let card = cc_validator::validate("4111-1111-1111-1111").unwrap();
+    /// assert_eq!(card.digit(0), Some(4));
+    /// assert_eq!(card.digit(16), None);
+    /// ```
+    #[cfg(not(feature = "no-pan-exposure"))]
+    #[inline]
+    pub fn digit(&self, index: usize) -> Option<u8> {
+        self.digits().get(index).copied()
+    }
+
+    /// Returns an iterator over the digit values (0-9), without allocating.
+    ///
+    /// # Security Warning
+    ///
+    /// Like [`number`](Self::number), this exposes the full card number, and
+    /// is not available with the `no-pan-exposure` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let card = cc_validator::validate("4111-1111-1111-1111").unwrap();
+    /// let sum: u32 = card.iter_digits().map(u32::from).sum();
+    /// assert_eq!(sum, 19);
+    /// assert_eq!(card.iter_digits().rev().next(), Some(1));
+    /// ```
+    #[cfg(not(feature = "no-pan-exposure"))]
+    #[inline]
+    pub fn iter_digits(&self) -> std::iter::Copied<std::slice::Iter<'_, u8>> {
+        self.digits().iter().copied()
+    }
+
     /// Returns the last four digits as a string.
     ///
     /// Safe for logging and display per PCI-DSS requirements.
@@ -184,6 +653,11 @@ impl ValidatedCard {
     ///
     /// The BIN identifies the issuing bank. Modern cards use 8-digit BINs,
     /// but 6-digit BINs are still common.
+    ///
+    /// PCI DSS allows at most 6 leading digits to be shown alongside the
+    /// last four for PANs shorter than 16 digits, so an 8-digit BIN of such a
+    /// card shouldn't be displayed. Use [`bin_checked`](Self::bin_checked)
+    /// when the BIN is shown to people.
     #[inline]
     pub fn bin(&self, length: usize) -> String {
         let bin_len = length.min(8).min(self.digit_count as usize);
@@ -193,6 +667,36 @@ impl ValidatedCard {
             .collect()
     }
 
+    /// Returns the first `length` digits, or an error if PCI DSS truncation
+    /// doesn't allow showing that many for this card's length.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`BinExposureError`] with the most digits that may be
+    /// shown when `length` exceeds [`crate::mask::pci_bin_length`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let visa = cc_validator::validate("4111 1111 1111 1111").unwrap();
+    /// assert_eq!(visa.bin_checked(8).unwrap(), "41111111");
+    ///
+    /// let diners = cc_validator::validate("3056 930902 5904").unwrap();
+    /// let err = diners.bin_checked(8).unwrap_err();
+    /// assert_eq!((err.requested, err.allowed), (8, 6));
+    /// ```
+    pub fn bin_checked(&self, length: usize) -> Result<String, BinExposureError> {
+        let allowed = crate::mask::pci_bin_length(self.digit_count as usize, length);
+        if allowed < length {
+            return Err(BinExposureError {
+                requested: length,
+                allowed,
+                pan_length: self.length(),
+            });
+        }
+        Ok(self.bin(length))
+    }
+
     /// Returns the 6-digit BIN (traditional format).
     #[inline]
     pub fn bin6(&self) -> String {
@@ -205,12 +709,28 @@ impl ValidatedCard {
         self.bin(8)
     }
 
+    /// Returns the leading digits (0-9) a BIN database is searched with:
+    /// the first [`BIN_SPACE_DIGITS`](crate::bin::BIN_SPACE_DIGITS).
+    ///
+    /// Unlike [`bin`](Self::bin) this isn't clamped for short PANs, so
+    /// together with [`last_four`](Self::last_four) it can cover the whole
+    /// number. It stays inside the crate: callers look BINs up through
+    /// [`Validator::lookup_bin`](crate::Validator::lookup_bin) or
+    /// [`AsyncBinDatabase::lookup_card`](crate::bin::AsyncBinDatabase::lookup_card).
+    #[inline]
+    pub(crate) fn bin_lookup_digits(&self) -> &[u8] {
+        let digits = self.digits();
+        &digits[..digits.len().min(crate::bin::BIN_SPACE_DIGITS)]
+    }
+
     /// Returns the full card number as a string.
     ///
     /// # Security Warning
     ///
     /// This method exposes the full card number. Use with extreme caution
     /// and never log the result. For display purposes, use `masked()` instead.
+    /// Builds with the `no-pan-exposure` feature leave this method out.
+    #[cfg(not(feature = "no-pan-exposure"))]
     #[inline]
     pub fn number(&self) -> String {
         self.digits[..self.digit_count as usize]
@@ -219,6 +739,29 @@ impl ValidatedCard {
             .collect()
     }
 
+    /// Returns the full card number wrapped in a [`secrecy::SecretString`],
+    /// which is zeroized on drop and redacted from `Debug` output.
+    ///
+    /// Requires the `secrecy` feature; left out with `no-pan-exposure`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use secrecy::ExposeSecret;
+    ///
+    /// let card = cc_validator::validate("4111-1111-1111-1111").unwrap();
+    /// let number = card.number_secret();
+    /// assert_eq!(number.expose_secret(), "4111111111111111");
+    /// assert!(!format!("{:?}", number).contains("4111"));
+    /// ```
+    #[cfg(all(feature = "secrecy", not(feature = "no-pan-exposure")))]
+    pub fn number_secret(&self) -> secrecy::SecretString {
+        // Sized exactly, so boxing the string doesn't leave a stray copy
+        let mut number = String::with_capacity(self.length());
+        number.extend(self.digits().iter().map(|&d| char::from(b'0' + d)));
+        secrecy::SecretString::from(number)
+    }
+
     /// Returns the card number with masking for safe display.
     ///
     /// Format: `****-****-****-1234` (shows only last 4 digits).
@@ -237,6 +780,97 @@ impl ValidatedCard {
         crate::mask::mask_with_bin(self)
     }
 
+    /// Returns the card number truncated to the digits PCI DSS permits.
+    ///
+    /// Shows the first 8 and last 4 digits of 16-19 digit PANs, and the
+    /// first 6 and last 4 of shorter PANs such as Amex. See
+    /// [`crate::mask::pci_visible_digits`] for the exact rule.
+    #[inline]
+    pub fn pci_truncated(&self) -> String {
+        crate::mask::pci_truncate(self)
+    }
+
+    /// Returns the keyed HMAC-SHA256 fingerprint of the card number.
+    ///
+    /// Equal numbers give equal fingerprints however they were formatted,
+    /// so cards can be matched and de-duplicated without storing the PAN.
+    /// Requires the `fingerprint` feature.
+    #[cfg(feature = "fingerprint")]
+    pub fn fingerprint(&self, key: &[u8]) -> crate::fingerprint::Fingerprint {
+        crate::fingerprint::Fingerprint::of_digits(key, self.digits())
+    }
+
+    /// Returns `true` if this card has the given fingerprint under `key`.
+    ///
+    /// The comparison is constant-time. Requires the `fingerprint` feature.
+    #[cfg(feature = "fingerprint")]
+    pub fn verify_fingerprint(
+        &self,
+        key: &[u8],
+        expected: &crate::fingerprint::Fingerprint,
+    ) -> bool {
+        self.fingerprint(key) == *expected
+    }
+
+    /// Returns `true` if this is a well-known processor test card number.
+    ///
+    /// See [`crate::testcard`] for the numbers and BINs recognized.
+    #[inline]
+    pub fn is_known_test_card(&self) -> bool {
+        crate::testcard::is_known_test_digits(self.digits())
+    }
+
+    /// Re-runs brand detection on the stored digits with `detect`, so a
+    /// long-lived card can pick up updated brand data without the number
+    /// being parsed again from plaintext.
+    ///
+    /// `detect` receives the leading digits (0-9) a BIN database would be
+    /// searched with, cut to [`pci_bin_length`](crate::mask::pci_bin_length)
+    /// so that short PANs never hand over more than a PCI-truncated number,
+    /// and returns the brand, like
+    /// [`detect_brand`](crate::detect::detect_brand). On success the brand
+    /// is replaced and the previous one returned; on error the card is left
+    /// unchanged.
+    ///
+    /// # Errors
+    ///
+    /// - [`ValidationError::UnknownBrand`] if `detect` returns `None`.
+    /// - [`ValidationError::InvalidLengthForBrand`] if the new brand
+    ///   doesn't issue numbers of this length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::detect::detect_brand;
+    /// use cc_validator::CardBrand;
+    ///
+    /// let mut card = cc_validator::validate("6011111111111117").unwrap();
+    ///
+    /// // Brand data reloaded at runtime, e.g. from a BIN feed
+    /// let reloaded = |digits: &[u8]| match digits {
+    ///     [6, 0, 1, 1, ..] => Some(CardBrand::RuPay),
+    ///     _ => detect_brand(digits),
+    /// };
+    /// assert_eq!(card.redetect(reloaded), Ok(CardBrand::Discover));
+    /// assert_eq!(card.brand(), CardBrand::RuPay);
+    /// ```
+    pub fn redetect(
+        &mut self,
+        detect: impl FnOnce(&[u8]) -> Option<CardBrand>,
+    ) -> Result<CardBrand, ValidationError> {
+        let length = self.length();
+        let prefix = crate::mask::pci_bin_length(length, crate::bin::BIN_SPACE_DIGITS);
+        let brand = detect(&self.digits()[..prefix]).ok_or(ValidationError::UnknownBrand)?;
+        if !brand.valid_lengths().contains(&(length as u8)) {
+            return Err(ValidationError::InvalidLengthForBrand {
+                brand,
+                length,
+                valid_lengths: brand.valid_lengths(),
+            });
+        }
+        Ok(std::mem::replace(&mut self.brand, brand))
+    }
+
     /// Returns the raw digit array (for internal/advanced use).
     ///
     /// # Security Warning
@@ -266,6 +900,31 @@ impl fmt::Display for ValidatedCard {
     }
 }
 
+/// Cards are equal if their numbers are, however they were formatted.
+///
+/// The digits are compared in constant time.
+impl PartialEq for ValidatedCard {
+    fn eq(&self, other: &Self) -> bool {
+        crate::mask::constant_time_eq(self.digits(), other.digits())
+    }
+}
+
+impl Eq for ValidatedCard {}
+
+/// With the `fingerprint` feature, hashes an HMAC-SHA256 digest of the
+/// number under a random per-process key, so the PAN never reaches the
+/// hasher. Otherwise hashes the digits directly.
+impl Hash for ValidatedCard {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        #[cfg(feature = "fingerprint")]
+        crate::fingerprint::Fingerprint::of_digits_salted(self.digits())
+            .as_bytes()
+            .hash(state);
+        #[cfg(not(feature = "fingerprint"))]
+        self.digits().hash(state);
+    }
+}
+
 // Ensure sensitive data is properly handled
 impl Drop for ValidatedCard {
     fn drop(&mut self) {
@@ -290,6 +949,61 @@ mod tests {
 
         assert!(CardBrand::Mastercard.is_valid_length(16));
         assert!(!CardBrand::Mastercard.is_valid_length(15));
+
+        for brand in CardBrand::all() {
+            for &length in brand.common_lengths() {
+                assert!(brand.is_valid_length(length.into()), "{} {}", brand, length);
+            }
+        }
+    }
+
+    #[test]
+    fn test_all_brands_unique() {
+        let all = CardBrand::all();
+        assert_eq!(all.len(), 20);
+        for (i, a) in all.iter().enumerate() {
+            for b in &all[i + 1..] {
+                assert_ne!(a, b);
+                assert_ne!(a.id(), b.id());
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_name_round_trip() {
+        for &brand in CardBrand::all() {
+            assert_eq!(CardBrand::from_name(brand.id()), Some(brand));
+            assert_eq!(CardBrand::from_name(brand.name()), Some(brand));
+            assert_eq!(
+                CardBrand::from_name(&brand.name().to_uppercase()),
+                Some(brand)
+            );
+        }
+        assert_eq!(CardBrand::from_name("mc"), Some(CardBrand::Mastercard));
+        assert_eq!(
+            CardBrand::from_name("dinersclub"),
+            Some(CardBrand::DinersClub)
+        );
+        assert_eq!(CardBrand::from_name("union pay"), Some(CardBrand::UnionPay));
+        assert_eq!(CardBrand::from_name(""), None);
+        assert_eq!(CardBrand::from_name("visaa"), None);
+    }
+
+    #[test]
+    fn test_numeric_ids() {
+        for (i, &brand) in CardBrand::all().iter().enumerate() {
+            assert_eq!(usize::from(brand.numeric_id()), i + 1);
+            assert_eq!(CardBrand::try_from(u8::from(brand)), Ok(brand));
+        }
+        assert_eq!(CardBrand::try_from(21), Err(UnknownBrandId(21)));
+        assert_eq!(UnknownBrandId(0).to_string(), "unknown card brand id 0");
+
+        let mut brands = vec![CardBrand::Voyager, CardBrand::Amex, CardBrand::Visa];
+        brands.sort();
+        assert_eq!(
+            brands,
+            [CardBrand::Visa, CardBrand::Amex, CardBrand::Voyager]
+        );
     }
 
     #[test]
@@ -301,7 +1015,7 @@ mod tests {
 
     #[test]
     fn test_validated_card_last_four() {
-        let mut digits = [0u8; MAX_CARD_DIGITS];
+        let mut digits = [0u8; MAX_SUPPORTED_DIGITS];
         digits[..16].copy_from_slice(&[4, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1]);
         let card = ValidatedCard::new(CardBrand::Visa, digits, 16);
         assert_eq!(card.last_four(), "1111");
@@ -309,16 +1023,41 @@ mod tests {
 
     #[test]
     fn test_validated_card_bin() {
-        let mut digits = [0u8; MAX_CARD_DIGITS];
+        let mut digits = [0u8; MAX_SUPPORTED_DIGITS];
         digits[..16].copy_from_slice(&[4, 5, 3, 2, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1]);
         let card = ValidatedCard::new(CardBrand::Visa, digits, 16);
         assert_eq!(card.bin6(), "453211");
         assert_eq!(card.bin8(), "45321111");
+        assert_eq!(card.bin_checked(8).unwrap(), "45321111");
+    }
+
+    #[test]
+    fn test_validated_card_bin_short_pan() {
+        let mut digits = [0u8; MAX_SUPPORTED_DIGITS];
+        digits[..14].copy_from_slice(&[3, 0, 5, 6, 9, 3, 0, 9, 0, 2, 5, 9, 0, 4]);
+        let card = ValidatedCard::new(CardBrand::DinersClub, digits, 14);
+        assert_eq!(card.bin6(), "305693");
+        assert_eq!(card.bin8(), "30569309");
+        assert_eq!(card.bin_checked(6).unwrap(), "305693");
+        assert_eq!(
+            card.bin_checked(8).unwrap_err(),
+            BinExposureError {
+                requested: 8,
+                allowed: 6,
+                pan_length: 14
+            }
+        );
+
+        let mut digits = [0u8; MAX_SUPPORTED_DIGITS];
+        digits[..15].copy_from_slice(&[3, 7, 8, 2, 8, 2, 2, 4, 6, 3, 1, 0, 0, 0, 5]);
+        let amex = ValidatedCard::new(CardBrand::Amex, digits, 15);
+        assert_eq!(amex.bin8(), "37828224");
+        assert_eq!(amex.bin_checked(7).unwrap_err().allowed, 6);
     }
 
     #[test]
     fn test_debug_is_masked() {
-        let mut digits = [0u8; MAX_CARD_DIGITS];
+        let mut digits = [0u8; MAX_SUPPORTED_DIGITS];
         digits[..16].copy_from_slice(&[4, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1]);
         let card = ValidatedCard::new(CardBrand::Visa, digits, 16);
         let debug = format!("{:?}", card);
@@ -333,4 +1072,91 @@ mod tests {
         fn assert_send_sync<T: Send + Sync>() {}
         assert_send_sync::<ValidatedCard>();
     }
+
+    #[test]
+    #[cfg(not(feature = "no-pan-exposure"))]
+    fn test_digit_access() {
+        let mut digits = [0u8; MAX_SUPPORTED_DIGITS];
+        digits[..15].copy_from_slice(&[3, 7, 8, 2, 8, 2, 2, 4, 6, 3, 1, 0, 0, 0, 5]);
+        let card = ValidatedCard::new(CardBrand::Amex, digits, 15);
+
+        assert_eq!(card.digit(0), Some(3));
+        assert_eq!(card.digit(14), Some(5));
+        // Padding past the card length is not exposed
+        assert_eq!(card.digit(15), None);
+        assert_eq!(card.iter_digits().len(), 15);
+        assert_eq!(card.iter_digits().collect::<Vec<_>>(), &digits[..15]);
+    }
+
+    #[test]
+    fn test_eq_and_hash_dedup() {
+        use std::collections::HashSet;
+
+        let a = crate::validate("4111 1111 1111 1111").unwrap();
+        let b = crate::validate("4111-1111-1111-1111").unwrap();
+        let c = crate::validate("4242424242424242").unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+
+        let set: HashSet<_> = [a, b, c].into_iter().collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_redetect() {
+        let mut card = crate::validate("4111111111111111").unwrap();
+        assert_eq!(
+            card.redetect(crate::detect::detect_brand),
+            Ok(CardBrand::Visa)
+        );
+        assert_eq!(card.redetect(|_| None), Err(ValidationError::UnknownBrand));
+        assert_eq!(
+            card.redetect(|digits| {
+                assert_eq!(digits, &[4, 1, 1, 1, 1, 1, 1, 1]);
+                Some(CardBrand::Visa)
+            }),
+            Ok(CardBrand::Visa)
+        );
+        assert_eq!(
+            card.redetect(|_| Some(CardBrand::Amex)),
+            Err(ValidationError::InvalidLengthForBrand {
+                brand: CardBrand::Amex,
+                length: 16,
+                valid_lengths: CardBrand::Amex.valid_lengths(),
+            })
+        );
+        assert_eq!(card.brand(), CardBrand::Visa);
+
+        assert_eq!(
+            card.redetect(|_| Some(CardBrand::Mastercard)),
+            Ok(CardBrand::Visa)
+        );
+        assert_eq!(card.brand(), CardBrand::Mastercard);
+        assert_eq!(card.last_four(), "1111");
+    }
+
+    #[test]
+    fn test_redetect_short_pan() {
+        let mut diners = crate::validate("30569309025904").unwrap();
+        assert_eq!(
+            diners.redetect(|digits| {
+                assert_eq!(digits, &[3, 0, 5, 6, 9, 3]);
+                Some(CardBrand::DinersClub)
+            }),
+            Ok(CardBrand::DinersClub)
+        );
+
+        let mut short = crate::Validator::new()
+            .with_strictness(crate::validator::Strictness::Lenient)
+            .with_digit_limits(8, 19)
+            .validate("77777779")
+            .unwrap();
+        assert_eq!(
+            short.redetect(|digits| {
+                assert_eq!(digits, &[7, 7, 7]);
+                None
+            }),
+            Err(ValidationError::UnknownBrand)
+        );
+    }
 }
@@ -0,0 +1,521 @@
+//! Client for a remote `ccvalidator-server`.
+//!
+//! [`CcValidatorClient`] sends the typed requests from [`schemas`](crate::schemas)
+//! and decodes the server's responses into the same types, so Rust callers
+//! never handle raw JSON.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use cc_validator::client::CcValidatorClient;
+//!
+//! # async fn run() -> Result<(), cc_validator::client::ClientError> {
+//! let client = CcValidatorClient::new("http://localhost:3000");
+//! let result = client.validate("4111-1111-1111-1111").await?;
+//! assert!(result.valid);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Feature
+//!
+//! Requires the `client` feature.
+
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::schemas::*;
+
+/// Error returned by [`CcValidatorClient`].
+#[derive(Debug)]
+pub enum ClientError {
+    /// The request could not be sent or the response could not be decoded.
+    Request(reqwest::Error),
+    /// The server answered with a non-success status.
+    Status {
+        /// HTTP status code.
+        status: u16,
+        /// Response body, usually a short error message.
+        message: String,
+    },
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Request(e) => write!(f, "Request failed: {}", e),
+            Self::Status { status, message } => {
+                write!(f, "Server returned {}: {}", status, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Request(e) => Some(e),
+            Self::Status { .. } => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Request(err)
+    }
+}
+
+/// Async client for the card validation REST API.
+///
+/// Cheap to clone; clones share the underlying connection pool.
+#[derive(Debug, Clone)]
+pub struct CcValidatorClient {
+    base_url: String,
+    http: reqwest::Client,
+}
+
+impl CcValidatorClient {
+    /// Creates a client for the server at `base_url` (e.g. `http://localhost:3000`).
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_http_client(base_url, reqwest::Client::new())
+    }
+
+    /// Creates a client that sends requests through `http`.
+    ///
+    /// Use this to configure timeouts, proxies or default headers.
+    pub fn with_http_client(base_url: impl Into<String>, http: reqwest::Client) -> Self {
+        let mut base_url = base_url.into();
+        while base_url.ends_with('/') {
+            base_url.pop();
+        }
+        Self { base_url, http }
+    }
+
+    /// Returns the server's base URL.
+    #[inline]
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Validates a single card number with the server's default masking.
+    pub async fn validate(&self, card_number: &str) -> Result<ValidateResponse, ClientError> {
+        self.validate_with(&ValidateRequest {
+            card_number: card_number.to_string(),
+            mask: None,
+            cvv: None,
+            allow_unknown_brand: false,
+        })
+        .await
+    }
+
+    /// Validates a card number and checks its CVV against the detected
+    /// brand in one request; see [`ValidateResponse::cvv_valid`].
+    pub async fn validate_with_cvv(
+        &self,
+        card_number: &str,
+        cvv: &str,
+    ) -> Result<ValidateResponse, ClientError> {
+        self.validate_with(&ValidateRequest {
+            card_number: card_number.to_string(),
+            mask: None,
+            cvv: Some(cvv.to_string()),
+            allow_unknown_brand: false,
+        })
+        .await
+    }
+
+    /// Sends a validation request as given.
+    pub async fn validate_with(
+        &self,
+        request: &ValidateRequest,
+    ) -> Result<ValidateResponse, ClientError> {
+        self.post("/validate", request).await
+    }
+
+    /// Validates a batch of card numbers.
+    pub async fn validate_batch(
+        &self,
+        request: &BatchValidateRequest,
+    ) -> Result<BatchValidateResponse, ClientError> {
+        self.post("/validate/batch", request).await
+    }
+
+    /// Detects the brand of a card number or prefix.
+    ///
+    /// Sends the digits in a `POST /detect` body, which servers before API
+    /// version 1.4.0 don't accept.
+    pub async fn detect(&self, card: &str) -> Result<DetectResponse, ClientError> {
+        self.post(
+            "/detect",
+            &DetectRequest {
+                card_number: card.to_string(),
+            },
+        )
+        .await
+    }
+
+    /// Lists the brands the server supports.
+    pub async fn brands(&self) -> Result<BrandsResponse, ClientError> {
+        self.get("/brands").await
+    }
+
+    /// Formats a card number with `separator` between groups.
+    pub async fn format(
+        &self,
+        card_number: &str,
+        separator: &str,
+    ) -> Result<FormatResponse, ClientError> {
+        self.post(
+            "/format",
+            &FormatRequest {
+                card_number: card_number.to_string(),
+                separator: separator.to_string(),
+            },
+        )
+        .await
+    }
+
+    /// Runs the Luhn check and reports the expected check digit.
+    pub async fn luhn(&self, card_number: &str) -> Result<LuhnResponse, ClientError> {
+        self.post(
+            "/luhn",
+            &LuhnRequest {
+                card_number: card_number.to_string(),
+            },
+        )
+        .await
+    }
+
+    /// Masks a card number; the server may downgrade the requested style.
+    pub async fn mask(&self, request: &MaskRequest) -> Result<MaskResponse, ClientError> {
+        self.post("/mask", request).await
+    }
+
+    /// Generates test card numbers.
+    ///
+    /// # Errors
+    ///
+    /// An unknown brand is reported as [`ClientError::Status`] with status 400.
+    pub async fn generate(
+        &self,
+        request: &GenerateRequest,
+    ) -> Result<GenerateResponse, ClientError> {
+        self.post("/generate", request).await
+    }
+
+    /// Validates a CVV, optionally against a brand's CVV length.
+    pub async fn validate_cvv(
+        &self,
+        cvv: &str,
+        brand: Option<&str>,
+    ) -> Result<CvvResponse, ClientError> {
+        self.post(
+            "/cvv/validate",
+            &CvvRequest {
+                cvv: cvv.to_string(),
+                brand: brand.map(str::to_string),
+            },
+        )
+        .await
+    }
+
+    /// Validates an expiry date such as `12/25` or `12/2025`.
+    pub async fn validate_expiry(&self, date: &str) -> Result<ExpiryResponse, ClientError> {
+        self.post(
+            "/expiry/validate",
+            &ExpiryRequest {
+                date: date.to_string(),
+            },
+        )
+        .await
+    }
+
+    /// Checks that the server is up.
+    ///
+    /// Compare [`HealthResponse::api_version`] against
+    /// [`is_compatible`] to detect a server
+    /// speaking an incompatible schema.
+    pub async fn health(&self) -> Result<HealthResponse, ClientError> {
+        self.get("/health").await
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, ClientError> {
+        let response = self.http.get(self.url(path)).send().await?;
+        Self::decode(response).await
+    }
+
+    async fn post<B: Serialize, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T, ClientError> {
+        let response = self.http.post(self.url(path)).json(body).send().await?;
+        Self::decode(response).await
+    }
+
+    async fn decode<T: DeserializeOwned>(response: reqwest::Response) -> Result<T, ClientError> {
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ClientError::Status {
+                status: status.as_u16(),
+                message: response.text().await.unwrap_or_default(),
+            });
+        }
+        Ok(response.json().await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expiry::ExpiryDate;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serves one request with a canned response and returns the raw request.
+    async fn serve_once(status: &'static str, body: &'static str) -> (String, MockHandle) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                if n == 0 || request_complete(&request) {
+                    break;
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        (url, handle)
+    }
+
+    type MockHandle = tokio::task::JoinHandle<String>;
+
+    fn request_complete(request: &[u8]) -> bool {
+        let text = String::from_utf8_lossy(request);
+        let Some(end) = text.find("\r\n\r\n") else {
+            return false;
+        };
+        let length = text[..end]
+            .lines()
+            .find_map(|l| {
+                l.to_ascii_lowercase()
+                    .strip_prefix("content-length:")
+                    .map(|v| v.trim().parse::<usize>().unwrap())
+            })
+            .unwrap_or(0);
+        request.len() >= end + 4 + length
+    }
+
+    #[tokio::test]
+    async fn test_validate() {
+        let (url, server) = serve_once(
+            "200 OK",
+            r#"{"api_version":"1.0.0","valid":true,"brand":"Visa","last_four":"1111","test_card":true,"masked":"****-****-****-1111"}"#,
+        )
+        .await;
+
+        let client = CcValidatorClient::new(format!("{}/", url));
+        let result = client.validate("4111111111111111").await.unwrap();
+        assert!(result.valid);
+        assert_eq!(result.brand.as_deref(), Some("Visa"));
+        assert!(is_compatible(&result.api_version));
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /validate HTTP/1.1"));
+        assert!(request.ends_with(r#"{"card_number":"4111111111111111"}"#));
+    }
+
+    #[tokio::test]
+    async fn test_validate_batch() {
+        let (url, server) = serve_once(
+            "200 OK",
+            r#"{"api_version":"1.0.0","results":[{"valid":false,"error":"card number is empty"}],"summary":{"total":1,"valid":0,"invalid":1,"parallel":false,"elapsed_ms":0.1}}"#,
+        )
+        .await;
+
+        let client = CcValidatorClient::new(url);
+        let response = client
+            .validate_batch(&BatchValidateRequest {
+                card_numbers: vec![String::new()],
+                mask: Some(MaskStyle::LastFour),
+                parallel: false,
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.summary.invalid, 1);
+        assert!(response.results[0].api_version.is_empty());
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /validate/batch HTTP/1.1"));
+        assert!(request.contains(r#""mask":"last_four""#));
+    }
+
+    #[tokio::test]
+    async fn test_error_status() {
+        let (url, _server) =
+            serve_once("500 Internal Server Error", "Batch validation failed").await;
+
+        let client = CcValidatorClient::new(url);
+        let err = client.health().await.unwrap_err();
+        match err {
+            ClientError::Status { status, message } => {
+                assert_eq!(status, 500);
+                assert_eq!(message, "Batch validation failed");
+            }
+            other => panic!("unexpected error: {}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_detect() {
+        let (url, server) = serve_once(
+            "200 OK",
+            r#"{"api_version":"1.0.0","brand":"Visa","valid_lengths":[13,16,19]}"#,
+        )
+        .await;
+
+        let client = CcValidatorClient::new(url);
+        let response = client.detect("4111 11").await.unwrap();
+        assert_eq!(response.brand.as_deref(), Some("Visa"));
+        assert_eq!(response.valid_lengths, Some(vec![13, 16, 19]));
+        // Fields added later are optional for older servers
+        assert_eq!(response.matched_range, None);
+        assert!(!response.tentative);
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /detect HTTP/1.1"));
+        assert!(request.ends_with(r#"{"card_number":"4111 11"}"#));
+    }
+
+    #[tokio::test]
+    async fn test_generate() {
+        let (url, server) = serve_once(
+            "200 OK",
+            r#"{"api_version":"1.0.0","cards":["4556737586899855","4916338506082832"],"seed":42}"#,
+        )
+        .await;
+
+        let client = CcValidatorClient::new(url);
+        let request = GenerateRequest {
+            count: 2,
+            seed: Some(42),
+            ..GenerateRequest::new("visa")
+        };
+        let response = client.generate(&request).await.unwrap();
+        assert_eq!(response.cards.len(), 2);
+        assert_eq!(response.seed, 42);
+        assert!(response.cvv.is_none());
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /generate HTTP/1.1"));
+        assert!(request.contains(r#""brand":"visa","count":2"#));
+    }
+
+    #[tokio::test]
+    async fn test_generate_unknown_brand() {
+        let (url, _server) = serve_once("400 Bad Request", "Unknown brand: foo").await;
+
+        let client = CcValidatorClient::new(url);
+        let err = client
+            .generate(&GenerateRequest::new("foo"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Server returned 400: Unknown brand: foo");
+    }
+
+    #[tokio::test]
+    async fn test_validate_cvv() {
+        let (url, server) = serve_once(
+            "200 OK",
+            r#"{"api_version":"1.0.0","valid":false,"error":"Invalid CVV length: expected 4, got 3"}"#,
+        )
+        .await;
+
+        let client = CcValidatorClient::new(url);
+        let response = client.validate_cvv("123", Some("amex")).await.unwrap();
+        assert!(!response.valid);
+        assert!(response.length.is_none());
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /cvv/validate HTTP/1.1"));
+        assert!(request.ends_with(r#"{"cvv":"123","brand":"amex"}"#));
+
+        let (url, server) = serve_once(
+            "200 OK",
+            r#"{"api_version":"1.3.0","valid":true,"brand":"Visa","cvv_valid":false,"cvv_error":"Visa cards require 3 digit CVV, got 4"}"#,
+        )
+        .await;
+        let client = CcValidatorClient::new(url);
+        let response = client
+            .validate_with_cvv("4111111111111111", "1234")
+            .await
+            .unwrap();
+        assert!(response.valid);
+        assert_eq!(response.cvv_valid, Some(false));
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /validate HTTP/1.1"));
+        assert!(request.ends_with(r#"{"card_number":"4111111111111111","cvv":"1234"}"#));
+    }
+
+    #[tokio::test]
+    async fn test_validate_expiry() {
+        let (url, server) = serve_once(
+            "200 OK",
+            r#"{"api_version":"1.0.0","valid":true,"month":12,"year":2030,"expired":false,"formatted":"12/30","expiry":"12/2030"}"#,
+        )
+        .await;
+
+        let client = CcValidatorClient::new(url);
+        let response = client.validate_expiry("12/2030").await.unwrap();
+        assert_eq!(response.month, Some(12));
+        assert_eq!(response.year, Some(2030));
+        assert_eq!(response.expired, Some(false));
+        assert_eq!(response.expiry, ExpiryDate::new(12, 2030));
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /expiry/validate HTTP/1.1"));
+    }
+
+    #[tokio::test]
+    async fn test_brands_and_format() {
+        let (url, _server) = serve_once(
+            "200 OK",
+            r#"{"api_version":"1.0.0","brands":[{"id":"visa","name":"Visa","valid_lengths":[13,16,19],"cvv_length":3}]}"#,
+        )
+        .await;
+        let client = CcValidatorClient::new(url);
+        let response = client.brands().await.unwrap();
+        assert_eq!(response.brands[0].id, "visa");
+
+        let (url, server) = serve_once(
+            "200 OK",
+            r#"{"api_version":"1.0.0","formatted":"4111-1111-1111-1111","stripped":"4111111111111111"}"#,
+        )
+        .await;
+        let client = CcValidatorClient::new(url);
+        let response = client.format("4111111111111111", "-").await.unwrap();
+        assert_eq!(response.formatted, "4111-1111-1111-1111");
+
+        let request = server.await.unwrap();
+        assert!(request.ends_with(r#"{"card_number":"4111111111111111","separator":"-"}"#));
+    }
+}
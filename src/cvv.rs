@@ -29,10 +29,16 @@
 //! assert!(validate_cvv_for_brand("1234", CardBrand::Visa).is_err());
 //! ```
 
-use crate::CardBrand;
+use crate::mask::constant_time_eq;
+use crate::{CardBrand, ValidatedCard, ValidationError};
 use std::fmt;
+use zeroize::Zeroize;
 
 /// A validated CVV/CVC code.
+///
+/// The digits are zeroed on drop and never shown by `Debug` or `Display`.
+/// Compare CVVs with `==` or [`matches`](Self::matches), both constant-time,
+/// rather than by extracting the plaintext.
 #[derive(Clone)]
 pub struct ValidatedCvv {
     /// The CVV digits.
@@ -42,12 +48,48 @@ pub struct ValidatedCvv {
 }
 
 impl ValidatedCvv {
-    /// Returns the CVV as a string.
-    pub fn as_str(&self) -> String {
-        self.digits[..self.length as usize]
-            .iter()
-            .map(|&d| (b'0' + d) as char)
-            .collect()
+    /// Returns the CVV in plaintext.
+    ///
+    /// The string is zeroed when dropped, but any copy made from it is not.
+    /// Prefer [`matches`](Self::matches) or a fingerprint when the goal is
+    /// comparison. Not available with the `no-pan-exposure` feature.
+    #[cfg(not(feature = "no-pan-exposure"))]
+    pub fn as_str_unchecked(&self) -> zeroize::Zeroizing<String> {
+        zeroize::Zeroizing::new(self.secret().iter().map(|&d| (b'0' + d) as char).collect())
+    }
+
+    /// Returns `true` if `input` is exactly this CVV.
+    ///
+    /// Runs in time independent of where the digits differ, so it is safe
+    /// to use when checking a submitted CVV against an expected one.
+    pub fn matches(&self, input: &str) -> bool {
+        let mut expected = [0u8; 4];
+        for (e, &d) in expected.iter_mut().zip(self.secret()) {
+            *e = b'0' + d;
+        }
+        let result = constant_time_eq(&expected[..self.length as usize], input.as_bytes());
+        expected.zeroize();
+        result
+    }
+
+    /// Returns the keyed HMAC-SHA256 fingerprint of this CVV.
+    ///
+    /// Requires the `fingerprint` feature.
+    #[cfg(feature = "fingerprint")]
+    pub fn fingerprint(&self, key: &[u8]) -> crate::fingerprint::Fingerprint {
+        crate::fingerprint::Fingerprint::of_digits(key, self.secret())
+    }
+
+    /// Returns `true` if this CVV has the given fingerprint under `key`.
+    ///
+    /// The comparison is constant-time. Requires the `fingerprint` feature.
+    #[cfg(feature = "fingerprint")]
+    pub fn verify_fingerprint(
+        &self,
+        key: &[u8],
+        expected: &crate::fingerprint::Fingerprint,
+    ) -> bool {
+        self.fingerprint(key) == *expected
     }
 
     /// Returns the number of digits.
@@ -63,7 +105,15 @@ impl ValidatedCvv {
     }
 
     /// Returns the CVV digits as a slice.
+    ///
+    /// Not available with the `no-pan-exposure` feature.
+    #[cfg(not(feature = "no-pan-exposure"))]
     pub fn digits(&self) -> &[u8] {
+        self.secret()
+    }
+
+    /// The CVV digits, for comparisons and fingerprints inside the crate.
+    fn secret(&self) -> &[u8] {
         &self.digits[..self.length as usize]
     }
 }
@@ -85,15 +135,25 @@ impl fmt::Display for ValidatedCvv {
     }
 }
 
+impl PartialEq for ValidatedCvv {
+    /// Compares in constant time.
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(self.secret(), other.secret())
+    }
+}
+
+impl Eq for ValidatedCvv {}
+
 impl Drop for ValidatedCvv {
     fn drop(&mut self) {
         // Zero out the CVV on drop
-        self.digits = [0; 4];
+        self.digits.zeroize();
     }
 }
 
 /// Errors that can occur during CVV validation.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum CvvError {
     /// The input is empty.
     Empty,
@@ -120,6 +180,11 @@ pub enum CvvError {
         /// Expected length for this brand.
         expected: usize,
     },
+    /// The card brand doesn't use a CVV (see [`CardBrand::has_cvv`]).
+    NotUsed {
+        /// The card brand.
+        brand: CardBrand,
+    },
 }
 
 impl fmt::Display for CvvError {
@@ -152,6 +217,7 @@ impl fmt::Display for CvvError {
                     length
                 )
             }
+            Self::NotUsed { brand } => write!(f, "{} cards have no CVV", brand.name()),
         }
     }
 }
@@ -161,13 +227,11 @@ impl std::error::Error for CvvError {}
 /// Returns the expected CVV length for a card brand.
 ///
 /// - American Express: 4 digits
+/// - UATP, WEX and Voyager: 0 (no CVV)
 /// - All other brands: 3 digits
 #[inline]
 pub const fn cvv_length_for_brand(brand: CardBrand) -> usize {
-    match brand {
-        CardBrand::Amex => 4,
-        _ => 3,
-    }
+    brand.cvv_length()
 }
 
 /// Validates a CVV string (accepts 3 or 4 digits).
@@ -226,7 +290,8 @@ pub fn validate_cvv(input: &str) -> Result<ValidatedCvv, CvvError> {
 
 /// Validates a CVV string for a specific card brand.
 ///
-/// Amex cards require 4-digit CVV, all others require 3 digits.
+/// Amex cards require 4-digit CVV, all others require 3 digits. UATP
+/// and fleet cards have no CVV, so any input fails with [`CvvError::NotUsed`].
 ///
 /// # Example
 ///
@@ -243,6 +308,9 @@ pub fn validate_cvv(input: &str) -> Result<ValidatedCvv, CvvError> {
 /// assert!(validate_cvv_for_brand("123", CardBrand::Amex).is_err());
 /// ```
 pub fn validate_cvv_for_brand(input: &str, brand: CardBrand) -> Result<ValidatedCvv, CvvError> {
+    if !brand.has_cvv() {
+        return Err(CvvError::NotUsed { brand });
+    }
     let cvv = validate_cvv(input)?;
     let expected = cvv_length_for_brand(brand);
 
@@ -269,33 +337,129 @@ pub fn is_valid_cvv_for_brand(input: &str, brand: CardBrand) -> bool {
     validate_cvv_for_brand(input, brand).is_ok()
 }
 
+/// A card number and its CVV validated in one call, by
+/// [`validate_with_cvv`](crate::validate_with_cvv) or
+/// [`Validator::validate_with_cvv`](crate::Validator::validate_with_cvv).
+///
+/// Both checks always run, so a form can flag both fields at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardWithCvv {
+    /// The card number's validation result.
+    pub card: Result<ValidatedCard, ValidationError>,
+    /// The CVV's validation result: checked against the card's brand when
+    /// the card is valid, otherwise as a generic 3- or 4-digit CVV.
+    /// `Ok(None)` when the brand has no CVV and none was given.
+    pub cvv: Result<Option<ValidatedCvv>, CvvError>,
+}
+
+impl CardWithCvv {
+    /// Validates `cvv` against the brand of an already validated `card`.
+    pub fn new(card: Result<ValidatedCard, ValidationError>, cvv: &str) -> Self {
+        let cvv = match &card {
+            Ok(card) if !card.brand().has_cvv() && cvv.is_empty() => Ok(None),
+            Ok(card) => validate_cvv_for_brand(cvv, card.brand()).map(Some),
+            Err(_) => validate_cvv(cvv).map(Some),
+        };
+        Self { card, cvv }
+    }
+
+    /// Returns true if both the card number and the CVV are valid.
+    pub fn is_valid(&self) -> bool {
+        self.card.is_ok() && self.cvv.is_ok()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
+    #[cfg(not(feature = "no-pan-exposure"))]
     fn test_validate_3_digit_cvv() {
         let cvv = validate_cvv("123").unwrap();
         assert_eq!(cvv.length(), 3);
-        assert_eq!(cvv.as_str(), "123");
+        assert_eq!(*cvv.as_str_unchecked(), "123");
         assert!(!cvv.is_four_digit());
     }
 
     #[test]
+    #[cfg(not(feature = "no-pan-exposure"))]
     fn test_validate_4_digit_cvv() {
         let cvv = validate_cvv("1234").unwrap();
         assert_eq!(cvv.length(), 4);
-        assert_eq!(cvv.as_str(), "1234");
+        assert_eq!(*cvv.as_str_unchecked(), "1234");
         assert!(cvv.is_four_digit());
     }
 
     #[test]
+    #[cfg(not(feature = "no-pan-exposure"))]
     fn test_cvv_with_leading_zeros() {
         let cvv = validate_cvv("007").unwrap();
-        assert_eq!(cvv.as_str(), "007");
+        assert_eq!(*cvv.as_str_unchecked(), "007");
 
         let cvv = validate_cvv("0001").unwrap();
-        assert_eq!(cvv.as_str(), "0001");
+        assert_eq!(*cvv.as_str_unchecked(), "0001");
+    }
+
+    #[test]
+    fn test_matches_and_eq() {
+        let cvv = validate_cvv("123").unwrap();
+        assert!(cvv.matches("123"));
+        assert!(!cvv.matches("124"));
+        assert!(!cvv.matches("1234"));
+        assert!(!cvv.matches(" 123"));
+
+        assert_eq!(cvv, validate_cvv("123").unwrap());
+        assert_ne!(cvv, validate_cvv("0123").unwrap());
+    }
+
+    #[cfg(feature = "fingerprint")]
+    #[test]
+    fn test_fingerprint() {
+        let cvv = validate_cvv("123").unwrap();
+        let fp = cvv.fingerprint(b"key");
+        assert!(cvv.verify_fingerprint(b"key", &fp));
+        assert!(!cvv.verify_fingerprint(b"other key", &fp));
+        assert!(!validate_cvv("321").unwrap().verify_fingerprint(b"key", &fp));
+    }
+
+    #[test]
+    fn test_card_with_cvv() {
+        let both = crate::validate_with_cvv("3782 822463 10005", "1234");
+        assert!(both.is_valid());
+        assert_eq!(both.cvv.unwrap().unwrap().length(), 4);
+
+        let wrong = crate::validate_with_cvv("4111111111111111", "1234");
+        assert!(wrong.card.is_ok());
+        assert_eq!(
+            wrong.cvv,
+            Err(CvvError::WrongLengthForBrand {
+                brand: CardBrand::Visa,
+                length: 4,
+                expected: 3
+            })
+        );
+
+        // Both errors are reported; the CVV falls back to the generic check
+        let neither = crate::validate_with_cvv("4111111111111112", "12");
+        assert_eq!(neither.card, Err(ValidationError::InvalidChecksum));
+        assert!(matches!(
+            neither.cvv,
+            Err(CvvError::InvalidLength { length: 2, .. })
+        ));
+        assert!(crate::validate_with_cvv("4111111111111112", "1234")
+            .cvv
+            .is_ok());
+
+        let uatp = crate::validate_with_cvv("100000000000009", "");
+        assert!(uatp.is_valid());
+        assert_eq!(uatp.cvv, Ok(None));
+        assert_eq!(
+            crate::validate_with_cvv("100000000000009", "123").cvv,
+            Err(CvvError::NotUsed {
+                brand: CardBrand::Uatp
+            })
+        );
     }
 
     #[test]
@@ -360,6 +524,13 @@ mod tests {
         assert_eq!(cvv_length_for_brand(CardBrand::Visa), 3);
         assert_eq!(cvv_length_for_brand(CardBrand::Mastercard), 3);
         assert_eq!(cvv_length_for_brand(CardBrand::Discover), 3);
+        assert_eq!(cvv_length_for_brand(CardBrand::Uatp), 0);
+        assert_eq!(
+            validate_cvv_for_brand("123", CardBrand::Uatp),
+            Err(CvvError::NotUsed {
+                brand: CardBrand::Uatp
+            })
+        );
     }
 
     #[test]
@@ -1,23 +1,273 @@
 //! Card brand detection using BIN/IIN prefix matching.
 //!
 //! The Bank Identification Number (BIN), also known as Issuer Identification
-//! Number (IIN), is the first 6-8 digits of a card number. This module uses
-//! pattern matching on these prefixes to detect the card brand.
+//! Number (IIN), is the first 6-8 digits of a card number. This module keeps
+//! the brand prefixes in a data table, [`BRAND_RANGES`], from which a dense
+//! lookup table of every prefix up to [`MAX_PREFIX_DIGITS`] digits is built
+//! at compile time.
+//!
+//! # Priority
+//!
+//! [`BRAND_RANGES`] is the single source of truth. Ranges of the same
+//! length never overlap, and when ranges of different lengths both match,
+//! the longest prefix wins. Every overlap in the table is one of these
+//! deliberate exceptions:
+//!
+//! | Prefix | Brand | Inside | Of |
+//! |--------|-------|--------|----|
+//! | 4506, 4519, 4536, 4724 | Interac | 4 | Visa |
+//! | 4571 | Dankort | 4 | Visa |
+//! | 506-507 | Verve | 50 | Maestro |
+//! | 509 | Elo | 50 | Maestro |
+//! | 5019 | Dankort | 50 | Maestro |
+//! | 6011 | Discover | 60 | Maestro |
+//! | 6362-6363 | Elo | 63 | Maestro |
+//! | 6703 | Bancontact | 66-69 | Maestro |
+//! | 690046 | WEX | 66-69 | Maestro |
+//!
+//! 4571 (Visa/Dankort) and 6703 (Bancontact/Maestro) are co-badged ranges
+//! reported as the domestic brand; the Interac prefixes are Canadian banks'
+//! debit-only cards.
+//!
+//! Mir (2200-2204) and the Mastercard 2-series (2221-2720) are disjoint, so
+//! 2205-2220 belong to no brand. RuPay also issues on 60 and 65, but those
+//! prefixes are shared with Maestro and Discover and can't be told apart
+//! without a BIN database; they resolve to Maestro and Discover.
+//!
+//! [`effective_ranges`] flattens the table into the brand every prefix
+//! actually resolves to, for review.
+//!
+//! The brand of every six-digit prefix is also snapshotted in
+//! `tests/fixtures/brand_prefixes.txt`, run-length encoded. A test fails
+//! whenever detection disagrees with it, so any edit to the ranges must
+//! come with a matching, reviewable diff of the fixture; regenerate it with
+//! `CC_VALIDATOR_BLESS=1 cargo test brand_prefix_snapshot`.
+//!
+//! A few fleet card ranges (WEX 690046 and 707138) need six digits. They
+//! are checked before the table once six digits are available, so a shorter
+//! input such as `6900` still reports Maestro.
 //!
 //! # Performance
 //!
-//! Detection is O(1) using pattern matching - no loops or hash lookups.
+//! Detection is O(1): it reads at most [`LONG_PREFIX_DIGITS`] digits, checks
+//! the handful of six-digit ranges and performs a single array lookup on the
+//! first [`MAX_PREFIX_DIGITS`] - no allocation, hashing, or searching.
+//!
+//! [`detect_brand`] is a `const fn` over the static table, so brands can
+//! also be resolved at compile time, e.g. into `static` tables.
 
 use crate::CardBrand;
+use std::fmt;
+
+/// Number of leading digits covered by the prefix lookup table.
+pub const MAX_PREFIX_DIGITS: usize = 4;
+
+/// Maximum number of leading digits examined by [`detect_brand`]; ranges
+/// longer than [`MAX_PREFIX_DIGITS`] are checked outside the table.
+pub const LONG_PREFIX_DIGITS: usize = 6;
+
+/// An inclusive range of card number prefixes assigned to a brand.
+///
+/// `start` and `end` are the numeric value of the first `digits` digits of
+/// the card number, e.g. `BrandRange::new(2, 2221, 2720, CardBrand::Mastercard)`
+/// covers numbers starting with 2221 through 2720.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BrandRange {
+    /// Number of leading digits the range applies to.
+    pub digits: u8,
+    /// First prefix in the range.
+    pub start: u32,
+    /// Last prefix in the range (inclusive).
+    pub end: u32,
+    /// Brand assigned to the range.
+    pub brand: CardBrand,
+}
+
+impl BrandRange {
+    /// Creates a prefix range.
+    pub const fn new(digits: u8, start: u32, end: u32, brand: CardBrand) -> Self {
+        Self {
+            digits,
+            start,
+            end,
+            brand,
+        }
+    }
+}
+
+impl fmt::Display for BrandRange {
+    /// Formats the prefixes, e.g. `4` or `2221-2720`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start)
+        } else {
+            write!(f, "{}-{}", self.start, self.end)
+        }
+    }
+}
+
+const fn range(digits: u8, start: u32, end: u32, brand: CardBrand) -> BrandRange {
+    BrandRange::new(digits, start, end, brand)
+}
+
+/// Brand prefix ranges, grouped by prefix length and sorted by `start`.
+///
+/// Ranges of the same length never overlap. When ranges of different
+/// lengths both match, the longer prefix wins, so exceptions such as Verve's
+/// 506-507 can sit inside Maestro's broader 50.
+pub const BRAND_RANGES: &[BrandRange] = &[
+    // 1 digit
+    range(1, 1, 1, CardBrand::Uatp),
+    range(1, 4, 4, CardBrand::Visa),
+    // 2 digits
+    range(2, 34, 34, CardBrand::Amex),
+    range(2, 36, 36, CardBrand::DinersClub),
+    range(2, 37, 37, CardBrand::Amex),
+    range(2, 38, 38, CardBrand::DinersClub),
+    range(2, 50, 50, CardBrand::Maestro),
+    range(2, 51, 55, CardBrand::Mastercard),
+    range(2, 56, 58, CardBrand::Maestro),
+    range(2, 60, 61, CardBrand::Maestro),
+    range(2, 62, 62, CardBrand::UnionPay),
+    range(2, 63, 63, CardBrand::Maestro),
+    range(2, 65, 65, CardBrand::Discover),
+    range(2, 66, 69, CardBrand::Maestro),
+    range(2, 81, 82, CardBrand::RuPay),
+    range(2, 94, 94, CardBrand::BcCard),
+    // 3 digits
+    range(3, 300, 305, CardBrand::DinersClub),
+    range(3, 309, 309, CardBrand::DinersClub),
+    range(3, 506, 507, CardBrand::Verve),
+    range(3, 509, 509, CardBrand::Elo),
+    range(3, 644, 649, CardBrand::Discover),
+    // 4 digits
+    range(4, 2200, 2204, CardBrand::Mir),
+    range(4, 2221, 2720, CardBrand::Mastercard),
+    range(4, 3528, 3589, CardBrand::Jcb),
+    range(4, 4506, 4506, CardBrand::Interac),
+    range(4, 4519, 4519, CardBrand::Interac),
+    range(4, 4536, 4536, CardBrand::Interac),
+    range(4, 4571, 4571, CardBrand::Dankort),
+    range(4, 4724, 4724, CardBrand::Interac),
+    range(4, 5019, 5019, CardBrand::Dankort),
+    range(4, 6011, 6011, CardBrand::Discover),
+    range(4, 6362, 6363, CardBrand::Elo),
+    range(4, 6703, 6703, CardBrand::Bancontact),
+    range(4, 7088, 7088, CardBrand::Voyager),
+    range(4, 9792, 9792, CardBrand::Troy),
+    // 6 digits
+    range(6, 690046, 690046, CardBrand::Wex),
+    range(6, 707138, 707138, CardBrand::Wex),
+];
+
+/// The [`BRAND_RANGES`] longer than [`MAX_PREFIX_DIGITS`], which the
+/// lookup table doesn't cover.
+const LONG_RANGES: &[BrandRange] = BRAND_RANGES.split_at(long_ranges_start()).1;
+
+const fn long_ranges_start() -> usize {
+    let mut i = 0;
+    while i < BRAND_RANGES.len() && BRAND_RANGES[i].digits as usize <= MAX_PREFIX_DIGITS {
+        i += 1;
+    }
+    i
+}
+
+/// Returns the brand of every [`MAX_PREFIX_DIGITS`]-digit prefix as
+/// disjoint, sorted ranges, with the priority rules already applied.
+///
+/// Unlike [`BRAND_RANGES`], where a longer range can carve an exception out
+/// of a shorter one, each prefix here appears in exactly one range (or none
+/// if no brand uses it). The six-digit ranges are not flattened: 6900
+/// appears as Maestro although 690046 is WEX.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::detect::effective_ranges;
+/// use cc_validator::CardBrand;
+///
+/// let ranges = effective_ranges();
+/// let maestro_50: Vec<String> = ranges
+///     .iter()
+///     .filter(|r| r.brand == CardBrand::Maestro && (5000..=5099).contains(&r.start))
+///     .map(|r| r.to_string())
+///     .collect();
+/// assert_eq!(maestro_50, ["5000-5018", "5020-5059", "5080-5089"]);
+/// ```
+pub fn effective_ranges() -> Vec<BrandRange> {
+    let offset = table_offset(MAX_PREFIX_DIGITS);
+    let count = 10usize.pow(MAX_PREFIX_DIGITS as u32);
+    let mut ranges: Vec<BrandRange> = Vec::new();
+
+    for prefix in 0..count {
+        let Some(brand) = PREFIX_TABLE[offset + prefix] else {
+            continue;
+        };
+        let prefix = prefix as u32;
+        match ranges.last_mut() {
+            Some(last) if last.brand == brand && last.end + 1 == prefix => last.end = prefix,
+            _ => ranges.push(range(MAX_PREFIX_DIGITS as u8, prefix, prefix, brand)),
+        }
+    }
+    ranges
+}
+
+/// Number of prefixes of each length up to [`MAX_PREFIX_DIGITS`] combined.
+const PREFIX_TABLE_LEN: usize = 11_110;
+
+/// Brand of every prefix of 1 to [`MAX_PREFIX_DIGITS`] digits, built from
+/// [`BRAND_RANGES`] at compile time.
+///
+/// The prefix `p` of length `len` lives at `table_offset(len) + p`.
+static PREFIX_TABLE: [Option<CardBrand>; PREFIX_TABLE_LEN] = build_prefix_table();
+
+/// Index of the first prefix of the given length in [`PREFIX_TABLE`].
+#[inline]
+const fn table_offset(len: usize) -> usize {
+    // 0, 10, 110, 1110: the count of all shorter prefixes
+    (10usize.pow(len as u32) - 10) / 9
+}
+
+const fn build_prefix_table() -> [Option<CardBrand>; PREFIX_TABLE_LEN] {
+    assert!(table_offset(MAX_PREFIX_DIGITS + 1) == PREFIX_TABLE_LEN);
+
+    let mut table = [None; PREFIX_TABLE_LEN];
+    let mut len = 1;
+    while len <= MAX_PREFIX_DIGITS {
+        // Longest matching range wins, so fill shorter ranges first and let
+        // longer ones overwrite them
+        let mut digits = 1;
+        while digits <= len {
+            let scale = 10usize.pow((len - digits) as u32);
+            let mut i = 0;
+            while i < BRAND_RANGES.len() {
+                let r = BRAND_RANGES[i];
+                if r.digits as usize == digits {
+                    let mut prefix = r.start as usize * scale;
+                    while prefix < (r.end as usize + 1) * scale {
+                        table[table_offset(len) + prefix] = Some(r.brand);
+                        prefix += 1;
+                    }
+                }
+                i += 1;
+            }
+            digits += 1;
+        }
+        len += 1;
+    }
+    table
+}
 
 /// Detects the card brand from a sequence of digits.
 ///
 /// Uses the BIN/IIN prefix to identify the card network. This function
-/// examines up to the first 8 digits to make the determination.
+/// examines up to the first [`LONG_PREFIX_DIGITS`] digits; the longest
+/// matching prefix in [`BRAND_RANGES`] determines the brand.
 ///
 /// # Arguments
 ///
 /// * `digits` - A slice of digits (0-9) representing the card number.
+///   Detection stops at the first element outside 0-9.
 ///
 /// # Returns
 ///
@@ -36,81 +286,223 @@ use crate::CardBrand;
 /// // Amex starts with 34 or 37
 /// let amex = [3, 7, 8, 2, 8, 2, 2, 4, 6, 3, 1, 0, 0, 0, 5];
 /// assert_eq!(detect_brand(&amex), Some(CardBrand::Amex));
+///
+/// // Also at compile time
+/// const WEX: Option<CardBrand> = detect_brand(&[6, 9, 0, 0, 4, 6]);
+/// assert_eq!(WEX, Some(CardBrand::Wex));
 /// ```
 #[inline]
-pub fn detect_brand(digits: &[u8]) -> Option<CardBrand> {
-    if digits.is_empty() {
-        return None;
-    }
-
-    // Match on prefixes - order matters for overlapping ranges
-    // More specific patterns must come before general ones
-    match digits {
-        // Mir: 2200-2204 (must be before Mastercard 2221-2720)
-        [2, 2, 0, 0..=4, ..] => Some(CardBrand::Mir),
-
-        // Mastercard: 51-55 or 2221-2720
-        [5, 1..=5, ..] => Some(CardBrand::Mastercard),
-        [2, 2, 2, 1..=9, ..] => Some(CardBrand::Mastercard), // 2221-2229
-        [2, 2, 3..=9, _, ..] => Some(CardBrand::Mastercard), // 2230-2299
-        [2, 3..=6, _, _, ..] => Some(CardBrand::Mastercard), // 2300-2699
-        [2, 7, 0..=1, _, ..] => Some(CardBrand::Mastercard), // 2700-2719
-        [2, 7, 2, 0, ..] => Some(CardBrand::Mastercard),     // 2720
-
-        // American Express: 34 or 37
-        [3, 4, ..] | [3, 7, ..] => Some(CardBrand::Amex),
-
-        // Diners Club: 36, 38, 300-305, 309
-        [3, 6, ..] | [3, 8, ..] => Some(CardBrand::DinersClub),
-        [3, 0, 0..=5, ..] => Some(CardBrand::DinersClub),
-        [3, 0, 9, ..] => Some(CardBrand::DinersClub),
-
-        // JCB: 3528-3589
-        [3, 5, 2, 8..=9, ..] => Some(CardBrand::Jcb),
-        [3, 5, 3..=8, _, ..] => Some(CardBrand::Jcb),
+pub const fn detect_brand(digits: &[u8]) -> Option<CardBrand> {
+    let mut prefix = 0;
+    let mut len = 0;
 
-        // Visa: starts with 4
-        [4, ..] => Some(CardBrand::Visa),
-
-        // Verve (Nigeria): 506, 507 (must be before Maestro 50x)
-        [5, 0, 6..=7, ..] => Some(CardBrand::Verve),
+    while len < digits.len() && len < LONG_PREFIX_DIGITS {
+        let d = digits[len];
+        if d > 9 {
+            break;
+        }
+        prefix = prefix * 10 + d as usize;
+        len += 1;
+    }
 
-        // Elo (Brazil): 509, 6362, 6363 (must be before Maestro 50x)
-        [5, 0, 9, ..] => Some(CardBrand::Elo), // 509xxx
+    if len == 0 {
+        return None;
+    }
+    if len > MAX_PREFIX_DIGITS {
+        // Longest first: the ranges are sorted by length
+        let mut i = LONG_RANGES.len();
+        while i > 0 {
+            i -= 1;
+            let r = LONG_RANGES[i];
+            let range_digits = r.digits as usize;
+            if range_digits <= len {
+                let head = (prefix / 10usize.pow((len - range_digits) as u32)) as u32;
+                if r.start <= head && head <= r.end {
+                    return Some(r.brand);
+                }
+            }
+        }
+        prefix /= 10usize.pow((len - MAX_PREFIX_DIGITS) as u32);
+        len = MAX_PREFIX_DIGITS;
+    }
+    PREFIX_TABLE[table_offset(len) + prefix]
+}
 
-        // Maestro: 50 (except 506, 507, 509), 56-58
-        [5, 0, ..] => Some(CardBrand::Maestro),
-        [5, 6..=8, ..] => Some(CardBrand::Maestro),
+/// Detects the card brand and returns the [`BRAND_RANGES`] entry that
+/// decided it.
+///
+/// Gives the same brand as [`detect_brand`], plus the matched prefixes and
+/// their length, which helps explain a surprising detection or resolve a
+/// co-badged card against a BIN database.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::detect::detect_brand_range;
+/// use cc_validator::CardBrand;
+///
+/// let range = detect_brand_range(&[2, 2, 2, 1, 0, 0]).unwrap();
+/// assert_eq!(range.brand, CardBrand::Mastercard);
+/// assert_eq!(range.digits, 4);
+/// assert_eq!(range.to_string(), "2221-2720");
+/// ```
+pub fn detect_brand_range(digits: &[u8]) -> Option<BrandRange> {
+    let mut prefix = 0u32;
+    let mut len = 0u8;
 
-        // Discover: 6011, 644-649, 65
-        [6, 0, 1, 1, ..] => Some(CardBrand::Discover),
-        [6, 4, 4..=9, ..] => Some(CardBrand::Discover),
-        [6, 5, ..] => Some(CardBrand::Discover),
+    for &d in digits.iter().take(LONG_PREFIX_DIGITS) {
+        if d > 9 {
+            break;
+        }
+        prefix = prefix * 10 + d as u32;
+        len += 1;
+    }
 
-        // Elo (Brazil): 6362, 6363 (must be after Discover 65, before Maestro 6x)
-        [6, 3, 6, 2..=3, ..] => Some(CardBrand::Elo), // 6362, 6363
+    // Longest matching range wins, as in the lookup table
+    BRAND_RANGES
+        .iter()
+        .filter(|r| r.digits <= len)
+        .filter(|r| {
+            let head = prefix / 10u32.pow((len - r.digits) as u32);
+            (r.start..=r.end).contains(&head)
+        })
+        .max_by_key(|r| r.digits)
+        .copied()
+}
 
-        // UnionPay: 62
-        [6, 2, ..] => Some(CardBrand::UnionPay),
+/// Returns true if typing more digits can't change what [`detect_brand`]
+/// reports for `digits`.
+///
+/// While a number is being entered the brand is tentative: `4` reads as
+/// Visa but `4571` is Dankort, and `6` matches no brand until the next
+/// digit. Once no longer prefix range overlaps the digits with another
+/// brand, the detection is final. Six digits are always final.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::detect::{detect_brand, is_detection_final};
+/// use cc_validator::CardBrand;
+///
+/// assert_eq!(detect_brand(&[4]), Some(CardBrand::Visa));
+/// assert!(!is_detection_final(&[4]));
+/// assert!(is_detection_final(&[4, 1]));
+/// assert!(!is_detection_final(&[]));
+/// ```
+pub fn is_detection_final(digits: &[u8]) -> bool {
+    let mut prefix = 0u32;
+    let mut len = 0u8;
+    for &d in digits.iter().take(LONG_PREFIX_DIGITS) {
+        if d > 9 {
+            // Detection stops here, whatever follows
+            return true;
+        }
+        prefix = prefix * 10 + d as u32;
+        len += 1;
+    }
 
-        // Maestro: remaining 6x ranges (60 except 6011, 61, 63, 66-69)
-        [6, 0, ..] => Some(CardBrand::Maestro),
-        [6, 1, ..] => Some(CardBrand::Maestro),
-        [6, 3, ..] => Some(CardBrand::Maestro),
-        [6, 6..=9, ..] => Some(CardBrand::Maestro),
+    let brand = detect_brand(digits);
+    BRAND_RANGES
+        .iter()
+        .filter(|r| r.digits > len && Some(r.brand) != brand)
+        .all(|r| {
+            let scale = 10u32.pow((r.digits - len) as u32);
+            let first = prefix * scale;
+            r.end < first || r.start > first + scale - 1
+        })
+}
 
-        // RuPay: Indian cards - 81, 82
-        [8, 1, ..] | [8, 2, ..] => Some(CardBrand::RuPay),
+/// Characters accepted as a masked digit by [`detect_from_masked`].
+pub const MASK_CHARS: &[char] = &['*', 'x', 'X', '#', '\u{2022}'];
 
-        // BC Card (South Korea): 94
-        [9, 4, ..] => Some(CardBrand::BcCard),
+/// What can still be read from a masked PAN, as returned by
+/// [`detect_from_masked`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaskedPan {
+    /// Brand detected from the visible leading digits.
+    pub brand: Option<CardBrand>,
+    /// First six digits, if all six are visible.
+    pub bin6: Option<String>,
+    /// Last four digits, if all four are visible.
+    pub last_four: Option<String>,
+    /// Number of digit positions, masked or not.
+    pub length: usize,
+}
 
-        // Troy (Turkey): 9792
-        [9, 7, 9, 2, ..] => Some(CardBrand::Troy),
+/// Reads the brand, BIN and last four digits from a masked or tokenized
+/// card number such as `411111******1111`.
+///
+/// Back-office tools often only ever see PANs masked upstream. The digits
+/// before the first mask character (any of [`MASK_CHARS`]) are the visible
+/// BIN the brand is detected from, and the digits after the last one hold
+/// the last four; digits in between are ignored. Spaces, dashes and dots
+/// are skipped.
+///
+/// Returns `None` if the input has any other character, or no digit
+/// positions at all. Nothing is validated: the number can't be Luhn-checked
+/// without its hidden digits.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::detect::detect_from_masked;
+/// use cc_validator::CardBrand;
+///
+/// let pan = detect_from_masked("411111******1111").unwrap();
+/// assert_eq!(pan.brand, Some(CardBrand::Visa));
+/// assert_eq!(pan.bin6.as_deref(), Some("411111"));
+/// assert_eq!(pan.last_four.as_deref(), Some("1111"));
+/// assert_eq!(pan.length, 16);
+///
+/// let pan = detect_from_masked("3782 XXXXXX X0005").unwrap();
+/// assert_eq!(pan.brand, Some(CardBrand::Amex));
+/// assert_eq!(pan.bin6, None);
+///
+/// assert!(detect_from_masked("****-****-****-1111").unwrap().brand.is_none());
+/// assert!(detect_from_masked("4111-????-1111").is_none());
+/// ```
+pub fn detect_from_masked(input: &str) -> Option<MaskedPan> {
+    let mut head = Vec::with_capacity(LONG_PREFIX_DIGITS);
+    let mut tail = Vec::new();
+    let mut masked = false;
+    let mut length = 0;
 
-        // Unknown
-        _ => None,
+    for c in input.chars() {
+        match c {
+            '0'..='9' => {
+                let digit = c as u8 - b'0';
+                if masked {
+                    tail.push(digit);
+                } else {
+                    head.push(digit);
+                }
+                length += 1;
+            }
+            ' ' | '-' | '.' => {}
+            c if MASK_CHARS.contains(&c) => {
+                masked = true;
+                // Only digits after the last mask are trailing
+                tail.clear();
+                length += 1;
+            }
+            _ => return None,
+        }
+    }
+    if length == 0 {
+        return None;
+    }
+    if !masked {
+        // Nothing hidden: the whole number is visible
+        tail.clone_from(&head);
     }
+
+    let text = |digits: &[u8]| digits.iter().map(|&d| char::from(b'0' + d)).collect();
+    Some(MaskedPan {
+        brand: detect_brand(&head),
+        bin6: head.get(..6).map(text),
+        last_four: tail.len().checked_sub(4).map(|start| text(&tail[start..])),
+        length,
+    })
 }
 
 /// Validates that the card length is appropriate for the detected brand.
@@ -128,6 +520,133 @@ pub fn is_valid_length_for_brand(brand: CardBrand, length: usize) -> bool {
     brand.is_valid_length(length)
 }
 
+/// How much of the subtree under a prefix belongs to a brand.
+#[derive(PartialEq)]
+enum Coverage {
+    Full,
+    Partial,
+    None,
+}
+
+fn coverage(brand: CardBrand, prefix: usize, len: usize) -> Coverage {
+    let span = 10usize.pow((MAX_PREFIX_DIGITS - len) as u32);
+    let first = table_offset(MAX_PREFIX_DIGITS) + prefix * span;
+    let matching = PREFIX_TABLE[first..first + span]
+        .iter()
+        .filter(|&&b| b == Some(brand))
+        .count();
+    match matching {
+        0 => Coverage::None,
+        n if n == span => Coverage::Full,
+        _ => Coverage::Partial,
+    }
+}
+
+/// Collects `(prefix length, regex)` items that together cover exactly the
+/// [`MAX_PREFIX_DIGITS`]-digit prefixes detected as `brand`.
+fn collect_prefixes(brand: CardBrand, prefix: usize, len: usize, out: &mut Vec<(usize, String)>) {
+    let head = if len == 0 {
+        String::new()
+    } else {
+        format!("{:0width$}", prefix, width = len)
+    };
+
+    // Fully covered digits share one character class
+    let mut full = Vec::new();
+    for digit in 0..10 {
+        let child = prefix * 10 + digit;
+        match coverage(brand, child, len + 1) {
+            Coverage::Full => full.push(digit),
+            Coverage::Partial => collect_prefixes(brand, child, len + 1, out),
+            Coverage::None => {}
+        }
+    }
+    if !full.is_empty() {
+        out.push((len + 1, format!("{}{}", head, digit_class(&full))));
+    }
+}
+
+/// Renders ascending digits as a regex class, e.g. `[015-7]`.
+fn digit_class(digits: &[usize]) -> String {
+    if let [single] = digits {
+        return single.to_string();
+    }
+
+    let mut class = String::from("[");
+    let mut i = 0;
+    while i < digits.len() {
+        let mut j = i;
+        while j + 1 < digits.len() && digits[j + 1] == digits[j] + 1 {
+            j += 1;
+        }
+        match j - i {
+            0 => class.push_str(&digits[i].to_string()),
+            1 => class.push_str(&format!("{}{}", digits[i], digits[j])),
+            _ => class.push_str(&format!("{}-{}", digits[i], digits[j])),
+        }
+        i = j + 1;
+    }
+    class.push(']');
+    class
+}
+
+/// Joins regex alternatives, grouping them when there is more than one.
+fn alternation(parts: &[String]) -> String {
+    match parts {
+        [single] => single.clone(),
+        _ => format!("(?:{})", parts.join("|")),
+    }
+}
+
+/// Returns a regex matching the card numbers detected as `brand` with a
+/// valid length. Backs [`CardBrand::input_pattern`].
+pub(crate) fn input_pattern(brand: CardBrand) -> String {
+    let mut prefixes = Vec::new();
+    collect_prefixes(brand, 0, 0, &mut prefixes);
+    for r in LONG_RANGES.iter().filter(|r| r.brand == brand) {
+        for prefix in r.start..=r.end {
+            let len = r.digits as usize;
+            prefixes.push((len, format!("{:0width$}", prefix, width = len)));
+        }
+    }
+
+    let mut groups = Vec::new();
+    for len in 1..=LONG_PREFIX_DIGITS {
+        let heads: Vec<String> = prefixes
+            .iter()
+            .filter(|(l, _)| *l == len)
+            .map(|(_, p)| p.clone())
+            .collect();
+        if heads.is_empty() {
+            continue;
+        }
+
+        // Remaining digit counts, with consecutive counts merged into ranges
+        let mut tails = Vec::new();
+        let rest: Vec<usize> = brand
+            .valid_lengths()
+            .iter()
+            .map(|&l| l as usize - len)
+            .collect();
+        let mut i = 0;
+        while i < rest.len() {
+            let mut j = i;
+            while j + 1 < rest.len() && rest[j + 1] == rest[j] + 1 {
+                j += 1;
+            }
+            tails.push(if i == j {
+                format!("\\d{{{}}}", rest[i])
+            } else {
+                format!("\\d{{{},{}}}", rest[i], rest[j])
+            });
+            i = j + 1;
+        }
+
+        groups.push(format!("{}{}", alternation(&heads), alternation(&tails)));
+    }
+    alternation(&groups)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -273,6 +792,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_regional_debit_and_uatp_detection() {
+        assert_eq!(brand_of("4519030000000000"), Some(CardBrand::Interac));
+        assert_eq!(brand_of("4520000000000000"), Some(CardBrand::Visa));
+        assert_eq!(brand_of("4571730000000000"), Some(CardBrand::Dankort));
+        assert_eq!(brand_of("5019000000000000"), Some(CardBrand::Dankort));
+        assert_eq!(brand_of("5018000000000000"), Some(CardBrand::Maestro));
+        assert_eq!(brand_of("6703444444444449"), Some(CardBrand::Bancontact));
+        assert_eq!(brand_of("6704000000000000"), Some(CardBrand::Maestro));
+        assert_eq!(brand_of("122000000000003"), Some(CardBrand::Uatp));
+    }
+
     #[test]
     fn test_unknown_brand() {
         // Invalid prefix
@@ -281,7 +812,7 @@ mod tests {
             None
         );
         assert_eq!(
-            detect_brand(&[1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+            detect_brand(&[7, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
             None
         );
         assert_eq!(
@@ -290,6 +821,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_ranges_sorted_and_disjoint() {
+        for pair in BRAND_RANGES.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            assert!(a.start <= a.end);
+            if a.digits == b.digits {
+                assert!(a.end < b.start, "{:?} overlaps {:?}", a, b);
+            } else {
+                assert!(a.digits < b.digits);
+            }
+        }
+    }
+
+    /// Linear scan of [`BRAND_RANGES`]: the longest matching range wins.
+    fn reference_brand(digits: &[u8]) -> Option<CardBrand> {
+        BRAND_RANGES
+            .iter()
+            .filter(|r| {
+                let len = r.digits as usize;
+                len <= digits.len()
+                    && (r.start..=r.end).contains(
+                        &digits[..len]
+                            .iter()
+                            .fold(0, |acc, &d| acc * 10 + u32::from(d)),
+                    )
+            })
+            .max_by_key(|r| r.digits)
+            .map(|r| r.brand)
+    }
+
+    #[test]
+    fn test_matches_reference_for_all_prefixes() {
+        // Every prefix of up to five digits, plus one longer than the table
+        for len in 1..=MAX_PREFIX_DIGITS + 1 {
+            for n in 0..10u32.pow(len as u32) {
+                let mut digits = [0u8; MAX_PREFIX_DIGITS + 1];
+                let mut v = n;
+                for i in (0..len).rev() {
+                    digits[i] = (v % 10) as u8;
+                    v /= 10;
+                }
+                let digits = &digits[..len];
+                assert_eq!(
+                    detect_brand(digits),
+                    reference_brand(digits),
+                    "{:?}",
+                    digits
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_empty_input() {
         assert_eq!(detect_brand(&[]), None);
@@ -311,4 +894,299 @@ mod tests {
         assert!(is_valid_length_for_brand(CardBrand::Mastercard, 16));
         assert!(!is_valid_length_for_brand(CardBrand::Mastercard, 15));
     }
+
+    #[test]
+    fn test_input_patterns_match_detection() {
+        let patterns: Vec<String> = CardBrand::all()
+            .iter()
+            .map(|b| format!("^(?:{})$", b.input_pattern()))
+            .collect();
+        let set = regex::RegexSet::new(&patterns).unwrap();
+        let any = regex::Regex::new(&format!("^{}$", CardBrand::any_input_pattern())).unwrap();
+
+        for prefix in 0..10_000 {
+            for len in 12..=19 {
+                let number = format!("{:04}{}", prefix, "0".repeat(len - 4));
+                let digits: Vec<u8> = number.bytes().map(|b| b - b'0').collect();
+                let expected = detect_brand(&digits).filter(|b| b.is_valid_length(len));
+
+                let matched: Vec<CardBrand> = set
+                    .matches(&number)
+                    .into_iter()
+                    .map(|i| CardBrand::all()[i])
+                    .collect();
+                assert_eq!(
+                    matched,
+                    expected.into_iter().collect::<Vec<_>>(),
+                    "{}",
+                    number
+                );
+                assert_eq!(any.is_match(&number), expected.is_some(), "{}", number);
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_agrees_with_detection() {
+        for len in 1..=MAX_PREFIX_DIGITS {
+            for prefix in 0..10usize.pow(len as u32) {
+                let digits: Vec<u8> = format!("{:0width$}", prefix, width = len)
+                    .bytes()
+                    .map(|b| b - b'0')
+                    .collect();
+                assert_eq!(
+                    detect_brand_range(&digits).map(|r| r.brand),
+                    detect_brand(&digits),
+                    "{:?}",
+                    digits
+                );
+            }
+        }
+
+        let verve = detect_brand_range(&[5, 0, 6, 1, 2, 3]).unwrap();
+        assert_eq!((verve.brand, verve.digits), (CardBrand::Verve, 3));
+        assert_eq!(verve.to_string(), "506-507");
+        assert_eq!(detect_brand_range(&[4]).unwrap().to_string(), "4");
+        let wex = detect_brand_range(&[6, 9, 0, 0, 4, 6, 1]).unwrap();
+        assert_eq!(
+            (wex.brand, wex.to_string()),
+            (CardBrand::Wex, "690046".into())
+        );
+        assert_eq!(detect_brand(&[6, 9, 0, 0, 4]), Some(CardBrand::Maestro));
+        assert!(detect_brand_range(&[]).is_none());
+    }
+
+    /// Detects the brand of a 16-digit number starting with `prefix`.
+    fn brand_of(prefix: &str) -> Option<CardBrand> {
+        let digits: Vec<u8> = format!("{:0<16}", prefix)
+            .bytes()
+            .map(|b| b - b'0')
+            .collect();
+        detect_brand(&digits)
+    }
+
+    #[test]
+    fn test_range_edges() {
+        use CardBrand::*;
+
+        let cases = [
+            // Mir and the Mastercard 2-series
+            ("2199", None),
+            ("2200", Some(Mir)),
+            ("2204", Some(Mir)),
+            ("2205", None),
+            ("2220", None),
+            ("2221", Some(Mastercard)),
+            ("2720", Some(Mastercard)),
+            ("2721", None),
+            ("5099", Some(Elo)),
+            ("5100", Some(Mastercard)),
+            ("5599", Some(Mastercard)),
+            ("5600", Some(Maestro)),
+            // Discover
+            ("643999", None),
+            ("644000", Some(Discover)),
+            ("649999", Some(Discover)),
+            ("650000", Some(Discover)),
+            ("659999", Some(Discover)),
+            ("660000", Some(Maestro)),
+            ("601099", Some(Maestro)),
+            ("601100", Some(Discover)),
+            ("601199", Some(Discover)),
+            ("601200", Some(Maestro)),
+            // JCB
+            ("3527", None),
+            ("3528", Some(Jcb)),
+            ("3589", Some(Jcb)),
+            ("3590", None),
+            // Exceptions inside Maestro 50 and 63
+            ("505999", Some(Maestro)),
+            ("506000", Some(Verve)),
+            ("507999", Some(Verve)),
+            ("508000", Some(Maestro)),
+            ("509000", Some(Elo)),
+            ("636199", Some(Maestro)),
+            ("636200", Some(Elo)),
+            ("636399", Some(Elo)),
+            ("636400", Some(Maestro)),
+            // Fleet cards, including the six-digit WEX ranges
+            ("690045", Some(Maestro)),
+            ("690046", Some(Wex)),
+            ("690047", Some(Maestro)),
+            ("707137", None),
+            ("707138", Some(Wex)),
+            ("708800", Some(Voyager)),
+            // RuPay only by its own prefixes
+            ("600000", Some(Maestro)),
+            ("670000", Some(Maestro)),
+            ("810000", Some(RuPay)),
+            ("829999", Some(RuPay)),
+            ("830000", None),
+        ];
+        for (prefix, expected) in cases {
+            assert_eq!(brand_of(prefix), expected, "{}", prefix);
+        }
+    }
+
+    #[test]
+    fn test_detection_final() {
+        for (digits, final_) in [
+            (&[][..], false),
+            (&[4], false),
+            (&[4, 1], true),
+            (&[4, 5], false),
+            (&[4, 5, 7, 1], true),
+            (&[6], false),
+            (&[6, 0, 1, 1], true),
+            (&[6, 9, 0], false),
+            (&[6, 9, 0, 0, 4, 6], true),
+            (&[3, 7], true),
+            (&[5, 5], true),
+            (&[1], true),
+        ] {
+            assert_eq!(is_detection_final(digits), final_, "{:?}", digits);
+        }
+
+        // A final detection holds for every next digit
+        for prefix in 0..100_000u32 {
+            let digits: Vec<u8> = (0..5)
+                .rev()
+                .map(|i| (prefix / 10u32.pow(i) % 10) as u8)
+                .collect();
+            if is_detection_final(&digits) {
+                let brand = detect_brand(&digits);
+                for d in 0..10 {
+                    let mut longer = digits.clone();
+                    longer.push(d);
+                    assert_eq!(detect_brand(&longer), brand, "{:?}", longer);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_effective_ranges() {
+        let ranges = effective_ranges();
+        for pair in ranges.windows(2) {
+            assert!(pair[0].end < pair[1].start, "{:?}", pair);
+        }
+        for r in &ranges {
+            for prefix in r.start..=r.end {
+                assert_eq!(brand_of(&format!("{:04}", prefix)), Some(r.brand));
+            }
+        }
+        let covered: usize = ranges.iter().map(|r| (r.end - r.start) as usize + 1).sum();
+        let detected = (0..10_000)
+            .filter(|p| brand_of(&format!("{:04}", p)).is_some())
+            .count();
+        assert_eq!(covered, detected);
+    }
+
+    #[test]
+    fn test_detect_from_masked() {
+        let pan = detect_from_masked("5500 00•• •••• 0004").unwrap();
+        assert_eq!(pan.brand, Some(CardBrand::Mastercard));
+        assert_eq!(pan.bin6.as_deref(), Some("550000"));
+        assert_eq!(pan.last_four.as_deref(), Some("0004"));
+        assert_eq!(pan.length, 16);
+
+        // WEX needs all six BIN digits to win over Maestro
+        assert_eq!(
+            detect_from_masked("690046#######12").unwrap().brand,
+            Some(CardBrand::Wex)
+        );
+        assert_eq!(
+            detect_from_masked("6900##########12").unwrap().brand,
+            Some(CardBrand::Maestro)
+        );
+
+        // Digits between masks are neither BIN nor last four
+        let pan = detect_from_masked("4111**11**111").unwrap();
+        assert_eq!(pan.bin6, None);
+        assert_eq!(pan.last_four, None);
+        assert_eq!(pan.length, 13);
+
+        let plain = detect_from_masked("4111.1111.1111.1111").unwrap();
+        assert_eq!(plain.bin6.as_deref(), Some("411111"));
+        assert_eq!(plain.last_four.as_deref(), Some("1111"));
+
+        assert_eq!(detect_from_masked(""), None);
+        assert_eq!(detect_from_masked(" - "), None);
+        assert_eq!(detect_from_masked("tok_4111"), None);
+    }
+
+    /// Run-length encodes the brand of every six-digit prefix, one
+    /// `start-end brand` line per run; prefixes with no brand are omitted.
+    fn brand_prefix_snapshot() -> String {
+        let mut snapshot = String::from(
+            "# Brand detected for every six-digit prefix, as `start-end brand`.\n\
+             # Prefixes without a brand are omitted. Generated by\n\
+             # `CC_VALIDATOR_BLESS=1 cargo test brand_prefix_snapshot`; do not edit.\n",
+        );
+        let mut runs: Vec<(u32, u32, CardBrand)> = Vec::new();
+        for prefix in 0..1_000_000u32 {
+            let mut digits = [0u8; LONG_PREFIX_DIGITS];
+            let mut v = prefix;
+            for digit in digits.iter_mut().rev() {
+                *digit = (v % 10) as u8;
+                v /= 10;
+            }
+            let Some(brand) = detect_brand(&digits) else {
+                continue;
+            };
+            match runs.last_mut() {
+                Some((_, end, b)) if *b == brand && *end + 1 == prefix => *end = prefix,
+                _ => runs.push((prefix, prefix, brand)),
+            }
+        }
+        for (start, end, brand) in runs {
+            snapshot.push_str(&format!("{:06}-{:06} {}\n", start, end, brand.id()));
+        }
+        snapshot
+    }
+
+    #[test]
+    fn test_brand_prefix_snapshot() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/brand_prefixes.txt"
+        );
+        let snapshot = brand_prefix_snapshot();
+        if std::env::var_os("CC_VALIDATOR_BLESS").is_some() {
+            std::fs::write(path, &snapshot).unwrap();
+        }
+        let fixture = std::fs::read_to_string(path).unwrap();
+        for (line, (got, want)) in snapshot.lines().zip(fixture.lines()).enumerate() {
+            assert_eq!(
+                got,
+                want,
+                "line {} of {} differs; if the change to BRAND_RANGES is intended, \
+                 rerun with CC_VALIDATOR_BLESS=1 and commit the fixture",
+                line + 1,
+                path
+            );
+        }
+        assert_eq!(snapshot.lines().count(), fixture.lines().count());
+    }
+
+    #[test]
+    fn test_readme_prefixes_match_table() {
+        let readme = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"));
+        for &brand in CardBrand::all() {
+            let row = readme
+                .lines()
+                .find(|line| line.starts_with(&format!("| {} |", brand.name())))
+                .expect("brand missing from README table");
+            let mut documented: Vec<&str> =
+                row.split('|').nth(2).unwrap().trim().split(", ").collect();
+            let mut ranges: Vec<String> = BRAND_RANGES
+                .iter()
+                .filter(|r| r.brand == brand)
+                .map(|r| r.to_string())
+                .collect();
+            documented.sort_unstable();
+            ranges.sort_unstable();
+            assert_eq!(documented, ranges, "{}", brand);
+        }
+    }
 }
@@ -10,6 +10,7 @@ use std::fmt;
 /// Each variant provides specific details about the validation failure,
 /// enabling users to understand and fix the issue.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ValidationError {
     /// The input string was empty.
     Empty,
@@ -34,8 +35,15 @@ pub enum ValidationError {
     ///
     /// Only digits (0-9), spaces, and hyphens are allowed.
     InvalidCharacter {
-        /// The position in the input string (0-indexed).
+        /// The position in the input string, in characters (0-indexed).
+        ///
+        /// This is a char index, not a byte offset: inputs read with
+        /// [`canonicalize`](crate::canonicalize) may have multi-byte digits
+        /// before the character.
         position: usize,
+        /// The number of digits before the character, i.e. its position in
+        /// the input with separators stripped.
+        digit_index: usize,
         /// The invalid character that was found.
         character: char,
     },
@@ -60,6 +68,24 @@ pub enum ValidationError {
 
     /// The card number contains only whitespace or separators.
     NoDigits,
+
+    /// The card number is a well-known test card and test cards are rejected.
+    TestCard,
+
+    /// The card brand is not in the validator's allowed list.
+    BrandNotAllowed {
+        /// The detected card brand.
+        brand: CardBrand,
+    },
+
+    /// The card brand is not accepted in the merchant's country, typically a
+    /// domestic-only scheme used abroad.
+    NotAcceptedInCountry {
+        /// The detected card brand.
+        brand: CardBrand,
+        /// The merchant country (ISO 3166-1 alpha-2).
+        country: String,
+    },
 }
 
 impl fmt::Display for ValidationError {
@@ -86,6 +112,7 @@ impl fmt::Display for ValidationError {
             Self::InvalidCharacter {
                 position,
                 character,
+                ..
             } => {
                 write!(
                     f,
@@ -107,12 +134,11 @@ impl fmt::Display for ValidationError {
                 length,
                 valid_lengths,
             } => {
-                let valid_str: Vec<String> = valid_lengths.iter().map(|l| l.to_string()).collect();
                 write!(
                     f,
-                    "{:?} cards must have {} digits, got {}",
-                    brand,
-                    valid_str.join(" or "),
+                    "{} numbers must be {} digits; got {}",
+                    brand.name(),
+                    join_lengths(valid_lengths, "or"),
                     length
                 )
             }
@@ -124,8 +150,172 @@ impl fmt::Display for ValidationError {
             Self::NoDigits => {
                 write!(f, "card number contains no digits")
             }
+
+            Self::TestCard => {
+                write!(f, "test card numbers are not accepted")
+            }
+
+            Self::BrandNotAllowed { brand } => {
+                write!(f, "{} cards are not accepted", brand)
+            }
+
+            Self::NotAcceptedInCountry { brand, country } => {
+                write!(f, "{} cards are not accepted in {}", brand, country)?;
+                match brand.home_country() {
+                    Some(home) => write!(f, " (domestic scheme of {})", home),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// Lists lengths as `13, 16 or 19`, with `or` as the last separator.
+pub(crate) fn join_lengths(lengths: &[u8], or: &str) -> String {
+    match lengths {
+        [] => String::new(),
+        [only] => only.to_string(),
+        [rest @ .., last] => {
+            let rest: Vec<String> = rest.iter().map(|l| l.to_string()).collect();
+            format!("{} {} {}", rest.join(", "), or, last)
+        }
+    }
+}
+
+impl ValidationError {
+    /// Returns a stable, machine-readable code for the error variant.
+    ///
+    /// Codes are snake_case and ignore the variant's fields, so errors of
+    /// the same kind share a code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Empty => "empty",
+            Self::TooShort { .. } => "too_short",
+            Self::TooLong { .. } => "too_long",
+            Self::InvalidCharacter { .. } => "invalid_character",
+            Self::InvalidChecksum => "invalid_checksum",
+            Self::InvalidLengthForBrand { .. } => "invalid_length_for_brand",
+            Self::UnknownBrand => "unknown_brand",
+            Self::NoDigits => "no_digits",
+            Self::TestCard => "test_card",
+            Self::BrandNotAllowed { .. } => "brand_not_allowed",
+            Self::NotAcceptedInCountry { .. } => "not_accepted_in_country",
+        }
+    }
+
+    /// Returns a short hint on how to fix the input, for showing next to
+    /// the error message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::validate;
+    ///
+    /// let err = validate("4111 1111 1111 1112").unwrap_err();
+    /// assert_eq!(
+    ///     err.suggestion(),
+    ///     "re-enter the number; a digit is likely mistyped or two are swapped"
+    /// );
+    /// ```
+    pub fn suggestion(&self) -> &'static str {
+        match self {
+            Self::Empty | Self::NoDigits => "enter the digits printed on the front of the card",
+            Self::TooShort { .. } => "check that no digits are missing",
+            Self::TooLong { .. } => "check for extra digits or a second number pasted in",
+            Self::InvalidCharacter { .. } => "remove everything except digits, spaces and hyphens",
+            Self::InvalidChecksum => {
+                "re-enter the number; a digit is likely mistyped or two are swapped"
+            }
+            Self::InvalidLengthForBrand { .. } => "check for a missing or extra digit",
+            Self::UnknownBrand => "check the first digits of the card number",
+            Self::TestCard => "use a real card; test numbers are only accepted in test mode",
+            Self::BrandNotAllowed { .. } => "use a card from one of the accepted brands",
+            Self::NotAcceptedInCountry { .. } => "use a card accepted internationally",
+        }
+    }
+
+    /// Returns the broad category of the error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::error::ErrorCategory;
+    /// use cc_validator::validate;
+    ///
+    /// let err = validate("4111 1111 1111 1112").unwrap_err();
+    /// assert_eq!(err.category(), ErrorCategory::Checksum);
+    /// assert!(err.category().is_user_fixable());
+    /// ```
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::Empty | Self::InvalidCharacter { .. } | Self::NoDigits => ErrorCategory::Format,
+            Self::TooShort { .. } | Self::TooLong { .. } | Self::InvalidLengthForBrand { .. } => {
+                ErrorCategory::Length
+            }
+            Self::InvalidChecksum => ErrorCategory::Checksum,
+            Self::UnknownBrand => ErrorCategory::Brand,
+            Self::TestCard | Self::BrandNotAllowed { .. } | Self::NotAcceptedInCountry { .. } => {
+                ErrorCategory::Policy
+            }
+        }
+    }
+}
+
+/// Broad groups of [`ValidationError`]s, for deciding how to react to one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// The input isn't shaped like a card number (empty, stray characters).
+    Format,
+    /// Too few or too many digits, overall or for the brand.
+    Length,
+    /// The Luhn check digit is wrong, usually a mistyped digit.
+    Checksum,
+    /// No known brand matches the number's prefix.
+    Brand,
+    /// A well-formed card that this validator's configuration rejects.
+    Policy,
+}
+
+impl ErrorCategory {
+    /// Returns a stable, machine-readable name (e.g. `"checksum"`).
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Format => "format",
+            Self::Length => "length",
+            Self::Checksum => "checksum",
+            Self::Brand => "brand",
+            Self::Policy => "policy",
         }
     }
+
+    /// Returns true if the user can fix the error by correcting what they
+    /// typed. Policy errors need a different card instead.
+    pub const fn is_user_fixable(&self) -> bool {
+        !matches!(self, Self::Policy)
+    }
+}
+
+impl fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Serializes as `{"code": ..., "category": ..., "message": ...}`.
+///
+/// The message is the [`Display`](fmt::Display) text, which never contains
+/// card digits.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ValidationError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("ValidationError", 3)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("category", self.category().as_str())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
 }
 
 impl std::error::Error for ValidationError {}
@@ -150,6 +340,7 @@ mod tests {
         assert_eq!(
             ValidationError::InvalidCharacter {
                 position: 5,
+                digit_index: 4,
                 character: 'x'
             }
             .to_string(),
@@ -160,6 +351,83 @@ mod tests {
             ValidationError::InvalidChecksum.to_string(),
             "invalid checksum (Luhn check failed) - please verify the card number"
         );
+
+        let length_error = |brand: CardBrand, length| ValidationError::InvalidLengthForBrand {
+            brand,
+            length,
+            valid_lengths: brand.valid_lengths(),
+        };
+        assert_eq!(
+            length_error(CardBrand::Visa, 14).to_string(),
+            "Visa numbers must be 13, 16 or 19 digits; got 14"
+        );
+        assert_eq!(
+            length_error(CardBrand::Amex, 16).to_string(),
+            "American Express numbers must be 15 digits; got 16"
+        );
+    }
+
+    #[test]
+    fn test_error_suggestion() {
+        assert_eq!(
+            ValidationError::TooShort {
+                length: 10,
+                minimum: 12
+            }
+            .suggestion(),
+            "check that no digits are missing"
+        );
+        assert_eq!(
+            ValidationError::Empty.suggestion(),
+            ValidationError::NoDigits.suggestion()
+        );
+    }
+
+    #[test]
+    fn test_error_code() {
+        assert_eq!(ValidationError::InvalidChecksum.code(), "invalid_checksum");
+        assert_eq!(
+            ValidationError::TooShort {
+                length: 3,
+                minimum: 12
+            }
+            .code(),
+            ValidationError::TooShort {
+                length: 10,
+                minimum: 12
+            }
+            .code()
+        );
+    }
+
+    #[test]
+    fn test_error_category() {
+        assert_eq!(ValidationError::NoDigits.category(), ErrorCategory::Format);
+        assert_eq!(
+            ValidationError::TooLong {
+                length: 20,
+                maximum: 19
+            }
+            .category(),
+            ErrorCategory::Length
+        );
+        assert_eq!(ValidationError::UnknownBrand.category().as_str(), "brand");
+        let policy = ValidationError::TestCard.category();
+        assert_eq!(policy, ErrorCategory::Policy);
+        assert!(!policy.is_user_fixable());
+    }
+
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    #[test]
+    fn test_error_serialize() {
+        let json = serde_json::to_string(&ValidationError::BrandNotAllowed {
+            brand: CardBrand::Amex,
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            r#"{"code":"brand_not_allowed","category":"policy","message":"American Express cards are not accepted"}"#
+        );
     }
 
     #[test]
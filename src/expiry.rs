@@ -15,23 +15,81 @@
 //! # Example
 //!
 //! ```
-//! use cc_validator::expiry::{parse_expiry, validate_expiry, ExpiryDate};
+//! use cc_validator::expiry::{parse_expiry, validate_expiry_with_clock, FixedClock};
 //!
-//! // Parse and validate
+//! // Parse
 //! let expiry = parse_expiry("12/30").unwrap();
 //! assert_eq!(expiry.month(), 12);
 //! assert_eq!(expiry.year(), 2030);
 //!
-//! // Check if expired (depends on current date)
-//! // assert!(!expiry.is_expired());
+//! // Check against a fixed current month (see Clocks below)
+//! let now = FixedClock::new(6, 2028).unwrap();
+//! assert!(!expiry.is_expired_at(&now));
+//! assert!(validate_expiry_with_clock("12/30", true, Some(20), &now).is_ok());
+//! ```
+//!
+//! # Clocks
+//!
+//! Time-dependent checks read the current month from a [`Clock`]. The plain
+//! methods use [`SystemClock`]; the `_at` / `_with_clock` variants take any
+//! clock, so tests can pin "now" with a [`FixedClock`] and never rot.
+//!
+//! ```
+//! use cc_validator::expiry::{parse_expiry, FixedClock};
 //!
-//! // Quick validation (use a future date)
-//! assert!(validate_expiry("12/30").is_ok());
+//! let now = FixedClock::new(6, 2030).unwrap();
+//! let expiry = parse_expiry("05/30").unwrap();
+//! assert!(expiry.is_expired_at(&now));
+//! assert!(!parse_expiry("06/30").unwrap().is_expired_at(&now));
 //! ```
 
 use std::fmt;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// A source of the current month for expiry checks.
+pub trait Clock {
+    /// Returns the current `(year, month)`, with the month in 1-12.
+    fn year_month(&self) -> (u16, u8);
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn year_month(&self) -> (u16, u8) {
+        (**self).year_month()
+    }
+}
+
+/// The system clock (UTC). Used by every check that doesn't take a clock.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn year_month(&self) -> (u16, u8) {
+        current_year_month()
+    }
+}
+
+/// A clock frozen at a fixed month, for deterministic tests and fixtures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock {
+    month: u8,
+    year: u16,
+}
+
+impl FixedClock {
+    /// Creates a clock that always reports `month`/`year`.
+    ///
+    /// Returns `None` if the month is invalid (not 1-12).
+    pub fn new(month: u8, year: u16) -> Option<Self> {
+        (1..=12).contains(&month).then_some(Self { month, year })
+    }
+}
+
+impl Clock for FixedClock {
+    fn year_month(&self) -> (u16, u8) {
+        (self.year, self.month)
+    }
+}
+
 /// A validated expiry date.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ExpiryDate {
@@ -64,11 +122,36 @@ impl ExpiryDate {
         self.year
     }
 
+    /// Returns the date `months` months after the clock's current month.
+    ///
+    /// Handy for fixtures such as "two years from now":
+    ///
+    /// ```
+    /// use cc_validator::expiry::{ExpiryDate, FixedClock};
+    ///
+    /// let now = FixedClock::new(11, 2030).unwrap();
+    /// assert_eq!(ExpiryDate::months_from_now(&now, 24).format_long(), "11/2032");
+    /// assert_eq!(ExpiryDate::months_from_now(&now, 3).format_long(), "02/2031");
+    /// ```
+    pub fn months_from_now(clock: &impl Clock, months: u32) -> Self {
+        let (year, month) = clock.year_month();
+        let target = year as u32 * 12 + (month as u32 - 1) + months;
+        Self {
+            month: (target % 12) as u8 + 1,
+            year: (target / 12).min(u16::MAX as u32) as u16,
+        }
+    }
+
     /// Returns true if the card has expired.
     ///
     /// A card expires at the end of its expiry month.
     pub fn is_expired(&self) -> bool {
-        let (current_year, current_month) = current_year_month();
+        self.is_expired_at(&SystemClock)
+    }
+
+    /// Like [`is_expired`](Self::is_expired), but relative to `clock`.
+    pub fn is_expired_at(&self, clock: &impl Clock) -> bool {
+        let (current_year, current_month) = clock.year_month();
 
         if self.year < current_year {
             return true;
@@ -83,15 +166,25 @@ impl ExpiryDate {
     ///
     /// Cards typically aren't issued with expiry dates more than 10 years out.
     pub fn is_too_far_future(&self, max_years: u16) -> bool {
-        let (current_year, _) = current_year_month();
-        self.year > current_year + max_years
+        self.is_too_far_future_at(max_years, &SystemClock)
+    }
+
+    /// Like [`is_too_far_future`](Self::is_too_far_future), but relative to `clock`.
+    pub fn is_too_far_future_at(&self, max_years: u16, clock: &impl Clock) -> bool {
+        let (current_year, _) = clock.year_month();
+        self.year > current_year.saturating_add(max_years)
     }
 
     /// Returns the number of months until expiration.
     ///
     /// Returns 0 if already expired.
     pub fn months_until_expiry(&self) -> u32 {
-        let (current_year, current_month) = current_year_month();
+        self.months_until_expiry_at(&SystemClock)
+    }
+
+    /// Like [`months_until_expiry`](Self::months_until_expiry), but relative to `clock`.
+    pub fn months_until_expiry_at(&self, clock: &impl Clock) -> u32 {
+        let (current_year, current_month) = clock.year_month();
 
         let expiry_months = (self.year as u32) * 12 + (self.month as u32);
         let current_months = (current_year as u32) * 12 + (current_month as u32);
@@ -116,6 +209,209 @@ impl fmt::Display for ExpiryDate {
     }
 }
 
+/// Serializes as `"MM/YYYY"`, which round-trips for every year.
+///
+/// Use [`wire::short`] or [`wire::object`] with `#[serde(with = ...)]` for
+/// the `"MM/YY"` or `{"month": 12, "year": 2030}` forms instead.
+///
+/// Requires the `serde` feature.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ExpiryDate {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{:02}/{:04}", self.month, self.year))
+    }
+}
+
+/// Deserializes any string [`parse_expiry`] accepts (`"12/30"`,
+/// `"12/2030"`, `"1230"`, ...) or a `{"month": 12, "year": 2030}` object,
+/// whose year must have four digits.
+///
+/// Only the format is checked: expired dates deserialize fine, so stored
+/// data can be read back; call [`ExpiryDate::is_expired`] to reject them.
+///
+/// Requires the `serde` feature.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ExpiryDate {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(wire::ExpiryVisitor)
+        } else {
+            deserializer.deserialize_str(wire::ExpiryVisitor)
+        }
+    }
+}
+
+/// Alternative serde representations of [`ExpiryDate`], for
+/// `#[serde(with = ...)]`.
+///
+/// Both modules deserialize every form the plain [`ExpiryDate`] impl
+/// accepts; they only change what is written.
+///
+/// Requires the `serde` feature.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::expiry::ExpiryDate;
+/// use serde::{Deserialize, Serialize};
+///
+/// // Serializes as {"expiry": "12/30", "renewal": {"month": 1, "year": 2031}}
+/// #[derive(Serialize, Deserialize)]
+/// struct SavedCard {
+///     #[serde(with = "cc_validator::expiry::wire::short")]
+///     expiry: ExpiryDate,
+///     #[serde(with = "cc_validator::expiry::wire::object")]
+///     renewal: ExpiryDate,
+/// }
+/// ```
+#[cfg(feature = "serde")]
+pub mod wire {
+    use super::{parse_expiry, ExpiryDate};
+    use serde::de::{self, MapAccess, Visitor};
+    use std::fmt;
+
+    /// Writes `"MM/YY"`.
+    ///
+    /// Two-digit years are read back as 20YY, so serializing a year outside
+    /// 2000-2099 fails rather than silently changing the century.
+    pub mod short {
+        use super::ExpiryDate;
+
+        /// Serializes `expiry` as `"MM/YY"`.
+        pub fn serialize<S: serde::Serializer>(
+            expiry: &ExpiryDate,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            if !(2000..=2099).contains(&expiry.year) {
+                return Err(serde::ser::Error::custom(format_args!(
+                    "year {} does not fit MM/YY",
+                    expiry.year
+                )));
+            }
+            serializer.collect_str(expiry)
+        }
+
+        /// Deserializes any form [`ExpiryDate`] accepts.
+        pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<ExpiryDate, D::Error> {
+            serde::Deserialize::deserialize(deserializer)
+        }
+    }
+
+    /// Writes `{"month": 12, "year": 2030}`.
+    pub mod object {
+        use super::ExpiryDate;
+        use serde::ser::SerializeStruct;
+
+        /// Serializes `expiry` as a `{month, year}` object.
+        pub fn serialize<S: serde::Serializer>(
+            expiry: &ExpiryDate,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("ExpiryDate", 2)?;
+            state.serialize_field("month", &expiry.month)?;
+            state.serialize_field("year", &expiry.year)?;
+            state.end()
+        }
+
+        /// Deserializes any form [`ExpiryDate`] accepts.
+        pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<ExpiryDate, D::Error> {
+            serde::Deserialize::deserialize(deserializer)
+        }
+    }
+
+    #[derive(serde::Deserialize)]
+    #[serde(field_identifier, rename_all = "lowercase")]
+    enum Field {
+        Month,
+        Year,
+    }
+
+    pub(super) struct ExpiryVisitor;
+
+    impl<'de> Visitor<'de> for ExpiryVisitor {
+        type Value = ExpiryDate;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("an expiry date such as \"12/30\" or {\"month\": 12, \"year\": 2030}")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<ExpiryDate, E> {
+            parse_expiry(v).map_err(E::custom)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<ExpiryDate, A::Error> {
+            let mut month: Option<u8> = None;
+            let mut year: Option<u16> = None;
+            while let Some(key) = map.next_key()? {
+                match key {
+                    Field::Month if month.is_some() => {
+                        return Err(de::Error::duplicate_field("month"))
+                    }
+                    Field::Year if year.is_some() => {
+                        return Err(de::Error::duplicate_field("year"))
+                    }
+                    Field::Month => month = Some(map.next_value()?),
+                    Field::Year => year = Some(map.next_value()?),
+                }
+            }
+            let month = month.ok_or_else(|| de::Error::missing_field("month"))?;
+            let year = year.ok_or_else(|| de::Error::missing_field("year"))?;
+            if !(1000..=9999).contains(&year) {
+                return Err(de::Error::custom(format_args!(
+                    "year {} must have four digits",
+                    year
+                )));
+            }
+            ExpiryDate::new(month, year)
+                .ok_or_else(|| de::Error::custom(super::ExpiryError::InvalidMonth(month)))
+        }
+    }
+}
+
+/// Documents the forms accepted by the [`Deserialize`](serde::Deserialize)
+/// impl: a date string, or a `{month, year}` object.
+#[cfg(feature = "server")]
+impl<'s> utoipa::ToSchema<'s> for ExpiryDate {
+    fn schema() -> (
+        &'s str,
+        utoipa::openapi::RefOr<utoipa::openapi::schema::Schema>,
+    ) {
+        use utoipa::openapi::schema::{ObjectBuilder, OneOfBuilder, SchemaType};
+
+        let text = ObjectBuilder::new()
+            .schema_type(SchemaType::String)
+            .pattern(Some(r"^(0[1-9]|1[0-2])[/-]?(\d{2}|\d{4})$"))
+            .description(Some("MM/YY, MM/YYYY, MMYY, MMYYYY, MM-YY or MM-YYYY"));
+        let object = ObjectBuilder::new()
+            .property(
+                "month",
+                ObjectBuilder::new()
+                    .schema_type(SchemaType::Integer)
+                    .minimum(Some(1.0))
+                    .maximum(Some(12.0)),
+            )
+            .required("month")
+            .property(
+                "year",
+                ObjectBuilder::new()
+                    .schema_type(SchemaType::Integer)
+                    .minimum(Some(1000.0))
+                    .maximum(Some(9999.0)),
+            )
+            .required("year");
+        let schema = OneOfBuilder::new()
+            .item(text)
+            .item(object)
+            .description(Some("Card expiry date, serialized as MM/YYYY"))
+            .example(Some(serde_json::json!("12/2030")));
+        ("ExpiryDate", schema.into())
+    }
+}
+
 /// Errors that can occur during expiry date parsing/validation.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ExpiryError {
@@ -244,13 +540,14 @@ fn parse_month_year(month_str: &str, year_str: &str) -> Result<ExpiryDate, Expir
 /// # Example
 ///
 /// ```
-/// use cc_validator::expiry::validate_expiry;
-///
-/// // Valid future date
-/// assert!(validate_expiry("12/30").is_ok());
+/// use cc_validator::expiry::{validate_expiry, validate_expiry_with_clock, FixedClock};
 ///
 /// // Expired date
 /// assert!(validate_expiry("01/20").is_err());
+///
+/// // The same checks against a fixed current month
+/// let now = FixedClock::new(6, 2028).unwrap();
+/// assert!(validate_expiry_with_clock("12/30", true, Some(20), &now).is_ok());
 /// ```
 pub fn validate_expiry(input: &str) -> Result<ExpiryDate, ExpiryError> {
     validate_expiry_with_options(input, true, Some(20))
@@ -267,10 +564,33 @@ pub fn validate_expiry_with_options(
     input: &str,
     check_expired: bool,
     max_years_future: Option<u16>,
+) -> Result<ExpiryDate, ExpiryError> {
+    validate_expiry_with_clock(input, check_expired, max_years_future, &SystemClock)
+}
+
+/// Validates an expiry date with custom options, relative to `clock`.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::expiry::{validate_expiry_with_clock, ExpiryError, FixedClock};
+///
+/// let now = FixedClock::new(3, 2031).unwrap();
+/// assert!(validate_expiry_with_clock("03/31", true, Some(20), &now).is_ok());
+/// assert!(matches!(
+///     validate_expiry_with_clock("02/31", true, Some(20), &now),
+///     Err(ExpiryError::Expired { .. })
+/// ));
+/// ```
+pub fn validate_expiry_with_clock(
+    input: &str,
+    check_expired: bool,
+    max_years_future: Option<u16>,
+    clock: &impl Clock,
 ) -> Result<ExpiryDate, ExpiryError> {
     let expiry = parse_expiry(input)?;
 
-    if check_expired && expiry.is_expired() {
+    if check_expired && expiry.is_expired_at(clock) {
         return Err(ExpiryError::Expired {
             month: expiry.month,
             year: expiry.year,
@@ -278,8 +598,8 @@ pub fn validate_expiry_with_options(
     }
 
     if let Some(max_years) = max_years_future {
-        if expiry.is_too_far_future(max_years) {
-            let (current_year, _) = current_year_month();
+        if expiry.is_too_far_future_at(max_years, clock) {
+            let (current_year, _) = clock.year_month();
             return Err(ExpiryError::TooFarFuture {
                 year: expiry.year,
                 max_year: current_year + max_years,
@@ -296,7 +616,14 @@ pub fn validate_expiry_with_options(
 /// Returns `false` if the input cannot be parsed.
 #[inline]
 pub fn is_expired(input: &str) -> bool {
-    parse_expiry(input).map(|e| e.is_expired()).unwrap_or(false)
+    is_expired_at(input, &SystemClock)
+}
+
+/// Like [`is_expired`], but relative to `clock`.
+pub fn is_expired_at(input: &str, clock: &impl Clock) -> bool {
+    parse_expiry(input)
+        .map(|e| e.is_expired_at(clock))
+        .unwrap_or(false)
 }
 
 /// Gets the current year and month.
@@ -395,13 +722,13 @@ mod tests {
 
     #[test]
     fn test_is_expired() {
-        // Far past date should be expired
+        let now = FixedClock::new(6, 2028).unwrap();
+
         let expiry = ExpiryDate::new(1, 2020).unwrap();
-        assert!(expiry.is_expired());
+        assert!(expiry.is_expired_at(&now));
 
-        // Far future date should not be expired
-        let expiry = ExpiryDate::new(12, 2099).unwrap();
-        assert!(!expiry.is_expired());
+        let expiry = ExpiryDate::new(12, 2030).unwrap();
+        assert!(!expiry.is_expired_at(&now));
     }
 
     #[test]
@@ -422,13 +749,15 @@ mod tests {
 
     #[test]
     fn test_validate_expired() {
-        let result = validate_expiry("01/20");
+        let now = FixedClock::new(6, 2028).unwrap();
+        let result = validate_expiry_with_clock("01/20", true, Some(20), &now);
         assert!(matches!(result, Err(ExpiryError::Expired { .. })));
     }
 
     #[test]
     fn test_validate_too_far_future() {
-        let result = validate_expiry("01/99");
+        let now = FixedClock::new(6, 2028).unwrap();
+        let result = validate_expiry_with_clock("01/99", true, Some(20), &now);
         assert!(matches!(result, Err(ExpiryError::TooFarFuture { .. })));
     }
 
@@ -441,19 +770,117 @@ mod tests {
 
     #[test]
     fn test_months_until_expiry() {
-        // Far future should have many months
-        let expiry = ExpiryDate::new(12, 2099).unwrap();
-        assert!(expiry.months_until_expiry() > 12);
+        let now = FixedClock::new(6, 2028).unwrap();
+
+        let expiry = ExpiryDate::new(12, 2030).unwrap();
+        assert_eq!(expiry.months_until_expiry_at(&now), 30);
 
         // Past should be 0
         let expiry = ExpiryDate::new(1, 2020).unwrap();
-        assert_eq!(expiry.months_until_expiry(), 0);
+        assert_eq!(expiry.months_until_expiry_at(&now), 0);
     }
 
     #[test]
     fn test_is_expired_function() {
-        assert!(is_expired("01/20"));
-        assert!(!is_expired("12/99"));
-        assert!(!is_expired("invalid")); // Returns false on parse error
+        let now = FixedClock::new(6, 2028).unwrap();
+        assert!(is_expired_at("01/20", &now));
+        assert!(!is_expired_at("12/99", &now));
+        assert!(!is_expired_at("invalid", &now)); // Returns false on parse error
+    }
+
+    #[test]
+    fn test_fixed_clock() {
+        let now = FixedClock::new(7, 2027).unwrap();
+        assert!(FixedClock::new(0, 2027).is_none());
+
+        assert!(is_expired_at("06/27", &now));
+        assert!(!is_expired_at("07/27", &now));
+        assert!(!is_expired_at("invalid", &now));
+
+        let expiry = ExpiryDate::new(7, 2028).unwrap();
+        assert_eq!(expiry.months_until_expiry_at(&now), 12);
+        assert!(expiry.is_too_far_future_at(0, &now));
+        assert!(!expiry.is_too_far_future_at(1, &now));
+
+        assert_eq!(
+            validate_expiry_with_clock("01/48", true, Some(20), &now),
+            Err(ExpiryError::TooFarFuture {
+                year: 2048,
+                max_year: 2047
+            })
+        );
+        let clock: &dyn Clock = &now;
+        assert!(validate_expiry_with_clock("12/47", true, Some(20), &clock).is_ok());
+    }
+
+    #[test]
+    fn test_months_from_now() {
+        let now = FixedClock::new(12, 2029).unwrap();
+        assert_eq!(
+            ExpiryDate::months_from_now(&now, 0),
+            ExpiryDate::new(12, 2029).unwrap()
+        );
+        assert_eq!(
+            ExpiryDate::months_from_now(&now, 1),
+            ExpiryDate::new(1, 2030).unwrap()
+        );
+        assert_eq!(
+            ExpiryDate::months_from_now(&now, 25),
+            ExpiryDate::new(1, 2032).unwrap()
+        );
+    }
+
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    #[test]
+    fn test_expiry_serde() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Forms {
+            long: ExpiryDate,
+            #[serde(with = "wire::short")]
+            short: ExpiryDate,
+            #[serde(with = "wire::object")]
+            object: ExpiryDate,
+        }
+
+        let date = ExpiryDate::new(3, 2031).unwrap();
+        let forms = Forms {
+            long: date,
+            short: date,
+            object: date,
+        };
+        let json = serde_json::to_string(&forms).unwrap();
+        assert_eq!(
+            json,
+            r#"{"long":"03/2031","short":"03/31","object":{"month":3,"year":2031}}"#
+        );
+        assert_eq!(serde_json::from_str::<Forms>(&json).unwrap(), forms);
+
+        // Every field accepts every form
+        for input in [r#""03/31""#, r#""0331""#, r#"{"year":2031,"month":3}"#] {
+            assert_eq!(serde_json::from_str::<ExpiryDate>(input).unwrap(), date);
+        }
+        // Expired dates still deserialize
+        let old = ExpiryDate::new(1, 1999).unwrap();
+        let json = serde_json::to_string(&old).unwrap();
+        assert_eq!(serde_json::from_str::<ExpiryDate>(&json).unwrap(), old);
+
+        for bad in [
+            r#""13/30""#,
+            r#"{"month":0,"year":2030}"#,
+            r#"{"month":1,"year":30}"#,
+            r#"{"month":1}"#,
+            r#"{"month":1,"year":2030,"day":1}"#,
+            r#"{"month":1,"month":2,"year":2030}"#,
+            "1230",
+        ] {
+            assert!(serde_json::from_str::<ExpiryDate>(bad).is_err(), "{}", bad);
+        }
+        // MM/YY can't hold a year outside 2000-2099
+        assert!(serde_json::to_string(&Forms {
+            long: old,
+            short: old,
+            object: old
+        })
+        .is_err());
     }
 }
@@ -0,0 +1,190 @@
+//! Axum extractor that validates card numbers in request bodies.
+//!
+//! [`ValidatedCardBody`] reads a JSON body with a `card_number` field,
+//! validates it and hands the handler a [`ValidatedCard`]. The raw number
+//! is zeroized as soon as it has been validated, so a handler taking a
+//! `ValidatedCardBody` never sees the PAN as a string, and logging the
+//! card only ever prints the masked form.
+//!
+//! Numbers are checked against a [`Validator`] placed in the request
+//! extensions as an `Arc<Validator>` (for example with
+//! `axum::Extension`), or the [`default_validator`] if there is none.
+//! Invalid numbers are rejected with a [`CardRejection`] before the handler
+//! runs.
+//!
+//! Only the deserialized string is zeroized; the request body buffer is
+//! owned by the HTTP stack.
+//!
+//! Requires the `axum-extract` feature.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use axum::{routing::post, Router};
+//! use cc_validator::extract::ValidatedCardBody;
+//!
+//! async fn pay(ValidatedCardBody(card): ValidatedCardBody) -> String {
+//!     format!("charging {} card ending {}", card.brand(), card.last_four())
+//! }
+//!
+//! let app: Router = Router::new().route("/pay", post(pay));
+//! ```
+
+use crate::validator::{default_validator, ConfigError, Validator};
+use crate::{ValidatedCard, ValidationError};
+use axum::async_trait;
+use axum::extract::rejection::JsonRejection;
+use axum::extract::{FromRequest, Request};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+use zeroize::Zeroize;
+
+/// The JSON body read by [`ValidatedCardBody`]; other fields are ignored.
+#[derive(serde::Deserialize)]
+struct CardPayload {
+    card_number: String,
+}
+
+/// A card number from the request body that passed validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatedCardBody(pub ValidatedCard);
+
+impl ValidatedCardBody {
+    /// Returns the validated card.
+    pub fn into_inner(self) -> ValidatedCard {
+        self.0
+    }
+}
+
+impl Deref for ValidatedCardBody {
+    type Target = ValidatedCard;
+
+    fn deref(&self) -> &ValidatedCard {
+        &self.0
+    }
+}
+
+#[async_trait]
+impl<S> FromRequest<S> for ValidatedCardBody
+where
+    S: Send + Sync,
+{
+    type Rejection = CardRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let validator = req.extensions().get::<Arc<Validator>>().cloned();
+        let Json(mut payload) = Json::<CardPayload>::from_request(req, state)
+            .await
+            .map_err(CardRejection::Body)?;
+
+        let validator = match validator.as_deref() {
+            Some(validator) => validator,
+            None => default_validator().map_err(CardRejection::Config)?,
+        };
+        let result = validator.validate(&payload.card_number);
+        payload.card_number.zeroize();
+        result.map(Self).map_err(CardRejection::Invalid)
+    }
+}
+
+/// Why a [`ValidatedCardBody`] couldn't be extracted.
+#[derive(Debug)]
+pub enum CardRejection {
+    /// The body is not a JSON object with a `card_number` string.
+    Body(JsonRejection),
+    /// The card number failed validation.
+    Invalid(ValidationError),
+    /// No validator was attached and the default one couldn't be configured.
+    Config(ConfigError),
+}
+
+impl fmt::Display for CardRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Body(e) => write!(f, "{}", e.body_text()),
+            Self::Invalid(e) => write!(f, "{}", e),
+            Self::Config(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for CardRejection {}
+
+/// Body errors keep axum's status and text. Invalid numbers answer `422`
+/// with `{"error": {"code", "category", "message"}, "suggestion"}`, and a
+/// misconfigured default validator `500` without details.
+impl IntoResponse for CardRejection {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Body(e) => e.into_response(),
+            Self::Invalid(e) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(serde_json::json!({ "error": e, "suggestion": e.suggestion() })),
+            )
+                .into_response(),
+            Self::Config(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CardBrand;
+    use axum::body::Body;
+    use axum::http::header;
+
+    fn request(body: &str) -> Request {
+        Request::builder()
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_validated_card_body() {
+        let body = r#"{"card_number": "4111 1111 1111 1111", "amount": 10}"#;
+        let card = ValidatedCardBody::from_request(request(body), &())
+            .await
+            .unwrap();
+        assert_eq!(card.brand(), CardBrand::Visa);
+        assert_eq!(card.last_four(), "1111");
+
+        let rejection =
+            ValidatedCardBody::from_request(request(r#"{"card_number": "4111111111111112"}"#), &())
+                .await
+                .unwrap_err();
+        assert!(matches!(
+            rejection,
+            CardRejection::Invalid(ValidationError::InvalidChecksum)
+        ));
+        assert_eq!(
+            rejection.into_response().status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+
+        let rejection =
+            ValidatedCardBody::from_request(request(r#"{"pan": "4111111111111111"}"#), &())
+                .await
+                .unwrap_err();
+        assert!(matches!(rejection, CardRejection::Body(_)));
+        assert_eq!(
+            rejection.into_response().status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+
+        let mut req = request(r#"{"card_number": "4111111111111111"}"#);
+        req.extensions_mut().insert(Arc::new(
+            Validator::new().with_allowed_brands([CardBrand::Mastercard]),
+        ));
+        let rejection = ValidatedCardBody::from_request(req, &()).await.unwrap_err();
+        assert!(matches!(
+            rejection,
+            CardRejection::Invalid(ValidationError::BrandNotAllowed { .. })
+        ));
+    }
+}
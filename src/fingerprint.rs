@@ -0,0 +1,165 @@
+//! Keyed fingerprints of sensitive values.
+//!
+//! A [`Fingerprint`] is an HMAC-SHA256 digest of a value's digits under a
+//! secret key. Equal values under the same key give equal fingerprints, so
+//! they can be stored and compared without keeping the plaintext. Without
+//! the key a fingerprint can't be brute-forced back to the value, which a
+//! plain hash of a 3-4 digit CVV (or a PAN with a known BIN) could.
+//!
+//! Keep the key secret and rotate it like any other credential; fingerprints
+//! made under different keys never match.
+//!
+//! # Feature
+//!
+//! Requires the `fingerprint` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use cc_validator::cvv::validate_cvv;
+//!
+//! let key = b"application secret";
+//! let stored = validate_cvv("123").unwrap().fingerprint(key);
+//!
+//! assert!(validate_cvv("123").unwrap().verify_fingerprint(key, &stored));
+//! assert!(!validate_cvv("124").unwrap().verify_fingerprint(key, &stored));
+//! ```
+
+use crate::mask::constant_time_eq;
+use std::fmt;
+use zeroize::Zeroize;
+
+/// Length of a fingerprint in bytes.
+pub const FINGERPRINT_LEN: usize = 32;
+
+/// An HMAC-SHA256 digest identifying a sensitive value.
+///
+/// Equality is constant-time. `Debug` and `Display` print lowercase hex.
+#[derive(Clone, Copy, Eq)]
+pub struct Fingerprint([u8; FINGERPRINT_LEN]);
+
+impl Fingerprint {
+    /// Wraps raw digest bytes, e.g. ones read back from storage.
+    #[inline]
+    pub const fn from_bytes(bytes: [u8; FINGERPRINT_LEN]) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns the digest bytes.
+    #[inline]
+    pub const fn as_bytes(&self) -> &[u8; FINGERPRINT_LEN] {
+        &self.0
+    }
+
+    /// Returns the digest as lowercase hex.
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Parses a digest from hex (either case).
+    ///
+    /// Returns `None` unless the input is exactly 64 hex digits.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        if hex.len() != FINGERPRINT_LEN * 2 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let mut bytes = [0u8; FINGERPRINT_LEN];
+        for (byte, pair) in bytes.iter_mut().zip(hex.as_bytes().chunks(2)) {
+            let pair = std::str::from_utf8(pair).ok()?;
+            *byte = u8::from_str_radix(pair, 16).ok()?;
+        }
+        Some(Self(bytes))
+    }
+
+    /// Computes the fingerprint of digit values (0-9) under a random key
+    /// generated once per process.
+    ///
+    /// Used to hash cards for `HashMap`/`HashSet` without feeding the PAN
+    /// itself to the hasher.
+    pub(crate) fn of_digits_salted(digits: &[u8]) -> Self {
+        use std::collections::hash_map::RandomState;
+        use std::hash::{BuildHasher, Hasher};
+        use std::sync::OnceLock;
+
+        static SALT: OnceLock<[u8; 32]> = OnceLock::new();
+        let salt = SALT.get_or_init(|| {
+            let mut salt = [0u8; 32];
+            // Each RandomState is seeded differently
+            for chunk in salt.chunks_mut(8) {
+                let hash = RandomState::new().build_hasher().finish();
+                chunk.copy_from_slice(&hash.to_le_bytes());
+            }
+            salt
+        });
+        Self::of_digits(salt, digits)
+    }
+
+    /// Computes the fingerprint of digit values (0-9) under `key`.
+    pub(crate) fn of_digits(key: &[u8], digits: &[u8]) -> Self {
+        let mut ascii = [0u8; crate::MAX_SUPPORTED_DIGITS];
+        let len = digits.len().min(ascii.len());
+        for (a, &d) in ascii.iter_mut().zip(&digits[..len]) {
+            *a = b'0' + d;
+        }
+        let digest = hmac_sha256::HMAC::mac(&ascii[..len], key);
+        ascii.zeroize();
+        Self(digest)
+    }
+}
+
+impl PartialEq for Fingerprint {
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(&self.0, &other.0)
+    }
+}
+
+impl std::hash::Hash for Fingerprint {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+impl fmt::Debug for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fingerprint({})", self.to_hex())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_digest() {
+        // HMAC-SHA256("key", "123")
+        let fp = Fingerprint::of_digits(b"key", &[1, 2, 3]);
+        assert_eq!(
+            fp.to_hex(),
+            "a7f7739b1dc5b4e922b1226c9fcbdc83498dee375382caee08fd52a13eb7cfe2"
+        );
+    }
+
+    #[test]
+    fn test_key_and_value_matter() {
+        let fp = Fingerprint::of_digits(b"key", &[1, 2, 3]);
+        assert_eq!(fp, Fingerprint::of_digits(b"key", &[1, 2, 3]));
+        assert_ne!(fp, Fingerprint::of_digits(b"other", &[1, 2, 3]));
+        assert_ne!(fp, Fingerprint::of_digits(b"key", &[1, 2, 4]));
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        let fp = Fingerprint::of_digits(b"key", &[4, 1, 1, 1]);
+        assert_eq!(Fingerprint::from_hex(&fp.to_hex()), Some(fp));
+        assert_eq!(Fingerprint::from_hex(&fp.to_hex().to_uppercase()), Some(fp));
+        assert_eq!(Fingerprint::from_bytes(*fp.as_bytes()), fp);
+        assert!(Fingerprint::from_hex("abcd").is_none());
+        assert!(Fingerprint::from_hex(&"zz".repeat(32)).is_none());
+    }
+}
@@ -8,8 +8,12 @@
 //! - **Visa/Mastercard/Discover** (16 digits): `XXXX XXXX XXXX XXXX`
 //! - **American Express** (15 digits): `XXXX XXXXXX XXXXX`
 //! - **Diners Club** (14 digits): `XXXX XXXXXX XXXX`
+//! - **Maestro** (13 and 15 digits): `XXXX XXXX XXXXX`, `XXXX XXXXXX XXXXX`
+//! - **UnionPay** (19 digits): `XXXXXX XXXXXXXXXXXXX`
 //! - **Other**: Groups of 4 with remainder at end
 //!
+//! [`grouping_for`] returns the group sizes for any brand and length.
+//!
 //! # Example
 //!
 //! ```
@@ -24,9 +28,18 @@
 //! // Custom separator
 //! assert_eq!(format_with_separator("4111111111111111", "-"), "4111-1111-1111-1111");
 //! ```
+//!
+//! # Right-to-left pages
+//!
+//! In an Arabic or Hebrew paragraph the spaces between digit groups take
+//! the paragraph's direction, so `4111 1111 1111 1234` displays as
+//! `1234 1111 1111 4111`. [`FormatOptions::directional_isolates`] and
+//! [`MaskedFormat::directional_isolates`] wrap the grouped number in a
+//! left-to-right isolate (U+2066 ... U+2069) so the groups keep their order
+//! in any context.
 
 use crate::detect::detect_brand;
-use crate::CardBrand;
+use crate::{CardBrand, ValidatedCard};
 
 /// Formats a credit card number with standard grouping.
 ///
@@ -67,10 +80,13 @@ pub fn format_with_separator(input: &str, separator: &str) -> String {
     let digit_values: Vec<u8> = digits.iter().map(|&c| (c as u8) - b'0').collect();
     let brand = detect_brand(&digit_values);
 
-    // Get grouping pattern
-    let groups = grouping_for_brand(brand, digits.len());
+    apply_grouping(&digits, &grouping_for_brand(brand, digits.len()), separator)
+}
 
-    // Apply grouping
+/// Joins digits into groups of the given sizes.
+///
+/// Digits left over after the last group are appended as a final group.
+fn apply_grouping(digits: &[char], groups: &[usize], separator: &str) -> String {
     let mut result = String::with_capacity(digits.len() + groups.len() * separator.len());
     let mut pos = 0;
 
@@ -127,47 +143,53 @@ pub fn format_for_brand_with_separator(input: &str, brand: CardBrand, separator:
         return String::new();
     }
 
-    let groups = grouping_for_brand(Some(brand), digits.len());
-
-    let mut result = String::with_capacity(digits.len() + groups.len() * separator.len());
-    let mut pos = 0;
-
-    for (i, &group_size) in groups.iter().enumerate() {
-        if i > 0 {
-            result.push_str(separator);
-        }
-        for _ in 0..group_size {
-            if pos < digits.len() {
-                result.push(digits[pos]);
-                pos += 1;
-            }
-        }
-    }
-
-    if pos < digits.len() {
-        if !result.is_empty() {
-            result.push_str(separator);
-        }
-        for c in &digits[pos..] {
-            result.push(*c);
-        }
-    }
+    apply_grouping(&digits, &grouping_for(brand, digits.len()), separator)
+}
 
-    result
+/// Returns the digit group sizes used to display a card of the given brand and length.
+///
+/// 13- and 17-digit numbers of any brand are shown 4-4-5 and 4-4-4-5, so no
+/// digit is left on its own. Other brand and length
+/// combinations without a specific convention fall back to groups of 4 with
+/// the remainder at the end.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::format::grouping_for;
+/// use cc_validator::CardBrand;
+///
+/// assert_eq!(grouping_for(CardBrand::Amex, 15), vec![4, 6, 5]);
+/// assert_eq!(grouping_for(CardBrand::Visa, 13), vec![4, 4, 5]);
+/// assert_eq!(grouping_for(CardBrand::Discover, 17), vec![4, 4, 4, 5]);
+/// assert_eq!(grouping_for(CardBrand::UnionPay, 19), vec![6, 13]);
+/// assert_eq!(grouping_for(CardBrand::Uatp, 15), vec![4, 5, 6]);
+/// assert_eq!(grouping_for(CardBrand::Visa, 19), vec![4, 4, 4, 4, 3]);
+/// ```
+pub fn grouping_for(brand: CardBrand, length: usize) -> Vec<usize> {
+    grouping_for_brand(Some(brand), length)
 }
 
-/// Returns the digit grouping pattern for a card brand.
+/// Returns the digit grouping pattern for a (possibly unknown) card brand.
 fn grouping_for_brand(brand: Option<CardBrand>, length: usize) -> Vec<usize> {
-    match brand {
-        Some(CardBrand::Amex) => {
-            // Amex: 4-6-5
-            vec![4, 6, 5]
-        }
-        Some(CardBrand::DinersClub) if length == 14 => {
-            // Diners 14-digit: 4-6-4
-            vec![4, 6, 4]
-        }
-        _ => {
+    let table: Option<&'static [usize]> = match (brand, length) {
+        // Amex: 4-6-5 regardless of length
+        (Some(CardBrand::Amex), _) => Some(&[4, 6, 5]),
+        (Some(CardBrand::DinersClub), 14) => Some(&[4, 6, 4]),
+        (Some(CardBrand::DinersClub), 15) => Some(&[4, 6, 5]),
+        (Some(CardBrand::Maestro), 14) => Some(&[4, 6, 4]),
+        (Some(CardBrand::Maestro), 15) => Some(&[4, 6, 5]),
+        (Some(CardBrand::UnionPay), 19) => Some(&[6, 13]),
+        (Some(CardBrand::Uatp), 15) => Some(&[4, 5, 6]),
+        // 13 and 17 digits: a final group of 5 rather than a lone trailing digit
+        (_, 13) => Some(&[4, 4, 5]),
+        (_, 17) => Some(&[4, 4, 4, 5]),
+        _ => None,
+    };
+
+    match table {
+        Some(groups) => groups.to_vec(),
+        None => {
             // Standard: groups of 4
             let full_groups = length / 4;
             let remainder = length % 4;
@@ -297,6 +319,214 @@ pub fn is_valid_format(input: &str) -> bool {
         .all(|c| c.is_ascii_digit() || c == ' ' || c == '-' || c == '.')
 }
 
+/// Left-to-right isolate (LRI), opening a run laid out left to right.
+const LEFT_TO_RIGHT_ISOLATE: char = '\u{2066}';
+
+/// Pop directional isolate (PDI), closing [`LEFT_TO_RIGHT_ISOLATE`].
+const POP_DIRECTIONAL_ISOLATE: char = '\u{2069}';
+
+/// Wraps a formatted number in a left-to-right isolate; empty stays empty.
+fn isolate(formatted: String) -> String {
+    if formatted.is_empty() {
+        return formatted;
+    }
+    let mut result = String::with_capacity(formatted.len() + 6);
+    result.push(LEFT_TO_RIGHT_ISOLATE);
+    result.push_str(&formatted);
+    result.push(POP_DIRECTIONAL_ISOLATE);
+    result
+}
+
+/// Options for [`format_with_options`].
+///
+/// The default matches [`format_card_number`]: space-separated brand
+/// groups with no directional marks.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::format::{format_with_options, FormatOptions};
+///
+/// let options = FormatOptions::new().separator("-").directional_isolates(true);
+/// assert_eq!(
+///     format_with_options("4111111111111111", &options),
+///     "\u{2066}4111-1111-1111-1111\u{2069}"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatOptions {
+    separator: String,
+    directional_isolates: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            separator: " ".to_string(),
+            directional_isolates: false,
+        }
+    }
+}
+
+impl FormatOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the string placed between digit groups.
+    pub fn separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Wraps the output in a left-to-right isolate, for numbers shown in
+    /// right-to-left (Arabic, Hebrew, ...) text.
+    pub fn directional_isolates(mut self, enabled: bool) -> Self {
+        self.directional_isolates = enabled;
+        self
+    }
+}
+
+/// Formats a card number with brand grouping and the given options.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::format::{format_with_options, FormatOptions};
+///
+/// let rtl = FormatOptions::new().directional_isolates(true);
+/// let formatted = format_with_options("3782 822463 10005", &rtl);
+/// assert_eq!(formatted, "\u{2066}3782 822463 10005\u{2069}");
+/// assert_eq!(format_with_options("", &rtl), "");
+/// ```
+pub fn format_with_options(input: &str, options: &FormatOptions) -> String {
+    let formatted = format_with_separator(input, &options.separator);
+    if options.directional_isolates {
+        isolate(formatted)
+    } else {
+        formatted
+    }
+}
+
+/// Display options for [`format_masked`].
+///
+/// The default renders `•••• •••• •••• 1111`: bullets, space-separated
+/// brand groups, and the last 4 digits visible.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::format::MaskedFormat;
+///
+/// let style = MaskedFormat::new().mask_char('*').separator("-");
+/// assert_eq!(style.visible_digits(), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaskedFormat {
+    mask_char: char,
+    separator: String,
+    visible: usize,
+    directional_isolates: bool,
+}
+
+impl Default for MaskedFormat {
+    fn default() -> Self {
+        Self {
+            mask_char: '\u{2022}',
+            separator: " ".to_string(),
+            visible: 4,
+            directional_isolates: false,
+        }
+    }
+}
+
+impl MaskedFormat {
+    /// Creates the default style.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the character that replaces hidden digits.
+    pub fn mask_char(mut self, c: char) -> Self {
+        self.mask_char = c;
+        self
+    }
+
+    /// Sets the string placed between digit groups.
+    pub fn separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Sets how many trailing digits stay visible.
+    ///
+    /// Capped at 4, the most PCI DSS allows for display of the tail alone.
+    pub fn visible(mut self, count: usize) -> Self {
+        self.visible = count.min(4);
+        self
+    }
+
+    /// Returns how many trailing digits stay visible.
+    #[inline]
+    pub fn visible_digits(&self) -> usize {
+        self.visible
+    }
+
+    /// Wraps the output in a left-to-right isolate, for numbers shown in
+    /// right-to-left (Arabic, Hebrew, ...) text.
+    pub fn directional_isolates(mut self, enabled: bool) -> Self {
+        self.directional_isolates = enabled;
+        self
+    }
+}
+
+/// Formats a card for read-only display, masked and grouped for its brand.
+///
+/// Masking runs right to left: the last [`MaskedFormat::visible_digits`]
+/// digits are shown and everything before them is replaced, then the
+/// result is split into the brand's groups (see [`grouping_for`]).
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::format::{format_masked, MaskedFormat};
+/// use cc_validator::validate;
+///
+/// let visa = validate("4111-1111-1111-1111").unwrap();
+/// assert_eq!(format_masked(&visa, &MaskedFormat::default()), "•••• •••• •••• 1111");
+///
+/// let amex = validate("378282246310005").unwrap();
+/// let style = MaskedFormat::new().mask_char('*').separator("-");
+/// assert_eq!(format_masked(&amex, &style), "****-******-*0005");
+/// ```
+pub fn format_masked(card: &ValidatedCard, style: &MaskedFormat) -> String {
+    let digits = card.digits();
+    let hidden = digits.len().saturating_sub(style.visible);
+    let chars: Vec<char> = digits
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| {
+            if i < hidden {
+                style.mask_char
+            } else {
+                (b'0' + d) as char
+            }
+        })
+        .collect();
+
+    let formatted = apply_grouping(
+        &chars,
+        &grouping_for(card.brand(), digits.len()),
+        &style.separator,
+    );
+    if style.directional_isolates {
+        isolate(formatted)
+    } else {
+        formatted
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -311,7 +541,7 @@ mod tests {
 
     #[test]
     fn test_format_visa_13() {
-        assert_eq!(format_card_number("4222222222222"), "4222 2222 2222 2");
+        assert_eq!(format_card_number("4222222222222"), "4222 2222 22222");
     }
 
     #[test]
@@ -412,9 +642,114 @@ mod tests {
         assert_eq!(groups, vec![4, 4, 4, 4, 3]);
     }
 
+    #[test]
+    fn test_grouping_every_brand_and_length() {
+        for &brand in CardBrand::all() {
+            for &len in brand.valid_lengths() {
+                let len = len as usize;
+                let groups = grouping_for(brand, len);
+                let expected: &[usize] = match (brand, len) {
+                    (CardBrand::Amex, 15) => &[4, 6, 5],
+                    (CardBrand::DinersClub, 14) | (CardBrand::Maestro, 14) => &[4, 6, 4],
+                    (CardBrand::DinersClub, 15) | (CardBrand::Maestro, 15) => &[4, 6, 5],
+                    (CardBrand::UnionPay, 19) => &[6, 13],
+                    (CardBrand::Uatp, 15) => &[4, 5, 6],
+                    (_, 12) => &[4, 4, 4],
+                    (_, 13) => &[4, 4, 5],
+                    (_, 16) => &[4, 4, 4, 4],
+                    (_, 17) => &[4, 4, 4, 5],
+                    (_, 18) => &[4, 4, 4, 4, 2],
+                    (_, 19) => &[4, 4, 4, 4, 3],
+                    _ => panic!("no expectation for {:?} length {}", brand, len),
+                };
+                assert_eq!(groups, expected, "{:?} length {}", brand, len);
+                assert_eq!(
+                    groups.iter().sum::<usize>(),
+                    len,
+                    "{:?} length {}",
+                    brand,
+                    len
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_maestro_variable_length() {
+        assert_eq!(
+            format_for_brand("5018000000009", CardBrand::Maestro),
+            "5018 0000 00009"
+        );
+        assert_eq!(
+            format_for_brand("501800000000009", CardBrand::Maestro),
+            "5018 000000 00009"
+        );
+    }
+
+    #[test]
+    fn test_format_unionpay_19() {
+        assert_eq!(
+            format_for_brand("6212345678901234567", CardBrand::UnionPay),
+            "621234 5678901234567"
+        );
+    }
+
     #[test]
     fn test_format_19_digit() {
         let card = format_card_number("4111111111111111111");
         assert_eq!(card, "4111 1111 1111 1111 111");
     }
+
+    #[test]
+    fn test_format_masked() {
+        let visa = crate::validate("4111111111111111").unwrap();
+        assert_eq!(
+            format_masked(&visa, &MaskedFormat::default()),
+            "\u{2022}\u{2022}\u{2022}\u{2022} \u{2022}\u{2022}\u{2022}\u{2022} \u{2022}\u{2022}\u{2022}\u{2022} 1111"
+        );
+
+        let diners = crate::validate("30569309025904").unwrap();
+        let style = MaskedFormat::new().mask_char('x').separator("");
+        assert_eq!(format_masked(&diners, &style), "xxxxxxxxxx5904");
+
+        let style = MaskedFormat::new().mask_char('*').visible(2);
+        assert_eq!(format_masked(&diners, &style), "**** ****** **04");
+
+        // More than 4 visible digits is capped
+        assert_eq!(MaskedFormat::new().visible(8).visible_digits(), 4);
+        let style = MaskedFormat::new().mask_char('*').visible(0);
+        assert_eq!(format_masked(&visa, &style), "**** **** **** ****");
+    }
+
+    #[test]
+    fn test_format_with_options() {
+        assert_eq!(
+            format_with_options("4111-1111-1111-1111", &FormatOptions::default()),
+            format_card_number("4111-1111-1111-1111")
+        );
+
+        let rtl = FormatOptions::new().directional_isolates(true);
+        assert_eq!(
+            format_with_options("4111111111111234", &rtl),
+            "\u{2066}4111 1111 1111 1234\u{2069}"
+        );
+        assert_eq!(
+            format_with_options("30569309025904", &rtl.clone().separator("")),
+            "\u{2066}30569309025904\u{2069}"
+        );
+        assert_eq!(format_with_options("   ", &rtl), "");
+    }
+
+    #[test]
+    fn test_format_masked_directional_isolates() {
+        let amex = crate::validate("378282246310005").unwrap();
+        let style = MaskedFormat::new()
+            .mask_char('*')
+            .directional_isolates(true);
+        let masked = format_masked(&amex, &style);
+        assert_eq!(masked, "\u{2066}**** ****** *0005\u{2069}");
+        // One isolate around the whole number, closed at the end
+        assert_eq!(masked.matches(LEFT_TO_RIGHT_ISOLATE).count(), 1);
+        assert!(masked.ends_with(POP_DIRECTIONAL_ISOLATE));
+    }
 }
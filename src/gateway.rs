@@ -0,0 +1,174 @@
+//! PAN redaction policy for HTTP gateway filters.
+//!
+//! A [`GatewayConfig`] decides which request and response bodies a filter
+//! running in a proxy (Envoy, Istio and other Proxy-WASM hosts) should
+//! inspect, and [`GatewayConfig::redact_body`] masks card numbers in them
+//! with [`scan::redact`](crate::scan::redact). The `proxy-wasm/` crate in
+//! this repository wires it into a filter, so PANs can be kept out of
+//! upstream logs and responses without changing the application.
+//!
+//! Redaction replaces digits with `*` one byte for one byte, so the body
+//! keeps its length and `Content-Length` stays correct.
+//!
+//! Requires the `gateway` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use cc_validator::gateway::GatewayConfig;
+//!
+//! let config = GatewayConfig::from_json(br#"{"paths": ["/checkout"]}"#).unwrap();
+//! assert!(config.inspects("/checkout/pay?step=2", Some("application/json; charset=utf-8"), None));
+//! assert!(!config.inspects("/health", Some("application/json"), None));
+//!
+//! let body = config.redact_body(br#"{"pan":"4111111111111111"}"#).unwrap();
+//! assert_eq!(body, br#"{"pan":"************1111"}"#);
+//! ```
+
+use crate::scan::redact;
+
+/// Largest body inspected by default (1 MiB).
+pub const DEFAULT_MAX_BODY_BYTES: usize = 1 << 20;
+
+/// Which HTTP bodies to redact, as read from the filter's plugin
+/// configuration.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GatewayConfig {
+    /// Media types to inspect, e.g. `application/json`; `text/*` matches
+    /// every subtype.
+    pub content_types: Vec<String>,
+    /// Path prefixes to inspect; empty inspects every path.
+    pub paths: Vec<String>,
+    /// Whether to redact request bodies.
+    pub requests: bool,
+    /// Whether to redact response bodies.
+    pub responses: bool,
+    /// Largest body to inspect, in bytes.
+    pub max_body_bytes: usize,
+    /// Reject bodies over `max_body_bytes` (`413` for requests, `502` for
+    /// responses) instead of passing them through unredacted.
+    pub reject_oversized: bool,
+}
+
+impl Default for GatewayConfig {
+    fn default() -> Self {
+        Self {
+            content_types: vec![
+                "application/json".to_string(),
+                "application/x-www-form-urlencoded".to_string(),
+                "text/*".to_string(),
+            ],
+            paths: Vec::new(),
+            requests: true,
+            responses: true,
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            reject_oversized: false,
+        }
+    }
+}
+
+impl GatewayConfig {
+    /// Parses a JSON plugin configuration; missing fields keep their
+    /// defaults.
+    pub fn from_json(json: &[u8]) -> Result<Self, serde_json::Error> {
+        serde_json::from_slice(json)
+    }
+
+    /// Returns true if a body sent to `path` with these headers should be
+    /// redacted.
+    ///
+    /// The query string is ignored when matching paths. Compressed bodies
+    /// (any `Content-Encoding` but `identity`) are skipped, since the digits
+    /// can't be seen.
+    pub fn inspects(
+        &self,
+        path: &str,
+        content_type: Option<&str>,
+        content_encoding: Option<&str>,
+    ) -> bool {
+        let path = path.split('?').next().unwrap_or_default();
+        let path_matches =
+            self.paths.is_empty() || self.paths.iter().any(|p| path.starts_with(p.as_str()));
+        let identity = content_encoding
+            .is_none_or(|e| e.trim().is_empty() || e.trim().eq_ignore_ascii_case("identity"));
+        path_matches && identity && content_type.is_some_and(|t| self.matches_content_type(t))
+    }
+
+    /// Returns true if `content_type` (parameters allowed) is configured.
+    fn matches_content_type(&self, content_type: &str) -> bool {
+        let media = content_type.split(';').next().unwrap_or_default().trim();
+        self.content_types
+            .iter()
+            .any(|pattern| match pattern.strip_suffix("/*") {
+                Some(kind) => media
+                    .split_once('/')
+                    .is_some_and(|(k, _)| k.eq_ignore_ascii_case(kind)),
+                None => media.eq_ignore_ascii_case(pattern),
+            })
+    }
+
+    /// Returns true if a body of `len` bytes is small enough to inspect.
+    pub fn fits(&self, len: usize) -> bool {
+        len <= self.max_body_bytes
+    }
+
+    /// Returns `body` with card numbers masked, or `None` if nothing needed
+    /// masking.
+    ///
+    /// Bodies that are not UTF-8 are left alone.
+    pub fn redact_body(&self, body: &[u8]) -> Option<Vec<u8>> {
+        let text = std::str::from_utf8(body).ok()?;
+        let redacted = redact(text);
+        (redacted != text).then(|| redacted.into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gateway_config() {
+        let config = GatewayConfig::default();
+        assert!(config.inspects("/", Some("text/plain"), None));
+        assert!(config.inspects("/", Some("Application/JSON"), Some("identity")));
+        assert!(!config.inspects("/", Some("application/json"), Some("gzip")));
+        assert!(!config.inspects("/", Some("image/png"), None));
+        assert!(!config.inspects("/", None, None));
+        assert!(config.fits(DEFAULT_MAX_BODY_BYTES));
+        assert!(!config.fits(DEFAULT_MAX_BODY_BYTES + 1));
+
+        let form = b"name=Jane&card=4111-1111-1111-1111&cvv=123";
+        assert_eq!(
+            config.redact_body(form).unwrap(),
+            b"name=Jane&card=****-****-****-1111&cvv=123"
+        );
+        // Form encoding turns the spaces of a grouped number into '+'
+        let form = b"card_number=4111+1111+1111+1111&name=Jane+Doe";
+        assert_eq!(
+            config.redact_body(form).unwrap(),
+            b"card_number=****+****+****+1111&name=Jane+Doe"
+        );
+        let query = b"card_number=4111%201111%201111%201111";
+        assert_eq!(
+            config.redact_body(query).unwrap(),
+            b"card_number=****%20****%20****%201111"
+        );
+        assert_eq!(config.redact_body(b"order=42"), None);
+        assert_eq!(config.redact_body(b"\xff4111111111111111"), None);
+
+        let config = GatewayConfig::from_json(
+            br#"{"content_types": ["application/json"], "responses": false, "max_body_bytes": 10}"#,
+        )
+        .unwrap();
+        assert!(!config.inspects("/", Some("text/plain"), None));
+        assert!(config.requests && !config.responses);
+        assert!(!config.fits(11));
+        assert!(GatewayConfig::from_json(br#"{"path": []}"#).is_err());
+        assert_eq!(
+            GatewayConfig::from_json(b"{}").unwrap(),
+            GatewayConfig::default()
+        );
+    }
+}
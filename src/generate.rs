@@ -24,11 +24,24 @@
 //! Generated card numbers are mathematically valid (pass Luhn) but are not
 //! connected to real accounts. They should only be used for testing.
 
+use crate::bin::{BinInfo, BinRange};
+use crate::detect::detect_brand;
+use crate::error::ValidationError;
 use crate::luhn;
+use crate::validate::validate;
 use crate::CardBrand;
+use std::fmt;
 
 #[cfg(feature = "generate")]
-use rand::Rng;
+use crate::expiry::{Clock, ExpiryDate, SystemClock};
+#[cfg(feature = "generate")]
+use rand::rngs::StdRng;
+#[cfg(feature = "generate")]
+use rand::seq::SliceRandom;
+#[cfg(feature = "generate")]
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "generate")]
+use std::io::{self, Write};
 
 /// Default prefixes for each card brand.
 const VISA_PREFIX: &str = "4";
@@ -45,6 +58,12 @@ const VERVE_PREFIX: &str = "506";
 const ELO_PREFIX: &str = "509";
 const TROY_PREFIX: &str = "9792";
 const BCCARD_PREFIX: &str = "94";
+const INTERAC_PREFIX: &str = "4506";
+const DANKORT_PREFIX: &str = "5019";
+const BANCONTACT_PREFIX: &str = "6703";
+const UATP_PREFIX: &str = "1";
+const WEX_PREFIX: &str = "690046";
+const VOYAGER_PREFIX: &str = "7088";
 
 /// Default length for each card brand.
 const fn default_length(brand: CardBrand) -> usize {
@@ -63,6 +82,12 @@ const fn default_length(brand: CardBrand) -> usize {
         CardBrand::Elo => 16,
         CardBrand::Troy => 16,
         CardBrand::BcCard => 16,
+        CardBrand::Interac => 16,
+        CardBrand::Dankort => 16,
+        CardBrand::Bancontact => 16,
+        CardBrand::Uatp => 15,
+        CardBrand::Wex => 16,
+        CardBrand::Voyager => 19,
     }
 }
 
@@ -83,6 +108,12 @@ pub const fn prefix_for_brand(brand: CardBrand) -> &'static str {
         CardBrand::Elo => ELO_PREFIX,
         CardBrand::Troy => TROY_PREFIX,
         CardBrand::BcCard => BCCARD_PREFIX,
+        CardBrand::Interac => INTERAC_PREFIX,
+        CardBrand::Dankort => DANKORT_PREFIX,
+        CardBrand::Bancontact => BANCONTACT_PREFIX,
+        CardBrand::Uatp => UATP_PREFIX,
+        CardBrand::Wex => WEX_PREFIX,
+        CardBrand::Voyager => VOYAGER_PREFIX,
     }
 }
 
@@ -106,6 +137,35 @@ pub fn generate_card(brand: CardBrand) -> String {
     generate_card_with_prefix(prefix, length)
 }
 
+/// Generates a valid card number of the given brand and length, such as a
+/// 19-digit Visa or a 12-digit Maestro.
+///
+/// Requires the `generate` feature.
+///
+/// # Errors
+///
+/// Returns [`ValidationError::InvalidLengthForBrand`] if the brand isn't
+/// issued with `length` digits (see [`CardBrand::valid_lengths`]).
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::generate::generate_card_with_length;
+/// use cc_validator::CardBrand;
+///
+/// let card = generate_card_with_length(CardBrand::Discover, 18).unwrap();
+/// assert_eq!(card.len(), 18);
+/// assert!(cc_validator::is_valid(&card));
+/// assert!(generate_card_with_length(CardBrand::Amex, 16).is_err());
+/// ```
+#[cfg(feature = "generate")]
+pub fn generate_card_with_length(
+    brand: CardBrand,
+    length: usize,
+) -> Result<String, ValidationError> {
+    Ok(CardGenerator::for_length(brand, length)?.generate())
+}
+
 /// Generates a valid card number with the given prefix and length.
 ///
 /// Requires the `generate` feature.
@@ -164,6 +224,414 @@ pub fn generate_card_with_rng<R: Rng>(prefix: &str, length: usize, rng: &mut R)
     digits.iter().map(|&d| (b'0' + d) as char).collect()
 }
 
+/// Creates a seeded RNG for reproducible card generation.
+///
+/// The same seed always yields the same sequence of generated values,
+/// which makes it suitable for CI fixtures.
+///
+/// Requires the `generate` feature.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::generate::{generate_card_with_rng, seeded_rng};
+///
+/// let a = generate_card_with_rng("4", 16, &mut seeded_rng(42));
+/// let b = generate_card_with_rng("4", 16, &mut seeded_rng(42));
+/// assert_eq!(a, b);
+/// ```
+#[cfg(feature = "generate")]
+pub fn seeded_rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// Generates multiple valid card numbers reproducibly from a seed.
+///
+/// Requires the `generate` feature.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::generate::generate_cards_seeded;
+/// use cc_validator::CardBrand;
+///
+/// let first = generate_cards_seeded(CardBrand::Visa, 5, 7);
+/// let second = generate_cards_seeded(CardBrand::Visa, 5, 7);
+/// assert_eq!(first, second);
+/// ```
+#[cfg(feature = "generate")]
+pub fn generate_cards_seeded(brand: CardBrand, count: usize, seed: u64) -> Vec<String> {
+    let mut rng = seeded_rng(seed);
+    CardGenerator::new(brand).generate_many_with_rng(count, &mut rng)
+}
+
+/// A test vector produced by [`generate_corpus`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorpusEntry {
+    /// Brand the underlying card was generated for.
+    pub generated_for: CardBrand,
+    /// How the input was derived from the valid card: `"valid"`,
+    /// `"formatted"`, `"checksum"` (last digit changed), `"truncated"` (last
+    /// digit dropped) or `"character"` (a digit replaced by `x`).
+    pub variant: &'static str,
+    /// The string to validate.
+    pub input: String,
+    /// What [`validate`] returns for the input: the detected brand, or the
+    /// error code.
+    pub expected: Result<CardBrand, &'static str>,
+}
+
+/// Generates a reproducible corpus of valid and invalid inputs, `per_brand`
+/// cards for each brand in [`CardBrand::all`] with five variants each.
+///
+/// Expected results are recorded from [`validate`], so bindings checked
+/// against the corpus must agree with the Rust core exactly. The same seed
+/// always gives the same corpus.
+///
+/// Requires the `generate` feature.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::generate::generate_corpus;
+/// use cc_validator::CardBrand;
+///
+/// let corpus = generate_corpus(2, 42);
+/// assert_eq!(corpus.len(), CardBrand::all().len() * 2 * 5);
+/// assert_eq!(corpus, generate_corpus(2, 42));
+///
+/// let checksum = corpus.iter().find(|e| e.variant == "checksum").unwrap();
+/// assert_eq!(checksum.expected, Err("invalid_checksum"));
+/// ```
+#[cfg(feature = "generate")]
+pub fn generate_corpus(per_brand: usize, seed: u64) -> Vec<CorpusEntry> {
+    let mut rng = seeded_rng(seed);
+    let mut corpus = Vec::with_capacity(CardBrand::all().len() * per_brand * 5);
+
+    for &brand in CardBrand::all() {
+        for card in CardGenerator::new(brand).generate_many_with_rng(per_brand, &mut rng) {
+            let last = card.as_bytes()[card.len() - 1] - b'0';
+            let middle = card.len() / 2;
+            let variants = [
+                ("valid", card.clone()),
+                ("formatted", crate::format::format_card_number(&card)),
+                (
+                    "checksum",
+                    format!("{}{}", &card[..card.len() - 1], (last + 1) % 10),
+                ),
+                ("truncated", card[..card.len() - 1].to_string()),
+                (
+                    "character",
+                    format!("{}x{}", &card[..middle], &card[middle + 1..]),
+                ),
+            ];
+            for (variant, input) in variants {
+                let expected = validate(&input).map(|c| c.brand()).map_err(|e| e.code());
+                corpus.push(CorpusEntry {
+                    generated_for: brand,
+                    variant,
+                    input,
+                    expected,
+                });
+            }
+        }
+    }
+    corpus
+}
+
+/// The shape of a synthetic dataset built by [`generate_dataset`].
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::generate::DatasetSpec;
+/// use cc_validator::CardBrand;
+///
+/// let spec = DatasetSpec::new(1000)
+///     .brand(CardBrand::Visa, 60)
+///     .brand(CardBrand::Mastercard, 30)
+///     .brand(CardBrand::Amex, 10)
+///     .duplicate_rate(0.05)
+///     .invalid_rate(0.02)
+///     .seed(42);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatasetSpec {
+    count: usize,
+    brands: Vec<(CardBrand, u32)>,
+    duplicate_rate: f64,
+    invalid_rate: f64,
+    seed: u64,
+}
+
+impl DatasetSpec {
+    /// Creates a spec for `count` records with no brands, duplicates or
+    /// invalid records yet, and seed 0.
+    pub fn new(count: usize) -> Self {
+        Self {
+            count,
+            brands: Vec::new(),
+            duplicate_rate: 0.0,
+            invalid_rate: 0.0,
+            seed: 0,
+        }
+    }
+
+    /// Adds `brand` with a relative `weight`; weights need not sum to 100.
+    pub fn brand(mut self, brand: CardBrand, weight: u32) -> Self {
+        self.brands.push((brand, weight));
+        self
+    }
+
+    /// Sets the fraction (0.0-1.0) of records that repeat an earlier
+    /// record's number.
+    pub fn duplicate_rate(mut self, rate: f64) -> Self {
+        self.duplicate_rate = rate;
+        self
+    }
+
+    /// Sets the fraction (0.0-1.0) of distinct numbers that fail the Luhn
+    /// check.
+    pub fn invalid_rate(mut self, rate: f64) -> Self {
+        self.invalid_rate = rate;
+        self
+    }
+
+    /// Sets the seed; the same spec always gives the same dataset.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Splits `total` records between the brands by weight, handing the
+    /// rounding remainder to the largest fractions.
+    #[cfg(feature = "generate")]
+    fn allocate(&self, total: usize) -> Vec<usize> {
+        let weights: u64 = self.brands.iter().map(|&(_, w)| u64::from(w)).sum();
+        let shares: Vec<(usize, u64)> = self
+            .brands
+            .iter()
+            .map(|&(_, w)| {
+                let exact = total as u128 * u128::from(w);
+                (
+                    (exact / u128::from(weights)) as usize,
+                    (exact % u128::from(weights)) as u64,
+                )
+            })
+            .collect();
+        let mut counts: Vec<usize> = shares.iter().map(|&(n, _)| n).collect();
+        let mut order: Vec<usize> = (0..shares.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(shares[i].1));
+        let short = total - counts.iter().sum::<usize>();
+        for &i in order.iter().take(short) {
+            counts[i] += 1;
+        }
+        counts
+    }
+}
+
+/// One record of a dataset from [`generate_dataset`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatasetRecord {
+    /// The card number, digits only.
+    pub number: String,
+    /// Brand the number was generated for.
+    pub brand: CardBrand,
+    /// Whether the number passes validation; invalid numbers have their
+    /// check digit changed.
+    pub valid: bool,
+    /// Whether the number already appeared earlier in the dataset.
+    pub duplicate: bool,
+}
+
+/// Generates a shuffled synthetic dataset following `spec`, for load
+/// testing downstream systems with a realistic brand mix.
+///
+/// Brand counts follow the weights exactly (up to rounding). Distinct
+/// numbers never repeat by chance, so `duplicate_rate` of the records are
+/// the only repeats, and `invalid_rate` of the distinct numbers fail the
+/// Luhn check. Records are shuffled, and `duplicate` marks every
+/// occurrence of a number after its first.
+///
+/// Requires the `generate` feature.
+///
+/// # Panics
+///
+/// Panics if `spec` has no brand with a non-zero weight, or a rate outside
+/// 0.0-1.0.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::generate::{generate_dataset, DatasetSpec};
+/// use cc_validator::CardBrand;
+///
+/// let spec = DatasetSpec::new(100)
+///     .brand(CardBrand::Visa, 60)
+///     .brand(CardBrand::Mastercard, 30)
+///     .brand(CardBrand::Amex, 10)
+///     .duplicate_rate(0.1);
+/// let records = generate_dataset(&spec);
+/// assert_eq!(records.len(), 100);
+/// assert_eq!(records.iter().filter(|r| r.duplicate).count(), 10);
+/// assert_eq!(records.iter().filter(|r| r.brand == CardBrand::Amex).count(), 10);
+/// ```
+#[cfg(feature = "generate")]
+pub fn generate_dataset(spec: &DatasetSpec) -> Vec<DatasetRecord> {
+    assert!(
+        spec.brands.iter().any(|&(_, w)| w > 0),
+        "dataset spec needs a brand with a non-zero weight"
+    );
+    for rate in [spec.duplicate_rate, spec.invalid_rate] {
+        assert!(
+            (0.0..=1.0).contains(&rate),
+            "dataset rates must be between 0.0 and 1.0, got {}",
+            rate
+        );
+    }
+
+    let mut rng = seeded_rng(spec.seed);
+    let duplicates = (spec.count as f64 * spec.duplicate_rate).round() as usize;
+    let distinct = spec.count - duplicates.min(spec.count.saturating_sub(1));
+    let duplicates = spec.count - distinct;
+
+    // Each brand's share is distinct and its duplicates are drawn from it,
+    // so the mix holds with duplicates too
+    let mut records = Vec::with_capacity(spec.count);
+    for (&(brand, _), count) in spec.brands.iter().zip(spec.allocate(distinct)) {
+        let numbers = unique_of_brand(brand, count, &mut rng);
+        records.extend(numbers.into_iter().map(|number| DatasetRecord {
+            number,
+            brand,
+            valid: true,
+            duplicate: false,
+        }));
+    }
+
+    records.shuffle(&mut rng);
+    let invalid = (distinct as f64 * spec.invalid_rate).round() as usize;
+    for record in records.iter_mut().take(invalid) {
+        let last = record.number.pop().map_or(0, |c| c as u8 - b'0');
+        record.number.push(char::from(b'0' + (last + 1) % 10));
+        record.valid = false;
+    }
+
+    for _ in 0..duplicates {
+        let original = &records[rng.gen_range(0..distinct)];
+        records.push(original.clone());
+    }
+    records.shuffle(&mut rng);
+
+    let mut seen = std::collections::HashSet::with_capacity(distinct);
+    for record in &mut records {
+        record.duplicate = !seen.insert(record.number.clone());
+    }
+    records
+}
+
+/// Generates `count` distinct numbers that [`detect_brand`] reports as
+/// `brand`, skipping the ones a brand's default prefix shares with a more
+/// specific range (Interac's 4506 inside Visa's 4, say).
+#[cfg(feature = "generate")]
+fn unique_of_brand<R: Rng>(brand: CardBrand, count: usize, rng: &mut R) -> Vec<String> {
+    let generator = CardGenerator::new(brand);
+    let capacity = usize::try_from(generator.capacity()).unwrap_or(usize::MAX);
+    let mut attempt = count;
+    loop {
+        let mut numbers = generator.generate_unique_with_rng(attempt.min(capacity), rng);
+        numbers.retain(|n| {
+            let digits: Vec<u8> = n.bytes().map(|b| b - b'0').collect();
+            detect_brand(&digits) == Some(brand)
+        });
+        if numbers.len() >= count || attempt >= capacity {
+            numbers.truncate(count);
+            return numbers;
+        }
+        attempt = attempt.saturating_mul(2).saturating_add(16);
+    }
+}
+
+/// Writes `records` as CSV with a `number,brand,valid,duplicate` header;
+/// brands are written as their [`id`](CardBrand::id).
+///
+/// Requires the `generate` feature.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::generate::{generate_dataset, write_dataset_csv, DatasetSpec};
+/// use cc_validator::CardBrand;
+///
+/// let records = generate_dataset(&DatasetSpec::new(2).brand(CardBrand::Visa, 1));
+/// let mut csv = Vec::new();
+/// write_dataset_csv(&records, &mut csv).unwrap();
+/// let csv = String::from_utf8(csv).unwrap();
+/// assert_eq!(csv.lines().count(), 3);
+/// assert!(csv.lines().nth(1).unwrap().ends_with(",visa,true,false"));
+/// ```
+#[cfg(feature = "generate")]
+pub fn write_dataset_csv<W: Write>(records: &[DatasetRecord], mut writer: W) -> io::Result<()> {
+    writeln!(writer, "number,brand,valid,duplicate")?;
+    for record in records {
+        writeln!(
+            writer,
+            "{},{},{},{}",
+            record.number,
+            record.brand.id(),
+            record.valid,
+            record.duplicate
+        )?;
+    }
+    writer.flush()
+}
+
+/// Generates a random CVV with the correct length for the brand.
+///
+/// Requires the `generate` feature.
+#[cfg(feature = "generate")]
+pub fn generate_cvv_with_rng<R: Rng>(brand: CardBrand, rng: &mut R) -> String {
+    let length = crate::cvv::cvv_length_for_brand(brand);
+    (0..length)
+        .map(|_| (b'0' + rng.gen_range(0..10u8)) as char)
+        .collect()
+}
+
+/// Generates a random, not-yet-expired expiry date.
+///
+/// The date falls between next month and five years from now. A seeded RNG
+/// fixes the offset from the current month, not the date itself; use
+/// [`generate_expiry_with_clock`] for dates that never change.
+///
+/// Requires the `generate` feature.
+#[cfg(feature = "generate")]
+pub fn generate_expiry_with_rng<R: Rng>(rng: &mut R) -> ExpiryDate {
+    generate_expiry_with_clock(rng, &SystemClock)
+}
+
+/// Generates a random expiry date between one month and five years after
+/// the clock's current month.
+///
+/// With a [`FixedClock`](crate::expiry::FixedClock) and a seeded RNG the
+/// result never changes, so fixtures built from it don't rot.
+///
+/// Requires the `generate` feature.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::expiry::FixedClock;
+/// use cc_validator::generate::{generate_expiry_with_clock, seeded_rng};
+///
+/// let now = FixedClock::new(1, 2030).unwrap();
+/// let expiry = generate_expiry_with_clock(&mut seeded_rng(7), &now);
+/// assert!(!expiry.is_expired_at(&now));
+/// assert_eq!(expiry, generate_expiry_with_clock(&mut seeded_rng(7), &now));
+/// ```
+#[cfg(feature = "generate")]
+pub fn generate_expiry_with_clock<R: Rng>(rng: &mut R, clock: &impl Clock) -> ExpiryDate {
+    ExpiryDate::months_from_now(clock, rng.gen_range(1..=60u32))
+}
+
 /// Generates a valid card number deterministically (no randomness).
 ///
 /// This version doesn't require the `generate` feature and produces
@@ -222,50 +690,214 @@ pub fn generate_cards(brand: CardBrand, count: usize) -> Vec<String> {
     (0..count).map(|_| generate_card(brand)).collect()
 }
 
-/// Generates a test card that matches a specific pattern.
+/// Generates `count` distinct valid card numbers for the given brand.
 ///
-/// Pattern uses 'X' for random digits, e.g., "4111-XXXX-XXXX-XXXX".
-/// Dashes and spaces are stripped from the output.
+/// Unlike [`generate_cards`], which draws each number independently and can
+/// repeat, this never returns duplicates and works for any count up to the
+/// brand's [`CardGenerator::capacity`], e.g. to seed large test vaults.
 ///
 /// Requires the `generate` feature.
 ///
+/// # Panics
+///
+/// Panics if `count` exceeds the capacity.
+///
 /// # Example
 ///
 /// ```
-/// use cc_validator::generate::generate_from_pattern;
+/// use cc_validator::generate::generate_unique_cards;
+/// use cc_validator::CardBrand;
+/// use std::collections::HashSet;
 ///
-/// let card = generate_from_pattern("4111-XXXX-XXXX-XXXX");
-/// assert!(card.starts_with("4111"));
-/// assert_eq!(card.len(), 16);
+/// let cards = generate_unique_cards(CardBrand::Visa, 1000);
+/// assert_eq!(cards.iter().collect::<HashSet<_>>().len(), 1000);
 /// ```
 #[cfg(feature = "generate")]
-pub fn generate_from_pattern(pattern: &str) -> String {
-    let mut rng = rand::thread_rng();
+pub fn generate_unique_cards(brand: CardBrand, count: usize) -> Vec<String> {
+    CardGenerator::new(brand).generate_unique_with_rng(count, &mut rand::thread_rng())
+}
+
+/// Greatest common divisor, for picking steps that visit a whole space.
+#[cfg(feature = "generate")]
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Errors from pattern-based generation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternError {
+    /// The pattern contains something other than digits, `X`, `C` and
+    /// separators.
+    InvalidCharacter {
+        /// The position in the pattern (0-indexed).
+        position: usize,
+        /// The character that was found.
+        character: char,
+    },
+    /// The pattern has more than one `C` placeholder.
+    MultipleCheckDigits,
+    /// The generated number isn't a valid card, e.g. the prefix belongs to
+    /// no known brand or the length doesn't suit the brand.
+    Invalid(ValidationError),
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCharacter {
+                position,
+                character,
+            } => write!(
+                f,
+                "invalid pattern character '{}' at position {} (use digits, X, C and separators)",
+                character.escape_default(),
+                position
+            ),
+            Self::MultipleCheckDigits => {
+                write!(f, "pattern has more than one check digit placeholder 'C'")
+            }
+            Self::Invalid(e) => write!(f, "pattern does not produce a valid card: {}", e),
+        }
+    }
+}
 
-    // Extract digits and X placeholders
-    let mut digits: Vec<u8> = Vec::new();
-    let mut has_check_placeholder = false;
+impl std::error::Error for PatternError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Invalid(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Fills a pattern using `fill` for `X` placeholders, then solves for the
+/// check digit and validates the result.
+fn fill_pattern(pattern: &str, mut fill: impl FnMut() -> u8) -> Result<String, PatternError> {
+    let mut digits: Vec<u8> = Vec::with_capacity(pattern.len());
+    let mut check: Option<usize> = None;
+    let mut last_random: Option<usize> = None;
 
-    for c in pattern.chars() {
+    for (position, c) in pattern.chars().enumerate() {
         match c {
             '0'..='9' => digits.push((c as u8) - b'0'),
             'X' | 'x' => {
-                digits.push(rng.gen_range(0..10));
-                has_check_placeholder = true;
+                last_random = Some(digits.len());
+                digits.push(fill());
+            }
+            'C' | 'c' => {
+                if check.is_some() {
+                    return Err(PatternError::MultipleCheckDigits);
+                }
+                check = Some(digits.len());
+                digits.push(0);
+            }
+            ' ' | '-' | '.' => {}
+            _ => {
+                return Err(PatternError::InvalidCharacter {
+                    position,
+                    character: c,
+                })
             }
-            ' ' | '-' | '.' => continue, // Skip separators
-            _ => continue,
         }
     }
 
-    // If the last digit was a placeholder, recalculate check digit
-    if has_check_placeholder && !digits.is_empty() {
-        digits.pop(); // Remove the random last digit
-        let check_digit = luhn::generate_check_digit(&digits);
-        digits.push(check_digit);
+    // Without a C the last X carries the check digit; any position works
+    // because doubling permutes the digits 0-9
+    if let Some(index) = check.or(last_random) {
+        digits[index] = 0;
+        let doubled = (digits.len() - 1 - index) % 2 == 1;
+        let sum = luhn::compute_checksum(&digits);
+        digits[index] = (0..10)
+            .find(|&d| {
+                let weight = match (doubled, d) {
+                    (false, d) => d,
+                    (true, 0..=4) => d * 2,
+                    (true, d) => d * 2 - 9,
+                };
+                (sum + weight as u32).is_multiple_of(10)
+            })
+            .unwrap_or(0);
     }
 
-    digits.iter().map(|&d| (b'0' + d) as char).collect()
+    let number: String = digits.iter().map(|&d| (b'0' + d) as char).collect();
+    validate(&number).map_err(PatternError::Invalid)?;
+    Ok(number)
+}
+
+/// Generates a test card that matches a specific pattern.
+///
+/// The pattern is made of:
+///
+/// - digits, kept as they are
+/// - `X`, a random digit
+/// - `C`, the check digit (at most one, at any position)
+/// - spaces, dashes and dots, which are stripped from the output
+///
+/// Without a `C`, the last `X` becomes the check digit. The result must be
+/// a valid card for a known brand, so the prefix and length have to fit
+/// one.
+///
+/// Requires the `generate` feature.
+///
+/// # Errors
+///
+/// Returns a [`PatternError`] for unknown characters, repeated `C`s, or a
+/// pattern that can't produce a valid card (e.g. only fixed digits that
+/// fail the Luhn check, or an unknown prefix).
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::generate::generate_from_pattern;
+///
+/// let card = generate_from_pattern("4111-XXXX-XXXX-XXXX").unwrap();
+/// assert!(card.starts_with("4111"));
+/// assert_eq!(card.len(), 16);
+///
+/// // Check digit in the middle, fixed last four
+/// let card = generate_from_pattern("4111-XXXC-XXXX-1234").unwrap();
+/// assert!(card.ends_with("1234"));
+///
+/// assert!(generate_from_pattern("4111-XXXX").is_err());
+/// ```
+#[cfg(feature = "generate")]
+pub fn generate_from_pattern(pattern: &str) -> Result<String, PatternError> {
+    generate_from_pattern_with_rng(pattern, &mut rand::thread_rng())
+}
+
+/// Generates a test card matching a pattern using a provided RNG.
+///
+/// See [`generate_from_pattern`] for the pattern syntax.
+///
+/// Requires the `generate` feature.
+#[cfg(feature = "generate")]
+pub fn generate_from_pattern_with_rng<R: Rng>(
+    pattern: &str,
+    rng: &mut R,
+) -> Result<String, PatternError> {
+    fill_pattern(pattern, || rng.gen_range(0..10))
+}
+
+/// Generates a test card matching a pattern, filling every `X` with `0`.
+///
+/// See [`generate_from_pattern`] for the pattern syntax. Doesn't require the
+/// `generate` feature.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::generate::generate_from_pattern_deterministic;
+///
+/// assert_eq!(
+///     generate_from_pattern_deterministic("5500 XXXX XXXX XXXX").unwrap(),
+///     "5500000000000004"
+/// );
+/// ```
+pub fn generate_from_pattern_deterministic(pattern: &str) -> Result<String, PatternError> {
+    fill_pattern(pattern, || 0)
 }
 
 /// Card generator builder for more complex generation scenarios.
@@ -273,6 +905,8 @@ pub fn generate_from_pattern(pattern: &str) -> String {
 pub struct CardGenerator {
     prefix: String,
     length: usize,
+    /// Number of consecutive prefixes, starting at `prefix`, to draw from.
+    bins: u64,
 }
 
 impl CardGenerator {
@@ -281,26 +915,117 @@ impl CardGenerator {
         Self {
             prefix: prefix_for_brand(brand).to_string(),
             length: default_length(brand),
+            bins: 1,
         }
     }
 
+    /// Creates a generator for cards of `brand` with `length` digits,
+    /// checking that the brand is issued with that length.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValidationError::InvalidLengthForBrand`] if `length` isn't
+    /// one of the brand's [`valid_lengths`](CardBrand::valid_lengths).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::generate::CardGenerator;
+    /// use cc_validator::CardBrand;
+    ///
+    /// let card = CardGenerator::for_length(CardBrand::Visa, 19)
+    ///     .unwrap()
+    ///     .generate_deterministic();
+    /// assert_eq!(card.len(), 19);
+    /// assert!(CardGenerator::for_length(CardBrand::Visa, 15).is_err());
+    /// ```
+    pub fn for_length(brand: CardBrand, length: usize) -> Result<Self, ValidationError> {
+        if !brand.is_valid_length(length) {
+            return Err(ValidationError::InvalidLengthForBrand {
+                brand,
+                length,
+                valid_lengths: brand.valid_lengths(),
+            });
+        }
+        Ok(Self::new(brand).length(length))
+    }
+
     /// Creates a new card generator with a custom prefix.
     pub fn with_prefix(prefix: impl Into<String>) -> Self {
         Self {
             prefix: prefix.into(),
             length: 16,
+            bins: 1,
+        }
+    }
+
+    /// Creates a generator drawing uniformly from every 8-digit BIN in a
+    /// canonical [`BinRange`], e.g. one entry of a BIN database, to
+    /// simulate traffic for a particular issuer.
+    ///
+    /// The length defaults to that of the brand the range belongs to, or 16.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty or not in the canonical 8-digit space.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::bin::BinRange;
+    /// use cc_validator::generate::CardGenerator;
+    ///
+    /// let range = BinRange::from_bounds("45320151", "45320199").unwrap();
+    /// let gen = CardGenerator::from_bin_range(&range);
+    /// let card = gen.generate_deterministic();
+    /// assert!(card.starts_with("45320151"));
+    /// assert!(cc_validator::is_valid(&card));
+    /// assert_eq!(gen.capacity(), 49 * 10_000_000);
+    /// ```
+    pub fn from_bin_range(range: &BinRange) -> Self {
+        assert!(
+            range.start <= range.end && range.end < 100_000_000,
+            "BIN range {}..={} is not a canonical 8-digit range",
+            range.start,
+            range.end
+        );
+        let prefix = format!("{:08}", range.start);
+        let digits: Vec<u8> = prefix.bytes().map(|b| b - b'0').collect();
+        Self {
+            length: detect_brand(&digits).map_or(16, default_length),
+            prefix,
+            bins: range.end - range.start + 1,
         }
     }
 
+    /// Creates a generator for the BIN of a database entry (see
+    /// [`from_bin_range`](Self::from_bin_range)).
+    ///
+    /// Returns `None` if the entry's BIN has no digits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::bin::BinInfo;
+    /// use cc_validator::generate::CardGenerator;
+    ///
+    /// let gen = CardGenerator::from_bin_info(&BinInfo::with_bin("552233")).unwrap();
+    /// assert!(gen.generate_deterministic().starts_with("55223300"));
+    /// ```
+    pub fn from_bin_info(info: &BinInfo) -> Option<Self> {
+        BinRange::from_prefix(&info.bin).map(|range| Self::from_bin_range(&range))
+    }
+
     /// Sets the card length.
     pub fn length(mut self, length: usize) -> Self {
         self.length = length;
         self
     }
 
-    /// Sets the prefix.
+    /// Sets the prefix, replacing any BIN range.
     pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
         self.prefix = prefix.into();
+        self.bins = 1;
         self
     }
 
@@ -320,6 +1045,125 @@ impl CardGenerator {
     pub fn generate_many(&self, count: usize) -> Vec<String> {
         (0..count).map(|_| self.generate()).collect()
     }
+
+    /// Generates a card number using the provided RNG.
+    #[cfg(feature = "generate")]
+    pub fn generate_with_rng<R: Rng>(&self, rng: &mut R) -> String {
+        if self.bins == 1 {
+            return generate_card_with_rng(&self.prefix, self.length, rng);
+        }
+        let prefix = self.nth_prefix(rng.gen_range(0..self.bins));
+        generate_card_with_rng(&prefix, self.length, rng)
+    }
+
+    /// Generates multiple card numbers using the provided RNG.
+    #[cfg(feature = "generate")]
+    pub fn generate_many_with_rng<R: Rng>(&self, count: usize, rng: &mut R) -> Vec<String> {
+        (0..count).map(|_| self.generate_with_rng(rng)).collect()
+    }
+
+    /// Returns how many distinct card numbers the generator can produce: one
+    /// per prefix and combination of the digits between the prefix and the
+    /// check digit (saturating at `u64::MAX`).
+    pub fn capacity(&self) -> u64 {
+        let free = self.length.saturating_sub(self.prefix_len() + 1);
+        10u64
+            .checked_pow(free as u32)
+            .and_then(|n| n.checked_mul(self.bins))
+            .unwrap_or(u64::MAX)
+    }
+
+    /// Generates `count` distinct card numbers using the provided RNG (see
+    /// [`generate_unique_cards`]).
+    ///
+    /// The numbers walk the generator's number space in a random affine
+    /// order, so they are unique and spread out but not independent of one
+    /// another; use them as test data, not where unpredictability matters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` exceeds [`capacity`](Self::capacity) or the
+    /// prefix is not shorter than the length.
+    #[cfg(feature = "generate")]
+    pub fn generate_unique_with_rng<R: Rng>(&self, count: usize, rng: &mut R) -> Vec<String> {
+        let prefix: Vec<u8> = self
+            .prefix
+            .chars()
+            .filter_map(|c| c.to_digit(10).map(|d| d as u8))
+            .collect();
+        assert!(
+            prefix.len() < self.length,
+            "Prefix length must be less than total length"
+        );
+        let free = self.length - prefix.len() - 1;
+        let per_bin = 10u128.pow(free as u32);
+        // Computed exactly rather than through capacity(), which saturates
+        let space = per_bin * u128::from(self.bins);
+        assert!(
+            count as u128 <= space,
+            "cannot generate {} unique cards from a space of {}",
+            count,
+            space
+        );
+
+        // i -> (step * i + offset) mod space is a bijection whenever step is
+        // coprime to space, so no value repeats.
+        let offset = rng.gen_range(0..space);
+        let step = loop {
+            let step = rng.gen_range(0..space);
+            if space == 1 || gcd(step, space) == 1 {
+                break step;
+            }
+        };
+
+        let mut digits = Vec::with_capacity(self.length);
+        let mut next = offset;
+        (0..count)
+            .map(|_| {
+                let value = next;
+                // Both terms are below space, which is well under u128::MAX / 2
+                next = (next + step) % space;
+                let (bin, mut value) = (value / per_bin, value % per_bin);
+                digits.clear();
+                if bin == 0 {
+                    digits.extend_from_slice(&prefix);
+                } else {
+                    digits.extend(self.nth_prefix(bin as u64).bytes().map(|b| b - b'0'));
+                }
+                digits.resize(prefix.len() + free, 0);
+                for d in digits[prefix.len()..].iter_mut().rev() {
+                    *d = (value % 10) as u8;
+                    value /= 10;
+                }
+                digits.push(luhn::generate_check_digit(&digits));
+                digits.iter().map(|&d| (b'0' + d) as char).collect()
+            })
+            .collect()
+    }
+
+    fn prefix_len(&self) -> usize {
+        self.prefix.chars().filter(|c| c.is_ascii_digit()).count()
+    }
+
+    /// Returns the prefix `n` BINs after the first, zero-padded to the same
+    /// width.
+    #[cfg(feature = "generate")]
+    fn nth_prefix(&self, n: u64) -> String {
+        let first: u64 = self.prefix.parse().unwrap_or(0);
+        format!("{:0width$}", first + n, width = self.prefix.len())
+    }
+
+    /// Generates a card number together with an expiry date relative to
+    /// `clock` (see [`generate_expiry_with_clock`]).
+    #[cfg(feature = "generate")]
+    pub fn generate_with_expiry<R: Rng>(
+        &self,
+        rng: &mut R,
+        clock: &impl Clock,
+    ) -> (String, ExpiryDate) {
+        let number = self.generate_with_rng(rng);
+        (number, generate_expiry_with_clock(rng, clock))
+    }
 }
 
 #[cfg(test)]
@@ -393,26 +1237,19 @@ mod tests {
         assert!(crate::passes_luhn(&card));
     }
 
+    #[test]
+    fn test_card_generator_from_bin_info() {
+        let gen = CardGenerator::from_bin_info(&BinInfo::with_bin("378282")).unwrap();
+        let card = gen.generate_deterministic();
+        assert_eq!(card.len(), 15);
+        assert!(card.starts_with("37828200"));
+        assert!(is_valid(&card));
+        assert!(CardGenerator::from_bin_info(&BinInfo::new()).is_none());
+    }
+
     #[test]
     fn test_prefix_for_all_brands() {
-        let brands = [
-            CardBrand::Visa,
-            CardBrand::Mastercard,
-            CardBrand::Amex,
-            CardBrand::Discover,
-            CardBrand::DinersClub,
-            CardBrand::Jcb,
-            CardBrand::UnionPay,
-            CardBrand::Maestro,
-            CardBrand::Mir,
-            CardBrand::RuPay,
-            CardBrand::Verve,
-            CardBrand::Elo,
-            CardBrand::Troy,
-            CardBrand::BcCard,
-        ];
-
-        for brand in brands {
+        for &brand in CardBrand::all() {
             let card = generate_card_deterministic(brand);
             assert!(
                 is_valid(&card) || crate::passes_luhn(&card),
@@ -423,6 +1260,85 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_card_generator_for_length() {
+        for &brand in CardBrand::all() {
+            for &length in brand.valid_lengths() {
+                let card = CardGenerator::for_length(brand, length as usize)
+                    .unwrap()
+                    .generate_deterministic();
+                assert_eq!(card.len(), length as usize);
+                assert_eq!(
+                    validate(&card).map(|c| c.brand()),
+                    Ok(brand),
+                    "{} at {} digits: {}",
+                    brand,
+                    length,
+                    card
+                );
+            }
+        }
+        assert_eq!(
+            CardGenerator::for_length(CardBrand::Discover, 15).unwrap_err(),
+            ValidationError::InvalidLengthForBrand {
+                brand: CardBrand::Discover,
+                length: 15,
+                valid_lengths: CardBrand::Discover.valid_lengths(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_pattern_check_digit_position() {
+        let card = generate_from_pattern_deterministic("4111 111C 1111 1111").unwrap();
+        assert_eq!(card, "4111111111111111");
+
+        let card = generate_from_pattern_deterministic("C111111111111111").unwrap();
+        assert_eq!(card, "4111111111111111");
+
+        // Without C the last X is solved, even before fixed digits
+        let card = generate_from_pattern_deterministic("4111-1111-1111-X111").unwrap();
+        assert_eq!(card, "4111111111111111");
+
+        // Fully fixed numbers are only checked
+        assert_eq!(
+            generate_from_pattern_deterministic("4111111111111111").unwrap(),
+            "4111111111111111"
+        );
+        assert_eq!(
+            generate_from_pattern_deterministic("4111111111111112"),
+            Err(PatternError::Invalid(ValidationError::InvalidChecksum))
+        );
+    }
+
+    #[test]
+    fn test_pattern_errors() {
+        assert_eq!(
+            generate_from_pattern_deterministic("4111-XXXX-XXXX-XXXZ"),
+            Err(PatternError::InvalidCharacter {
+                position: 18,
+                character: 'Z'
+            })
+        );
+        assert_eq!(
+            generate_from_pattern_deterministic("4111-XXXX-XXXC-XXXC"),
+            Err(PatternError::MultipleCheckDigits)
+        );
+        assert_eq!(
+            generate_from_pattern_deterministic("9999-XXXX-XXXX-XXXX"),
+            Err(PatternError::Invalid(ValidationError::UnknownBrand))
+        );
+        assert!(matches!(
+            generate_from_pattern_deterministic("3782-XXXXXX-XXXXXX"),
+            Err(PatternError::Invalid(
+                ValidationError::InvalidLengthForBrand { .. }
+            ))
+        ));
+        assert!(PatternError::MultipleCheckDigits
+            .to_string()
+            .contains("'C'"));
+    }
+
     #[cfg(feature = "generate")]
     mod random_tests {
         use super::*;
@@ -454,10 +1370,19 @@ mod tests {
 
         #[test]
         fn test_generate_from_pattern() {
-            let card = generate_from_pattern("4111-XXXX-XXXX-XXXX");
+            let card = generate_from_pattern("4111-XXXX-XXXX-XXXX").unwrap();
             assert!(card.starts_with("4111"));
             assert_eq!(card.len(), 16);
             assert!(is_valid(&card));
+
+            let mut rng = seeded_rng(7);
+            for _ in 0..50 {
+                let card = generate_from_pattern_with_rng("XXXX-C000-0000-0000", &mut rng);
+                match card {
+                    Ok(card) => assert!(is_valid(&card)),
+                    Err(e) => assert!(matches!(e, PatternError::Invalid(_))),
+                }
+            }
         }
 
         #[test]
@@ -471,6 +1396,200 @@ mod tests {
             assert!(unique.len() >= 90);
         }
 
+        #[test]
+        fn test_generate_unique_cards() {
+            let cards = generate_unique_cards(CardBrand::Amex, 500);
+            let unique: std::collections::HashSet<_> = cards.iter().collect();
+            assert_eq!(unique.len(), 500);
+            assert!(cards.iter().all(|c| is_valid(c) && c.starts_with("34")));
+
+            // The whole space of a narrow prefix
+            let gen = CardGenerator::with_prefix("41111111111111");
+            assert_eq!(gen.capacity(), 10);
+            let mut all = gen.generate_unique_with_rng(10, &mut seeded_rng(3));
+            all.sort();
+            all.dedup();
+            assert_eq!(all.len(), 10);
+            assert!(all.iter().all(|c| is_valid(c)));
+        }
+
+        #[test]
+        #[should_panic(expected = "cannot generate 11 unique cards")]
+        fn test_generate_unique_cards_over_capacity() {
+            CardGenerator::with_prefix("41111111111111")
+                .generate_unique_with_rng(11, &mut seeded_rng(3));
+        }
+
+        #[test]
+        fn test_generate_from_bin_range() {
+            let range = BinRange::from_bounds("45320151", "45320153").unwrap();
+            let gen = CardGenerator::from_bin_range(&range);
+            let mut rng = seeded_rng(5);
+            let mut seen = std::collections::HashSet::new();
+            for _ in 0..200 {
+                let card = gen.generate_with_rng(&mut rng);
+                assert!(is_valid(&card));
+                let bin: u64 = card[..8].parse().unwrap();
+                assert!(range.contains(bin));
+                seen.insert(bin);
+            }
+            assert_eq!(seen.len(), 3);
+
+            // Unique generation covers every BIN of the range
+            let gen = CardGenerator::from_bin_range(&range).length(10);
+            assert_eq!(gen.capacity(), 30);
+            let mut all = gen.generate_unique_with_rng(30, &mut rng);
+            all.sort();
+            all.dedup();
+            assert_eq!(all.len(), 30);
+            assert!(all.iter().all(|c| crate::passes_luhn(c)));
+            assert!(all[29].starts_with("453201539"));
+        }
+
+        #[test]
+        fn test_generate_unique_from_uneven_bin_range() {
+            // 7 BINs: the step has to be coprime to 7 as well as to 10
+            let range = BinRange::from_bounds("45320151", "45320157").unwrap();
+            let gen = CardGenerator::from_bin_range(&range).length(12);
+            assert_eq!(gen.capacity(), 7000);
+            for seed in 0..5 {
+                let cards = gen.generate_unique_with_rng(7000, &mut seeded_rng(seed));
+                let unique: std::collections::HashSet<_> = cards.iter().collect();
+                assert_eq!(unique.len(), 7000);
+            }
+        }
+
+        #[test]
+        fn test_generate_cards_seeded_is_reproducible() {
+            let first = generate_cards_seeded(CardBrand::Mastercard, 20, 1234);
+            let second = generate_cards_seeded(CardBrand::Mastercard, 20, 1234);
+            assert_eq!(first, second);
+            assert!(first.iter().all(|c| is_valid(c)));
+
+            let other = generate_cards_seeded(CardBrand::Mastercard, 20, 4321);
+            assert_ne!(first, other);
+        }
+
+        #[test]
+        fn test_generate_dataset() {
+            let spec = DatasetSpec::new(1000)
+                .brand(CardBrand::Visa, 60)
+                .brand(CardBrand::Mastercard, 30)
+                .brand(CardBrand::Amex, 10)
+                .duplicate_rate(0.05)
+                .invalid_rate(0.02)
+                .seed(7);
+            let records = generate_dataset(&spec);
+            assert_eq!(records, generate_dataset(&spec));
+            assert_eq!(records.len(), 1000);
+
+            let duplicates = records.iter().filter(|r| r.duplicate).count();
+            assert_eq!(duplicates, 50);
+            let distinct: std::collections::HashSet<_> =
+                records.iter().map(|r| &r.number).collect();
+            assert_eq!(distinct.len(), 950);
+            let invalid = records.iter().filter(|r| !r.valid && !r.duplicate);
+            assert_eq!(invalid.count(), 19);
+            for record in &records {
+                assert_eq!(is_valid(&record.number), record.valid, "{:?}", record);
+                let digits: Vec<u8> = record.number.bytes().map(|b| b - b'0').collect();
+                assert_eq!(detect_brand(&digits), Some(record.brand));
+            }
+
+            // Brand shares hold for the distinct numbers exactly
+            let amex = records
+                .iter()
+                .filter(|r| r.brand == CardBrand::Amex && !r.duplicate)
+                .count();
+            assert_eq!(amex, 95);
+            assert_ne!(records, generate_dataset(&spec.clone().seed(8)));
+        }
+
+        #[test]
+        #[should_panic(expected = "non-zero weight")]
+        fn test_generate_dataset_needs_brand() {
+            generate_dataset(&DatasetSpec::new(10).brand(CardBrand::Visa, 0));
+        }
+
+        #[test]
+        fn test_generate_dataset_skips_nested_ranges() {
+            // Maestro's 50 holds Verve, Elo and Dankort ranges
+            let spec = DatasetSpec::new(200).brand(CardBrand::Maestro, 1);
+            let records = generate_dataset(&spec);
+            assert_eq!(records.len(), 200);
+            for record in &records {
+                let card = crate::validate(&record.number).unwrap();
+                assert_eq!(card.brand(), CardBrand::Maestro);
+            }
+        }
+
+        #[test]
+        fn test_generate_corpus() {
+            let corpus = generate_corpus(3, 9);
+            assert_eq!(corpus.len(), CardBrand::all().len() * 15);
+            for entry in &corpus {
+                match entry.variant {
+                    "valid" | "formatted" => assert!(entry.expected.is_ok(), "{:?}", entry),
+                    "checksum" => assert_eq!(entry.expected, Err("invalid_checksum")),
+                    "character" => assert_eq!(entry.expected, Err("invalid_character")),
+                    // A dropped digit can still leave a valid number
+                    _ => {}
+                }
+            }
+            assert_ne!(corpus, generate_corpus(3, 10));
+        }
+
+        #[test]
+        fn test_generate_cvv_with_rng() {
+            let mut rng = seeded_rng(1);
+            let cvv = generate_cvv_with_rng(CardBrand::Amex, &mut rng);
+            assert_eq!(cvv.len(), 4);
+            assert!(crate::cvv::is_valid_cvv_for_brand(&cvv, CardBrand::Amex));
+
+            let cvv = generate_cvv_with_rng(CardBrand::Visa, &mut rng);
+            assert_eq!(cvv.len(), 3);
+        }
+
+        #[test]
+        fn test_generate_expiry_with_rng() {
+            let mut rng = seeded_rng(99);
+            for _ in 0..50 {
+                let exp = generate_expiry_with_rng(&mut rng);
+                assert!(!exp.is_expired());
+                assert!(!exp.is_too_far_future(6));
+            }
+        }
+
+        #[test]
+        fn test_seeded_expiry_follows_clock() {
+            // The seed fixes the offset; the date moves with the current month
+            let jan = crate::expiry::FixedClock::new(1, 2030).unwrap();
+            let feb = crate::expiry::FixedClock::new(2, 2030).unwrap();
+            let a = generate_expiry_with_clock(&mut seeded_rng(7), &jan);
+            let b = generate_expiry_with_clock(&mut seeded_rng(7), &feb);
+            assert_ne!(a, b);
+            assert_eq!(
+                a.months_until_expiry_at(&jan),
+                b.months_until_expiry_at(&feb)
+            );
+        }
+
+        #[test]
+        fn test_generate_with_expiry_fixed_clock() {
+            let now = crate::expiry::FixedClock::new(12, 2040).unwrap();
+            let gen = CardGenerator::new(CardBrand::Visa);
+            let mut rng = seeded_rng(5);
+            for _ in 0..50 {
+                let (card, exp) = gen.generate_with_expiry(&mut rng, &now);
+                assert!(is_valid(&card));
+                assert!(!exp.is_expired_at(&now));
+                assert!(!exp.is_too_far_future_at(5, &now));
+                assert!(exp.year() >= 2041);
+            }
+            let first = gen.generate_with_expiry(&mut seeded_rng(5), &now);
+            assert_eq!(first, gen.generate_with_expiry(&mut seeded_rng(5), &now));
+        }
+
         #[test]
         fn test_card_generator_random() {
             let gen = CardGenerator::new(CardBrand::Mastercard);
@@ -0,0 +1,497 @@
+//! Localized error messages.
+//!
+//! `Display` on [`ValidationError`], [`CvvError`] and [`ExpiryError`] is
+//! always English so logs stay uniform. For user-facing text, the
+//! [`Localize`] trait renders the same messages in English, Spanish,
+//! Portuguese, German or French, with the error's details filled in (an
+//! error code alone loses them).
+//!
+//! The locale is either passed per call or taken from a process-wide
+//! default set with [`set_locale`].
+//!
+//! # Feature
+//!
+//! Requires the `i18n` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use cc_validator::i18n::{Locale, Localize};
+//! use cc_validator::validate;
+//!
+//! let err = validate("4111-1111-1111-1112").unwrap_err();
+//! assert_eq!(
+//!     err.localize(Locale::Es),
+//!     "suma de verificación no válida (falló la comprobación de Luhn): verifique el número de tarjeta"
+//! );
+//!
+//! let locale = Locale::from_tag("fr-CA").unwrap();
+//! assert_eq!(locale, Locale::Fr);
+//! ```
+
+use crate::cvv::CvvError;
+use crate::error::ValidationError;
+use crate::expiry::ExpiryError;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// A supported message language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(u8)]
+pub enum Locale {
+    /// English (same text as `Display`).
+    #[default]
+    En,
+    /// Spanish.
+    Es,
+    /// Portuguese.
+    Pt,
+    /// German.
+    De,
+    /// French.
+    Fr,
+}
+
+impl Locale {
+    /// All supported locales.
+    pub const ALL: [Locale; 5] = [Locale::En, Locale::Es, Locale::Pt, Locale::De, Locale::Fr];
+
+    /// Parses a language tag such as `es`, `pt-BR` or `de_AT`.
+    ///
+    /// Only the primary language subtag is used; regional variants fall back
+    /// to the base language. Returns `None` for unsupported languages.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.split(['-', '_']).next().unwrap_or("");
+        match primary.to_ascii_lowercase().as_str() {
+            "en" => Some(Locale::En),
+            "es" => Some(Locale::Es),
+            "pt" => Some(Locale::Pt),
+            "de" => Some(Locale::De),
+            "fr" => Some(Locale::Fr),
+            _ => None,
+        }
+    }
+
+    /// Returns the two-letter language code.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+            Locale::Pt => "pt",
+            Locale::De => "de",
+            Locale::Fr => "fr",
+        }
+    }
+
+    /// Word joining alternatives, as in "16 or 19 digits".
+    fn or(&self) -> &'static str {
+        match self {
+            Locale::En => "or",
+            Locale::Es => "o",
+            Locale::Pt | Locale::Fr => "ou",
+            Locale::De => "oder",
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        Self::ALL.get(value as usize).copied().unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.tag())
+    }
+}
+
+static LOCALE: AtomicU8 = AtomicU8::new(Locale::En as u8);
+
+/// Sets the process-wide default locale used by [`Localize::localized`].
+pub fn set_locale(locale: Locale) {
+    LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+/// Returns the process-wide default locale (English unless changed).
+pub fn locale() -> Locale {
+    Locale::from_u8(LOCALE.load(Ordering::Relaxed))
+}
+
+/// Errors with messages in several languages.
+pub trait Localize: fmt::Display {
+    /// Returns the message in `locale`.
+    fn localize(&self, locale: Locale) -> String;
+
+    /// Returns the message in the default locale (see [`set_locale`]).
+    fn localized(&self) -> String {
+        self.localize(locale())
+    }
+}
+
+impl Localize for ValidationError {
+    fn localize(&self, locale: Locale) -> String {
+        use Locale::*;
+
+        if locale == En {
+            return self.to_string();
+        }
+        match self {
+            Self::Empty => match locale {
+                Es => "el número de tarjeta está vacío".into(),
+                Pt => "o número do cartão está vazio".into(),
+                De => "die Kartennummer ist leer".into(),
+                _ => "le numéro de carte est vide".into(),
+            },
+            Self::TooShort { length, minimum } => match locale {
+                Es => format!(
+                    "número de tarjeta demasiado corto: tiene {} dígitos, el mínimo es {}",
+                    length, minimum
+                ),
+                Pt => format!(
+                    "número do cartão muito curto: tem {} dígitos, o mínimo é {}",
+                    length, minimum
+                ),
+                De => format!(
+                    "Kartennummer zu kurz: {} Ziffern, mindestens {} erforderlich",
+                    length, minimum
+                ),
+                _ => format!(
+                    "numéro de carte trop court : {} chiffres, minimum {}",
+                    length, minimum
+                ),
+            },
+            Self::TooLong { length, maximum } => match locale {
+                Es => format!(
+                    "número de tarjeta demasiado largo: tiene {} dígitos, el máximo es {}",
+                    length, maximum
+                ),
+                Pt => format!(
+                    "número do cartão muito longo: tem {} dígitos, o máximo é {}",
+                    length, maximum
+                ),
+                De => format!(
+                    "Kartennummer zu lang: {} Ziffern, höchstens {} erlaubt",
+                    length, maximum
+                ),
+                _ => format!(
+                    "numéro de carte trop long : {} chiffres, maximum {}",
+                    length, maximum
+                ),
+            },
+            Self::InvalidCharacter {
+                position,
+                character,
+                ..
+            } => {
+                let c = character.escape_default();
+                match locale {
+                    Es => format!(
+                        "carácter no válido '{}' en la posición {} (solo se permiten dígitos, espacios y guiones)",
+                        c, position
+                    ),
+                    Pt => format!(
+                        "caractere inválido '{}' na posição {} (apenas dígitos, espaços e hífens são permitidos)",
+                        c, position
+                    ),
+                    De => format!(
+                        "ungültiges Zeichen '{}' an Position {} (nur Ziffern, Leerzeichen und Bindestriche erlaubt)",
+                        c, position
+                    ),
+                    _ => format!(
+                        "caractère invalide '{}' à la position {} (seuls les chiffres, espaces et tirets sont autorisés)",
+                        c, position
+                    ),
+                }
+            }
+            Self::InvalidChecksum => match locale {
+                Es => "suma de verificación no válida (falló la comprobación de Luhn): verifique el número de tarjeta".into(),
+                Pt => "dígito verificador inválido (falha na verificação de Luhn): confira o número do cartão".into(),
+                De => "ungültige Prüfsumme (Luhn-Prüfung fehlgeschlagen) – bitte die Kartennummer überprüfen".into(),
+                _ => "somme de contrôle invalide (échec du contrôle de Luhn) : veuillez vérifier le numéro de carte".into(),
+            },
+            Self::InvalidLengthForBrand {
+                brand,
+                length,
+                valid_lengths,
+            } => {
+                let lengths = crate::error::join_lengths(valid_lengths, locale.or());
+                match locale {
+                    Es => format!(
+                        "las tarjetas {} deben tener {} dígitos, tiene {}",
+                        brand, lengths, length
+                    ),
+                    Pt => format!(
+                        "cartões {} devem ter {} dígitos, tem {}",
+                        brand, lengths, length
+                    ),
+                    De => format!(
+                        "{}-Karten müssen {} Ziffern haben, erhalten: {}",
+                        brand, lengths, length
+                    ),
+                    _ => format!(
+                        "les cartes {} doivent comporter {} chiffres, reçu {}",
+                        brand, lengths, length
+                    ),
+                }
+            }
+            Self::UnknownBrand => match locale {
+                Es => "marca de tarjeta desconocida: compruebe el prefijo del número".into(),
+                Pt => "bandeira do cartão desconhecida: verifique o prefixo do número".into(),
+                De => "unbekannte Kartenmarke – bitte das Präfix der Kartennummer prüfen".into(),
+                _ => "marque de carte inconnue : vérifiez le préfixe du numéro".into(),
+            },
+            Self::NoDigits => match locale {
+                Es => "el número de tarjeta no contiene dígitos".into(),
+                Pt => "o número do cartão não contém dígitos".into(),
+                De => "die Kartennummer enthält keine Ziffern".into(),
+                _ => "le numéro de carte ne contient aucun chiffre".into(),
+            },
+            Self::TestCard => match locale {
+                Es => "no se aceptan números de tarjeta de prueba".into(),
+                Pt => "números de cartão de teste não são aceitos".into(),
+                De => "Testkartennummern werden nicht akzeptiert".into(),
+                _ => "les numéros de carte de test ne sont pas acceptés".into(),
+            },
+            Self::BrandNotAllowed { brand } => match locale {
+                Es => format!("no se aceptan tarjetas {}", brand),
+                Pt => format!("cartões {} não são aceitos", brand),
+                De => format!("{}-Karten werden nicht akzeptiert", brand),
+                _ => format!("les cartes {} ne sont pas acceptées", brand),
+            },
+            Self::NotAcceptedInCountry { brand, country } => {
+                let message = match locale {
+                    Es => format!("no se aceptan tarjetas {} en {}", brand, country),
+                    Pt => format!("cartões {} não são aceitos em {}", brand, country),
+                    De => format!("{}-Karten werden in {} nicht akzeptiert", brand, country),
+                    _ => format!("les cartes {} ne sont pas acceptées en {}", brand, country),
+                };
+                match brand.home_country() {
+                    Some(home) => match locale {
+                        Es => format!("{} (sistema nacional de {})", message, home),
+                        Pt => format!("{} (bandeira nacional de {})", message, home),
+                        De => format!("{} (nationales System von {})", message, home),
+                        _ => format!("{} (réseau national de {})", message, home),
+                    },
+                    None => message,
+                }
+            }
+        }
+    }
+}
+
+impl Localize for CvvError {
+    fn localize(&self, locale: Locale) -> String {
+        use Locale::*;
+
+        if locale == En {
+            return self.to_string();
+        }
+        match self {
+            Self::Empty => match locale {
+                Es => "el CVV está vacío".into(),
+                Pt => "o CVV está vazio".into(),
+                De => "der CVV ist leer".into(),
+                _ => "le CVV est vide".into(),
+            },
+            Self::InvalidCharacter {
+                character,
+                position,
+            } => match locale {
+                Es => format!(
+                    "carácter no válido '{}' en la posición {}",
+                    character, position
+                ),
+                Pt => format!("caractere inválido '{}' na posição {}", character, position),
+                De => format!(
+                    "ungültiges Zeichen '{}' an Position {}",
+                    character, position
+                ),
+                _ => format!(
+                    "caractère invalide '{}' à la position {}",
+                    character, position
+                ),
+            },
+            Self::InvalidLength { length, expected } => {
+                let expected = expected.replace(" or ", &format!(" {} ", locale.or()));
+                match locale {
+                    Es => format!("el CVV debe tener {} dígitos, tiene {}", expected, length),
+                    Pt => format!("o CVV deve ter {} dígitos, tem {}", expected, length),
+                    De => format!(
+                        "der CVV muss {} Ziffern haben, erhalten: {}",
+                        expected, length
+                    ),
+                    _ => format!(
+                        "le CVV doit comporter {} chiffres, reçu {}",
+                        expected, length
+                    ),
+                }
+            }
+            Self::WrongLengthForBrand {
+                brand,
+                length,
+                expected,
+            } => match locale {
+                Es => format!(
+                    "las tarjetas {} requieren un CVV de {} dígitos, tiene {}",
+                    brand, expected, length
+                ),
+                Pt => format!(
+                    "cartões {} exigem CVV de {} dígitos, tem {}",
+                    brand, expected, length
+                ),
+                De => format!(
+                    "{}-Karten erfordern einen {}-stelligen CVV, erhalten: {}",
+                    brand, expected, length
+                ),
+                _ => format!(
+                    "les cartes {} exigent un CVV à {} chiffres, reçu {}",
+                    brand, expected, length
+                ),
+            },
+            Self::NotUsed { brand } => match locale {
+                Es => format!("las tarjetas {} no tienen CVV", brand),
+                Pt => format!("cartões {} não têm CVV", brand),
+                De => format!("{}-Karten haben keinen CVV", brand),
+                _ => format!("les cartes {} n'ont pas de CVV", brand),
+            },
+        }
+    }
+}
+
+impl Localize for ExpiryError {
+    fn localize(&self, locale: Locale) -> String {
+        use Locale::*;
+
+        if locale == En {
+            return self.to_string();
+        }
+        match self {
+            Self::Empty => match locale {
+                Es => "la fecha de vencimiento está vacía".into(),
+                Pt => "a data de validade está vazia".into(),
+                De => "das Ablaufdatum ist leer".into(),
+                _ => "la date d'expiration est vide".into(),
+            },
+            Self::InvalidFormat => match locale {
+                Es => "formato de vencimiento no válido (se espera MM/AA o MM/AAAA)".into(),
+                Pt => "formato de validade inválido (esperado MM/AA ou MM/AAAA)".into(),
+                De => "ungültiges Ablaufdatum (erwartet MM/JJ oder MM/JJJJ)".into(),
+                _ => "format d'expiration invalide (attendu MM/AA ou MM/AAAA)".into(),
+            },
+            Self::InvalidMonth(month) => match locale {
+                Es => format!("mes no válido {}: debe estar entre 1 y 12", month),
+                Pt => format!("mês inválido {}: deve estar entre 1 e 12", month),
+                De => format!("ungültiger Monat {}: muss zwischen 1 und 12 liegen", month),
+                _ => format!("mois invalide {} : doit être compris entre 1 et 12", month),
+            },
+            Self::Expired { month, year } => match locale {
+                Es => format!("tarjeta vencida ({:02}/{})", month, year),
+                Pt => format!("cartão vencido ({:02}/{})", month, year),
+                De => format!("Karte abgelaufen ({:02}/{})", month, year),
+                _ => format!("carte expirée ({:02}/{})", month, year),
+            },
+            Self::TooFarFuture { year, max_year } => match locale {
+                Es => format!(
+                    "el año de vencimiento {} es demasiado lejano (máx.: {})",
+                    year, max_year
+                ),
+                Pt => format!(
+                    "o ano de validade {} está muito no futuro (máx.: {})",
+                    year, max_year
+                ),
+                De => format!(
+                    "Ablaufjahr {} liegt zu weit in der Zukunft (max.: {})",
+                    year, max_year
+                ),
+                _ => format!(
+                    "l'année d'expiration {} est trop lointaine (max. : {})",
+                    year, max_year
+                ),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CardBrand;
+
+    #[test]
+    fn test_from_tag() {
+        assert_eq!(Locale::from_tag("es"), Some(Locale::Es));
+        assert_eq!(Locale::from_tag("pt-BR"), Some(Locale::Pt));
+        assert_eq!(Locale::from_tag("DE_at"), Some(Locale::De));
+        assert_eq!(Locale::from_tag("it"), None);
+        assert_eq!(Locale::from_tag(""), None);
+        for locale in Locale::ALL {
+            assert_eq!(Locale::from_tag(locale.tag()), Some(locale));
+        }
+    }
+
+    #[test]
+    fn test_english_matches_display() {
+        let errors = [
+            ValidationError::TooShort {
+                length: 10,
+                minimum: 12,
+            },
+            ValidationError::InvalidLengthForBrand {
+                brand: CardBrand::Amex,
+                length: 16,
+                valid_lengths: &[15],
+            },
+            ValidationError::TestCard,
+        ];
+        for err in errors {
+            assert_eq!(err.localize(Locale::En), err.to_string());
+        }
+        let err = CvvError::Empty;
+        assert_eq!(err.localize(Locale::En), err.to_string());
+        let err = ExpiryError::InvalidMonth(13);
+        assert_eq!(err.localize(Locale::En), err.to_string());
+    }
+
+    #[test]
+    fn test_details_are_kept() {
+        let err = ValidationError::InvalidLengthForBrand {
+            brand: CardBrand::Visa,
+            length: 15,
+            valid_lengths: &[13, 16, 19],
+        };
+        assert_eq!(
+            err.localize(Locale::De),
+            "Visa-Karten müssen 13, 16 oder 19 Ziffern haben, erhalten: 15"
+        );
+
+        let err = CvvError::InvalidLength {
+            length: 5,
+            expected: "3 or 4",
+        };
+        assert_eq!(
+            err.localize(Locale::Pt),
+            "o CVV deve ter 3 ou 4 dígitos, tem 5"
+        );
+
+        let err = ExpiryError::Expired {
+            month: 3,
+            year: 2020,
+        };
+        assert_eq!(err.localize(Locale::Fr), "carte expirée (03/2020)");
+    }
+
+    #[test]
+    fn test_global_locale() {
+        assert_eq!(Locale::from_u8(Locale::Fr as u8), Locale::Fr);
+        assert_eq!(Locale::from_u8(200), Locale::En);
+
+        // The only test touching the global, so no other test observes it
+        set_locale(Locale::Es);
+        assert_eq!(locale(), Locale::Es);
+        assert_eq!(
+            ValidationError::Empty.localized(),
+            "el número de tarjeta está vacío"
+        );
+        set_locale(Locale::En);
+        assert_eq!(ValidationError::Empty.localized(), "card number is empty");
+    }
+}
@@ -5,7 +5,7 @@
 //! ## Features
 //!
 //! - Luhn algorithm validation
-//! - Card brand detection (14 brands)
+//! - Card brand detection (20 brands)
 //! - CVV and expiry date validation
 //! - Card number masking for display
 //! - Multiple interfaces: library, CLI, REST API, WASM, Node.js
@@ -45,13 +45,15 @@
 //! ## Expiry Validation
 //!
 //! ```rust
-//! use cc_validator::expiry;
+//! use cc_validator::expiry::{self, FixedClock};
 //!
-//! // Validate expiry (rejects expired cards)
-//! let exp = expiry::validate_expiry("12/30").unwrap();
+//! // Validate expiry (rejects expired cards), here against a fixed month;
+//! // `expiry::validate_expiry` uses the system clock
+//! let now = FixedClock::new(6, 2028).unwrap();
+//! let exp = expiry::validate_expiry_with_clock("12/30", true, Some(20), &now).unwrap();
 //! assert_eq!(exp.month(), 12);
 //! assert_eq!(exp.year(), 2030);
-//! assert!(!exp.is_expired());
+//! assert!(!exp.is_expired_at(&now));
 //!
 //! // Parse without expiry check
 //! let exp = expiry::parse_expiry("01/20").unwrap();
@@ -83,12 +85,14 @@
 //! ## Test Card Generation
 //!
 //! ```rust
+//! # #[cfg(not(feature = "no-pan-exposure"))] {
 //! use cc_validator::{generate, is_valid, CardBrand};
 //!
 //! // Generate valid test card (deterministic, no randomness)
 //! let card = generate::generate_card_deterministic(CardBrand::Visa);
 //! assert!(is_valid(&card));
 //! assert!(card.starts_with("4"));
+//! # }
 //! ```
 //!
 //! ## Batch Processing
@@ -126,6 +130,26 @@
 //! assert_eq!(valid_cards.len(), 2);
 //! ```
 //!
+//! ## Prelude and Stability
+//!
+//! [`prelude`] re-exports the stable, recommended API, so most code needs
+//! one import:
+//!
+//! ```rust
+//! use cc_validator::prelude::*;
+//!
+//! let card = validate("4111111111111111").unwrap();
+//! assert_eq!(card.brand(), CardBrand::Visa);
+//! ```
+//!
+//! The prelude and the crate-root re-exports follow semver, checked in CI
+//! with `cargo semver-checks`. [`simd`] and [`scan`] are experimental:
+//! their APIs may change in a minor release.
+//!
+//! [`CardBrand`], [`ValidationError`] and [`cvv::CvvError`] are
+//! `#[non_exhaustive]`: new brands and failure reasons can arrive in a minor
+//! release, so matches on them need a wildcard arm.
+//!
 //! ## Supported Card Brands
 //!
 //! | Brand | Prefix | Length | CVV |
@@ -133,31 +157,52 @@
 //! | Visa | 4 | 13, 16, 19 | 3 |
 //! | Mastercard | 51-55, 2221-2720 | 16 | 3 |
 //! | American Express | 34, 37 | 15 | 4 |
-//! | Discover | 6011, 644-649, 65 | 16-19 | 3 |
-//! | Diners Club | 36, 38, 300-305 | 14-19 | 3 |
+//! | Discover | 65, 644-649, 6011 | 16-19 | 3 |
+//! | Diners Club | 36, 38, 300-305, 309 | 14-19 | 3 |
 //! | JCB | 3528-3589 | 16-19 | 3 |
 //! | UnionPay | 62 | 16-19 | 3 |
-//! | Maestro | 50, 56-69 | 12-19 | 3 |
+//! | Maestro | 50, 56-58, 60-61, 63, 66-69 | 12-19 | 3 |
 //! | Mir | 2200-2204 | 16-19 | 3 |
-//! | RuPay | 60, 65, 81, 82 | 16 | 3 |
-//! | Verve | 506, 507 | 16-19 | 3 |
-//! | Elo | 509, 636 | 16 | 3 |
+//! | RuPay | 81-82 | 16 | 3 |
+//! | Verve | 506-507 | 16-19 | 3 |
+//! | Elo | 509, 6362-6363 | 16 | 3 |
 //! | Troy | 9792 | 16 | 3 |
 //! | BC Card | 94 | 16 | 3 |
+//! | Interac | 4506, 4519, 4536, 4724 | 16 | 3 |
+//! | Dankort | 4571, 5019 | 16 | 3 |
+//! | Bancontact | 6703 | 16-19 | 3 |
+//! | UATP | 1 | 15 | none |
+//! | WEX | 690046, 707138 | 13, 16, 19 | none |
+//! | Voyager | 7088 | 16, 19 | none |
 //!
 //! ## Feature Flags
 //!
 //! | Feature | Description |
 //! |---------|-------------|
 //! | `generate` | Test card generation |
+//! | `fingerprint` | Keyed HMAC-SHA256 fingerprints of CVVs and card numbers |
+//! | `secrecy` | [`secrecy::SecretString`] card number inputs and outputs |
+//! | `i18n` | Localized error messages (en, es, pt, de, fr) |
+//! | `serde` | Serialize [`ValidationError`]; serialize and deserialize [`expiry::ExpiryDate`] |
+//! | `cli-core` | Command-line tool with only the subcommands that build for `wasm32-wasip1` |
+//! | `cli-stdin-only` | Command-line tool that only reads card numbers from stdin |
 //! | `cli` | Command-line tool |
 //! | `server` | REST API with Swagger UI |
+//! | `client` | Typed async client for the REST API |
+//! | `gateway` | PAN redaction policy for Proxy-WASM filters |
+//! | `axum-extract` | Axum extractor yielding validated cards from JSON bodies |
 //! | `wasm` | WebAssembly support |
 //! | `parallel` | Rayon-based parallelism |
-//! | `simd` | SIMD Luhn (nightly only) |
-//! | `bin-json` | JSON BIN database loader |
+//! | `batch-parquet` | Parquet output for batch results |
+//! | `simd` | SIMD Luhn, 16 or 32 cards per instruction (nightly only) |
+//! | `bin-json` | JSON BIN database loader (plain or gzip-compressed) |
 //! | `bin-csv` | CSV BIN database loader |
 //! | `bin-sqlite` | SQLite BIN database |
+//! | `bin-redis` | Redis BIN database |
+//! | `bin-redis-async` | Async lookups for the Redis BIN database (tokio) |
+//! | `bin-mmap` | Memory-mapped compiled BIN database (`.ccbin`) |
+//! | `config` | TOML configuration for [`Validator`] |
+//! | `no-pan-exposure` | Remove every API that returns a full PAN or CVV |
 //!
 //! ## Security
 //!
@@ -167,37 +212,82 @@
 //! - Automatic memory zeroization when `ValidatedCard` is dropped
 //! - `Debug` and `Display` show masked numbers only
 //! - Constant-time comparison for sensitive operations
-//! - No unsafe code (`#![deny(unsafe_code)]`)
+//! - Optional `no-pan-exposure` builds without any API returning a full PAN
+//!   or CVV (`ValidatedCard::number`, the `generate` module,
+//!   `ValidatedCvv::as_str_unchecked`, ...), for PCI-scoped services
+//! - No unsafe code (`#![deny(unsafe_code)]`) apart from mapping `.ccbin` files
+//!   with the `bin-mmap` feature
 
 #![cfg_attr(feature = "simd", feature(portable_simd))]
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 #![deny(unsafe_code)]
 
+pub mod acceptance;
 pub mod batch;
 #[path = "bindb/mod.rs"]
 pub mod bin;
+pub mod cache;
 pub mod card;
+#[cfg(feature = "client")]
+pub mod client;
 pub mod cvv;
 pub mod detect;
 pub mod error;
 pub mod expiry;
+#[cfg(feature = "axum-extract")]
+pub mod extract;
+#[cfg(feature = "fingerprint")]
+pub mod fingerprint;
 pub mod format;
+#[cfg(feature = "gateway")]
+pub mod gateway;
+#[cfg(not(feature = "no-pan-exposure"))]
 pub mod generate;
+#[cfg(feature = "i18n")]
+pub mod i18n;
 pub mod luhn;
 pub mod mask;
+pub mod metrics;
+pub mod network;
+pub mod outcome;
+pub mod output;
+pub mod partial;
+pub mod prelude;
+pub mod profile;
+pub mod rules;
+pub mod sanitize;
+pub mod scan;
+#[cfg(any(feature = "server", feature = "client"))]
+pub mod schemas;
 pub mod simd;
 pub mod stream;
+pub mod testcard;
 pub mod validate;
+pub mod validator;
 
 #[cfg(feature = "wasm")]
 mod wasm;
 
 // Re-export main types at crate root
 pub use batch::BatchValidator;
-pub use card::{CardBrand, ValidatedCard, MAX_CARD_DIGITS, MIN_CARD_DIGITS};
+pub use cache::ValidatorCache;
+pub use card::{
+    BinExposureError, CardBrand, UnknownBrandId, ValidatedCard, MAX_CARD_DIGITS,
+    MAX_SUPPORTED_DIGITS, MIN_CARD_DIGITS, MIN_SUPPORTED_DIGITS,
+};
 pub use error::ValidationError;
-pub use validate::{is_valid, passes_luhn, validate, validate_any, validate_digits};
+pub use outcome::{ValidationOutcome, ValidationWarning};
+pub use profile::CardProfile;
+#[cfg(not(feature = "no-pan-exposure"))]
+pub use validate::canonicalize;
+#[cfg(feature = "secrecy")]
+pub use validate::validate_secret;
+pub use validate::{
+    is_valid, is_valid_digits, passes_luhn, validate, validate_any, validate_digits,
+    validate_partial_info, validate_with_cvv, validate_with_warnings, PartialInfo, Workspace,
+};
+pub use validator::{default_validator, set_default_validator, Validator};
 
 // Re-export mask utilities
 pub use mask::{constant_time_eq, constant_time_eq_str, mask_string};
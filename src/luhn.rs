@@ -8,6 +8,12 @@
 //! This implementation uses a lookup table for the doubling operation,
 //! making it O(n) with minimal overhead. For SIMD acceleration on nightly
 //! Rust, enable the `simd` feature.
+//!
+//! Every check here except [`check`] and [`complete`] is a `const fn`, so
+//! checksums can be verified at compile time (see
+//! [`test_card!`](crate::test_card)) or in firmware without runtime setup.
+
+use crate::error::ValidationError;
 
 /// Lookup table for doubled digits: double the value, subtract 9 if >= 10.
 /// This avoids the branch and division in the inner loop.
@@ -46,7 +52,7 @@ const DOUBLE_TABLE: [u8; 10] = [0, 2, 4, 6, 8, 1, 3, 5, 7, 9];
 /// assert!(!validate(&invalid));
 /// ```
 #[inline]
-pub fn validate(digits: &[u8]) -> bool {
+pub const fn validate(digits: &[u8]) -> bool {
     if digits.is_empty() {
         return false;
     }
@@ -67,7 +73,7 @@ pub fn validate(digits: &[u8]) -> bool {
 ///
 /// The Luhn sum (not modulo 10).
 #[inline]
-pub fn compute_checksum(digits: &[u8]) -> u32 {
+pub const fn compute_checksum(digits: &[u8]) -> u32 {
     let len = digits.len();
     let mut sum: u32 = 0;
 
@@ -92,6 +98,56 @@ pub fn compute_checksum(digits: &[u8]) -> u32 {
     sum
 }
 
+/// Outcome of a Luhn check, with the digits needed to explain a failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LuhnReport {
+    /// Whether the number passes the Luhn check.
+    pub valid: bool,
+    /// The check digit computed from all digits but the last, i.e. the one
+    /// the number should end with.
+    pub computed_check: u8,
+    /// The check digit the number actually ends with.
+    pub found_check: u8,
+    /// The Luhn sum over the whole number (see [`compute_checksum`]).
+    pub sum: u32,
+}
+
+/// Runs the Luhn check and reports the check digit the number should end
+/// with next to the one it ends with.
+///
+/// Unlike [`validate`], a failure says what the last digit should have
+/// been, e.g. for "check digit should be 7" messages or repairing data.
+/// An empty slice is reported as invalid with all fields zero.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::luhn::check;
+///
+/// let report = check(&[4, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 2]);
+/// assert!(!report.valid);
+/// assert_eq!(report.computed_check, 1);
+/// assert_eq!(report.found_check, 2);
+/// ```
+pub fn check(digits: &[u8]) -> LuhnReport {
+    let Some((&last, payload)) = digits.split_last() else {
+        return LuhnReport {
+            valid: false,
+            computed_check: 0,
+            found_check: 0,
+            sum: 0,
+        };
+    };
+
+    let sum = compute_checksum(digits);
+    LuhnReport {
+        valid: sum.is_multiple_of(10),
+        computed_check: generate_check_digit(payload),
+        found_check: last,
+        sum,
+    }
+}
+
 /// Generates the check digit for a partial card number.
 ///
 /// Given digits without the check digit, computes what the check digit
@@ -115,7 +171,7 @@ pub fn compute_checksum(digits: &[u8]) -> u32 {
 /// assert_eq!(generate_check_digit(&partial), 1);
 /// ```
 #[inline]
-pub fn generate_check_digit(digits: &[u8]) -> u8 {
+pub const fn generate_check_digit(digits: &[u8]) -> u8 {
     // When computing for check digit generation, the digits we have
     // will all be shifted one position to the left (position-wise)
     // compared to the final number.
@@ -152,11 +208,152 @@ pub fn generate_check_digit(digits: &[u8]) -> u8 {
     ((10 - (sum % 10)) % 10) as u8
 }
 
+/// Appends the Luhn check digit to a partial card number.
+///
+/// Separators (spaces, hyphens, dots) are stripped, so the result is the
+/// bare digit string.
+///
+/// # Errors
+///
+/// Returns [`ValidationError::InvalidCharacter`] for any other non-digit
+/// and [`ValidationError::NoDigits`] if the prefix has no digits.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::luhn::complete;
+///
+/// assert_eq!(complete("4111 1111 1111 111").unwrap(), "4111111111111111");
+/// assert!(complete("4111-x").is_err());
+/// ```
+pub fn complete(prefix: &str) -> Result<String, ValidationError> {
+    let mut digits = Vec::with_capacity(prefix.len() + 1);
+    for (position, c) in prefix.chars().enumerate() {
+        match c {
+            '0'..='9' => digits.push(c as u8 - b'0'),
+            ' ' | '-' | '.' => {}
+            _ => {
+                return Err(ValidationError::InvalidCharacter {
+                    position,
+                    digit_index: digits.len(),
+                    character: c,
+                })
+            }
+        }
+    }
+    if digits.is_empty() {
+        return Err(ValidationError::NoDigits);
+    }
+
+    digits.push(generate_check_digit(&digits));
+    Ok(digits.iter().map(|&d| (b'0' + d) as char).collect())
+}
+
+/// Luhn state fed one digit at a time, left to right.
+///
+/// The Luhn sum is defined from the rightmost digit, which a stream only
+/// reaches at the end. The accumulator keeps the sum (mod 10) under both
+/// possible parities, so digits can come from a socket, an async byte
+/// stream or a scanner without buffering the number. It holds no digits.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::luhn::Accumulator;
+///
+/// let mut acc = Accumulator::new();
+/// for b in "411111111111111".bytes() {
+///     acc.push(b - b'0');
+/// }
+/// assert!(!acc.is_valid());
+/// assert_eq!(acc.check_digit(), 1);
+///
+/// acc.push(1);
+/// assert!(acc.is_valid());
+/// assert_eq!(acc.len(), 16);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Accumulator {
+    /// Sum (mod 10) with the last digit fed as the check digit.
+    as_number: u8,
+    /// Sum (mod 10) with the last digit fed doubled, i.e. as a payload
+    /// awaiting its check digit.
+    as_payload: u8,
+    /// Number of digits fed.
+    count: usize,
+}
+
+impl Accumulator {
+    /// Creates an empty accumulator.
+    pub const fn new() -> Self {
+        Self {
+            as_number: 0,
+            as_payload: 0,
+            count: 0,
+        }
+    }
+
+    /// Feeds the next digit (0-9).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `digit` is greater than 9.
+    #[inline]
+    pub fn push(&mut self, digit: u8) {
+        // Every digit already fed moves one position left, swapping parity
+        let as_number = (self.as_payload + digit) % 10;
+        self.as_payload = (self.as_number + DOUBLE_TABLE[digit as usize]) % 10;
+        self.as_number = as_number;
+        self.count += 1;
+    }
+
+    /// Feeds a slice of digits (0-9).
+    #[inline]
+    pub fn extend_from_slice(&mut self, digits: &[u8]) {
+        for &digit in digits {
+            self.push(digit);
+        }
+    }
+
+    /// Returns true if the digits fed so far pass the Luhn check.
+    ///
+    /// Like [`validate`], no digits is not valid.
+    #[inline]
+    pub const fn is_valid(&self) -> bool {
+        self.count > 0 && self.as_number == 0
+    }
+
+    /// Returns the check digit to append to the digits fed so far, as
+    /// [`generate_check_digit`] would.
+    #[inline]
+    pub const fn check_digit(&self) -> u8 {
+        (10 - self.as_payload) % 10
+    }
+
+    /// Returns the number of digits fed.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns true if no digits have been fed.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Starts over, e.g. at the next candidate in a stream.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
 /// Validates digits using an optimized unrolled loop for 16-digit cards.
 ///
 /// This is the most common card length, so we optimize for it.
 #[inline]
-pub fn validate_16(digits: &[u8; 16]) -> bool {
+pub const fn validate_16(digits: &[u8; 16]) -> bool {
     // Unrolled loop for 16 digits
     // Positions from right: 0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15
     // Double positions: 1,3,5,7,9,11,13,15 (indices 14,12,10,8,6,4,2,0)
@@ -184,7 +381,7 @@ pub fn validate_16(digits: &[u8; 16]) -> bool {
 
 /// Validates digits using an optimized unrolled loop for 15-digit cards (Amex).
 #[inline]
-pub fn validate_15(digits: &[u8; 15]) -> bool {
+pub const fn validate_15(digits: &[u8; 15]) -> bool {
     // 15 digits - odd length
     // Positions from right: 0,1,2,3,4,5,6,7,8,9,10,11,12,13,14
     // Double positions: 1,3,5,7,9,11,13 (indices 13,11,9,7,5,3,1)
@@ -213,6 +410,20 @@ pub fn validate_15(digits: &[u8; 15]) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_const_validate() {
+        const CHECK: u8 = generate_check_digit(&[3, 7, 8, 2, 8, 2, 2, 4, 6, 3, 1, 0, 0, 0]);
+        const {
+            assert!(validate(&[4, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1]));
+            assert!(!validate(&[4, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 2]));
+            assert!(validate_16(&[
+                5, 5, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4
+            ]));
+            assert!(validate_15(&[3, 7, 8, 2, 8, 2, 2, 4, 6, 3, 1, 0, 0, 0, 5]));
+        }
+        assert_eq!(CHECK, 5);
+    }
+
     #[test]
     fn test_valid_cards() {
         // Visa test cards
@@ -261,6 +472,26 @@ mod tests {
         assert_eq!(generate_check_digit(&partial), 5);
     }
 
+    #[test]
+    fn test_complete() {
+        assert_eq!(complete("411111111111111").unwrap(), "4111111111111111");
+        assert_eq!(complete("3782-822463-1000").unwrap(), "378282246310005");
+        assert_eq!(complete("7992739871").unwrap(), "79927398713");
+        let full = complete("5500.0000.0000.000").unwrap();
+        let digits: Vec<u8> = full.bytes().map(|b| b - b'0').collect();
+        assert!(validate(&digits));
+
+        assert_eq!(
+            complete("4111a"),
+            Err(ValidationError::InvalidCharacter {
+                position: 4,
+                digit_index: 4,
+                character: 'a'
+            })
+        );
+        assert_eq!(complete(" - "), Err(ValidationError::NoDigits));
+    }
+
     #[test]
     fn test_validate_16() {
         let valid: [u8; 16] = [4, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1];
@@ -302,4 +533,52 @@ mod tests {
             assert_eq!(DOUBLE_TABLE[i], expected as u8);
         }
     }
+
+    #[test]
+    fn test_check_report() {
+        let valid = [4, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1];
+        let report = check(&valid);
+        assert!(report.valid);
+        assert_eq!(report.computed_check, report.found_check);
+        assert_eq!(report.sum, compute_checksum(&valid));
+
+        // Every wrong check digit points back at the right one
+        for wrong in 0..10 {
+            let mut digits = [3, 7, 8, 2, 8, 2, 2, 4, 6, 3, 1, 0, 0, 0, wrong];
+            let report = check(&digits);
+            assert_eq!(report.valid, wrong == 5);
+            assert_eq!(report.computed_check, 5);
+            assert_eq!(report.found_check, wrong);
+            digits[14] = report.computed_check;
+            assert!(validate(&digits));
+        }
+
+        assert_eq!(check(&[0]).computed_check, 0);
+        assert!(!check(&[]).valid);
+    }
+
+    #[test]
+    fn test_accumulator_matches_slices() {
+        let numbers: [&[u8]; 4] = [
+            &[4, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1],
+            &[3, 7, 8, 2, 8, 2, 2, 4, 6, 3, 1, 0, 0, 0, 5],
+            &[7, 9, 9, 2, 7, 3, 9, 8, 7, 1],
+            &[0],
+        ];
+        for digits in numbers {
+            let mut acc = Accumulator::new();
+            for (i, &digit) in digits.iter().enumerate() {
+                assert_eq!(acc.check_digit(), generate_check_digit(&digits[..i]));
+                acc.push(digit);
+                assert_eq!(acc.is_valid(), validate(&digits[..=i]));
+            }
+            assert_eq!(acc.len(), digits.len());
+        }
+
+        let mut acc = Accumulator::new();
+        assert!(!acc.is_valid());
+        acc.extend_from_slice(&[4, 2]);
+        acc.reset();
+        assert!(acc.is_empty());
+    }
 }
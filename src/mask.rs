@@ -8,12 +8,166 @@
 //!
 //! PCI-DSS allows displaying:
 //! - First 6 digits (BIN) and last 4 digits
+//! - First 8 digits and last 4 digits for PANs of 16 or more digits
 //! - Only the last 4 digits (preferred for customer-facing display)
 //!
+//! PANs shorter than 16 digits may show at most the first 6 and last 4; see
+//! [`pci_visible_digits`].
+//!
 //! Never display or log the full card number.
+//!
+//! # Other identifiers
+//!
+//! The [`MaskStrategy`] trait applies the same conventions to adjacent
+//! sensitive fields: [`PanMask`] for card numbers, [`IbanMask`] for bank
+//! accounts and [`KeepLast`] for anything else, such as SSNs or phone
+//! numbers.
+//!
+//! ```
+//! use cc_validator::mask::{IbanMask, KeepLast, MaskStrategy, PanMask};
+//!
+//! assert_eq!(PanMask.mask("4111 1111 1111 1111"), "****-****-****-1111");
+//! assert_eq!(IbanMask.mask("DE89 3704 0044 0532 0130 00"), "DE89 **** **** **** **30 00");
+//! assert_eq!(KeepLast::new(4).mask("123-45-6789"), "***-**-6789");
+//! ```
+//!
+//! # Pseudonyms
+//!
+//! With the `fingerprint` feature, [`pseudonymize`] replaces a card with a
+//! keyed, Luhn-valid surrogate of the same brand and length, for analytics
+//! datasets that need realistic numbers but no real ones.
 
 use crate::ValidatedCard;
 
+/// A way of masking a sensitive identifier for display or logging.
+pub trait MaskStrategy {
+    /// Returns `value` with everything but the visible characters masked.
+    fn mask(&self, value: &str) -> String;
+}
+
+impl<S: MaskStrategy + ?Sized> MaskStrategy for &S {
+    fn mask(&self, value: &str) -> String {
+        (**self).mask(value)
+    }
+}
+
+/// Masks card numbers as `****-****-****-1234`, keeping the last 4 digits.
+///
+/// Non-digits are dropped first. Same as [`mask_string`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PanMask;
+
+impl MaskStrategy for PanMask {
+    fn mask(&self, value: &str) -> String {
+        let digits: Vec<char> = value.chars().filter(|c| c.is_ascii_digit()).collect();
+        let len = digits.len();
+
+        if len <= 4 {
+            return "*".repeat(len);
+        }
+
+        let masked_count = len - 4;
+        let mut result = String::with_capacity(len + (len / 4));
+
+        // Add masked portion with grouping
+        for i in 0..masked_count {
+            if i > 0 && i % 4 == 0 {
+                result.push('-');
+            }
+            result.push('*');
+        }
+
+        // Add separator before last 4 if needed
+        if masked_count.is_multiple_of(4) {
+            result.push('-');
+        }
+
+        result.extend(&digits[len - 4..]);
+        result
+    }
+}
+
+/// Masks IBAN-like account numbers, keeping the country code, check digits
+/// and last 4 characters.
+///
+/// Spaces and other separators are dropped, letters are uppercased and the
+/// result is printed in groups of four, as IBANs are on paper. Values of 8
+/// characters or fewer are masked entirely.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IbanMask;
+
+impl MaskStrategy for IbanMask {
+    fn mask(&self, value: &str) -> String {
+        let chars: Vec<char> = value
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        let len = chars.len();
+        let visible = |i: usize| len > 8 && (i < 4 || i >= len - 4);
+
+        let mut result = String::with_capacity(len + len / 4);
+        for (i, &c) in chars.iter().enumerate() {
+            if i > 0 && i % 4 == 0 {
+                result.push(' ');
+            }
+            result.push(if visible(i) { c } else { '*' });
+        }
+        result
+    }
+}
+
+/// Keeps the last `n` letters and digits and masks the others, leaving
+/// separators in place (`123-45-6789` becomes `***-**-6789`).
+///
+/// If the value has no more than `n` letters and digits, all of them are
+/// masked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeepLast {
+    n: usize,
+    mask_char: char,
+}
+
+impl KeepLast {
+    /// Keeps the last `n` characters, masking with `*`.
+    pub const fn new(n: usize) -> Self {
+        Self { n, mask_char: '*' }
+    }
+
+    /// Masks with `mask_char` instead of `*`.
+    pub const fn with_mask_char(mut self, mask_char: char) -> Self {
+        self.mask_char = mask_char;
+        self
+    }
+}
+
+impl MaskStrategy for KeepLast {
+    fn mask(&self, value: &str) -> String {
+        let total = value.chars().filter(|c| c.is_alphanumeric()).count();
+        let hidden = if total > self.n {
+            total - self.n
+        } else {
+            total
+        };
+
+        let mut seen = 0;
+        value
+            .chars()
+            .map(|c| {
+                if !c.is_alphanumeric() {
+                    return c;
+                }
+                seen += 1;
+                if seen <= hidden {
+                    self.mask_char
+                } else {
+                    c
+                }
+            })
+            .collect()
+    }
+}
+
 /// Masks a card number showing only the last 4 digits.
 ///
 /// Format: `****-****-****-1234`
@@ -57,11 +211,89 @@ pub fn mask_card(card: &ValidatedCard) -> String {
     result
 }
 
+/// Returns how many leading and trailing digits PCI DSS truncation allows
+/// to be shown for a PAN of `len` digits.
+///
+/// - 16 digits or more: first 8 and last 4.
+/// - Shorter PANs (e.g. 15-digit Amex, 14-digit Diners Club): at most the
+///   first 6 and last 4.
+///
+/// Numbers too short for that to hide anything (10 digits or fewer, which
+/// no card brand issues) show fewer leading digits, so at least one stays
+/// masked.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::mask::pci_visible_digits;
+///
+/// assert_eq!(pci_visible_digits(16), (8, 4));
+/// assert_eq!(pci_visible_digits(15), (6, 4));
+/// assert_eq!(pci_visible_digits(12), (6, 4));
+/// ```
+pub const fn pci_visible_digits(len: usize) -> (usize, usize) {
+    if len >= 16 {
+        return (8, 4);
+    }
+    if len <= 4 {
+        return (0, 0);
+    }
+    let lead = len.saturating_sub(5);
+    (if lead < 6 { lead } else { 6 }, 4)
+}
+
+/// Returns how many leading digits of a PAN of `len` digits may be shown
+/// when `requested` are asked for: at most 8, and no more than
+/// [`pci_visible_digits`] allows, so a BIN and the last four together never
+/// reveal more than a PCI-truncated number.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::mask::pci_bin_length;
+///
+/// assert_eq!(pci_bin_length(16, 6), 6);
+/// assert_eq!(pci_bin_length(19, 10), 8);
+/// // 14-digit Diners Club: PCI DSS allows at most 6 leading digits
+/// assert_eq!(pci_bin_length(14, 8), 6);
+/// ```
+pub const fn pci_bin_length(len: usize, requested: usize) -> usize {
+    let (lead, _) = pci_visible_digits(len);
+    let max = if lead < 8 { lead } else { 8 };
+    if requested < max {
+        requested
+    } else {
+        max
+    }
+}
+
+/// Truncates a card number to the maximum digits PCI DSS allows.
+///
+/// Shows the leading and trailing digits permitted by
+/// [`pci_visible_digits`] and masks the rest.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::validate;
+///
+/// let visa = validate("4111-1111-1111-1111").unwrap();
+/// assert_eq!(visa.pci_truncated(), "41111111****1111");
+///
+/// let amex = validate("3782-822463-10005").unwrap();
+/// assert_eq!(amex.pci_truncated(), "378282*****0005");
+/// ```
+pub fn pci_truncate(card: &ValidatedCard) -> String {
+    let (lead, trail) = pci_visible_digits(card.length());
+    reveal(card.digits(), lead, trail)
+}
+
 /// Masks a card number showing the BIN (first 6) and last 4 digits.
 ///
 /// Format: `411111******1234`
 ///
 /// This format is sometimes acceptable for logging in secure environments.
+/// The result never reveals more than [`pci_truncate`] would.
 ///
 /// # Example
 ///
@@ -73,30 +305,27 @@ pub fn mask_card(card: &ValidatedCard) -> String {
 /// ```
 #[inline]
 pub fn mask_with_bin(card: &ValidatedCard) -> String {
-    let digits = card.digits();
-    let len = digits.len();
+    let (lead, trail) = pci_visible_digits(card.length());
 
-    if len <= 10 {
+    if lead == 0 {
         // If card is too short, just mask everything except last 4
         return mask_card(card);
     }
 
-    let mut result = String::with_capacity(len);
-
-    // First 6 digits (BIN)
-    for &d in &digits[..6] {
-        result.push((b'0' + d) as char);
-    }
+    reveal(card.digits(), pci_bin_length(card.length(), 6), trail)
+}
 
-    // Masked middle section
-    let middle_count = len - 10; // 6 (bin) + 4 (last four)
-    for _ in 0..middle_count {
-        result.push('*');
-    }
+/// Shows the first `lead` and last `trail` digits, masking the middle.
+fn reveal(digits: &[u8], lead: usize, trail: usize) -> String {
+    let len = digits.len();
+    let mut result = String::with_capacity(len);
 
-    // Last 4 digits
-    for &d in &digits[len - 4..] {
-        result.push((b'0' + d) as char);
+    for (i, &d) in digits.iter().enumerate() {
+        if i < lead || i >= len - trail {
+            result.push((b'0' + d) as char);
+        } else {
+            result.push('*');
+        }
     }
 
     result
@@ -113,36 +342,7 @@ pub fn mask_with_bin(card: &ValidatedCard) -> String {
 /// as this function will process any input string.
 #[inline]
 pub fn mask_string(input: &str) -> String {
-    // Extract only digits
-    let digits: Vec<char> = input.chars().filter(|c| c.is_ascii_digit()).collect();
-    let len = digits.len();
-
-    if len <= 4 {
-        return "*".repeat(len);
-    }
-
-    let masked_count = len - 4;
-    let mut result = String::with_capacity(len + (len / 4));
-
-    // Add masked portion with grouping
-    for i in 0..masked_count {
-        if i > 0 && i % 4 == 0 {
-            result.push('-');
-        }
-        result.push('*');
-    }
-
-    // Add separator before last 4 if needed
-    if masked_count.is_multiple_of(4) {
-        result.push('-');
-    }
-
-    // Add last 4 digits
-    for &c in &digits[len - 4..] {
-        result.push(c);
-    }
-
-    result
+    PanMask.mask(input)
 }
 
 /// Extracts just the last 4 digits from a card number string.
@@ -158,6 +358,76 @@ pub fn last_four_from_string(input: &str) -> String {
     }
 }
 
+/// Leading digits [`pseudonymize`] keeps from the original number; enough
+/// to fall in the same brand range.
+#[cfg(feature = "fingerprint")]
+pub const PSEUDONYM_PREFIX_DIGITS: usize = 6;
+
+/// Returns a surrogate card number derived from `card` under `key`.
+///
+/// The surrogate keeps the first [`PSEUDONYM_PREFIX_DIGITS`] digits and the
+/// length, so it is detected as the same brand. The digits after that are
+/// drawn from an HMAC-SHA256 of the whole number under `key`, and a Luhn
+/// check digit is appended. The same card and key always give the same
+/// surrogate, so joins and distinct counts survive; without the key the
+/// surrogate can't be linked back to the card.
+///
+/// This is keyed pseudonymization, **not** format-preserving encryption
+/// (NIST FF1/FF3): there is no way to decrypt a surrogate, even with the
+/// key, and two cards may map to the same surrogate. Surrogates are valid
+/// card numbers, possibly someone's real one, so keep them out of payment
+/// systems.
+///
+/// Requires the `fingerprint` feature.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::mask::pseudonymize;
+/// use cc_validator::{validate, CardBrand};
+///
+/// let card = validate("4532 0151 1283 0366").unwrap();
+/// let surrogate = pseudonymize(&card, b"analytics key");
+/// assert_eq!(surrogate, pseudonymize(&card, b"analytics key"));
+/// assert_ne!(surrogate, pseudonymize(&card, b"other key"));
+///
+/// let surrogate = validate(&surrogate).unwrap();
+/// assert_eq!(surrogate.brand(), CardBrand::Visa);
+/// assert_eq!(surrogate.bin6(), "453201");
+/// ```
+#[cfg(feature = "fingerprint")]
+pub fn pseudonymize(card: &ValidatedCard, key: &[u8]) -> String {
+    use zeroize::Zeroize;
+
+    let digits = card.digits();
+    let len = digits.len();
+    let mut out = [0u8; crate::MAX_SUPPORTED_DIGITS];
+    let keep = PSEUDONYM_PREFIX_DIGITS.min(len - 1);
+    out[..keep].copy_from_slice(&digits[..keep]);
+
+    let mut stream = *crate::fingerprint::Fingerprint::of_digits(key, digits).as_bytes();
+    let mut next = 0;
+    let mut filled = keep;
+    while filled < len - 1 {
+        if next == stream.len() {
+            stream = hmac_sha256::HMAC::mac(stream, key);
+            next = 0;
+        }
+        // Skip 250..=255 so every digit is equally likely
+        if stream[next] < 250 {
+            out[filled] = stream[next] % 10;
+            filled += 1;
+        }
+        next += 1;
+    }
+    out[len - 1] = crate::luhn::generate_check_digit(&out[..len - 1]);
+
+    let surrogate = out[..len].iter().map(|&d| char::from(b'0' + d)).collect();
+    out.zeroize();
+    stream.zeroize();
+    surrogate
+}
+
 /// Constant-time comparison of two byte slices.
 ///
 /// This function takes the same amount of time regardless of where
@@ -206,10 +476,10 @@ pub fn constant_time_eq_str(a: &str, b: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::card::{CardBrand, ValidatedCard, MAX_CARD_DIGITS};
+    use crate::card::{CardBrand, ValidatedCard, MAX_SUPPORTED_DIGITS};
 
     fn make_card(digits_slice: &[u8]) -> ValidatedCard {
-        let mut digits = [0u8; MAX_CARD_DIGITS];
+        let mut digits = [0u8; MAX_SUPPORTED_DIGITS];
         digits[..digits_slice.len()].copy_from_slice(digits_slice);
         ValidatedCard::new(CardBrand::Visa, digits, digits_slice.len() as u8)
     }
@@ -234,6 +504,51 @@ mod tests {
         assert_eq!(mask_with_bin(&card), "411111******1111");
     }
 
+    #[test]
+    fn test_pci_truncate() {
+        let visa = make_card(&[4, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1]);
+        assert_eq!(pci_truncate(&visa), "41111111****1111");
+
+        let amex = make_card(&[3, 7, 8, 2, 8, 2, 2, 4, 6, 3, 1, 0, 0, 0, 5]);
+        assert_eq!(pci_truncate(&amex), "378282*****0005");
+        assert_eq!(mask_with_bin(&amex), "378282*****0005");
+
+        let diners = make_card(&[3, 0, 5, 6, 9, 3, 0, 9, 0, 2, 5, 9, 0, 4]);
+        assert_eq!(pci_truncate(&diners), "305693****5904");
+        assert_eq!(mask_with_bin(&diners), "305693****5904");
+    }
+
+    #[test]
+    fn test_pci_bin_length_within_visible_digits() {
+        for len in 0..=23 {
+            let (lead, _) = pci_visible_digits(len);
+            for requested in 0..=10 {
+                let bin = pci_bin_length(len, requested);
+                assert!(bin <= requested.min(8).min(lead), "{} of {}", bin, len);
+            }
+            assert_eq!(pci_bin_length(len, 8), lead.min(8));
+        }
+    }
+
+    #[test]
+    fn test_pci_visible_digits_never_exceed_limits() {
+        for len in 0..=19 {
+            let (lead, trail) = pci_visible_digits(len);
+            assert!(lead + trail <= len);
+            if len < 16 {
+                assert!(lead <= 6);
+                if len > 4 {
+                    assert!(lead + trail < len);
+                }
+                if len > 10 {
+                    assert_eq!((lead, trail), (6, 4));
+                }
+            } else {
+                assert_eq!((lead, trail), (8, 4));
+            }
+        }
+    }
+
     #[test]
     fn test_mask_string() {
         assert_eq!(mask_string("4111111111111111"), "****-****-****-1111");
@@ -241,6 +556,57 @@ mod tests {
         assert_eq!(mask_string("4111 1111 1111 1111"), "****-****-****-1111");
     }
 
+    #[test]
+    fn test_strategies() {
+        assert_eq!(PanMask.mask("4111"), "****");
+        assert_eq!(PanMask.mask("378282246310005"), "****-****-***0005");
+
+        assert_eq!(
+            IbanMask.mask("gb29nwbk60161331926819"),
+            "GB29 **** **** **** **68 19"
+        );
+        assert_eq!(IbanMask.mask("DE89 3704"), "**** ****");
+
+        assert_eq!(
+            KeepLast::new(4).mask("+1 (555) 010-9999"),
+            "+* (***) ***-9999"
+        );
+        assert_eq!(KeepLast::new(4).mask("1234"), "****");
+        assert_eq!(KeepLast::new(2).with_mask_char('x').mask("AB-12"), "xx-12");
+
+        // Usable as a trait object, e.g. a per-field strategy table
+        let strategies: [&dyn MaskStrategy; 2] = [&PanMask, &KeepLast::new(4)];
+        assert!(strategies
+            .iter()
+            .all(|s| s.mask("123456789012").ends_with("9012")));
+    }
+
+    #[cfg(feature = "fingerprint")]
+    #[test]
+    fn test_pseudonymize() {
+        let key = b"key";
+        for number in ["4111111111111111", "378282246310005", "6011111111111117"] {
+            let card = crate::validate(number).unwrap();
+            let surrogate = pseudonymize(&card, key);
+            assert_ne!(surrogate, number);
+            assert_eq!(surrogate[..6], number[..6]);
+            let pseudo = crate::validate(&surrogate).unwrap();
+            assert_eq!(pseudo.brand(), card.brand());
+            assert_eq!(pseudo.length(), card.length());
+        }
+
+        // Cards sharing a BIN get different surrogates
+        let a = crate::validate("4111111111111111").unwrap();
+        let b = crate::validate("4111111111111129").unwrap();
+        assert_ne!(pseudonymize(&a, key), pseudonymize(&b, key));
+
+        // The longest numbers get every digit filled
+        let card = make_card(&[7; MAX_SUPPORTED_DIGITS]);
+        let surrogate = pseudonymize(&card, key);
+        assert_eq!(surrogate.len(), MAX_SUPPORTED_DIGITS);
+        assert!(surrogate.starts_with("777777"));
+    }
+
     #[test]
     fn test_last_four_from_string() {
         assert_eq!(last_four_from_string("4111111111111111"), "1111");
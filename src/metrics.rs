@@ -0,0 +1,221 @@
+//! Validation counters by outcome and brand.
+//!
+//! Services embedding the validator usually want to know how many cards it
+//! sees and why they fail. A [`ValidationMetrics`] attached with
+//! [`Validator::with_metrics`](crate::Validator::with_metrics) counts every
+//! validation the validator runs: valid cards by brand, failures by
+//! [`ValidationError::code`]. Read the counters with
+//! [`snapshot`](ValidationMetrics::snapshot), or render them in the
+//! Prometheus text format with
+//! [`render_prometheus`](ValidationMetrics::render_prometheus), as
+//! `ccvalidator-server` does on `/metrics`.
+//!
+//! The counters are shared through an [`Arc`], so one set can cover several
+//! validators (tenants, say), or the [`default_validator`] via
+//! [`set_default_validator`]. Nothing about the numbers themselves is
+//! recorded.
+//!
+//! [`default_validator`]: crate::default_validator
+//! [`set_default_validator`]: crate::set_default_validator
+//!
+//! # Example
+//!
+//! ```
+//! use cc_validator::metrics::ValidationMetrics;
+//! use cc_validator::{CardBrand, Validator};
+//! use std::sync::Arc;
+//!
+//! let metrics = Arc::new(ValidationMetrics::new());
+//! let validator = Validator::new().with_metrics(metrics.clone());
+//! validator.validate("4111111111111111").unwrap();
+//! validator.validate("4111111111111112").unwrap_err();
+//!
+//! let snapshot = metrics.snapshot();
+//! assert_eq!(snapshot.valid, [(CardBrand::Visa, 1)]);
+//! assert_eq!(snapshot.invalid, [("invalid_checksum", 1)]);
+//! ```
+
+use crate::{CardBrand, ValidatedCard, ValidationError};
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+const BRAND_COUNT: usize = CardBrand::all().len();
+
+/// Thread-safe validation counters.
+pub struct ValidationMetrics {
+    valid: [AtomicU64; BRAND_COUNT],
+    invalid: Mutex<BTreeMap<&'static str, u64>>,
+}
+
+impl ValidationMetrics {
+    /// Creates a set of counters, all zero.
+    pub fn new() -> Self {
+        Self {
+            valid: std::array::from_fn(|_| AtomicU64::new(0)),
+            invalid: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Counts one validation result.
+    ///
+    /// Validators with these metrics attached call this themselves; use it
+    /// to count results produced elsewhere.
+    pub fn record(&self, result: &Result<ValidatedCard, ValidationError>) {
+        match result {
+            Ok(card) => {
+                self.valid[card.brand() as usize].fetch_add(1, Ordering::Relaxed);
+            }
+            Err(e) => *self.invalid.lock().unwrap().entry(e.code()).or_insert(0) += 1,
+        }
+    }
+
+    /// Returns the current counts.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let valid = CardBrand::all()
+            .iter()
+            .map(|&brand| (brand, self.valid[brand as usize].load(Ordering::Relaxed)))
+            .filter(|&(_, count)| count > 0)
+            .collect();
+        let invalid = self
+            .invalid
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(&code, &count)| (code, count))
+            .collect();
+        MetricsSnapshot { valid, invalid }
+    }
+
+    /// Sets every counter back to zero.
+    pub fn reset(&self) {
+        for counter in &self.valid {
+            counter.store(0, Ordering::Relaxed);
+        }
+        self.invalid.lock().unwrap().clear();
+    }
+
+    /// Renders the counters in the Prometheus text exposition format, as
+    /// `cc_validator_validations_total` with `outcome` and `brand` or
+    /// `error` labels.
+    pub fn render_prometheus(&self) -> String {
+        let snapshot = self.snapshot();
+        let mut out = String::from(
+            "# HELP cc_validator_validations_total Card numbers validated, by outcome.\n\
+             # TYPE cc_validator_validations_total counter\n",
+        );
+        for (brand, count) in &snapshot.valid {
+            let _ = writeln!(
+                out,
+                "cc_validator_validations_total{{outcome=\"valid\",brand=\"{}\"}} {}",
+                brand.id(),
+                count
+            );
+        }
+        for (code, count) in &snapshot.invalid {
+            let _ = writeln!(
+                out,
+                "cc_validator_validations_total{{outcome=\"invalid\",error=\"{}\"}} {}",
+                code, count
+            );
+        }
+        out
+    }
+}
+
+impl Default for ValidationMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ValidationMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ValidationMetrics")
+            .field(&self.snapshot())
+            .finish()
+    }
+}
+
+/// Counts read from a [`ValidationMetrics`] at one point in time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetricsSnapshot {
+    /// Valid cards by brand, in [`CardBrand::all`] order; brands never
+    /// seen are left out.
+    pub valid: Vec<(CardBrand, u64)>,
+    /// Failures by [`ValidationError::code`], sorted by code.
+    pub invalid: Vec<(&'static str, u64)>,
+}
+
+impl MetricsSnapshot {
+    /// Returns the number of valid cards.
+    pub fn total_valid(&self) -> u64 {
+        self.valid.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Returns the number of failed validations.
+    pub fn total_invalid(&self) -> u64 {
+        self.invalid.iter().map(|(_, count)| count).sum()
+    }
+}
+
+/// Counts `result` in `metrics`, if any, and passes it through.
+pub(crate) fn recorded(
+    metrics: Option<&Arc<ValidationMetrics>>,
+    result: Result<ValidatedCard, ValidationError>,
+) -> Result<ValidatedCard, ValidationError> {
+    if let Some(metrics) = metrics {
+        metrics.record(&result);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BatchValidator, Validator, ValidatorCache};
+
+    #[test]
+    fn test_validation_metrics() {
+        let metrics = Arc::new(ValidationMetrics::new());
+        let validator = Validator::new()
+            .with_allowed_brands([CardBrand::Visa, CardBrand::Amex])
+            .with_metrics(metrics.clone());
+
+        validator.validate("4111111111111111").unwrap();
+        validator.validate("378282246310005").unwrap();
+        validator.validate("5500000000000004").unwrap_err();
+        validator.validate("").unwrap_err();
+        BatchValidator::with_validator(validator.clone())
+            .validate_all(&["4111111111111111", "4111111111111112"]);
+        let cache = ValidatorCache::new(validator, 10);
+        cache.validate("4111111111111111").unwrap();
+        cache.validate("4111111111111111").unwrap();
+        cache.validate("12").unwrap_err();
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.valid, [(CardBrand::Visa, 4), (CardBrand::Amex, 1)]);
+        assert_eq!(
+            snapshot.invalid,
+            [
+                ("brand_not_allowed", 1),
+                ("empty", 1),
+                ("invalid_checksum", 1),
+                ("too_short", 1)
+            ]
+        );
+        assert_eq!((snapshot.total_valid(), snapshot.total_invalid()), (5, 4));
+
+        let text = metrics.render_prometheus();
+        assert!(text.contains("# TYPE cc_validator_validations_total counter\n"));
+        assert!(
+            text.contains("cc_validator_validations_total{outcome=\"valid\",brand=\"visa\"} 4\n")
+        );
+        assert!(text
+            .contains("cc_validator_validations_total{outcome=\"invalid\",error=\"empty\"} 1\n"));
+
+        metrics.reset();
+        assert_eq!(metrics.snapshot(), MetricsSnapshot::default());
+    }
+}
@@ -0,0 +1,169 @@
+//! Processing networks and network sharing.
+//!
+//! The brand printed on a card is not always the network that carries the
+//! transaction. Under long-standing alliances Discover processes Diners Club
+//! cards acquired in North America, UnionPay and JCB cards acquired in the
+//! United States, and Discover co-branded UnionPay cards everywhere.
+//! Routing engines need the processing network, not just the logo:
+//!
+//! | Card brand | IIN range | Acquirer country | Processed by |
+//! |------------|-----------|------------------|--------------|
+//! | Diners Club | 300000-305999, 309500-309599, 360000-369999, 380000-399999 | US, CA | Discover |
+//! | UnionPay | 622126-622925 | any | Discover |
+//! | UnionPay | 620000-629999 | US | Discover |
+//! | JCB | 352800-358999 | US | Discover |
+//!
+//! Cards outside these ranges, or acquired elsewhere, are processed by their
+//! own network. The table reflects the public alliances; an acquirer's own
+//! routing agreements take precedence.
+//!
+//! # Example
+//!
+//! ```
+//! use cc_validator::{validate, CardBrand};
+//!
+//! let card = validate("36227206271667").unwrap();
+//! assert_eq!(card.brand(), CardBrand::DinersClub);
+//! assert_eq!(card.processing_network("US"), CardBrand::Discover);
+//! assert_eq!(card.processing_network("FR"), CardBrand::DinersClub);
+//! assert_eq!(card.network_alias("us"), Some(CardBrand::Discover));
+//! ```
+
+use crate::card::{CardBrand, ValidatedCard};
+use CardBrand::{DinersClub, Discover, Jcb, UnionPay};
+
+/// A range of IINs carried by another network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkShare {
+    /// Brand on the card.
+    pub brand: CardBrand,
+    /// First 6-digit IIN of the range (inclusive).
+    pub start: u32,
+    /// Last 6-digit IIN of the range (inclusive).
+    pub end: u32,
+    /// Network that processes the range.
+    pub network: CardBrand,
+    /// ISO 3166-1 alpha-2 acquirer countries where the sharing applies, or
+    /// `None` for everywhere.
+    pub countries: Option<&'static [&'static str]>,
+}
+
+const NORTH_AMERICA: &[&str] = &["US", "CA"];
+const US: &[&str] = &["US"];
+
+const fn share(
+    brand: CardBrand,
+    start: u32,
+    end: u32,
+    network: CardBrand,
+    countries: Option<&'static [&'static str]>,
+) -> NetworkShare {
+    NetworkShare {
+        brand,
+        start,
+        end,
+        network,
+        countries,
+    }
+}
+
+/// Network-sharing rules, most specific first.
+static SHARES: &[NetworkShare] = &[
+    share(DinersClub, 300000, 305999, Discover, Some(NORTH_AMERICA)),
+    share(DinersClub, 309500, 309599, Discover, Some(NORTH_AMERICA)),
+    share(DinersClub, 360000, 369999, Discover, Some(NORTH_AMERICA)),
+    share(DinersClub, 380000, 399999, Discover, Some(NORTH_AMERICA)),
+    share(UnionPay, 622126, 622925, Discover, None),
+    share(UnionPay, 620000, 629999, Discover, Some(US)),
+    share(Jcb, 352800, 358999, Discover, Some(US)),
+];
+
+/// Returns the network-sharing table.
+pub fn shares() -> &'static [NetworkShare] {
+    SHARES
+}
+
+/// Returns the network that processes a card with the given brand and
+/// 6-digit IIN when acquired in `country` (ISO 3166-1 alpha-2, either case).
+///
+/// Falls back to `brand` if no sharing rule applies.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::network::processing_network;
+/// use cc_validator::CardBrand;
+///
+/// assert_eq!(processing_network(CardBrand::UnionPay, 622126, "CN"), CardBrand::Discover);
+/// assert_eq!(processing_network(CardBrand::UnionPay, 621700, "CN"), CardBrand::UnionPay);
+/// assert_eq!(processing_network(CardBrand::Jcb, 353011, "US"), CardBrand::Discover);
+/// ```
+pub fn processing_network(brand: CardBrand, iin: u32, country: &str) -> CardBrand {
+    let country = country.trim();
+    SHARES
+        .iter()
+        .find(|s| {
+            s.brand == brand
+                && (s.start..=s.end).contains(&iin)
+                && match s.countries {
+                    Some(list) => list.iter().any(|c| c.eq_ignore_ascii_case(country)),
+                    None => true,
+                }
+        })
+        .map_or(brand, |s| s.network)
+}
+
+impl ValidatedCard {
+    /// Returns the network that processes this card when acquired in
+    /// `country` (ISO 3166-1 alpha-2, either case).
+    ///
+    /// This is the card's brand unless a network-sharing rule applies; see
+    /// the [`network`](crate::network) module for the table.
+    pub fn processing_network(&self, country: &str) -> CardBrand {
+        let iin = self.digits()[..6]
+            .iter()
+            .fold(0u32, |acc, &d| acc * 10 + u32::from(d));
+        processing_network(self.brand(), iin, country)
+    }
+
+    /// Returns the network that processes this card in `country` if it is
+    /// not the brand on the card.
+    pub fn network_alias(&self, country: &str) -> Option<CardBrand> {
+        let network = self.processing_network(country);
+        (network != self.brand()).then_some(network)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate;
+
+    #[test]
+    fn test_table_ranges() {
+        for share in shares() {
+            assert!(share.start <= share.end);
+            assert!((100000..=999999).contains(&share.start));
+            assert!((100000..=999999).contains(&share.end));
+            assert_ne!(share.brand, share.network);
+        }
+    }
+
+    #[test]
+    fn test_processing_network() {
+        let diners = validate("30569309025904").unwrap();
+        assert_eq!(diners.processing_network("ca"), CardBrand::Discover);
+        assert_eq!(diners.network_alias("JP"), None);
+
+        let unionpay = validate("6200000000000005").unwrap();
+        assert_eq!(unionpay.processing_network(" us "), CardBrand::Discover);
+        assert_eq!(unionpay.processing_network("CN"), CardBrand::UnionPay);
+
+        let jcb = validate("3530111333300000").unwrap();
+        assert_eq!(jcb.network_alias("US"), Some(CardBrand::Discover));
+        assert_eq!(jcb.network_alias("JP"), None);
+
+        let visa = validate("4111111111111111").unwrap();
+        assert_eq!(visa.processing_network("US"), CardBrand::Visa);
+    }
+}
@@ -0,0 +1,202 @@
+//! Validation results that carry warnings.
+//!
+//! Some numbers pass validation but still deserve a second look: a Visa
+//! with 13 digits is legal but rare, a processor test card shouldn't show
+//! up in production, and a card expiring next month may fail a recurring
+//! charge. [`validate_with_warnings`](crate::validate_with_warnings) and
+//! [`Validator::validate_with_warnings`](crate::Validator::validate_with_warnings)
+//! return a [`ValidationOutcome`] listing such [`ValidationWarning`]s next to
+//! the card, so callers can surface them without failing the payment.
+//!
+//! # Example
+//!
+//! ```
+//! use cc_validator::expiry::{ExpiryDate, FixedClock};
+//! use cc_validator::{validate_with_warnings, ValidationWarning};
+//!
+//! let outcome = validate_with_warnings("4111 1111 1111 1111").unwrap();
+//! assert_eq!(outcome.warnings, [ValidationWarning::TestCard]);
+//!
+//! let now = FixedClock::new(5, 2030).unwrap();
+//! let outcome = outcome.with_expiry_at(&ExpiryDate::new(6, 2030).unwrap(), &now);
+//! assert!(outcome.warnings.contains(&ValidationWarning::ExpiringSoon { months: 1 }));
+//! ```
+
+use crate::detect::detect_brand;
+use crate::expiry::{Clock, ExpiryDate, SystemClock};
+use crate::{CardBrand, ValidatedCard};
+use std::fmt;
+
+/// Cards expiring within this many months get
+/// [`ValidationWarning::ExpiringSoon`].
+pub const EXPIRING_SOON_MONTHS: u32 = 3;
+
+/// A condition worth surfacing that doesn't fail validation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValidationWarning {
+    /// The length is valid for the brand but rarely issued (see
+    /// [`CardBrand::common_lengths`]).
+    UnusualLength {
+        /// The detected brand.
+        brand: CardBrand,
+        /// The number of digits.
+        length: usize,
+    },
+
+    /// The number is a well-known processor test card.
+    TestCard,
+
+    /// No brand range matched; the brand is a placeholder from
+    /// [`Strictness::Lenient`](crate::validator::Strictness::Lenient)
+    /// validation.
+    TentativeBrand,
+
+    /// The card expires within [`EXPIRING_SOON_MONTHS`] months.
+    ExpiringSoon {
+        /// Months left after the current one (0 = expires this month).
+        months: u32,
+    },
+}
+
+impl ValidationWarning {
+    /// Returns a stable machine-readable code, like
+    /// [`ValidationError::code`](crate::ValidationError::code).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnusualLength { .. } => "unusual_length",
+            Self::TestCard => "test_card",
+            Self::TentativeBrand => "tentative_brand",
+            Self::ExpiringSoon { .. } => "expiring_soon",
+        }
+    }
+}
+
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnusualLength { brand, length } => {
+                write!(f, "{} cards rarely have {} digits", brand.name(), length)
+            }
+            Self::TestCard => write!(f, "card number is a known test card"),
+            Self::TentativeBrand => write!(f, "card brand could not be determined"),
+            Self::ExpiringSoon { months: 0 } => write!(f, "card expires this month"),
+            Self::ExpiringSoon { months } => {
+                write!(f, "card expires in {} month(s)", months)
+            }
+        }
+    }
+}
+
+/// A validated card and the warnings raised while validating it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationOutcome {
+    /// The validated card.
+    pub card: ValidatedCard,
+    /// Warnings in the order they were found; empty if there were none.
+    pub warnings: Vec<ValidationWarning>,
+}
+
+impl ValidationOutcome {
+    /// Wraps `card`, raising the warnings that follow from its number.
+    pub fn new(card: ValidatedCard) -> Self {
+        let mut warnings = Vec::new();
+        if detect_brand(card.digits()).is_none() {
+            warnings.push(ValidationWarning::TentativeBrand);
+        } else if !card
+            .brand()
+            .common_lengths()
+            .contains(&(card.length() as u8))
+        {
+            warnings.push(ValidationWarning::UnusualLength {
+                brand: card.brand(),
+                length: card.length(),
+            });
+        }
+        if card.is_known_test_card() {
+            warnings.push(ValidationWarning::TestCard);
+        }
+        Self { card, warnings }
+    }
+
+    /// Adds [`ValidationWarning::ExpiringSoon`] if `expiry` is close.
+    ///
+    /// Expects a date that already passed expiry validation; an expired
+    /// date counts as expiring this month.
+    pub fn with_expiry(self, expiry: &ExpiryDate) -> Self {
+        self.with_expiry_at(expiry, &SystemClock)
+    }
+
+    /// Like [`with_expiry`](Self::with_expiry), but relative to `clock`.
+    pub fn with_expiry_at(mut self, expiry: &ExpiryDate, clock: &impl Clock) -> Self {
+        let months = expiry.months_until_expiry_at(clock);
+        if months < EXPIRING_SOON_MONTHS {
+            self.warnings
+                .push(ValidationWarning::ExpiringSoon { months });
+        }
+        self
+    }
+
+    /// Returns true if any warning was raised.
+    pub fn has_warnings(&self) -> bool {
+        !self.warnings.is_empty()
+    }
+
+    /// Returns the card, dropping the warnings.
+    pub fn into_card(self) -> ValidatedCard {
+        self.card
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expiry::FixedClock;
+    use crate::validator::{Strictness, Validator};
+
+    #[test]
+    fn test_warnings() {
+        let outcome = crate::validate_with_warnings("5500000000000004").unwrap();
+        assert_eq!(outcome.warnings, [ValidationWarning::TestCard]);
+
+        let outcome = crate::validate_with_warnings("4222222222222").unwrap();
+        assert_eq!(
+            outcome.warnings,
+            [
+                ValidationWarning::UnusualLength {
+                    brand: CardBrand::Visa,
+                    length: 13
+                },
+                ValidationWarning::TestCard
+            ]
+        );
+        assert_eq!(outcome.warnings[0].code(), "unusual_length");
+        assert_eq!(
+            outcome.warnings[0].to_string(),
+            "Visa cards rarely have 13 digits"
+        );
+
+        let clean = crate::validate_with_warnings("4532015112830366").unwrap();
+        assert!(!clean.has_warnings());
+
+        let lenient = Validator::new().with_strictness(Strictness::Lenient);
+        let outcome = lenient.validate_with_warnings("9000000000000001").unwrap();
+        assert_eq!(outcome.warnings, [ValidationWarning::TentativeBrand]);
+
+        let now = FixedClock::new(11, 2030).unwrap();
+        let soon = |month, year| {
+            clean
+                .clone()
+                .with_expiry_at(&ExpiryDate::new(month, year).unwrap(), &now)
+                .warnings
+        };
+        assert_eq!(
+            soon(11, 2030),
+            [ValidationWarning::ExpiringSoon { months: 0 }]
+        );
+        assert_eq!(
+            soon(1, 2031),
+            [ValidationWarning::ExpiringSoon { months: 2 }]
+        );
+        assert!(soon(2, 2031).is_empty());
+    }
+}
@@ -0,0 +1,436 @@
+//! Result writers for batch runs.
+//!
+//! Large batch runs usually end up in a file for another system to pick up.
+//! A [`ResultWriter`] takes one [`ResultRow`] per card and serializes it, so
+//! [`BatchValidator::validate_into`](crate::BatchValidator::validate_into)
+//! can stream results straight to disk instead of collecting them first.
+//!
+//! Rows never hold the card number: the PAN is masked to its last four
+//! digits (`****-****-****-1111`, see [`mask_string`]) before any writer
+//! sees it. Three formats are provided:
+//!
+//! - [`CsvWriter`]: a header line, then one line per card
+//! - [`JsonWriter`]: JSON Lines, or a single JSON array
+//! - `ParquetWriter`: Parquet, with the `batch-parquet` feature
+//!
+//! # Example
+//!
+//! ```
+//! use cc_validator::output::{CsvWriter, ResultWriter};
+//! use cc_validator::BatchValidator;
+//!
+//! let mut writer = CsvWriter::new(Vec::new());
+//! let mut batch = BatchValidator::new();
+//! batch
+//!     .validate_into(["4111111111111111", "4111111111111112"], &mut writer)
+//!     .unwrap();
+//! writer.finish().unwrap();
+//!
+//! let csv = String::from_utf8(writer.into_inner()).unwrap();
+//! assert_eq!(
+//!     csv,
+//!     "index,masked,valid,brand,error\n\
+//!      0,****-****-****-1111,true,visa,\n\
+//!      1,****-****-****-1112,false,,invalid_checksum\n"
+//! );
+//! ```
+
+use crate::mask::mask_string;
+use crate::{CardBrand, ValidatedCard, ValidationError};
+use std::io::{self, Write};
+
+/// One validated card, ready to be written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultRow {
+    /// Position of the card in the batch.
+    pub index: u64,
+    /// The card number with all but the last four digits masked.
+    pub masked: String,
+    /// The detected brand, for valid cards.
+    pub brand: Option<CardBrand>,
+    /// [`ValidationError::code`] of the failure, for invalid cards.
+    pub error: Option<&'static str>,
+}
+
+impl ResultRow {
+    /// Builds the row for `input`, which validated to `result`.
+    pub fn new(index: u64, input: &str, result: &Result<ValidatedCard, ValidationError>) -> Self {
+        match result {
+            Ok(card) => Self {
+                index,
+                masked: card.masked(),
+                brand: Some(card.brand()),
+                error: None,
+            },
+            Err(e) => Self {
+                index,
+                masked: mask_string(input),
+                brand: None,
+                error: Some(e.code()),
+            },
+        }
+    }
+
+    /// Returns `true` if the card is valid.
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Serializes [`ResultRow`]s.
+///
+/// Call [`finish`](Self::finish) once after the last row: formats with a
+/// trailer (a JSON array, Parquet's footer) are incomplete until then.
+pub trait ResultWriter {
+    /// Writes one row.
+    fn write_row(&mut self, row: &ResultRow) -> io::Result<()>;
+
+    /// Completes the output and flushes it.
+    fn finish(&mut self) -> io::Result<()>;
+}
+
+impl<W: ResultWriter + ?Sized> ResultWriter for &mut W {
+    fn write_row(&mut self, row: &ResultRow) -> io::Result<()> {
+        (**self).write_row(row)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        (**self).finish()
+    }
+}
+
+/// Writes rows as CSV with an `index,masked,valid,brand,error` header.
+///
+/// Brands are written as [`CardBrand::id`]; the column of the other outcome
+/// is left empty.
+#[derive(Debug)]
+pub struct CsvWriter<W: Write> {
+    out: W,
+    header: bool,
+}
+
+impl<W: Write> CsvWriter<W> {
+    /// Writes to `out`. Wrap files in a [`BufWriter`](std::io::BufWriter).
+    pub fn new(out: W) -> Self {
+        Self { out, header: false }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+impl<W: Write> ResultWriter for CsvWriter<W> {
+    fn write_row(&mut self, row: &ResultRow) -> io::Result<()> {
+        if !self.header {
+            self.out.write_all(b"index,masked,valid,brand,error\n")?;
+            self.header = true;
+        }
+        // Masked numbers, brand ids and error codes never need quoting
+        writeln!(
+            self.out,
+            "{},{},{},{},{}",
+            row.index,
+            row.masked,
+            row.is_valid(),
+            row.brand.map_or("", |b| b.id()),
+            row.error.unwrap_or("")
+        )
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        if !self.header {
+            self.out.write_all(b"index,masked,valid,brand,error\n")?;
+            self.header = true;
+        }
+        self.out.flush()
+    }
+}
+
+/// Writes rows as JSON objects with `index`, `masked`, `valid`, `brand`
+/// and `error` keys; the key of the other outcome is `null`.
+#[derive(Debug)]
+pub struct JsonWriter<W: Write> {
+    out: W,
+    array: bool,
+    rows: u64,
+}
+
+impl<W: Write> JsonWriter<W> {
+    /// Writes JSON Lines: one object per line.
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            array: false,
+            rows: 0,
+        }
+    }
+
+    /// Writes a single JSON array, closed by
+    /// [`finish`](ResultWriter::finish).
+    pub fn array(out: W) -> Self {
+        Self {
+            out,
+            array: true,
+            rows: 0,
+        }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+impl<W: Write> ResultWriter for JsonWriter<W> {
+    fn write_row(&mut self, row: &ResultRow) -> io::Result<()> {
+        if self.array {
+            self.out
+                .write_all(if self.rows == 0 { b"[\n" } else { b",\n" })?;
+        }
+        self.rows += 1;
+        let quoted =
+            |value: Option<&str>| value.map_or("null".to_string(), |v| format!("\"{}\"", v));
+        write!(
+            self.out,
+            "{{\"index\":{},\"masked\":\"{}\",\"valid\":{},\"brand\":{},\"error\":{}}}",
+            row.index,
+            row.masked,
+            row.is_valid(),
+            quoted(row.brand.map(|b| b.id())),
+            quoted(row.error)
+        )?;
+        if !self.array {
+            self.out.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        if self.array {
+            self.out
+                .write_all(if self.rows == 0 { b"[]\n" } else { b"\n]\n" })?;
+            // Further rows start a new array
+            self.rows = 0;
+        }
+        self.out.flush()
+    }
+}
+
+#[cfg(feature = "batch-parquet")]
+pub use self::parquet_output::ParquetWriter;
+
+#[cfg(feature = "batch-parquet")]
+mod parquet_output {
+    use super::{ResultRow, ResultWriter};
+    use arrow_array::{ArrayRef, BooleanArray, RecordBatch, StringArray, UInt64Array};
+    use arrow_schema::{DataType, Field, Schema, SchemaRef};
+    use parquet::arrow::ArrowWriter;
+    use std::io::{self, Write};
+    use std::sync::Arc;
+
+    /// Rows buffered per record batch.
+    const BATCH_ROWS: usize = 64 * 1024;
+
+    /// Writes rows to a Parquet file with `index` (uint64), `masked`
+    /// (string), `valid` (boolean), and nullable `brand` and `error`
+    /// (string) columns.
+    ///
+    /// Rows are buffered and written in record batches of 65536. Requires
+    /// the `batch-parquet` feature.
+    pub struct ParquetWriter<W: Write + Send> {
+        writer: Option<ArrowWriter<W>>,
+        out: Option<W>,
+        schema: SchemaRef,
+        rows: Vec<ResultRow>,
+    }
+
+    impl<W: Write + Send> ParquetWriter<W> {
+        /// Writes to `out`.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if the Parquet header can't be written.
+        pub fn new(out: W) -> io::Result<Self> {
+            let schema = Arc::new(Schema::new(vec![
+                Field::new("index", DataType::UInt64, false),
+                Field::new("masked", DataType::Utf8, false),
+                Field::new("valid", DataType::Boolean, false),
+                Field::new("brand", DataType::Utf8, true),
+                Field::new("error", DataType::Utf8, true),
+            ]));
+            let writer =
+                ArrowWriter::try_new(out, schema.clone(), None).map_err(io::Error::other)?;
+            Ok(Self {
+                writer: Some(writer),
+                out: None,
+                schema,
+                rows: Vec::new(),
+            })
+        }
+
+        /// Writes the buffered rows as one record batch.
+        fn flush_rows(&mut self) -> io::Result<()> {
+            let Some(writer) = self.writer.as_mut() else {
+                return Err(finished());
+            };
+            if self.rows.is_empty() {
+                return Ok(());
+            }
+            let rows = &self.rows;
+            let columns: Vec<ArrayRef> = vec![
+                Arc::new(UInt64Array::from_iter_values(rows.iter().map(|r| r.index))),
+                Arc::new(StringArray::from_iter_values(
+                    rows.iter().map(|r| r.masked.as_str()),
+                )),
+                Arc::new(BooleanArray::from_iter(
+                    rows.iter().map(|r| Some(r.is_valid())),
+                )),
+                Arc::new(StringArray::from_iter(
+                    rows.iter().map(|r| r.brand.map(|b| b.id())),
+                )),
+                Arc::new(StringArray::from_iter(rows.iter().map(|r| r.error))),
+            ];
+            let batch =
+                RecordBatch::try_new(self.schema.clone(), columns).map_err(io::Error::other)?;
+            writer.write(&batch).map_err(io::Error::other)?;
+            self.rows.clear();
+            Ok(())
+        }
+
+        /// Finishes the output if needed and returns the underlying
+        /// writer.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if the remaining rows or the footer can't be
+        /// written.
+        pub fn into_inner(mut self) -> io::Result<W> {
+            self.finish()?;
+            self.out.take().ok_or_else(finished)
+        }
+    }
+
+    fn finished() -> io::Error {
+        io::Error::other("Parquet output already finished")
+    }
+
+    impl<W: Write + Send> ResultWriter for ParquetWriter<W> {
+        fn write_row(&mut self, row: &ResultRow) -> io::Result<()> {
+            if self.writer.is_none() {
+                return Err(finished());
+            }
+            self.rows.push(row.clone());
+            if self.rows.len() >= BATCH_ROWS {
+                self.flush_rows()?;
+            }
+            Ok(())
+        }
+
+        fn finish(&mut self) -> io::Result<()> {
+            if self.writer.is_some() {
+                self.flush_rows()?;
+            }
+            if let Some(writer) = self.writer.take() {
+                self.out = Some(writer.into_inner().map_err(io::Error::other)?);
+            }
+            Ok(())
+        }
+    }
+
+    impl<W: Write + Send> std::fmt::Debug for ParquetWriter<W> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("ParquetWriter")
+                .field("buffered", &self.rows.len())
+                .field("finished", &self.writer.is_none())
+                .finish()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BatchValidator;
+
+    const CARDS: [&str; 3] = ["4111 1111 1111 1111", "4111111111111112", "378282246310005"];
+
+    #[test]
+    fn test_json_writer() {
+        let mut batch = BatchValidator::new();
+        let mut writer = JsonWriter::new(Vec::new());
+        let stats = batch.validate_into(CARDS, &mut writer).unwrap();
+        writer.finish().unwrap();
+        assert_eq!((stats.items, stats.valid, stats.invalid), (3, 2, 1));
+
+        let text = String::from_utf8(writer.into_inner()).unwrap();
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(
+            lines[0],
+            r#"{"index":0,"masked":"****-****-****-1111","valid":true,"brand":"visa","error":null}"#
+        );
+        assert_eq!(
+            lines[1],
+            r#"{"index":1,"masked":"****-****-****-1112","valid":false,"brand":null,"error":"invalid_checksum"}"#
+        );
+        assert_eq!(lines.len(), 3);
+        assert!(!text.contains("4111111111111111"));
+
+        let mut writer = JsonWriter::array(Vec::new());
+        batch.validate_into(&CARDS[..1], &mut writer).unwrap();
+        writer.finish().unwrap();
+        let text = String::from_utf8(writer.into_inner()).unwrap();
+        assert!(text.starts_with("[\n{\"index\":0,"));
+        assert!(text.ends_with("}\n]\n"));
+
+        let mut writer = JsonWriter::array(Vec::new());
+        writer.finish().unwrap();
+        assert_eq!(writer.into_inner(), b"[]\n");
+
+        let mut writer = CsvWriter::new(Vec::new());
+        writer.finish().unwrap();
+        assert_eq!(writer.into_inner(), b"index,masked,valid,brand,error\n");
+    }
+
+    #[cfg(feature = "batch-parquet")]
+    #[test]
+    fn test_parquet_writer() {
+        use arrow_array::{Array, StringArray};
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let path = std::env::temp_dir().join(format!("ccv-results-{}.parquet", std::process::id()));
+        let mut writer = ParquetWriter::new(std::fs::File::create(&path).unwrap()).unwrap();
+        BatchValidator::new()
+            .validate_into(CARDS, &mut writer)
+            .unwrap();
+        writer.finish().unwrap();
+        assert!(writer
+            .write_row(&ResultRow::new(3, "", &Err(ValidationError::Empty)))
+            .is_err());
+        writer.into_inner().unwrap();
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<_> = reader.map(Result::unwrap).collect();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].num_rows(), 3);
+        let column = |name| {
+            batches[0]
+                .column_by_name(name)
+                .unwrap()
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(column("masked").value(2), "****-****-***0005");
+        assert_eq!(column("brand").value(2), "amex");
+        assert!(column("brand").is_null(1));
+        assert_eq!(column("error").value(1), "invalid_checksum");
+    }
+}
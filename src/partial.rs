@@ -0,0 +1,118 @@
+//! Loggable view of a card number that is still being typed.
+//!
+//! Checkout forms often want debug telemetry about card entry — how many
+//! digits are in, which brand is showing — before there is anything to
+//! validate. A [`PartialCard`] keeps only the first [`VISIBLE_DIGITS`]
+//! digits and the digit count, so its `Display` and `Debug` output can go
+//! to logs without exposing the PAN:
+//!
+//! ```
+//! use cc_validator::partial::PartialCard;
+//! use cc_validator::CardBrand;
+//!
+//! let entry = PartialCard::new("4111 1111 11");
+//! assert_eq!(entry.to_string(), "411111****");
+//! assert_eq!(entry.len(), 10);
+//! assert_eq!(entry.brand(), Some(CardBrand::Visa));
+//! ```
+
+use crate::detect::detect_brand;
+use crate::CardBrand;
+use std::fmt;
+use zeroize::Zeroize;
+
+/// Number of leading digits a [`PartialCard`] shows; later digits print as
+/// `*`.
+pub const VISIBLE_DIGITS: usize = 6;
+
+/// A card number prefix that can be logged safely.
+///
+/// Digits beyond the first [`VISIBLE_DIGITS`] are counted but never
+/// stored. Characters other than ASCII digits are ignored.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PartialCard {
+    head: [u8; VISIBLE_DIGITS],
+    len: usize,
+}
+
+impl PartialCard {
+    /// Reads the digits typed so far.
+    pub fn new(input: &str) -> Self {
+        let mut head = [0u8; VISIBLE_DIGITS];
+        let mut len = 0;
+        for b in input.bytes().filter(u8::is_ascii_digit) {
+            if let Some(slot) = head.get_mut(len) {
+                *slot = b - b'0';
+            }
+            len += 1;
+        }
+        Self { head, len }
+    }
+
+    /// Returns the number of digits typed.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if no digits have been typed.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the brand the visible digits point to, if any.
+    pub fn brand(&self) -> Option<CardBrand> {
+        detect_brand(&self.head[..self.len.min(VISIBLE_DIGITS)])
+    }
+}
+
+impl From<&str> for PartialCard {
+    fn from(input: &str) -> Self {
+        Self::new(input)
+    }
+}
+
+impl fmt::Display for PartialCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let visible = self.len.min(VISIBLE_DIGITS);
+        let mut text = String::with_capacity(self.len);
+        text.extend(self.head[..visible].iter().map(|&d| char::from(b'0' + d)));
+        text.extend(std::iter::repeat_n('*', self.len - visible));
+        f.pad(&text)
+    }
+}
+
+impl fmt::Debug for PartialCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PartialCard")
+            .field(&self.to_string())
+            .finish()
+    }
+}
+
+impl Drop for PartialCard {
+    fn drop(&mut self) {
+        self.head.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_masks_beyond_visible_digits() {
+        let entry = PartialCard::new("5500-0000-0000-0004");
+        assert_eq!(entry.to_string(), "550000**********");
+        assert_eq!(format!("{:?}", entry), r#"PartialCard("550000**********")"#);
+        assert_eq!(entry.brand(), Some(CardBrand::Mastercard));
+
+        let short = PartialCard::from("37");
+        assert_eq!(short.to_string(), "37");
+        assert_eq!(short.brand(), Some(CardBrand::Amex));
+
+        let empty = PartialCard::new(" - ");
+        assert!(empty.is_empty());
+        assert_eq!(empty.to_string(), "");
+        assert_eq!(empty.brand(), None);
+    }
+}
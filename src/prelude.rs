@@ -0,0 +1,29 @@
+//! The stable, recommended API in one import.
+//!
+//! ```
+//! use cc_validator::prelude::*;
+//!
+//! let card = validate("4111 1111 1111 1111").unwrap();
+//! assert_eq!(card.brand(), CardBrand::Visa);
+//!
+//! let validator = Validator::new().with_strictness(Strictness::Strict);
+//! assert_eq!(validator.validate("4111111111111111"), Err(ValidationError::TestCard));
+//!
+//! let valid = ["4111111111111111", "1234"].iter().validate_valid_only().count();
+//! assert_eq!(valid, 1);
+//! ```
+//!
+//! Everything re-exported here follows semver: it is only removed or
+//! changed incompatibly in a major release. Items are added to the prelude
+//! sparingly, since a new glob-imported name can clash with a downstream
+//! one. Modules documented as experimental ([`simd`](crate::simd),
+//! [`scan`](crate::scan)) are never re-exported here.
+
+pub use crate::batch::BatchValidator;
+pub use crate::card::{CardBrand, ValidatedCard};
+pub use crate::cvv::{CvvError, ValidatedCvv};
+pub use crate::error::ValidationError;
+pub use crate::expiry::{ExpiryDate, ExpiryError};
+pub use crate::stream::ValidateExt;
+pub use crate::validate::{is_valid, validate};
+pub use crate::validator::{Strictness, Validator};
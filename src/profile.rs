@@ -0,0 +1,248 @@
+//! Composite card profiles.
+//!
+//! A [`CardProfile`] bundles a [`ValidatedCard`] with the other pieces that
+//! are usually validated alongside it: the expiry date, whether a CVV was
+//! supplied, the cardholder name, and BIN information. Downstream code can
+//! pass a single value around instead of threading each piece separately.
+//!
+//! The CVV itself is never stored (PCI-DSS forbids retaining it after
+//! authorization); only the fact that one was validated is recorded.
+//!
+//! # Example
+//!
+//! ```
+//! use cc_validator::expiry::FixedClock;
+//! use cc_validator::{cvv, expiry, validate, CardProfile};
+//!
+//! let card = validate("4111-1111-1111-1111").unwrap();
+//! let profile = CardProfile::new(card)
+//!     .with_expiry(expiry::parse_expiry("12/30").unwrap())
+//!     .with_cvv(&cvv::validate_cvv("123").unwrap())
+//!     .with_cardholder_name("Jane Doe");
+//!
+//! assert_eq!(profile.card().last_four(), "1111");
+//! assert!(profile.cvv_present());
+//! assert!(!profile.is_expired_at(&FixedClock::new(6, 2028).unwrap()));
+//! ```
+
+use crate::bin::{BinDatabase, BinInfo};
+use crate::cvv::ValidatedCvv;
+use crate::expiry::{Clock, ExpiryDate};
+use crate::ValidatedCard;
+use std::fmt;
+use std::sync::Arc;
+
+/// A validated card together with its associated details.
+///
+/// Built with [`CardProfile::new`] and the `with_*` methods. `Debug` output
+/// inherits the card's masking and redacts the cardholder name.
+#[derive(Clone)]
+pub struct CardProfile {
+    card: ValidatedCard,
+    expiry: Option<ExpiryDate>,
+    cvv_present: bool,
+    cardholder_name: Option<String>,
+    bin_info: Option<Arc<BinInfo>>,
+}
+
+impl CardProfile {
+    /// Creates a profile holding only the validated card.
+    pub fn new(card: ValidatedCard) -> Self {
+        Self {
+            card,
+            expiry: None,
+            cvv_present: false,
+            cardholder_name: None,
+            bin_info: None,
+        }
+    }
+
+    /// Attaches a validated expiry date.
+    pub fn with_expiry(mut self, expiry: ExpiryDate) -> Self {
+        self.expiry = Some(expiry);
+        self
+    }
+
+    /// Records that a CVV was supplied and validated.
+    ///
+    /// The CVV value is not retained.
+    pub fn with_cvv(mut self, _cvv: &ValidatedCvv) -> Self {
+        self.cvv_present = true;
+        self
+    }
+
+    /// Attaches the cardholder name.
+    pub fn with_cardholder_name(mut self, name: impl Into<String>) -> Self {
+        self.cardholder_name = Some(name.into());
+        self
+    }
+
+    /// Attaches BIN information.
+    pub fn with_bin_info(mut self, info: BinInfo) -> Self {
+        self.bin_info = Some(Arc::new(info));
+        self
+    }
+
+    /// Attaches BIN information shared with other profiles.
+    pub(crate) fn with_shared_bin_info(mut self, info: Arc<BinInfo>) -> Self {
+        self.bin_info = Some(info);
+        self
+    }
+
+    /// Looks up the card's BIN in `db` and attaches the result, if any.
+    pub fn with_bin_lookup<D: BinDatabase + ?Sized>(mut self, db: &D) -> Self {
+        self.bin_info = db.lookup(self.card.bin_lookup_digits()).map(Arc::new);
+        self
+    }
+
+    /// Returns the validated card.
+    #[inline]
+    pub fn card(&self) -> &ValidatedCard {
+        &self.card
+    }
+
+    /// Returns the expiry date, if attached.
+    #[inline]
+    pub fn expiry(&self) -> Option<ExpiryDate> {
+        self.expiry
+    }
+
+    /// Returns `true` if a validated CVV was supplied.
+    #[inline]
+    pub fn cvv_present(&self) -> bool {
+        self.cvv_present
+    }
+
+    /// Returns the cardholder name, if attached.
+    #[inline]
+    pub fn cardholder_name(&self) -> Option<&str> {
+        self.cardholder_name.as_deref()
+    }
+
+    /// Returns the BIN information, if attached.
+    #[inline]
+    pub fn bin_info(&self) -> Option<&BinInfo> {
+        self.bin_info.as_deref()
+    }
+
+    /// Returns the ISO 4217 currency of the issuer country, if BIN
+    /// information with a known country is attached.
+    ///
+    /// See [`crate::bin::infer_currency`].
+    pub fn currency(&self) -> Option<&'static str> {
+        self.bin_info.as_deref().and_then(BinInfo::currency)
+    }
+
+    /// Returns `true` if an expiry date is attached and it has passed.
+    pub fn is_expired(&self) -> bool {
+        self.expiry.is_some_and(|e| e.is_expired())
+    }
+
+    /// Like [`is_expired`](Self::is_expired), but relative to `clock`.
+    pub fn is_expired_at(&self, clock: &impl Clock) -> bool {
+        self.expiry.is_some_and(|e| e.is_expired_at(clock))
+    }
+
+    /// Consumes the profile and returns the validated card.
+    pub fn into_card(self) -> ValidatedCard {
+        self.card
+    }
+}
+
+impl fmt::Debug for CardProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The cardholder name is personal data; only record that one is set
+        f.debug_struct("CardProfile")
+            .field("card", &self.card)
+            .field("expiry", &self.expiry)
+            .field("cvv_present", &self.cvv_present)
+            .field(
+                "cardholder_name",
+                &self.cardholder_name.as_ref().map(|_| "[redacted]"),
+            )
+            .field("bin_info", &self.bin_info)
+            .finish()
+    }
+}
+
+impl From<ValidatedCard> for CardProfile {
+    fn from(card: ValidatedCard) -> Self {
+        Self::new(card)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bin::MemoryBinDbBuilder;
+    use crate::{cvv, expiry, validate};
+
+    #[test]
+    fn test_profile_defaults() {
+        let profile = CardProfile::from(validate("4111111111111111").unwrap());
+        assert!(profile.expiry().is_none());
+        assert!(!profile.cvv_present());
+        assert!(profile.cardholder_name().is_none());
+        assert!(profile.bin_info().is_none());
+        assert!(!profile.is_expired());
+    }
+
+    #[test]
+    fn test_profile_builder() {
+        let profile = CardProfile::new(validate("378282246310005").unwrap())
+            .with_expiry(expiry::parse_expiry("01/20").unwrap())
+            .with_cvv(&cvv::validate_cvv("1234").unwrap())
+            .with_cardholder_name("Jane Doe")
+            .with_bin_info(BinInfo::with_bin("378282").issuer("Test Bank"));
+
+        assert_eq!(profile.expiry().unwrap().year(), 2020);
+        assert!(profile.is_expired());
+        assert!(!profile.is_expired_at(&expiry::FixedClock::new(1, 2020).unwrap()));
+        assert!(profile.cvv_present());
+        assert_eq!(profile.cardholder_name(), Some("Jane Doe"));
+        assert_eq!(
+            profile.bin_info().unwrap().issuer.as_deref(),
+            Some("Test Bank")
+        );
+        assert_eq!(profile.into_card().last_four(), "0005");
+    }
+
+    #[test]
+    fn test_profile_bin_lookup() {
+        let db = MemoryBinDbBuilder::new()
+            .add(
+                "411111",
+                BinInfo::with_bin("411111")
+                    .issuer("Test Bank")
+                    .country("CA"),
+            )
+            .build();
+
+        let profile = CardProfile::new(validate("4111111111111111").unwrap()).with_bin_lookup(&db);
+        assert_eq!(
+            profile.bin_info().unwrap().issuer.as_deref(),
+            Some("Test Bank")
+        );
+        assert_eq!(profile.currency(), Some("CAD"));
+
+        let profile = CardProfile::new(validate("5500000000000004").unwrap()).with_bin_lookup(&db);
+        assert!(profile.bin_info().is_none());
+        assert!(profile.currency().is_none());
+    }
+
+    #[test]
+    fn test_profile_debug_is_masked() {
+        let profile = CardProfile::new(validate("4111111111111111").unwrap());
+        assert!(!format!("{:?}", profile).contains("4111111111111111"));
+    }
+
+    #[test]
+    fn test_profile_debug_redacts_name() {
+        let profile = CardProfile::new(validate("4111111111111111").unwrap())
+            .with_cardholder_name("Jane Doe");
+        let debug = format!("{:?}", profile);
+        assert!(!debug.contains("Jane"));
+        assert!(!debug.contains("Doe"));
+        assert!(debug.contains("[redacted]"));
+    }
+}
@@ -0,0 +1,404 @@
+//! Acceptance rules for purchases.
+//!
+//! Card validation says whether a number is well formed; whether a merchant
+//! takes it for a given purchase is a separate policy. A [`RuleSet`] holds
+//! such a policy: spending caps for prepaid cards, no corporate cards at a
+//! consumer checkout, currencies cards from a country may pay in. It
+//! evaluates a [`Purchase`] against a [`CardProfile`] (the card and its
+//! [`BinInfo`](crate::bin::BinInfo)) and returns a [`Decision`] listing the
+//! broken rules as [`Violation`]s.
+//!
+//! Most rules depend on BIN data. When the profile has none, or lacks the
+//! card type or country a rule needs, the rule is reported as unchecked
+//! rather than passed or failed, so the caller decides whether to accept
+//! such cards or send them to review.
+//!
+//! # Example
+//!
+//! ```
+//! use cc_validator::bin::{BinInfo, CardType};
+//! use cc_validator::rules::{Purchase, RuleSet, Violation};
+//! use cc_validator::{validate, CardProfile};
+//!
+//! let rules = RuleSet::new()
+//!     .max_amount(CardType::Prepaid, 20000, "USD")
+//!     .deny_card_type(CardType::Corporate);
+//!
+//! let card = validate("4111111111111111").unwrap();
+//! let bin = BinInfo::with_bin("411111").card_type(CardType::Prepaid).country("US");
+//! let profile = CardProfile::new(card).with_bin_info(bin);
+//!
+//! assert!(rules.evaluate(&profile, &Purchase::new(15000, "USD")).is_accepted());
+//! let decision = rules.evaluate(&profile, &Purchase::new(25000, "usd"));
+//! assert_eq!(decision.violations[0].code(), "amount_over_limit");
+//! ```
+
+use crate::bin::CardType;
+use crate::CardProfile;
+use std::fmt;
+
+/// A purchase to check against a [`RuleSet`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Purchase {
+    /// Amount in minor units of the currency (cents for USD).
+    pub amount: u64,
+    /// ISO 4217 currency code, upper case.
+    pub currency: String,
+}
+
+impl Purchase {
+    /// Creates a purchase of `amount` minor units of `currency` (either
+    /// case).
+    pub fn new(amount: u64, currency: &str) -> Self {
+        Self {
+            amount,
+            currency: normalize(currency),
+        }
+    }
+}
+
+/// One acceptance rule; see the [`RuleSet`] builder methods.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    /// Cards of `card_type` may spend at most `max` minor units of
+    /// `currency` per purchase. Purchases in other currencies are not
+    /// limited.
+    MaxAmount {
+        /// The card type limited.
+        card_type: CardType,
+        /// ISO 4217 currency of the limit.
+        currency: String,
+        /// Largest amount accepted, in minor units.
+        max: u64,
+    },
+
+    /// Cards of this type are declined.
+    DenyCardType(CardType),
+
+    /// Cards issued in `country` may only pay in `currencies`.
+    CountryCurrencies {
+        /// ISO 3166-1 alpha-2 issuer country.
+        country: String,
+        /// ISO 4217 currencies accepted.
+        currencies: Vec<String>,
+    },
+
+    /// Cards may only pay in their issuer country's currency (see
+    /// [`CardProfile::currency`]).
+    IssuerCurrency,
+}
+
+impl Rule {
+    /// Checks `purchase` against the rule. Returns `Err(())` if the profile
+    /// lacks the BIN data needed to tell.
+    fn check(&self, profile: &CardProfile, purchase: &Purchase) -> Result<Option<Violation>, ()> {
+        let info = profile.bin_info();
+        let card_type = info
+            .and_then(|i| i.card_type)
+            .filter(|&t| t != CardType::Unknown);
+        let country = info.and_then(|i| i.country.as_deref()).map(normalize);
+
+        match self {
+            Self::MaxAmount {
+                card_type: limited,
+                currency,
+                max,
+            } => {
+                if purchase.currency != *currency || purchase.amount <= *max {
+                    return Ok(None);
+                }
+                Ok(
+                    (card_type.ok_or(())? == *limited).then(|| Violation::AmountOverLimit {
+                        card_type: *limited,
+                        currency: currency.clone(),
+                        max: *max,
+                    }),
+                )
+            }
+            Self::DenyCardType(denied) => Ok((card_type.ok_or(())? == *denied)
+                .then_some(Violation::CardTypeDenied { card_type: *denied })),
+            Self::CountryCurrencies {
+                country: restricted,
+                currencies,
+            } => {
+                let country = country.ok_or(())?;
+                let allowed = country != *restricted || currencies.contains(&purchase.currency);
+                Ok((!allowed).then(|| Violation::CurrencyNotAllowed {
+                    country,
+                    currency: purchase.currency.clone(),
+                }))
+            }
+            Self::IssuerCurrency => {
+                let home = profile.currency().ok_or(())?;
+                Ok(
+                    (purchase.currency != home).then(|| Violation::CurrencyNotAllowed {
+                        country: country.unwrap_or_default(),
+                        currency: purchase.currency.clone(),
+                    }),
+                )
+            }
+        }
+    }
+}
+
+/// Why a purchase was declined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// The amount is over the limit for the card type.
+    AmountOverLimit {
+        /// The card type limited.
+        card_type: CardType,
+        /// ISO 4217 currency of the limit.
+        currency: String,
+        /// Largest amount accepted, in minor units.
+        max: u64,
+    },
+
+    /// Cards of this type are not accepted.
+    CardTypeDenied {
+        /// The card's type.
+        card_type: CardType,
+    },
+
+    /// Cards from the issuer country can't pay in this currency.
+    CurrencyNotAllowed {
+        /// ISO 3166-1 alpha-2 issuer country.
+        country: String,
+        /// The purchase currency.
+        currency: String,
+    },
+}
+
+impl Violation {
+    /// Returns a stable machine-readable code, like
+    /// [`ValidationError::code`](crate::ValidationError::code).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::AmountOverLimit { .. } => "amount_over_limit",
+            Self::CardTypeDenied { .. } => "card_type_denied",
+            Self::CurrencyNotAllowed { .. } => "currency_not_allowed",
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AmountOverLimit {
+                card_type,
+                currency,
+                max,
+            } => write!(
+                f,
+                "{} cards are limited to {} {} (minor units) per purchase",
+                card_type, max, currency
+            ),
+            Self::CardTypeDenied { card_type } => {
+                write!(f, "{} cards are not accepted", card_type)
+            }
+            Self::CurrencyNotAllowed { country, currency } if country.is_empty() => {
+                write!(f, "card can't pay in {}", currency)
+            }
+            Self::CurrencyNotAllowed { country, currency } => {
+                write!(f, "cards issued in {} can't pay in {}", country, currency)
+            }
+        }
+    }
+}
+
+/// The outcome of [`RuleSet::evaluate`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Decision {
+    /// Broken rules, in rule order.
+    pub violations: Vec<Violation>,
+    /// Indexes (into [`RuleSet::rules`]) of rules that couldn't be checked
+    /// for lack of BIN data.
+    pub unchecked: Vec<usize>,
+}
+
+impl Decision {
+    /// Returns true if every rule was checked and passed.
+    pub fn is_accepted(&self) -> bool {
+        self.violations.is_empty() && self.unchecked.is_empty()
+    }
+
+    /// Returns true if any rule was broken.
+    pub fn is_declined(&self) -> bool {
+        !self.violations.is_empty()
+    }
+
+    /// Returns true if no rule was broken but some couldn't be checked.
+    pub fn needs_review(&self) -> bool {
+        self.violations.is_empty() && !self.unchecked.is_empty()
+    }
+}
+
+/// An ordered list of [`Rule`]s.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleSet {
+    rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    /// Creates an empty rule set, which accepts every purchase.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rule.
+    pub fn with(mut self, rule: Rule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Limits purchases with `card_type` cards to `max` minor units of
+    /// `currency`.
+    pub fn max_amount(self, card_type: CardType, max: u64, currency: &str) -> Self {
+        self.with(Rule::MaxAmount {
+            card_type,
+            currency: normalize(currency),
+            max,
+        })
+    }
+
+    /// Declines `card_type` cards.
+    pub fn deny_card_type(self, card_type: CardType) -> Self {
+        self.with(Rule::DenyCardType(card_type))
+    }
+
+    /// Lets cards issued in `country` pay only in `currencies`.
+    pub fn restrict_currencies<I, S>(self, country: &str, currencies: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.with(Rule::CountryCurrencies {
+            country: normalize(country),
+            currencies: currencies
+                .into_iter()
+                .map(|c| normalize(c.as_ref()))
+                .collect(),
+        })
+    }
+
+    /// Lets cards pay only in their issuer country's currency.
+    pub fn require_issuer_currency(self) -> Self {
+        self.with(Rule::IssuerCurrency)
+    }
+
+    /// Returns the rules in evaluation order.
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Checks `purchase` with the card in `profile` against every rule.
+    pub fn evaluate(&self, profile: &CardProfile, purchase: &Purchase) -> Decision {
+        let mut decision = Decision::default();
+        for (index, rule) in self.rules.iter().enumerate() {
+            match rule.check(profile, purchase) {
+                Ok(Some(violation)) => decision.violations.push(violation),
+                Ok(None) => {}
+                Err(()) => decision.unchecked.push(index),
+            }
+        }
+        decision
+    }
+}
+
+fn normalize(code: &str) -> String {
+    code.trim().to_ascii_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bin::BinInfo;
+    use crate::validate;
+
+    fn profile(info: Option<BinInfo>) -> CardProfile {
+        let profile = CardProfile::new(validate("4111111111111111").unwrap());
+        match info {
+            Some(info) => profile.with_bin_info(info),
+            None => profile,
+        }
+    }
+
+    #[test]
+    fn test_rules() {
+        let rules = RuleSet::new()
+            .max_amount(CardType::Prepaid, 10000, "usd")
+            .deny_card_type(CardType::Corporate)
+            .restrict_currencies(" ru", ["rub"]);
+        assert_eq!(rules.rules().len(), 3);
+
+        let prepaid = profile(Some(
+            BinInfo::with_bin("411111")
+                .card_type(CardType::Prepaid)
+                .country("US"),
+        ));
+        assert!(rules
+            .evaluate(&prepaid, &Purchase::new(10000, "USD"))
+            .is_accepted());
+        assert!(rules
+            .evaluate(&prepaid, &Purchase::new(50000, "EUR"))
+            .is_accepted());
+        let decision = rules.evaluate(&prepaid, &Purchase::new(10001, "USD"));
+        assert!(decision.is_declined());
+        assert_eq!(
+            decision.violations,
+            [Violation::AmountOverLimit {
+                card_type: CardType::Prepaid,
+                currency: "USD".to_string(),
+                max: 10000
+            }]
+        );
+        assert_eq!(
+            decision.violations[0].to_string(),
+            "Prepaid cards are limited to 10000 USD (minor units) per purchase"
+        );
+
+        let corporate = profile(Some(
+            BinInfo::with_bin("411111")
+                .card_type(CardType::Corporate)
+                .country("RU"),
+        ));
+        let decision = rules.evaluate(&corporate, &Purchase::new(100, "USD"));
+        let codes: Vec<_> = decision.violations.iter().map(Violation::code).collect();
+        assert_eq!(codes, ["card_type_denied", "currency_not_allowed"]);
+        assert!(!rules
+            .evaluate(&corporate, &Purchase::new(100, "RUB"))
+            .is_accepted());
+
+        // Without BIN data, only rules that don't need it can pass
+        let unknown = profile(None);
+        let decision = rules.evaluate(&unknown, &Purchase::new(50000, "USD"));
+        assert!(decision.needs_review());
+        assert_eq!(decision.unchecked, [0, 1, 2]);
+        let decision = rules.evaluate(&unknown, &Purchase::new(500, "USD"));
+        assert_eq!(decision.unchecked, [1, 2]);
+    }
+
+    #[test]
+    fn test_issuer_currency() {
+        let rules = RuleSet::new().require_issuer_currency();
+        let danish = profile(Some(BinInfo::with_bin("457173").country("dk")));
+
+        assert!(rules
+            .evaluate(&danish, &Purchase::new(1, "DKK"))
+            .is_accepted());
+        let decision = rules.evaluate(&danish, &Purchase::new(1, "EUR"));
+        assert_eq!(
+            decision.violations[0].to_string(),
+            "cards issued in DK can't pay in EUR"
+        );
+        assert_eq!(
+            rules
+                .evaluate(&profile(None), &Purchase::new(1, "EUR"))
+                .unchecked,
+            [0]
+        );
+        assert!(RuleSet::new()
+            .evaluate(&profile(None), &Purchase::new(1, "EUR"))
+            .is_accepted());
+    }
+}
@@ -0,0 +1,157 @@
+//! Correction of OCR and voice-transcription mistakes in card numbers.
+//!
+//! Card scanners and speech-to-text engines often return letters that look
+//! or sound like digits, which [`validate`](crate::validate()) rejects with
+//! [`InvalidCharacter`](crate::ValidationError::InvalidCharacter).
+//! [`sanitize_ocr`] replaces the usual confusions and reports each change so
+//! the app can highlight the corrected positions for the user to confirm:
+//!
+//! | Read as | Corrected to |
+//! |---------|--------------|
+//! | `O`, `o` | `0` |
+//! | `I`, `l`, `\|` | `1` |
+//! | `S`, `s` | `5` |
+//! | `B` | `8` |
+//!
+//! Separators and any other characters are left alone. Correction is opt-in:
+//! call [`sanitize_ocr`] yourself, or enable
+//! [`Validator::with_ocr_correction`](crate::Validator::with_ocr_correction)
+//! to apply it before every validation.
+//!
+//! # Example
+//!
+//! ```
+//! use cc_validator::sanitize::sanitize_ocr;
+//! use cc_validator::validate;
+//!
+//! let fixed = sanitize_ocr("4lll 1111 IIII 1lll");
+//! assert_eq!(fixed.substitutions().len(), 10);
+//! assert!(validate(fixed.as_str()).is_ok());
+//!
+//! let sub = fixed.substitutions()[0];
+//! assert_eq!((sub.position, sub.from, sub.to), (1, 'l', '1'));
+//! ```
+
+use crate::mask::mask_string;
+use std::fmt;
+use zeroize::Zeroize;
+
+/// One character replaced by [`sanitize_ocr`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Substitution {
+    /// Position in the input, in characters (0-indexed).
+    pub position: usize,
+    /// The character that was read.
+    pub from: char,
+    /// The digit it was replaced with.
+    pub to: char,
+}
+
+/// A corrected card number and the substitutions made.
+///
+/// The text is a card number, so `Debug` masks it and it is zeroized on
+/// drop.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Sanitized {
+    text: String,
+    substitutions: Vec<Substitution>,
+}
+
+impl Sanitized {
+    /// Returns the corrected text.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the substitutions made, in input order.
+    pub fn substitutions(&self) -> &[Substitution] {
+        &self.substitutions
+    }
+
+    /// Returns true if any character was replaced.
+    pub fn is_corrected(&self) -> bool {
+        !self.substitutions.is_empty()
+    }
+}
+
+impl fmt::Debug for Sanitized {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sanitized")
+            .field("text", &mask_string(&self.text))
+            .field("substitutions", &self.substitutions)
+            .finish()
+    }
+}
+
+impl Drop for Sanitized {
+    fn drop(&mut self) {
+        self.text.zeroize();
+    }
+}
+
+/// Returns the digit a misread character most likely stands for.
+pub fn ocr_digit(c: char) -> Option<char> {
+    match c {
+        'O' | 'o' => Some('0'),
+        'I' | 'l' | '|' => Some('1'),
+        'S' | 's' => Some('5'),
+        'B' => Some('8'),
+        _ => None,
+    }
+}
+
+/// Replaces characters commonly misread for digits (see the
+/// [module docs](self)).
+pub fn sanitize_ocr(input: &str) -> Sanitized {
+    let mut text = String::with_capacity(input.len());
+    let mut substitutions = Vec::new();
+    for (position, c) in input.chars().enumerate() {
+        match ocr_digit(c) {
+            Some(to) => {
+                substitutions.push(Substitution {
+                    position,
+                    from: c,
+                    to,
+                });
+                text.push(to);
+            }
+            None => text.push(c),
+        }
+    }
+    Sanitized {
+        text,
+        substitutions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{validate, ValidationError};
+
+    #[test]
+    fn test_sanitize_ocr() {
+        let fixed = sanitize_ocr("5SOO-OOOO-OOOO-OOO4");
+        assert_eq!(fixed.as_str(), "5500-0000-0000-0004");
+        assert_eq!(fixed.substitutions().len(), 14);
+        assert_eq!(
+            fixed.substitutions()[0],
+            Substitution {
+                position: 1,
+                from: 'S',
+                to: '5'
+            }
+        );
+        assert!(!format!("{:?}", fixed).contains("5500"));
+
+        let clean = sanitize_ocr("4111 1111 1111 1111");
+        assert!(!clean.is_corrected());
+        assert_eq!(clean.as_str(), "4111 1111 1111 1111");
+
+        // Characters without a likely digit are left for validation to reject
+        assert!(matches!(
+            validate(sanitize_ocr("4111 1111 1111 111X").as_str()),
+            Err(ValidationError::InvalidCharacter { character: 'X', .. })
+        ));
+    }
+}
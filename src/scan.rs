@@ -0,0 +1,357 @@
+//! Finding card numbers in free text.
+//!
+//! Scans logs, exports and other text for runs of 12-19 digits (optionally
+//! grouped with single spaces or dashes) that pass full validation. Each
+//! [`Finding`] carries only the brand and a masked number, never the PAN, so
+//! findings can be forwarded to a log pipeline or SIEM as they are.
+//!
+//! A candidate must not touch letters or other digits on either side, so
+//! order ids, hashes and long numeric tokens are not reported piecewise.
+//!
+//! [`redact`] masks candidates in place, valid or not, for text that is about
+//! to be logged. It casts a wider net: any separators, and no boundary
+//! rules.
+//!
+//! # Stability
+//!
+//! Experimental: the candidate rules and the [`Finding`] fields may change
+//! in a minor release as false positives and misses are tuned. This module
+//! is not part of the [prelude](crate::prelude).
+//!
+//! # Example
+//!
+//! ```
+//! use cc_validator::scan::find_in_line;
+//! use cc_validator::CardBrand;
+//!
+//! let findings = find_in_line("paid with 4111 1111 1111 1111 at 10:42");
+//! assert_eq!(findings.len(), 1);
+//! assert_eq!(findings[0].brand, CardBrand::Visa);
+//! assert_eq!(findings[0].column, 11);
+//! assert_eq!(findings[0].masked, "411111******1111");
+//! ```
+
+use crate::card::{CardBrand, MAX_CARD_DIGITS, MIN_CARD_DIGITS};
+use crate::luhn::Accumulator;
+use crate::validate::validate_digits;
+use std::io::{self, BufRead};
+use zeroize::Zeroize;
+
+/// A card number found in text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Finding {
+    /// 1-based line number (always 1 for [`find_in_line`]).
+    pub line: usize,
+    /// 1-based character column where the number starts.
+    pub column: usize,
+    /// Detected card brand.
+    pub brand: CardBrand,
+    /// The number with only the BIN and last four digits visible.
+    pub masked: String,
+}
+
+/// Finds valid card numbers in a single line of text.
+pub fn find_in_line(text: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for_each_candidate(text, Rules::Findings, |candidate| {
+        if !candidate.luhn.is_valid() {
+            return;
+        }
+        if let Ok(card) = validate_digits(candidate.digits) {
+            findings.push(Finding {
+                line: 1,
+                column: text[..candidate.positions[0]].chars().count() + 1,
+                brand: card.brand(),
+                masked: card.masked_with_bin(),
+            });
+        }
+    });
+    findings
+}
+
+/// Replaces every digit but the last four of anything that looks like a
+/// card number with `*`.
+///
+/// Unlike [`find_in_line`], candidates don't have to pass validation:
+/// mistyped numbers are still sensitive, so every run of 12 or more digits
+/// is redacted, even when it touches letters. Digits may be grouped with
+/// any run of spaces, dashes, dots, `+` or `%20`, so form-encoded and
+/// URL-encoded numbers are caught too. Separators are kept, so the text
+/// keeps its shape (and JSON stays valid). Use this before writing
+/// untrusted text, such as request bodies, to logs.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::scan::redact;
+///
+/// assert_eq!(
+///     redact(r#"{"card_number":"4111 1111 1111 1112","order":42}"#),
+///     r#"{"card_number":"**** **** **** 1112","order":42}"#
+/// );
+/// assert_eq!(redact("pan=4111+1111+1111+1112"), "pan=****+****+****+1112");
+/// ```
+pub fn redact(text: &str) -> String {
+    let mut out = text.as_bytes().to_vec();
+    for_each_candidate(text, Rules::Redaction, |candidate| {
+        let hidden = candidate.positions.len().saturating_sub(4);
+        for &pos in &candidate.positions[..hidden] {
+            out[pos] = b'*';
+        }
+    });
+    // Only ASCII digits were replaced with ASCII, so this can't fail
+    String::from_utf8(out).unwrap_or_default()
+}
+
+/// Returns true if `text` has anything [`redact`] would mask, valid or not.
+///
+/// The CLI's `--stdin-only` mode uses this to refuse arguments carrying card
+/// numbers.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::scan::contains_card_number;
+///
+/// assert!(contains_card_number("--note=4111-1111-1111-1112"));
+/// assert!(!contains_card_number("order 12345"));
+/// ```
+pub fn contains_card_number(text: &str) -> bool {
+    let mut found = false;
+    for_each_candidate(text, Rules::Redaction, |_| found = true);
+    found
+}
+
+/// Which runs of digits count as candidates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rules {
+    /// 12-19 digits grouped with single spaces or dashes, not touching
+    /// letters or other digits, as reported by [`find_in_line`].
+    Findings,
+    /// 12 or more digits grouped with any run of separators, wherever they
+    /// are, as masked by [`redact`].
+    Redaction,
+}
+
+/// Returns the length of the separator `bytes` starts with, or 0.
+fn separator_len(bytes: &[u8], rules: Rules) -> usize {
+    match (bytes, rules) {
+        ([b' ' | b'-', ..], _) => 1,
+        ([b'.' | b'+', ..], Rules::Redaction) => 1,
+        ([b'%', b'2', b'0', ..], Rules::Redaction) => 3,
+        _ => 0,
+    }
+}
+
+/// A run of digits found by [`for_each_candidate`].
+struct Candidate<'a> {
+    /// Byte offset of every digit in the run, in order.
+    positions: &'a [usize],
+    /// The digit values; empty for runs longer than a card number.
+    digits: &'a [u8],
+    /// Luhn state of the whole run, so failing runs can be skipped cheaply.
+    luhn: &'a Accumulator,
+}
+
+/// Calls `f` for every run of digits in `text` that is a candidate under
+/// `rules`.
+fn for_each_candidate(text: &str, rules: Rules, mut f: impl FnMut(&Candidate<'_>)) {
+    let bytes = text.as_bytes();
+    let mut digits = [0u8; MAX_CARD_DIGITS];
+    let mut positions = Vec::new();
+    let mut luhn = Accumulator::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if rules == Rules::Redaction {
+            // Skip separators here so the digits of a leading %20 don't
+            // start a run
+            let sep = separator_len(&bytes[i..], rules);
+            if sep > 0 {
+                i += sep;
+                continue;
+            }
+        }
+        let touches = i > 0 && bytes[i - 1].is_ascii_alphanumeric();
+        if !bytes[i].is_ascii_digit() || (rules == Rules::Findings && touches) {
+            i += 1;
+            continue;
+        }
+
+        positions.clear();
+        luhn.reset();
+        while i < bytes.len() {
+            let b = bytes[i];
+            if b.is_ascii_digit() {
+                if positions.len() < digits.len() {
+                    digits[positions.len()] = b - b'0';
+                }
+                positions.push(i);
+                luhn.push(b - b'0');
+                i += 1;
+                continue;
+            }
+            let mut next = i;
+            loop {
+                let sep = separator_len(&bytes[next..], rules);
+                if sep == 0 {
+                    break;
+                }
+                next += sep;
+                if rules == Rules::Findings {
+                    break;
+                }
+            }
+            if next == i || !bytes.get(next).is_some_and(u8::is_ascii_digit) {
+                break;
+            }
+            i = next;
+        }
+
+        let count = positions.len();
+        let candidate = match rules {
+            Rules::Findings => {
+                !bytes.get(i).is_some_and(u8::is_ascii_alphanumeric)
+                    && (MIN_CARD_DIGITS..=MAX_CARD_DIGITS).contains(&count)
+            }
+            Rules::Redaction => count >= MIN_CARD_DIGITS,
+        };
+        if candidate {
+            f(&Candidate {
+                positions: &positions,
+                digits: if count <= MAX_CARD_DIGITS {
+                    &digits[..count]
+                } else {
+                    &[]
+                },
+                luhn: &luhn,
+            });
+        }
+    }
+
+    digits.zeroize();
+}
+
+/// Finds valid card numbers in every line of `reader`.
+///
+/// Invalid UTF-8 is replaced rather than rejected, so binary files and
+/// mixed-encoding logs can be scanned too. Line buffers are zeroized after
+/// use.
+pub fn scan_reader<R: BufRead>(mut reader: R) -> io::Result<Vec<Finding>> {
+    let mut findings = Vec::new();
+    let mut buf = Vec::new();
+    let mut line = 0;
+
+    loop {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+        line += 1;
+        let text = String::from_utf8_lossy(&buf);
+        findings.extend(
+            find_in_line(&text)
+                .into_iter()
+                .map(|finding| Finding { line, ..finding }),
+        );
+        if let std::borrow::Cow::Owned(mut text) = text {
+            text.zeroize();
+        }
+    }
+
+    buf.zeroize();
+    Ok(findings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finds_grouped_and_plain() {
+        let found = find_in_line("a=4111111111111111, b=5500-0000-0000-0004; c=378282246310005");
+        let brands: Vec<_> = found.iter().map(|f| f.brand).collect();
+        assert_eq!(
+            brands,
+            [CardBrand::Visa, CardBrand::Mastercard, CardBrand::Amex]
+        );
+        assert_eq!(found[0].column, 3);
+        assert_eq!(found[1].masked, "550000******0004");
+        assert!(found.iter().all(|f| !f.masked.contains("00000000")));
+    }
+
+    #[test]
+    fn test_ignores_embedded_and_invalid() {
+        // Touching letters or extra digits
+        assert!(find_in_line("id4111111111111111").is_empty());
+        assert!(find_in_line("4111111111111111x").is_empty());
+        assert!(find_in_line("94111111111111111111").is_empty());
+        // Fails Luhn, too short, doubled separator
+        assert!(find_in_line("4111111111111112").is_empty());
+        assert!(find_in_line("41111111111").is_empty());
+        assert!(find_in_line("4111  1111 1111 1111").is_empty());
+    }
+
+    #[test]
+    fn test_redact() {
+        assert_eq!(
+            redact("pan=5500-0000-0000-0004 bad=4111111111111112"),
+            "pan=****-****-****-0004 bad=************1112"
+        );
+        // Short numbers are left alone
+        assert_eq!(
+            redact("order 12345, id 41111111111"),
+            "order 12345, id 41111111111"
+        );
+        assert_eq!(redact("café 378282246310005"), "café ***********0005");
+    }
+
+    #[test]
+    fn test_redact_separators() {
+        assert_eq!(
+            redact(r#"{"card_number":"4111.1111.1111.1111"}"#),
+            r#"{"card_number":"****.****.****.1111"}"#
+        );
+        assert_eq!(
+            redact("card_number=4111+1111+1111+1111&cvv=123"),
+            "card_number=****+****+****+1111&cvv=123"
+        );
+        assert_eq!(
+            redact("/pay?pan=4111%201111%201111%201111"),
+            "/pay?pan=****%20****%20****%201111"
+        );
+        assert_eq!(redact("%204111111111111111"), "%20************1111");
+        // Runs of separators don't split the number
+        assert_eq!(redact("4111  1111 1111 1111"), "****  **** **** 1111");
+        assert_eq!(
+            redact("4111 - 1111 - 1111 - 1111"),
+            "**** - **** - **** - 1111"
+        );
+    }
+
+    #[test]
+    fn test_redact_touching_letters() {
+        assert_eq!(redact("pan:4111111111111111x"), "pan:************1111x");
+        assert_eq!(redact("id a4111111111111111"), "id a************1111");
+        // Longer runs may hide a card number inside
+        assert_eq!(redact("94111111111111111111"), "****************1111");
+    }
+
+    #[test]
+    fn test_contains_card_number() {
+        assert!(contains_card_number("4111 1111 1111 1111"));
+        assert!(contains_card_number("card=4111111111111112"));
+        assert!(!contains_card_number("41111111111"));
+        assert!(contains_card_number("id4111111111111111"));
+        assert!(contains_card_number("4111.1111.1111.1111"));
+        assert!(!contains_card_number(""));
+    }
+
+    #[test]
+    fn test_scan_reader_lines() {
+        let text = b"nothing here\n\xff card 4111111111111111\r\nsecond 4242424242424242 and 5555555555554444\n";
+        let found = scan_reader(&text[..]).unwrap();
+        let positions: Vec<_> = found.iter().map(|f| (f.line, f.column)).collect();
+        assert_eq!(positions, [(2, 8), (3, 8), (3, 29)]);
+    }
+}
@@ -0,0 +1,673 @@
+//! Request and response types for the REST API.
+//!
+//! These are the wire types of `ccvalidator-server`, shared with the
+//! [`client`](crate::client) so both sides serialize the same JSON. With the
+//! `server` feature they also derive the OpenAPI schema.
+//!
+//! # Versioning
+//!
+//! Every top-level response carries an `api_version` field holding
+//! [`API_VERSION`]. The major version changes only when a field is removed
+//! or changes meaning; new optional fields bump the minor version. Clients
+//! should ignore fields they don't know.
+//!
+//! # Feature
+//!
+//! Requires the `server` or `client` feature.
+
+use crate::bin::{currency_for_country, BinInfo, CardLevel, CardType};
+use serde::{Deserialize, Serialize};
+
+/// Version of the request/response schemas in this module.
+pub const API_VERSION: &str = "1.5.0";
+
+/// Returns [`API_VERSION`] as an owned string, for filling response fields.
+pub fn api_version() -> String {
+    API_VERSION.to_string()
+}
+
+/// Returns `true` if a server reporting `version` speaks a schema compatible
+/// with this crate's [`API_VERSION`] (same major version).
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::schemas::is_compatible;
+///
+/// assert!(is_compatible("1.0.0"));
+/// assert!(!is_compatible("2.0.0"));
+/// ```
+pub fn is_compatible(version: &str) -> bool {
+    let major = |v: &str| v.split('.').next().map(str::to_string);
+    major(version) == major(API_VERSION)
+}
+
+/// How card numbers are masked in validation responses.
+///
+/// Ordered from least to most exposed. The server's `--max-mask` policy caps
+/// the style a client may request; anything above the cap is downgraded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum MaskStyle {
+    /// Only `last_four` is returned; `masked` is omitted
+    LastFour,
+    /// `****-****-****-1234`
+    #[default]
+    Masked,
+    /// `411111******1234` (first 6 and last 4 digits)
+    MaskedWithBin,
+}
+
+impl MaskStyle {
+    /// Parses a mask style name, accepting the short aliases `last4` and
+    /// `with_bin`.
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "last_four" | "last4" => Some(MaskStyle::LastFour),
+            "masked" => Some(MaskStyle::Masked),
+            "masked_with_bin" | "with_bin" => Some(MaskStyle::MaskedWithBin),
+            _ => None,
+        }
+    }
+}
+
+/// Body of `POST /validate`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "server", schema(example = json!({"card_number": "4111-1111-1111-1111", "mask": "masked"})))]
+pub struct ValidateRequest {
+    /// Card number to validate. Accepts digits with optional spaces or dashes as separators.
+    /// Example formats: "4111111111111111", "4111-1111-1111-1111", "4111 1111 1111 1111"
+    pub card_number: String,
+    /// Masking style for the response (default: masked). Capped by server policy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mask: Option<MaskStyle>,
+    /// CVV to check against the detected brand in the same call; empty for brands without one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cvv: Option<String>,
+    /// Accept numbers that pass the Luhn check but match no known brand; they come back with
+    /// no `brand` and `brand_known: false`, or fail as `unknown_brand` when the allowed brands
+    /// are restricted
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_unknown_brand: bool,
+}
+
+/// Result of validating one card number.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "server", schema(example = json!({
+    "api_version": API_VERSION,
+    "valid": true,
+    "brand": "Visa",
+    "brand_known": true,
+    "last_four": "1111",
+    "test_card": true,
+    "masked": "****-****-****-1111",
+    "warnings": ["test_card"]
+})))]
+pub struct ValidateResponse {
+    /// Schema version of the response (omitted on results nested in a batch)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub api_version: String,
+    /// Whether the card number passed all validation checks (Luhn checksum, length, brand detection)
+    pub valid: bool,
+    /// Detected card brand. Supports: Visa, Mastercard, American Express, Discover, Diners Club, JCB, UnionPay, Maestro, Mir, RuPay, Verve, Elo, Troy, BC Card, Interac, Dankort, Bancontact, UATP, WEX, Voyager
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brand: Option<String>,
+    /// Whether the brand was detected; `false` (with no `brand`) for unknown brands accepted
+    /// with `allow_unknown_brand` or by a lenient server
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brand_known: Option<bool>,
+    /// Last 4 digits of the card number (safe for display per PCI-DSS)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_four: Option<String>,
+    /// Whether the number is a well-known processor test card (e.g. 4111111111111111)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_card: Option<bool>,
+    /// ISO 4217 currency of the issuer country (only when the server has a BIN database
+    /// with a country for the card's BIN)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Masked card number, ****-****-****-1234 by default or 411111******1234 with the BIN
+    /// (omitted for the last_four style)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub masked: Option<String>,
+    /// Human-readable error message explaining why validation failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Machine-readable error code, e.g. `invalid_checksum`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    /// Error category: `format`, `length`, `checksum` and `brand` errors can be fixed by
+    /// correcting the input; `policy` errors need a different card
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_category: Option<String>,
+    /// Hint on how to fix the input, e.g. `check that no digits are missing`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+    /// Character index (0-based) of the invalid character, for `invalid_character` errors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_position: Option<usize>,
+    /// Number of digits before the invalid character, for `invalid_character` errors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_digit_index: Option<usize>,
+    /// Codes of conditions that don't fail validation but deserve a look: `unusual_length`,
+    /// `test_card`, `tentative_brand` (omitted when there are none)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Whether the CVV is valid for the card's brand (only when the request had a `cvv`);
+    /// `valid` covers the card number alone
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cvv_valid: Option<bool>,
+    /// Why the CVV failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cvv_error: Option<String>,
+    /// Issuer details, when the server has a BIN database that knows the card's BIN
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bin: Option<BinDetails>,
+}
+
+/// Issuer details from the server's BIN database; fields the database lacks are omitted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "server", schema(example = json!({
+    "issuer": "Test Bank",
+    "card_type": "Credit",
+    "card_level": "Platinum",
+    "country": "US"
+})))]
+pub struct BinDetails {
+    /// Issuing bank
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    /// Card type: `Credit`, `Debit`, `Prepaid`, `Charge` or `Corporate`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub card_type: Option<String>,
+    /// Card tier, e.g. `Gold`, `Platinum` or `Business`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub card_level: Option<String>,
+    /// ISO 3166-1 alpha-2 code of the issuing country
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// Name of the issuing country
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country_name: Option<String>,
+}
+
+impl From<BinInfo> for BinDetails {
+    fn from(info: BinInfo) -> Self {
+        Self {
+            issuer: info.issuer,
+            card_type: info
+                .card_type
+                .filter(|&t| t != CardType::Unknown)
+                .map(|t| t.to_string()),
+            card_level: info
+                .card_level
+                .filter(|&l| l != CardLevel::Unknown)
+                .map(|l| l.to_string()),
+            country: info.country,
+            country_name: info.country_name,
+        }
+    }
+}
+
+impl BinDetails {
+    /// Returns the ISO 4217 currency of the issuing country, if known.
+    pub fn currency(&self) -> Option<&'static str> {
+        currency_for_country(self.country.as_deref()?)
+    }
+}
+
+/// Body of `POST /validate/batch`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "server", schema(example = json!({"card_numbers": ["4111111111111111", "5500000000000004", "378282246310005"]})))]
+pub struct BatchValidateRequest {
+    /// List of card numbers to validate. Each number can include spaces or dashes.
+    pub card_numbers: Vec<String>,
+    /// Masking style applied to every result (default: masked). Capped by server policy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mask: Option<MaskStyle>,
+    /// Validate the cards on all CPU cores (worthwhile for batches of 10k+ cards)
+    #[serde(default)]
+    pub parallel: bool,
+}
+
+/// Response of `POST /validate/batch`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct BatchValidateResponse {
+    /// Schema version of the response
+    #[serde(default)]
+    pub api_version: String,
+    /// Validation results for each card
+    pub results: Vec<ValidateResponse>,
+    /// Summary statistics
+    pub summary: BatchSummary,
+}
+
+/// Counts and timing for a batch validation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct BatchSummary {
+    /// Total cards processed
+    pub total: usize,
+    /// Number of valid cards
+    pub valid: usize,
+    /// Number of invalid cards
+    pub invalid: usize,
+    /// Whether the batch was validated in parallel
+    #[serde(default)]
+    pub parallel: bool,
+    /// Time spent validating the batch, in milliseconds
+    #[serde(default)]
+    pub elapsed_ms: f64,
+}
+
+/// Query string of the deprecated `GET /detect`; prefer [`DetectRequest`],
+/// which keeps the digits out of access logs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema, utoipa::IntoParams))]
+pub struct DetectQuery {
+    /// Card number or prefix to detect
+    pub card: String,
+}
+
+/// Body of `POST /detect`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "server", schema(example = json!({"card_number": "4571"})))]
+pub struct DetectRequest {
+    /// Card number or prefix to detect
+    pub card_number: String,
+}
+
+/// Response of `POST /detect` (and `GET /detect`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct DetectResponse {
+    /// Schema version of the response
+    #[serde(default)]
+    pub api_version: String,
+    /// Detected brand name
+    pub brand: Option<String>,
+    /// Valid lengths for this brand
+    pub valid_lengths: Option<Vec<usize>>,
+    /// Prefix rule that matched, e.g. "2221-2720"
+    #[serde(default)]
+    pub matched_range: Option<String>,
+    /// Number of leading digits the matched rule applies to
+    #[serde(default)]
+    pub prefix_length: Option<usize>,
+    /// Whether more digits could still change the brand (e.g. "4" is Visa until "4571" makes it
+    /// Dankort); `false` once the detection is final
+    #[serde(default)]
+    pub tentative: bool,
+}
+
+/// One entry of `GET /brands`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct BrandSummary {
+    /// Identifier accepted wherever a brand is requested (e.g. "amex")
+    pub id: String,
+    /// Display name (e.g. "American Express")
+    pub name: String,
+    /// Valid card number lengths
+    pub valid_lengths: Vec<usize>,
+    /// CVV length
+    pub cvv_length: usize,
+}
+
+/// Response of `GET /brands`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct BrandsResponse {
+    /// Schema version of the response
+    #[serde(default)]
+    pub api_version: String,
+    /// All supported brands
+    pub brands: Vec<BrandSummary>,
+}
+
+/// Body of `POST /format`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct FormatRequest {
+    /// Card number to format
+    pub card_number: String,
+    /// Separator character (default: space)
+    #[serde(default = "default_separator")]
+    pub separator: String,
+}
+
+fn default_separator() -> String {
+    " ".to_string()
+}
+
+/// Response of `POST /format`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct FormatResponse {
+    /// Schema version of the response
+    #[serde(default)]
+    pub api_version: String,
+    /// Formatted card number
+    pub formatted: String,
+    /// Card number with formatting stripped
+    pub stripped: String,
+}
+
+/// Body of `POST /luhn`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "server", schema(example = json!({"card_number": "4111-1111-1111-1112"})))]
+pub struct LuhnRequest {
+    /// Digits to check; non-digit characters are ignored
+    pub card_number: String,
+}
+
+/// Response of `POST /luhn`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "server", schema(example = json!({
+    "api_version": API_VERSION,
+    "valid": false,
+    "check_digit": 1,
+    "found": 2
+})))]
+pub struct LuhnResponse {
+    /// Schema version of the response
+    #[serde(default)]
+    pub api_version: String,
+    /// Whether the digits pass the Luhn checksum (brand and length are not checked)
+    pub valid: bool,
+    /// Check digit the number should end with (omitted for fewer than 2 digits)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check_digit: Option<u8>,
+    /// Check digit the number actually ends with (omitted for fewer than 2 digits)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub found: Option<u8>,
+}
+
+/// Body of `POST /mask`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "server", schema(example = json!({"card_number": "4111 1111 1111 1111", "with_bin": true})))]
+pub struct MaskRequest {
+    /// Card number to mask
+    pub card_number: String,
+    /// Masking style (default: masked). Capped by server policy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<MaskStyle>,
+    /// Keep the first 6 digits visible; shorthand for the masked_with_bin style
+    #[serde(default)]
+    pub with_bin: bool,
+}
+
+/// Response of `POST /mask`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "server", schema(example = json!({
+    "api_version": API_VERSION,
+    "masked": "411111******1111",
+    "last_four": "1111"
+})))]
+pub struct MaskResponse {
+    /// Schema version of the response
+    #[serde(default)]
+    pub api_version: String,
+    /// Masked card number (omitted for the last_four style or on error)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub masked: Option<String>,
+    /// Last 4 digits of the card number (omitted on error)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_four: Option<String>,
+    /// Why the number couldn't be masked: fewer than 4 digits, or an invalid
+    /// card for the masked_with_bin style
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Body of `POST /generate`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "server", schema(example = json!({"brand": "visa", "count": 3, "formatted": true, "seed": 42, "include_expiry": true, "include_cvv": true})))]
+pub struct GenerateRequest {
+    /// Card brand to generate, by id or name (see GET /brands for the list)
+    pub brand: String,
+    /// Number of test cards to generate (1-100, default: 1)
+    #[serde(default = "default_count")]
+    pub count: usize,
+    /// Card length, one of the brand's valid lengths (e.g. 19 for Visa, 17-19 for Discover, 12-19 for Maestro). Defaults to the brand's usual length.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length: Option<usize>,
+    /// Whether to format output with spaces (e.g., "4111 1111 1111 1111")
+    #[serde(default)]
+    pub formatted: bool,
+    /// Seed for reproducible output. The same seed and request always return the same cards and
+    /// CVVs; expiry dates are offsets from the current month, so they move forward as time passes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Use a fixed seed (0) when no `seed` is given, for reproducible fixtures
+    #[serde(default)]
+    pub deterministic: bool,
+    /// Also generate an expiry date (MM/YY) for each card
+    #[serde(default)]
+    pub include_expiry: bool,
+    /// Also generate a CVV of the correct length for each card
+    #[serde(default)]
+    pub include_cvv: bool,
+}
+
+fn default_count() -> usize {
+    1
+}
+
+impl GenerateRequest {
+    /// Creates a request for one unformatted card of `brand`, with a random seed.
+    pub fn new(brand: impl Into<String>) -> Self {
+        Self {
+            brand: brand.into(),
+            count: default_count(),
+            length: None,
+            formatted: false,
+            seed: None,
+            deterministic: false,
+            include_expiry: false,
+            include_cvv: false,
+        }
+    }
+}
+
+/// Response of `POST /generate`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct GenerateResponse {
+    /// Schema version of the response
+    #[serde(default)]
+    pub api_version: String,
+    /// Generated card numbers
+    pub cards: Vec<String>,
+    /// Seed used for generation. Pass it back to reproduce this response (expiry dates excepted,
+    /// see `GenerateRequest::seed`).
+    pub seed: u64,
+    /// Expiry dates (MM/YY), one per card, when `include_expiry` was set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiry: Option<Vec<String>>,
+    /// CVVs, one per card, when `include_cvv` was set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cvv: Option<Vec<String>>,
+}
+
+/// Body of `POST /cvv/validate`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "server", schema(example = json!({"cvv": "123", "brand": "visa"})))]
+pub struct CvvRequest {
+    /// CVV/CVC/CID code to validate (3-4 digits)
+    pub cvv: String,
+    /// Card brand for brand-specific validation. American Express requires 4 digits, all others require 3.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub brand: Option<String>,
+}
+
+/// Response of `POST /cvv/validate`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct CvvResponse {
+    /// Schema version of the response
+    #[serde(default)]
+    pub api_version: String,
+    /// Whether the CVV is valid
+    pub valid: bool,
+    /// CVV length
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length: Option<usize>,
+    /// Error message if validation failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Body of `POST /expiry/validate`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[cfg_attr(feature = "server", schema(example = json!({"date": "12/25"})))]
+pub struct ExpiryRequest {
+    /// Expiry date in various formats: MM/YY, MM/YYYY, MMYY, MMYYYY, MM-YY, MM-YYYY
+    pub date: String,
+}
+
+/// Response of `POST /expiry/validate`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct ExpiryResponse {
+    /// Schema version of the response
+    #[serde(default)]
+    pub api_version: String,
+    /// Whether the expiry is valid
+    pub valid: bool,
+    /// Month (1-12)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub month: Option<u8>,
+    /// Year (4 digits)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub year: Option<u16>,
+    /// Whether the card is expired
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expired: Option<bool>,
+    /// Formatted date (MM/YY)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub formatted: Option<String>,
+    /// The parsed date, serialized as MM/YYYY
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiry: Option<crate::expiry::ExpiryDate>,
+    /// Error message if validation failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response of `GET /health`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct HealthResponse {
+    /// Schema version of the response
+    #[serde(default)]
+    pub api_version: String,
+    /// Service status
+    pub status: String,
+    /// Server (crate) version
+    pub version: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_style_from_name() {
+        assert_eq!(MaskStyle::from_name("last4"), Some(MaskStyle::LastFour));
+        assert_eq!(
+            MaskStyle::from_name("WITH_BIN"),
+            Some(MaskStyle::MaskedWithBin)
+        );
+        assert_eq!(MaskStyle::from_name("full"), None);
+        assert!(MaskStyle::LastFour < MaskStyle::MaskedWithBin);
+    }
+
+    #[test]
+    fn test_nested_result_omits_version() {
+        let result = ValidateResponse {
+            api_version: String::new(),
+            valid: false,
+            brand: None,
+            brand_known: None,
+            last_four: None,
+            test_card: None,
+            currency: None,
+            masked: None,
+            error: Some("card number is empty".to_string()),
+            error_code: None,
+            error_category: None,
+            suggestion: None,
+            error_position: None,
+            error_digit_index: None,
+            warnings: Vec::new(),
+            cvv_valid: None,
+            cvv_error: None,
+            bin: None,
+        };
+        let json = serde_json::to_string(&result).unwrap();
+        assert_eq!(json, r#"{"valid":false,"error":"card number is empty"}"#);
+    }
+
+    #[test]
+    fn test_bin_details() {
+        let info = BinInfo::with_bin("411111")
+            .issuer("Test Bank")
+            .card_type(CardType::Debit)
+            .country("US");
+        let details = BinDetails::from(info);
+        assert_eq!(details.currency(), Some("USD"));
+        let json = serde_json::to_string(&details).unwrap();
+        assert_eq!(
+            json,
+            r#"{"issuer":"Test Bank","card_type":"Debit","country":"US"}"#
+        );
+
+        let info = BinInfo::with_bin("411111").card_type(CardType::Unknown);
+        assert_eq!(BinDetails::from(info), BinDetails::default());
+        assert!(BinDetails::default().currency().is_none());
+    }
+
+    #[test]
+    fn test_request_defaults() {
+        let req: GenerateRequest = serde_json::from_str(r#"{"brand": "visa"}"#).unwrap();
+        assert_eq!(req.count, 1);
+        assert!(req.seed.is_none());
+
+        let req: FormatRequest = serde_json::from_str(r#"{"card_number": "4111"}"#).unwrap();
+        assert_eq!(req.separator, " ");
+
+        let req: MaskRequest = serde_json::from_str(r#"{"card_number": "4111"}"#).unwrap();
+        assert!(req.style.is_none());
+        assert!(!req.with_bin);
+    }
+
+    #[test]
+    fn test_older_response_without_version() {
+        let resp: HealthResponse =
+            serde_json::from_str(r#"{"status": "ok", "version": "0.1.0"}"#).unwrap();
+        assert!(resp.api_version.is_empty());
+    }
+
+    #[test]
+    fn test_is_compatible() {
+        assert!(is_compatible(API_VERSION));
+        assert!(is_compatible("1.9.3"));
+        assert!(!is_compatible("0.1.0"));
+        assert!(!is_compatible(""));
+    }
+}
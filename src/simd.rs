@@ -20,9 +20,42 @@
 //!
 //! The SIMD implementation processes 16 digits at once, providing
 //! significant speedup for 16+ digit card numbers on supported hardware.
+//!
+//! # Lanes
+//!
+//! [`validate_lanes_16`] and [`validate_lanes_32`] check 16 or 32 cards of
+//! any length at once. The digits are transposed into a matrix aligned at
+//! the check digit, so each SIMD lane holds one card and each row one digit
+//! position; a card's Luhn sum builds up in its lane as the rows are added.
+//! [`validate_batch_simd`] and
+//! [`BatchValidator::passes_luhn_all`](crate::BatchValidator::passes_luhn_all)
+//! run in groups of 32 this way. Without the `simd` feature the same
+//! functions check each card with [`luhn::validate`](crate::luhn::validate).
+//!
+//! ```
+//! use cc_validator::simd::validate_lanes_16;
+//!
+//! let visa: &[u8] = &[4, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1];
+//! let amex: &[u8] = &[3, 7, 8, 2, 8, 2, 2, 4, 6, 3, 1, 0, 0, 0, 5];
+//! let mut cards = [visa; 16];
+//! cards[1] = amex;
+//! cards[2] = &[];
+//! let valid = validate_lanes_16(&cards);
+//! assert!(valid[0] && valid[1] && !valid[2]);
+//! ```
+//!
+//! # Stability
+//!
+//! Experimental: this module tracks the unstable `portable_simd` API and may
+//! change in a minor release. It is not part of the [prelude](crate::prelude).
 
 #[cfg(feature = "simd")]
-use std::simd::{cmp::SimdPartialOrd, u8x16};
+use crate::card::MAX_SUPPORTED_DIGITS;
+#[cfg(feature = "simd")]
+use std::simd::{
+    cmp::{SimdPartialEq, SimdPartialOrd},
+    u8x16, u8x32, Select,
+};
 
 /// Validates a 16-digit card number using SIMD.
 ///
@@ -68,7 +101,7 @@ pub fn validate_16_simd(digits: &[u8; 16]) -> bool {
     // Sum all values
     let sum: u32 = final_values.as_array().iter().map(|&x| x as u32).sum();
 
-    sum % 10 == 0
+    sum.is_multiple_of(10)
 }
 
 /// Validates any length card number using SIMD where possible.
@@ -95,23 +128,93 @@ pub fn validate_simd(digits: &[u8]) -> bool {
     }
 }
 
+/// Transposes `cards` into rows aligned at the check digit: row `r` holds
+/// every card's `r`th digit from the right, or 0 where a card is shorter.
+#[cfg(feature = "simd")]
+fn transpose<const N: usize>(cards: &[&[u8]; N]) -> [[u8; N]; MAX_SUPPORTED_DIGITS] {
+    let mut rows = [[0u8; N]; MAX_SUPPORTED_DIGITS];
+    for (lane, digits) in cards.iter().enumerate() {
+        for (row, &d) in digits.iter().rev().take(MAX_SUPPORTED_DIGITS).enumerate() {
+            rows[row][lane] = d;
+        }
+    }
+    rows
+}
+
+/// Rechecks the lanes the matrix can't represent: empty cards (a zero sum
+/// would pass) and cards longer than [`MAX_SUPPORTED_DIGITS`].
+#[cfg(feature = "simd")]
+fn fix_up_lanes<const N: usize>(cards: &[&[u8]; N], valid: &mut [bool; N]) {
+    for (lane, digits) in cards.iter().enumerate() {
+        if digits.is_empty() || digits.len() > MAX_SUPPORTED_DIGITS {
+            valid[lane] = crate::luhn::validate(digits);
+        }
+    }
+}
+
+/// Defines a lane-parallel Luhn check over a SIMD vector of `$lanes` bytes.
+///
+/// A lane's sum is at most 9 per row, 207 in all, so it fits in a `u8`.
+#[cfg(feature = "simd")]
+macro_rules! lanes_fn {
+    ($(#[$attr:meta])* $name:ident, $vector:ty, $lanes:literal) => {
+        $(#[$attr])*
+        pub fn $name(cards: &[&[u8]; $lanes]) -> [bool; $lanes] {
+            let nine = <$vector>::splat(9);
+            let mut sum = <$vector>::splat(0);
+            for (row, digits) in transpose(cards).iter().enumerate() {
+                let d = <$vector>::from_array(*digits);
+                sum += if row % 2 == 1 {
+                    let doubled = d + d;
+                    doubled.simd_gt(nine).select(doubled - nine, doubled)
+                } else {
+                    d
+                };
+            }
+            let zero = <$vector>::splat(0);
+            let mut valid = (sum % <$vector>::splat(10)).simd_eq(zero).to_array();
+            fix_up_lanes(cards, &mut valid);
+            valid
+        }
+    };
+}
+
+#[cfg(feature = "simd")]
+lanes_fn!(
+    /// Luhn-checks 16 cards of any length at once, one per SIMD lane.
+    ///
+    /// Each card is a slice of digits (0-9). Returns the same results as
+    /// [`luhn::validate`](crate::luhn::validate) on each card.
+    validate_lanes_16,
+    u8x16,
+    16
+);
+
+#[cfg(feature = "simd")]
+lanes_fn!(
+    /// Luhn-checks 32 cards of any length at once, one per SIMD lane.
+    ///
+    /// Each card is a slice of digits (0-9). Returns the same results as
+    /// [`luhn::validate`](crate::luhn::validate) on each card.
+    validate_lanes_32,
+    u8x32,
+    32
+);
+
 /// SIMD-accelerated batch validation.
 ///
-/// Validates multiple 16-digit cards in parallel using SIMD.
-/// Cards of other lengths are processed with the scalar implementation.
+/// Validates cards 32 at a time with [`validate_lanes_32`]; the last
+/// partial group is checked one card at a time with [`validate_simd`].
 #[cfg(feature = "simd")]
 pub fn validate_batch_simd(cards: &[&[u8]]) -> Vec<bool> {
-    cards
-        .iter()
-        .map(|digits| {
-            if digits.len() == 16 {
-                let arr: [u8; 16] = (*digits).try_into().unwrap();
-                validate_16_simd(&arr)
-            } else {
-                crate::luhn::validate(digits)
-            }
-        })
-        .collect()
+    let mut results = Vec::with_capacity(cards.len());
+    let groups = cards.chunks_exact(32);
+    let rest = groups.remainder();
+    for group in groups {
+        results.extend(validate_lanes_32(group.try_into().unwrap()));
+    }
+    results.extend(rest.iter().map(|digits| validate_simd(digits)));
+    results
 }
 
 // Provide stub implementations when SIMD is not enabled
@@ -137,6 +240,24 @@ pub fn validate_simd(digits: &[u8]) -> bool {
     crate::luhn::validate(digits)
 }
 
+/// Luhn-checks 16 cards of any length.
+///
+/// This is a stub that falls back to the scalar implementation
+/// when the `simd` feature is not enabled.
+#[cfg(not(feature = "simd"))]
+pub fn validate_lanes_16(cards: &[&[u8]; 16]) -> [bool; 16] {
+    cards.map(crate::luhn::validate)
+}
+
+/// Luhn-checks 32 cards of any length.
+///
+/// This is a stub that falls back to the scalar implementation
+/// when the `simd` feature is not enabled.
+#[cfg(not(feature = "simd"))]
+pub fn validate_lanes_32(cards: &[&[u8]; 32]) -> [bool; 32] {
+    cards.map(crate::luhn::validate)
+}
+
 /// Batch validates multiple card numbers.
 ///
 /// This is a stub that falls back to the scalar implementation
@@ -198,6 +319,33 @@ mod tests {
         assert_eq!(results, vec![true, true, false]);
     }
 
+    #[test]
+    fn test_lanes_match_scalar() {
+        // Every length from empty to past MAX_SUPPORTED_DIGITS, with a valid
+        // and an invalid check digit each
+        let mut cards: Vec<Vec<u8>> = Vec::new();
+        for len in 0..=25 {
+            let body: Vec<u8> = (0..len).map(|i| ((i * 7 + len) % 10) as u8).collect();
+            for offset in 0..2 {
+                let mut card = body.clone();
+                if len > 0 {
+                    let check = crate::luhn::generate_check_digit(&body[..len - 1]);
+                    card[len - 1] = (check + offset) % 10;
+                }
+                cards.push(card);
+            }
+        }
+        let slices: Vec<&[u8]> = cards.iter().map(Vec::as_slice).collect();
+        let expected: Vec<bool> = slices.iter().map(|d| crate::luhn::validate(d)).collect();
+        assert!(expected.iter().filter(|&&v| v).count() >= 25);
+
+        assert_eq!(validate_batch_simd(&slices), expected);
+        let lanes_16 = validate_lanes_16(slices[..16].try_into().unwrap());
+        assert_eq!(lanes_16[..], expected[..16]);
+        let lanes_32 = validate_lanes_32(slices[20..52].try_into().unwrap());
+        assert_eq!(lanes_32[..], expected[20..52]);
+    }
+
     #[test]
     fn test_simd_matches_scalar() {
         // Ensure SIMD gives same results as scalar
@@ -18,7 +18,24 @@
 //!
 //! assert_eq!(valid_count, 2);
 //! ```
+//!
+//! # Chunks
+//!
+//! [`ChunkedValidateExt::validate_chunks`] sits between this module and
+//! [`batch`](crate::batch): it pulls up to `n` numbers at a time from the
+//! iterator and validates them with a [`BatchValidator`], yielding one
+//! `Vec` of results per chunk. That suits per-chunk database writes or
+//! handing chunks to worker threads, without collecting the whole input.
+//!
+//! ```
+//! use cc_validator::stream::ChunkedValidateExt;
+//!
+//! let cards = ["4111111111111111", "invalid", "5500000000000004"];
+//! let sizes: Vec<_> = cards.iter().validate_chunks(2).map(|c| c.len()).collect();
+//! assert_eq!(sizes, [2, 1]);
+//! ```
 
+use crate::batch::BatchValidator;
 use crate::error::ValidationError;
 use crate::validate::validate;
 use crate::ValidatedCard;
@@ -242,6 +259,123 @@ impl<I: Iterator + Sized> ValidateExt for I {
     }
 }
 
+/// A streaming validator that yields results a chunk at a time.
+///
+/// This struct is created by the `validate_chunks` method on iterators.
+/// Each chunk holds up to `size` results, in input order; only the last
+/// may be shorter.
+#[derive(Debug)]
+pub struct ChunkedValidateStream<I: Iterator> {
+    inner: I,
+    size: usize,
+    batch: BatchValidator,
+    buffer: Vec<I::Item>,
+}
+
+impl<I: Iterator> ChunkedValidateStream<I> {
+    /// Creates a new ChunkedValidateStream validating with `batch`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn new(inner: I, size: usize, batch: BatchValidator) -> Self {
+        assert!(size != 0, "chunk size must be non-zero");
+        Self {
+            inner,
+            size,
+            batch,
+            buffer: Vec::with_capacity(size.min(4096)),
+        }
+    }
+
+    /// Consumes the stream and returns the batch validator.
+    #[inline]
+    pub fn into_batch(self) -> BatchValidator {
+        self.batch
+    }
+}
+
+impl<I, S> Iterator for ChunkedValidateStream<I>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    type Item = Vec<Result<ValidatedCard, ValidationError>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buffer.clear();
+        self.buffer.extend(self.inner.by_ref().take(self.size));
+        if self.buffer.is_empty() {
+            return None;
+        }
+        Some(self.batch.validate_all(&self.buffer))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.inner.size_hint();
+        (
+            lower.div_ceil(self.size),
+            upper.map(|n| n.div_ceil(self.size)),
+        )
+    }
+}
+
+/// Extension trait for validating any iterator a chunk at a time.
+///
+/// This trait is automatically implemented for all iterators.
+pub trait ChunkedValidateExt: Iterator + Sized {
+    /// Validates the card numbers in chunks of up to `size`.
+    ///
+    /// Returns an iterator that yields one
+    /// `Vec<Result<ValidatedCard, ValidationError>>` per chunk.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::stream::ChunkedValidateExt;
+    ///
+    /// let cards = ["4111111111111111", "1234", "5500000000000004"];
+    /// for chunk in cards.iter().validate_chunks(2) {
+    ///     let valid = chunk.iter().filter(|r| r.is_ok()).count();
+    ///     println!("{} of {} valid", valid, chunk.len());
+    /// }
+    /// ```
+    fn validate_chunks(self, size: usize) -> ChunkedValidateStream<Self> {
+        self.validate_chunks_with(size, BatchValidator::new())
+    }
+
+    /// Like [`validate_chunks`](Self::validate_chunks), but validates with
+    /// `batch`, so its validator policy and observer apply. The observer
+    /// sees each chunk as one [`on_chunk_complete`](crate::batch::BatchObserver::on_chunk_complete).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::stream::ChunkedValidateExt;
+    /// use cc_validator::{BatchValidator, CardBrand, Validator};
+    ///
+    /// let validator = Validator::new().with_allowed_brands([CardBrand::Visa]);
+    /// let batch = BatchValidator::with_validator(validator);
+    /// let cards = ["4111111111111111", "5500000000000004"];
+    /// let chunk = cards.iter().validate_chunks_with(10, batch).next().unwrap();
+    /// assert!(chunk[0].is_ok() && chunk[1].is_err());
+    /// ```
+    fn validate_chunks_with(
+        self,
+        size: usize,
+        batch: BatchValidator,
+    ) -> ChunkedValidateStream<Self> {
+        ChunkedValidateStream::new(self, size, batch)
+    }
+}
+
+impl<I: Iterator + Sized> ChunkedValidateExt for I {}
+
 /// Creates a validation stream from a slice of strings.
 ///
 /// Convenience function for creating a stream without using the trait.
@@ -355,6 +489,29 @@ mod tests {
         assert!(results.iter().all(|r| r.is_ok()));
     }
 
+    #[test]
+    fn test_chunked_stream() {
+        let cards = [VALID_VISA, INVALID, VALID_MC, "bad", "378282246310005"];
+        let stream = cards.iter().validate_chunks(2);
+        assert_eq!(stream.size_hint(), (3, Some(3)));
+
+        let chunks: Vec<_> = stream.collect();
+        let sizes: Vec<_> = chunks.iter().map(Vec::len).collect();
+        assert_eq!(sizes, [2, 2, 1]);
+        let flat: Vec<_> = chunks.into_iter().flatten().collect();
+        let expected: Vec<_> = cards.iter().validate_cards().collect();
+        assert_eq!(flat, expected);
+
+        let empty: [&str; 0] = [];
+        assert_eq!(empty.iter().validate_chunks(3).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn test_chunked_stream_zero_size() {
+        let _ = [VALID_VISA].iter().validate_chunks(0);
+    }
+
     #[test]
     fn test_empty_stream() {
         let cards: Vec<&str> = vec![];
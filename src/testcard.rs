@@ -0,0 +1,280 @@
+//! Detection of well-known test card numbers.
+//!
+//! Payment processors publish test numbers that pass every structural check
+//! (length, brand, Luhn) but must never be accepted in production. This module
+//! recognizes those numbers. Only exact numbers are matched: BINs used for
+//! testing, such as Visa's 400000, are also issued to real cards.
+//!
+//! # Example
+//!
+//! ```
+//! use cc_validator::testcard::{self, TestCardPolicy};
+//! use cc_validator::ValidationError;
+//!
+//! assert!(testcard::is_known_test_card("4111-1111-1111-1111"));
+//!
+//! let err = testcard::validate_with_policy("4242424242424242", TestCardPolicy::Reject)
+//!     .unwrap_err();
+//! assert_eq!(err, ValidationError::TestCard);
+//! ```
+//!
+//! Fixtures written with [`test_card!`](crate::test_card) are checked at
+//! compile time, so a mistyped digit fails the build instead of a test.
+
+use crate::validate::is_valid_digits;
+use crate::{validate, ValidatedCard, ValidationError, MAX_CARD_DIGITS};
+
+/// Test card numbers published by major payment processors.
+pub const KNOWN_TEST_CARDS: &[&str] = &[
+    // Visa
+    "4111111111111111",
+    "4242424242424242",
+    "4012888888881881",
+    "4222222222222",
+    "4000056655665556",
+    "4005519200000004",
+    "4009348888881881",
+    "4012000033330026",
+    "4012000077777777",
+    "4217651111111119",
+    "4500600000000061",
+    // Mastercard
+    "5555555555554444",
+    "5105105105105100",
+    "5500000000000004",
+    "5200828282828210",
+    "2223003122003222",
+    "2223000048400011",
+    // American Express
+    "378282246310005",
+    "371449635398431",
+    "378734493671000",
+    "340000000000009",
+    // Discover
+    "6011111111111117",
+    "6011000990139424",
+    "6011000400000000",
+    // Diners Club
+    "30569309025904",
+    "38520000023237",
+    "36227206271667",
+    // JCB
+    "3530111333300000",
+    "3566002020360505",
+    "3566111111111113",
+    // UnionPay
+    "6200000000000005",
+    "6221261111113245",
+];
+
+/// What to do when validation encounters a known test card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TestCardPolicy {
+    /// Accept test cards like any other valid card.
+    #[default]
+    Allow,
+    /// Reject test cards with [`ValidationError::TestCard`].
+    Reject,
+}
+
+/// Returns `true` if the digits form a known test card number.
+///
+/// Each element must be a digit value (0-9), not an ASCII character.
+pub fn is_known_test_digits(digits: &[u8]) -> bool {
+    KNOWN_TEST_CARDS.iter().any(|card| {
+        card.len() == digits.len() && card.bytes().zip(digits).all(|(c, &d)| c - b'0' == d)
+    })
+}
+
+/// Returns `true` if the input is a known test card number.
+///
+/// Separators are ignored; non-digit characters other than separators
+/// make the input not match.
+pub fn is_known_test_card(input: &str) -> bool {
+    let mut digits = [0u8; crate::MAX_CARD_DIGITS];
+    let mut count = 0;
+
+    for c in input.chars() {
+        match c {
+            '0'..='9' if count < digits.len() => {
+                digits[count] = c as u8 - b'0';
+                count += 1;
+            }
+            ' ' | '-' | '.' => {}
+            _ => return false,
+        }
+    }
+
+    is_known_test_digits(&digits[..count])
+}
+
+/// Returns `true` if a card number literal is a valid card: a known brand,
+/// a length issued for it and a passing Luhn check, as with
+/// [`validate()`].
+///
+/// Spaces and hyphens are allowed between digits; anything else makes the
+/// literal invalid. Usable in const contexts; [`test_card!`](crate::test_card)
+/// calls it at compile time.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::testcard::is_valid_literal;
+///
+/// const OK: bool = is_valid_literal("4111 1111 1111 1111");
+/// assert!(OK);
+/// assert!(!is_valid_literal("4111 1111 1111 1112"));
+/// assert!(!is_valid_literal("4111"));
+/// // Luhn-valid, but Amex numbers have 15 digits
+/// assert!(!is_valid_literal("3700 0000 0000 0007"));
+/// ```
+pub const fn is_valid_literal(number: &str) -> bool {
+    let bytes = number.as_bytes();
+    let mut digits = [0u8; MAX_CARD_DIGITS];
+    let mut count = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b @ b'0'..=b'9' => {
+                if count == MAX_CARD_DIGITS {
+                    return false;
+                }
+                digits[count] = b - b'0';
+                count += 1;
+            }
+            b' ' | b'-' => {}
+            _ => return false,
+        }
+        i += 1;
+    }
+    is_valid_digits(digits.split_at(count).0)
+}
+
+/// Checks a test card number literal at compile time and yields it as a
+/// `&'static str`.
+///
+/// The literal must pass [`is_valid_literal`]: digits, optionally separated
+/// by spaces or hyphens, of a known brand at one of its lengths, with a
+/// valid Luhn check digit. Anything else is a build error, so typos in
+/// fixtures are caught before tests run.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::test_card;
+///
+/// const VISA: &str = test_card!("4111 1111 1111 1111");
+/// assert!(cc_validator::is_valid(VISA));
+/// ```
+///
+/// A mistyped check digit doesn't compile:
+///
+/// ```compile_fail
+/// let visa = cc_validator::test_card!("4111 1111 1111 1112");
+/// ```
+#[macro_export]
+macro_rules! test_card {
+    ($number:literal) => {{
+        const NUMBER: &str = $number;
+        const _: () = ::core::assert!(
+            $crate::testcard::is_valid_literal(NUMBER),
+            ::core::concat!("not a valid test card number: ", $number)
+        );
+        NUMBER
+    }};
+}
+
+/// Validates a card number, applying the given policy to test cards.
+///
+/// # Errors
+///
+/// Returns any error from [`validate()`], or [`ValidationError::TestCard`]
+/// when the policy is [`TestCardPolicy::Reject`] and the card is a test card.
+pub fn validate_with_policy(
+    input: &str,
+    policy: TestCardPolicy,
+) -> Result<ValidatedCard, ValidationError> {
+    let card = validate(input)?;
+    if policy == TestCardPolicy::Reject && card.is_known_test_card() {
+        return Err(ValidationError::TestCard);
+    }
+    Ok(card)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_test_cards_are_valid() {
+        for card in KNOWN_TEST_CARDS {
+            assert!(crate::is_valid(card), "{} should be valid", card);
+            assert!(is_known_test_card(card));
+        }
+    }
+
+    #[test]
+    fn test_exact_match_only() {
+        assert!(is_known_test_card("4242424242424242"));
+        assert!(!is_known_test_card("4532015112830366"));
+        // Same BIN as a published test number, but not one of them
+        assert!(!is_known_test_card("4242421234567897"));
+        assert!(!is_known_test_card("4000001234567899"));
+    }
+
+    #[test]
+    fn test_separators() {
+        assert!(is_known_test_card("4111 1111 1111 1111"));
+        assert!(is_known_test_card("4111-1111-1111-1111"));
+        assert!(!is_known_test_card("4111x1111x1111x1111"));
+    }
+
+    #[test]
+    fn test_prefix_of_test_card_does_not_match() {
+        assert!(!is_known_test_card("411111111111"));
+    }
+
+    #[test]
+    fn test_validated_card_flag() {
+        assert!(validate("5555555555554444").unwrap().is_known_test_card());
+        assert!(!validate("4532015112830366").unwrap().is_known_test_card());
+    }
+
+    #[test]
+    fn test_policy() {
+        assert!(validate_with_policy("4111111111111111", TestCardPolicy::Allow).is_ok());
+        assert_eq!(
+            validate_with_policy("4111111111111111", TestCardPolicy::Reject).unwrap_err(),
+            ValidationError::TestCard
+        );
+        assert!(validate_with_policy("4532015112830366", TestCardPolicy::Reject).is_ok());
+        assert_eq!(
+            validate_with_policy("4111111111111112", TestCardPolicy::Reject).unwrap_err(),
+            ValidationError::InvalidChecksum
+        );
+    }
+
+    #[test]
+    fn test_is_valid_literal() {
+        for card in KNOWN_TEST_CARDS {
+            assert!(is_valid_literal(card), "{}", card);
+        }
+        assert!(is_valid_literal("3782-822463-10005"));
+        assert!(!is_valid_literal("3782-822463-10006"));
+        assert!(!is_valid_literal("4111.1111.1111.1111"));
+        assert!(!is_valid_literal("4111 1111 1111 111x"));
+        assert!(!is_valid_literal(""));
+        assert!(!is_valid_literal("41111111111111111111"));
+        // Luhn-valid, but no brand or the wrong length for the brand
+        assert!(!is_valid_literal("9000 0000 0000 0001"));
+        assert!(!is_valid_literal("3700 0000 0000 0007"));
+    }
+
+    #[test]
+    fn test_card_macro() {
+        const AMEX: &str = crate::test_card!("3782 822463 10005");
+        assert_eq!(AMEX, "3782 822463 10005");
+        assert!(crate::is_valid(AMEX));
+        assert!(is_known_test_card(crate::test_card!("4242424242424242")));
+    }
+}
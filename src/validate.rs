@@ -10,10 +10,15 @@
 //! - Single-pass digit extraction
 //! - O(n) complexity where n is the input length
 
-use crate::card::{CardBrand, ValidatedCard, MAX_CARD_DIGITS, MIN_CARD_DIGITS};
+use crate::card::{
+    CardBrand, ValidatedCard, MAX_CARD_DIGITS, MAX_SUPPORTED_DIGITS, MIN_CARD_DIGITS,
+};
+use crate::cvv::CardWithCvv;
 use crate::detect::detect_brand;
 use crate::error::ValidationError;
 use crate::luhn;
+use crate::outcome::ValidationOutcome;
+use zeroize::Zeroize;
 
 /// Validates a credit card number string.
 ///
@@ -48,69 +53,27 @@ use crate::luhn;
 /// println!("Error: {}", err);
 /// ```
 pub fn validate(input: &str) -> Result<ValidatedCard, ValidationError> {
-    if input.is_empty() {
-        return Err(ValidationError::Empty);
-    }
-
-    // Parse input into digits array (zero-copy - we just extract digits)
-    let mut digits = [0u8; MAX_CARD_DIGITS];
-    let mut count = 0usize;
-
-    for (pos, c) in input.chars().enumerate() {
-        match c {
-            '0'..='9' => {
-                if count >= MAX_CARD_DIGITS {
-                    return Err(ValidationError::TooLong {
-                        length: count + 1,
-                        maximum: MAX_CARD_DIGITS,
-                    });
-                }
-                digits[count] = (c as u8) - b'0';
-                count += 1;
-            }
-            ' ' | '-' | '.' => {
-                // Allowed separators, skip them
-            }
-            _ => {
-                return Err(ValidationError::InvalidCharacter {
-                    position: pos,
-                    character: c,
-                });
-            }
-        }
-    }
-
-    // Check for empty after stripping
-    if count == 0 {
-        return Err(ValidationError::NoDigits);
-    }
-
-    // Check minimum length
-    if count < MIN_CARD_DIGITS {
-        return Err(ValidationError::TooShort {
-            length: count,
-            minimum: MIN_CARD_DIGITS,
-        });
-    }
-
-    // Validate Luhn checksum
-    if !luhn::validate(&digits[..count]) {
-        return Err(ValidationError::InvalidChecksum);
-    }
-
-    // Detect card brand
-    let brand = detect_brand(&digits[..count]).ok_or(ValidationError::UnknownBrand)?;
-
-    // Validate length for detected brand
-    if !brand.is_valid_length(count) {
-        return Err(ValidationError::InvalidLengthForBrand {
-            brand,
-            length: count,
-            valid_lengths: brand.valid_lengths(),
-        });
-    }
+    Workspace::new().validate_into(input)
+}
 
-    Ok(ValidatedCard::new(brand, digits, count as u8))
+/// Validates a card number held in a [`secrecy::SecretString`], like
+/// [`validate`].
+///
+/// Requires the `secrecy` feature.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::validate::validate_secret;
+/// use secrecy::SecretString;
+///
+/// let input = SecretString::from("4111 1111 1111 1111");
+/// assert!(validate_secret(&input).is_ok());
+/// ```
+#[cfg(feature = "secrecy")]
+pub fn validate_secret(input: &secrecy::SecretString) -> Result<ValidatedCard, ValidationError> {
+    use secrecy::ExposeSecret;
+    validate(input.expose_secret())
 }
 
 /// Validates a credit card number, allowing unknown brands.
@@ -131,68 +94,412 @@ pub fn validate(input: &str) -> Result<ValidatedCard, ValidationError> {
 /// // This might succeed with brand Unknown if Luhn passes
 /// ```
 pub fn validate_any(input: &str) -> Result<ValidatedCard, ValidationError> {
-    if input.is_empty() {
-        return Err(ValidationError::Empty);
-    }
+    Workspace::new().validate_any_into(input)
+}
 
-    let mut digits = [0u8; MAX_CARD_DIGITS];
-    let mut count = 0usize;
+/// Returns the digits of a card number as a plain ASCII string, without
+/// checking the Luhn checksum or brand.
+///
+/// Separators are stripped and decimal digits from other scripts (fullwidth,
+/// Arabic-Indic, Devanagari, ...) become ASCII digits; anything else is an
+/// [`InvalidCharacter`](ValidationError::InvalidCharacter) error, and the
+/// digit count must be within [`MIN_CARD_DIGITS`] to [`MAX_CARD_DIGITS`].
+/// The result is a stable key for the same number however it was typed, for
+/// hashing or storage ahead of full validation, and canonicalizing it again
+/// returns it unchanged.
+///
+/// The result is the full card number: hash or encrypt it rather than
+/// storing it as is. Not available with the `no-pan-exposure` feature.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::canonicalize;
+///
+/// let key = canonicalize("4111-1111 1111.1112").unwrap();
+/// assert_eq!(key, "4111111111111112");
+/// assert_eq!(canonicalize("４１１１ １１１１ １１１１ １１１２").unwrap(), key);
+/// assert_eq!(canonicalize(&key).unwrap(), key);
+/// assert!(canonicalize("4111 1111").is_err());
+/// ```
+#[cfg(not(feature = "no-pan-exposure"))]
+pub fn canonicalize(input: &str) -> Result<String, ValidationError> {
+    Workspace::new().canonicalize_into(input)
+}
+
+/// Validates a card number like [`validate`], also returning the
+/// non-fatal [`ValidationWarning`](crate::ValidationWarning)s it raises.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::{validate_with_warnings, ValidationWarning};
+///
+/// let outcome = validate_with_warnings("4111 1111 1111 1111").unwrap();
+/// assert_eq!(outcome.card.last_four(), "1111");
+/// assert_eq!(outcome.warnings, [ValidationWarning::TestCard]);
+/// ```
+pub fn validate_with_warnings(input: &str) -> Result<ValidationOutcome, ValidationError> {
+    validate(input).map(ValidationOutcome::new)
+}
+
+/// Validates a card number like [`validate`] and its CVV against the
+/// detected brand, reporting both results.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::validate_with_cvv;
+/// use cc_validator::cvv::CvvError;
+///
+/// assert!(validate_with_cvv("3782 822463 10005", "1234").is_valid());
+///
+/// let result = validate_with_cvv("4111 1111 1111 1111", "1234");
+/// assert!(result.card.is_ok());
+/// assert!(matches!(result.cvv, Err(CvvError::WrongLengthForBrand { .. })));
+/// ```
+pub fn validate_with_cvv(card_number: &str, cvv: &str) -> CardWithCvv {
+    CardWithCvv::new(validate(card_number), cvv)
+}
 
-    for (pos, c) in input.chars().enumerate() {
+/// What could still be learned from an input that failed validation.
+///
+/// Returned by [`validate_partial_info`] so an error UI can highlight the
+/// characters to fix and show the brand the user seems to be typing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PartialInfo {
+    /// Number of digits in the input, including any beyond the maximum.
+    pub digit_count: usize,
+    /// Brand detected from the leading digits, if any.
+    pub brand: Option<CardBrand>,
+    /// 0-based character positions of every character that isn't a digit
+    /// or an allowed separator.
+    pub invalid_positions: Vec<usize>,
+}
+
+/// Validates a card number like [`validate`], but on failure also returns
+/// the [`PartialInfo`] gathered while parsing.
+///
+/// Unlike [`ValidationError::InvalidCharacter`], which stops at the first
+/// bad character, [`PartialInfo::invalid_positions`] lists all of them.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::validate::validate_partial_info;
+/// use cc_validator::{CardBrand, ValidationError};
+///
+/// let (err, info) = validate_partial_info("4111 11l1 1111 111O").unwrap_err();
+/// assert!(matches!(err, ValidationError::InvalidCharacter { position: 7, .. }));
+/// assert_eq!(info.invalid_positions, [7, 18]);
+/// assert_eq!(info.digit_count, 14);
+/// assert_eq!(info.brand, Some(CardBrand::Visa));
+///
+/// assert!(validate_partial_info("4111 1111 1111 1111").is_ok());
+/// ```
+pub fn validate_partial_info(input: &str) -> Result<ValidatedCard, (ValidationError, PartialInfo)> {
+    let error = match validate(input) {
+        Ok(card) => return Ok(card),
+        Err(error) => error,
+    };
+
+    let mut digits = [0u8; MAX_CARD_DIGITS];
+    let mut info = PartialInfo::default();
+    for (position, c) in input.chars().enumerate() {
         match c {
             '0'..='9' => {
-                if count >= MAX_CARD_DIGITS {
-                    return Err(ValidationError::TooLong {
-                        length: count + 1,
-                        maximum: MAX_CARD_DIGITS,
-                    });
+                if info.digit_count < MAX_CARD_DIGITS {
+                    digits[info.digit_count] = c as u8 - b'0';
                 }
-                digits[count] = (c as u8) - b'0';
-                count += 1;
+                info.digit_count += 1;
             }
             ' ' | '-' | '.' => {}
-            _ => {
-                return Err(ValidationError::InvalidCharacter {
-                    position: pos,
-                    character: c,
-                });
+            _ => info.invalid_positions.push(position),
+        }
+    }
+    info.brand = detect_brand(&digits[..info.digit_count.min(MAX_CARD_DIGITS)]);
+    digits.zeroize();
+
+    Err((error, info))
+}
+
+/// Code points of the zero in each run of Unicode decimal digits (`Nd`)
+/// outside ASCII; every run holds 0 through 9 in order.
+const UNICODE_DIGIT_ZEROS: [u32; 65] = [
+    0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6, 0x0D66,
+    0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80, 0x1A90,
+    0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0, 0xFF10,
+    0x104A0, 0x10D30, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0, 0x11650,
+    0x116C0, 0x11730, 0x118E0, 0x11950, 0x11C50, 0x11D50, 0x11DA0, 0x16A60, 0x16AC0, 0x16B50,
+    0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E950, 0x1FBF0,
+];
+
+/// Returns the value of a non-ASCII decimal digit such as `'５'` or `'٣'`.
+fn unicode_digit(c: char) -> Option<u8> {
+    let c = u32::from(c);
+    let zero = match UNICODE_DIGIT_ZEROS.binary_search(&c) {
+        Ok(i) => UNICODE_DIGIT_ZEROS[i],
+        Err(0) => return None,
+        Err(i) => UNICODE_DIGIT_ZEROS[i - 1],
+    };
+    (c - zero < 10).then(|| (c - zero) as u8)
+}
+
+/// Accepted range of digit counts, checked while parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DigitLimits {
+    pub(crate) min: u8,
+    pub(crate) max: u8,
+}
+
+impl DigitLimits {
+    /// [`MIN_CARD_DIGITS`] to [`MAX_CARD_DIGITS`], used by the free functions.
+    pub(crate) const STANDARD: Self = Self {
+        min: MIN_CARD_DIGITS as u8,
+        max: MAX_CARD_DIGITS as u8,
+    };
+}
+
+impl Default for DigitLimits {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+/// Reusable parsing state for validating many card numbers in a loop.
+///
+/// [`validate`] and [`validate_any`] set up a fresh workspace on every call;
+/// keeping one around skips that setup, which matters when most inputs are
+/// short and invalid and parsing is the bulk of the work. Parsing scans
+/// bytes rather than chars and only decodes UTF-8 when it meets a non-ASCII
+/// byte.
+///
+/// The digit buffer is zeroed on drop and by [`clear`](Self::clear).
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::Workspace;
+///
+/// let mut ws = Workspace::new();
+/// for input in ["4111-1111-1111-1111", "4111", "5500 0000 0000 0004"] {
+///     match ws.validate_into(input) {
+///         Ok(card) => println!("{}", card.masked()),
+///         Err(e) => println!("{}", e),
+///     }
+/// }
+/// ```
+pub struct Workspace {
+    digits: [u8; MAX_SUPPORTED_DIGITS],
+    count: usize,
+}
+
+impl Default for Workspace {
+    fn default() -> Self {
+        Self {
+            digits: [0; MAX_SUPPORTED_DIGITS],
+            count: 0,
+        }
+    }
+}
+
+impl Workspace {
+    /// Creates an empty workspace.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validates a card number like [`validate`], reusing this workspace's
+    /// buffer.
+    pub fn validate_into(&mut self, input: &str) -> Result<ValidatedCard, ValidationError> {
+        self.validate_within(input, DigitLimits::STANDARD, detect_brand)
+    }
+
+    /// Validates a card number like [`validate_any`], reusing this
+    /// workspace's buffer.
+    pub fn validate_any_into(&mut self, input: &str) -> Result<ValidatedCard, ValidationError> {
+        self.validate_any_within(input, DigitLimits::STANDARD, detect_brand)
+    }
+
+    /// Validates within `limits`, identifying the brand with `detect`
+    /// (normally [`detect_brand`]).
+    pub(crate) fn validate_within(
+        &mut self,
+        input: &str,
+        limits: DigitLimits,
+        detect: impl FnOnce(&[u8]) -> Option<CardBrand>,
+    ) -> Result<ValidatedCard, ValidationError> {
+        self.parse(input, limits)?;
+        let digits = &self.digits[..self.count];
+        let brand = detect(digits).ok_or(ValidationError::UnknownBrand)?;
+        self.finish(brand)
+    }
+
+    /// Like [`validate_within`](Self::validate_within), but accepts numbers
+    /// of unknown brands.
+    pub(crate) fn validate_any_within(
+        &mut self,
+        input: &str,
+        limits: DigitLimits,
+        detect: impl FnOnce(&[u8]) -> Option<CardBrand>,
+    ) -> Result<ValidatedCard, ValidationError> {
+        self.parse(input, limits)?;
+        match detect(&self.digits[..self.count]) {
+            Some(brand) => self.finish(brand),
+            None => {
+                // Unknown brand - accept any length between MIN and MAX
+                // We use Visa as a placeholder since we need some brand
+                // Note: This is a limitation - ideally we'd have an Unknown variant
+                Ok(ValidatedCard::new(
+                    CardBrand::Visa,
+                    self.digits,
+                    self.count as u8,
+                ))
             }
         }
     }
 
-    if count == 0 {
-        return Err(ValidationError::NoDigits);
+    /// Zeroes the digit buffer.
+    pub fn clear(&mut self) {
+        self.digits.zeroize();
+        self.count = 0;
     }
 
-    if count < MIN_CARD_DIGITS {
-        return Err(ValidationError::TooShort {
-            length: count,
-            minimum: MIN_CARD_DIGITS,
-        });
+    /// Extracts digits, then checks length and the Luhn checksum.
+    fn parse(&mut self, input: &str, limits: DigitLimits) -> Result<(), ValidationError> {
+        self.extract(input, limits)?;
+        if !luhn::validate(&self.digits[..self.count]) {
+            return Err(ValidationError::InvalidChecksum);
+        }
+        Ok(())
+    }
+
+    /// Returns the digits of `input` as an ASCII string, like
+    /// [`canonicalize`], reusing this workspace's buffer.
+    #[cfg(not(feature = "no-pan-exposure"))]
+    pub fn canonicalize_into(&mut self, input: &str) -> Result<String, ValidationError> {
+        self.extract_digits(input, DigitLimits::STANDARD, true)?;
+        Ok(self
+            .digits()
+            .iter()
+            .map(|&d| char::from(b'0' + d))
+            .collect())
+    }
+
+    /// Extracts digits and checks their count against `limits`.
+    pub(crate) fn extract(
+        &mut self,
+        input: &str,
+        limits: DigitLimits,
+    ) -> Result<(), ValidationError> {
+        self.extract_digits(input, limits, false)
+    }
+
+    /// Like [`extract`](Self::extract), also reading non-ASCII decimal
+    /// digits when `unicode_digits` is set.
+    fn extract_digits(
+        &mut self,
+        input: &str,
+        limits: DigitLimits,
+        unicode_digits: bool,
+    ) -> Result<(), ValidationError> {
+        if input.is_empty() {
+            return Err(ValidationError::Empty);
+        }
+
+        // Drop the previous number so no stale digits trail a shorter one
+        self.digits[..self.count].fill(0);
+        self.count = 0;
+        let maximum = usize::from(limits.max);
+        let bytes = input.as_bytes();
+        // End of the last multi-byte digit read, whose tail bytes are skipped
+        let mut resume = 0;
+        for (i, &b) in bytes.iter().enumerate() {
+            match b {
+                b'0'..=b'9' => self.push_digit(b - b'0', maximum)?,
+                b' ' | b'-' | b'.' => {
+                    // Allowed separators, skip them
+                }
+                _ if i < resume => {}
+                _ => {
+                    // Only now pay for UTF-8 decoding; positions count chars
+                    let character = input[i..].chars().next().unwrap_or_default();
+                    if let Some(d) = unicode_digits.then(|| unicode_digit(character)).flatten() {
+                        self.push_digit(d, maximum)?;
+                        resume = i + character.len_utf8();
+                        continue;
+                    }
+                    return Err(ValidationError::InvalidCharacter {
+                        position: input[..i].chars().count(),
+                        digit_index: self.count,
+                        character,
+                    });
+                }
+            }
+        }
+
+        if self.count == 0 {
+            return Err(ValidationError::NoDigits);
+        }
+
+        let minimum = usize::from(limits.min);
+        if self.count < minimum {
+            return Err(ValidationError::TooShort {
+                length: self.count,
+                minimum,
+            });
+        }
+
+        Ok(())
     }
 
-    if !luhn::validate(&digits[..count]) {
-        return Err(ValidationError::InvalidChecksum);
+    /// Appends one digit, failing once more than `maximum` have been read.
+    #[inline]
+    fn push_digit(&mut self, digit: u8, maximum: usize) -> Result<(), ValidationError> {
+        if self.count >= maximum {
+            return Err(ValidationError::TooLong {
+                length: self.count + 1,
+                maximum,
+            });
+        }
+        self.digits[self.count] = digit;
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Returns the digits of the last number parsed.
+    pub(crate) fn digits(&self) -> &[u8] {
+        &self.digits[..self.count]
     }
 
-    // Detect brand but don't require it
-    let brand = detect_brand(&digits[..count]);
+    /// Wraps the last number parsed as a card of `brand`, skipping checks.
+    pub(crate) fn card(&self, brand: CardBrand) -> ValidatedCard {
+        ValidatedCard::new(brand, self.digits, self.count as u8)
+    }
 
-    // If we detected a brand, validate the length for it
-    if let Some(b) = brand {
-        if !b.is_valid_length(count) {
+    fn finish(&self, brand: CardBrand) -> Result<ValidatedCard, ValidationError> {
+        if !brand.is_valid_length(self.count) {
             return Err(ValidationError::InvalidLengthForBrand {
-                brand: b,
-                length: count,
-                valid_lengths: b.valid_lengths(),
+                brand,
+                length: self.count,
+                valid_lengths: brand.valid_lengths(),
             });
         }
-        Ok(ValidatedCard::new(b, digits, count as u8))
-    } else {
-        // Unknown brand - accept any length between MIN and MAX
-        // We use Visa as a placeholder since we need some brand
-        // Note: This is a limitation - ideally we'd have an Unknown variant
-        Ok(ValidatedCard::new(CardBrand::Visa, digits, count as u8))
+        Ok(ValidatedCard::new(brand, self.digits, self.count as u8))
+    }
+}
+
+impl Drop for Workspace {
+    fn drop(&mut self) {
+        self.digits.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Workspace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print buffered digits
+        f.debug_struct("Workspace").finish_non_exhaustive()
     }
 }
 
@@ -253,12 +560,39 @@ pub fn validate_digits(digits: &[u8]) -> Result<ValidatedCard, ValidationError>
     }
 
     // Copy to fixed array
-    let mut fixed_digits = [0u8; MAX_CARD_DIGITS];
+    let mut fixed_digits = [0u8; MAX_SUPPORTED_DIGITS];
     fixed_digits[..count].copy_from_slice(digits);
 
     Ok(ValidatedCard::new(brand, fixed_digits, count as u8))
 }
 
+/// Returns `true` if pre-parsed digits form a valid card: the checks of
+/// [`validate_digits`] without building a [`ValidatedCard`].
+///
+/// A `const fn`, for compile-time checks and firmware that can't allocate
+/// or run initialization code.
+///
+/// # Example
+///
+/// ```
+/// use cc_validator::validate::is_valid_digits;
+///
+/// const AMEX: bool = is_valid_digits(&[3, 7, 8, 2, 8, 2, 2, 4, 6, 3, 1, 0, 0, 0, 5]);
+/// assert!(AMEX);
+/// // Passes Luhn, but Amex isn't issued with 16 digits
+/// assert!(!is_valid_digits(&[3, 7, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 7]));
+/// ```
+pub const fn is_valid_digits(digits: &[u8]) -> bool {
+    let count = digits.len();
+    if count < MIN_CARD_DIGITS || count > MAX_CARD_DIGITS || !luhn::validate(digits) {
+        return false;
+    }
+    match detect_brand(digits) {
+        Some(brand) => brand.is_valid_length(count),
+        None => false,
+    }
+}
+
 /// Quickly checks if a card number is valid without returning detailed info.
 ///
 /// This is faster than `validate()` when you only need a yes/no answer.
@@ -438,6 +772,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(not(feature = "no-pan-exposure"))]
     fn test_full_number_retrieval() {
         let card = validate(VISA_VALID).unwrap();
         assert_eq!(card.number(), "4111111111111111");
@@ -467,4 +802,196 @@ mod tests {
         // JCB
         assert!(is_valid("3530111333300000"));
     }
+
+    /// Reduces a result to comparable parts, brand included (`PartialEq` on
+    /// cards compares digits only).
+    fn outcome(
+        result: Result<ValidatedCard, ValidationError>,
+    ) -> Result<(CardBrand, Vec<u8>), ValidationError> {
+        result.map(|card| (card.brand(), card.digits().to_vec()))
+    }
+
+    #[test]
+    fn test_workspace_reuse() {
+        let mut ws = Workspace::new();
+        let inputs = [
+            "6011 0009 9013 9424 011",
+            AMEX_VALID,
+            VISA_VALID_FORMATTED,
+            "",
+            " - ",
+            "4111",
+            "4111-1111-1111-1112",
+            "1234567890123452",
+            "41111111111111111111",
+        ];
+        for input in inputs {
+            assert_eq!(outcome(ws.validate_into(input)), outcome(validate(input)));
+            assert_eq!(
+                outcome(ws.validate_any_into(input)),
+                outcome(validate_any(input))
+            );
+        }
+
+        // A shorter number after a longer one carries no stale digits
+        ws.validate_into(VISA_VALID).unwrap();
+        let card = ws.validate_into(AMEX_VALID).unwrap();
+        assert_eq!(card.length(), 15);
+        assert_eq!(ws.digits[15..], [0; MAX_SUPPORTED_DIGITS - 15]);
+
+        ws.clear();
+        assert_eq!(ws.digits, [0; MAX_SUPPORTED_DIGITS]);
+    }
+
+    #[test]
+    fn test_validate_partial_info() {
+        let (err, info) = validate_partial_info("").unwrap_err();
+        assert_eq!(err, ValidationError::Empty);
+        assert_eq!(info, PartialInfo::default());
+
+        let (err, info) = validate_partial_info("3782 8224 6310 006").unwrap_err();
+        assert_eq!(err, ValidationError::InvalidChecksum);
+        assert_eq!(info.digit_count, 15);
+        assert_eq!(info.brand, Some(CardBrand::Amex));
+        assert!(info.invalid_positions.is_empty());
+
+        // Positions count characters, not bytes
+        let (_, info) = validate_partial_info("€4111x1111111111111111111").unwrap_err();
+        assert_eq!(info.invalid_positions, [0, 5]);
+        assert_eq!(info.digit_count, 23);
+        assert_eq!(info.brand, Some(CardBrand::Visa));
+    }
+
+    #[test]
+    fn test_workspace_non_ascii_position() {
+        let mut ws = Workspace::new();
+        assert_eq!(
+            ws.validate_into("4111\u{2013}1111").unwrap_err(),
+            ValidationError::InvalidCharacter {
+                position: 4,
+                digit_index: 4,
+                character: '\u{2013}'
+            }
+        );
+        assert_eq!(
+            ws.validate_into("é4x").unwrap_err(),
+            ValidationError::InvalidCharacter {
+                position: 0,
+                digit_index: 0,
+                character: 'é'
+            }
+        );
+        assert_eq!(
+            ws.validate_into("4é x").unwrap_err(),
+            ValidationError::InvalidCharacter {
+                position: 1,
+                digit_index: 1,
+                character: 'é'
+            }
+        );
+        // Separators count toward the position but not the digit index
+        assert_eq!(
+            ws.validate_into("4111-1111 x111").unwrap_err(),
+            ValidationError::InvalidCharacter {
+                position: 10,
+                digit_index: 8,
+                character: 'x'
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-pan-exposure"))]
+    fn test_canonicalize() {
+        // Checksum and brand aren't checked
+        assert_eq!(
+            canonicalize("4111 1111-1111.1112").unwrap(),
+            "4111111111111112"
+        );
+        assert_eq!(
+            canonicalize("9999 9999 9999 9999").unwrap(),
+            "9999999999999999"
+        );
+
+        // Fullwidth, Arabic-Indic and Devanagari digits, mixed with ASCII
+        assert_eq!(
+            canonicalize("４１１１ ١١١١ १११११ 111").unwrap(),
+            "4111111111111111"
+        );
+        for input in [
+            "4111-1111-1111-1111",
+            "٤١١١١١١١١١١١١١١١",
+            "𝟒𝟏𝟏𝟏𝟏𝟏𝟏𝟏𝟏𝟏𝟏𝟏𝟏𝟏𝟏𝟏",
+        ] {
+            let key = canonicalize(input).unwrap();
+            assert_eq!(key, VISA_VALID);
+            assert_eq!(canonicalize(&key).unwrap(), key);
+        }
+
+        // Positions still count characters after multi-byte digits
+        assert_eq!(
+            canonicalize("４１x1").unwrap_err(),
+            ValidationError::InvalidCharacter {
+                position: 2,
+                digit_index: 2,
+                character: 'x'
+            }
+        );
+        // Superscripts are numeric but not decimal digits
+        assert!(matches!(
+            canonicalize("4111 1111 1111 111²"),
+            Err(ValidationError::InvalidCharacter {
+                character: '²', ..
+            })
+        ));
+        assert_eq!(canonicalize(""), Err(ValidationError::Empty));
+        assert!(matches!(
+            canonicalize("４１１１"),
+            Err(ValidationError::TooShort { length: 4, .. })
+        ));
+        assert!(matches!(
+            canonicalize("４１１１１１１１１１１１１１１１１１１１"),
+            Err(ValidationError::TooLong { length: 20, .. })
+        ));
+
+        // validate() itself still only takes ASCII digits
+        assert!(validate("４１１１１１１１１１１１１１１１").is_err());
+    }
+
+    #[test]
+    fn test_unicode_digit() {
+        assert_eq!(unicode_digit('٠'), Some(0));
+        assert_eq!(unicode_digit('٩'), Some(9));
+        assert_eq!(unicode_digit('５'), Some(5));
+        assert_eq!(unicode_digit('\u{1FBF9}'), Some(9));
+        assert_eq!(unicode_digit('5'), None);
+        assert_eq!(unicode_digit('\u{065F}'), None);
+        assert_eq!(unicode_digit('\u{066A}'), None);
+        assert_eq!(unicode_digit('é'), None);
+    }
+
+    #[test]
+    fn test_is_valid_digits_matches_validate_digits() {
+        let cases: [&[u8]; 6] = [
+            &[4, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1],
+            &[4, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 2],
+            &[3, 7, 8, 2, 8, 2, 2, 4, 6, 3, 1, 0, 0, 0, 5],
+            &[3, 7, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 7],
+            &[9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+            &[4, 1, 1, 1],
+        ];
+        for digits in cases {
+            assert_eq!(
+                is_valid_digits(digits),
+                validate_digits(digits).is_ok(),
+                "{:?}",
+                digits
+            );
+        }
+        const {
+            assert!(is_valid_digits(&[
+                6, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 7
+            ]))
+        };
+    }
 }
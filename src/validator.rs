@@ -0,0 +1,961 @@
+//! Configurable validator and process-wide default instance.
+//!
+//! The free functions in [`mod@crate::validate`] have fixed behavior. A
+//! [`Validator`] carries configuration — which brands are accepted (and
+//! where), how strict validation is, and an optional BIN database — so an application
+//! and its bindings can share one configured instance.
+//!
+//! # Configuration
+//!
+//! [`ValidatorConfig::from_env`] reads:
+//!
+//! | Variable | Meaning |
+//! |----------|---------|
+//! | `CC_VALIDATOR_CONFIG` | Path to a TOML file (requires the `config` feature) |
+//! | `CC_VALIDATOR_ALLOWED_BRANDS` | Comma-separated brand ids, e.g. `visa,mastercard` |
+//! | `CC_VALIDATOR_STRICTNESS` | `lenient`, `standard`, or `strict` |
+//! | `CC_VALIDATOR_BIN_DB` | Path to a `.json`, `.csv`, or `.db`/`.sqlite` BIN database, or a `redis://` URL |
+//!
+//! Environment variables override values from the TOML file. The TOML file
+//! uses the same keys in snake case:
+//!
+//! ```toml
+//! allowed_brands = ["visa", "mastercard"]
+//! strictness = "strict"
+//! bin_db_path = "bins.json"
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use cc_validator::validator::{Strictness, Validator};
+//! use cc_validator::{CardBrand, ValidationError};
+//!
+//! let validator = Validator::new()
+//!     .with_allowed_brands([CardBrand::Visa, CardBrand::Mastercard])
+//!     .with_strictness(Strictness::Strict);
+//!
+//! assert!(validator.validate("4532015112830366").is_ok());
+//! assert_eq!(
+//!     validator.validate("378282246310005").unwrap_err(),
+//!     ValidationError::BrandNotAllowed { brand: CardBrand::Amex }
+//! );
+//! // Strict mode rejects well-known test numbers
+//! assert_eq!(
+//!     validator.validate("4111111111111111").unwrap_err(),
+//!     ValidationError::TestCard
+//! );
+//! ```
+
+use crate::acceptance::AcceptanceMatrix;
+use crate::bin::{BinDatabase, BinDbError, BinInfo};
+use crate::cvv::CardWithCvv;
+use crate::detect::detect_brand;
+use crate::metrics::{recorded, ValidationMetrics};
+use crate::outcome::ValidationOutcome;
+use crate::profile::CardProfile;
+use crate::sanitize::sanitize_ocr;
+use crate::testcard::TestCardPolicy;
+use crate::validate::DigitLimits;
+use crate::{
+    CardBrand, ValidatedCard, ValidationError, Workspace, MAX_SUPPORTED_DIGITS,
+    MIN_SUPPORTED_DIGITS,
+};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+/// How strictly card numbers are validated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize))]
+#[cfg_attr(feature = "config", serde(rename_all = "lowercase"))]
+pub enum Strictness {
+    /// Accept any number that passes Luhn, even without a known brand.
+    ///
+    /// Numbers without a known brand are still rejected with
+    /// [`ValidationError::UnknownBrand`] when allowed brands or a merchant
+    /// country are set, since those policies can't be checked.
+    Lenient,
+    /// Require a known brand with a valid length (same as [`crate::validate()`]).
+    #[default]
+    Standard,
+    /// Like `Standard`, and also reject well-known test card numbers.
+    Strict,
+}
+
+impl Strictness {
+    /// Parses a strictness level (`lenient`, `standard`, or `strict`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "lenient" => Some(Self::Lenient),
+            "standard" => Some(Self::Standard),
+            "strict" => Some(Self::Strict),
+            _ => None,
+        }
+    }
+}
+
+/// Errors that can occur while loading validator configuration.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// A brand name was not recognized.
+    UnknownBrand(String),
+    /// The strictness level was not recognized.
+    InvalidStrictness(String),
+    /// The configuration file could not be read.
+    Io(std::io::Error),
+    /// The configuration file could not be parsed.
+    Parse(String),
+    /// The BIN database could not be loaded.
+    BinDb(BinDbError),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownBrand(s) => write!(f, "unknown card brand: {}", s),
+            Self::InvalidStrictness(s) => write!(
+                f,
+                "invalid strictness '{}' (expected lenient, standard, or strict)",
+                s
+            ),
+            Self::Io(e) => write!(f, "failed to read config: {}", e),
+            Self::Parse(s) => write!(f, "failed to parse config: {}", s),
+            Self::BinDb(e) => write!(f, "failed to load BIN database: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::BinDb(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ConfigError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<BinDbError> for ConfigError {
+    fn from(err: BinDbError) -> Self {
+        Self::BinDb(err)
+    }
+}
+
+/// Plain configuration data for building a [`Validator`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "config", derive(serde::Deserialize))]
+#[cfg_attr(feature = "config", serde(default, deny_unknown_fields))]
+pub struct ValidatorConfig {
+    /// Brand ids or names to accept; `None` accepts every brand.
+    pub allowed_brands: Option<Vec<String>>,
+    /// Validation strictness.
+    pub strictness: Strictness,
+    /// Path to a BIN database file.
+    pub bin_db_path: Option<PathBuf>,
+}
+
+impl ValidatorConfig {
+    /// Reads configuration from `CC_VALIDATOR_*` environment variables.
+    ///
+    /// See the [module documentation](self) for the variables read.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    /// Reads configuration using `var` to look up each variable.
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, ConfigError> {
+        let mut config = match var("CC_VALIDATOR_CONFIG") {
+            Some(path) => Self::from_toml_file(path)?,
+            None => Self::default(),
+        };
+
+        if let Some(brands) = var("CC_VALIDATOR_ALLOWED_BRANDS") {
+            config.allowed_brands = Some(
+                brands
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(String::from)
+                    .collect(),
+            );
+        }
+        if let Some(strictness) = var("CC_VALIDATOR_STRICTNESS") {
+            config.strictness = Strictness::from_name(&strictness)
+                .ok_or(ConfigError::InvalidStrictness(strictness))?;
+        }
+        if let Some(path) = var("CC_VALIDATOR_BIN_DB") {
+            config.bin_db_path = Some(PathBuf::from(path));
+        }
+
+        Ok(config)
+    }
+
+    /// Parses configuration from a TOML string.
+    #[cfg(feature = "config")]
+    pub fn from_toml_str(toml: &str) -> Result<Self, ConfigError> {
+        toml::from_str(toml).map_err(|e| ConfigError::Parse(e.to_string()))
+    }
+
+    /// Reads configuration from a TOML file.
+    ///
+    /// Without the `config` feature this always returns an error.
+    pub fn from_toml_file<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        #[cfg(feature = "config")]
+        {
+            Self::from_toml_str(&std::fs::read_to_string(path)?)
+        }
+        #[cfg(not(feature = "config"))]
+        {
+            let _ = path;
+            Err(ConfigError::Parse(
+                "TOML configuration requires the 'config' feature".to_string(),
+            ))
+        }
+    }
+}
+
+/// A card validator with configurable brand policy, strictness, and BIN data.
+///
+/// Cheap to share: the BIN database is reference counted.
+#[derive(Clone, Default)]
+pub struct Validator {
+    allowed_brands: Option<Vec<CardBrand>>,
+    merchant_country: Option<String>,
+    acceptance: AcceptanceMatrix,
+    strictness: Strictness,
+    test_cards: TestCardPolicy,
+    ocr_correction: bool,
+    digit_limits: DigitLimits,
+    bin_db: Option<Arc<dyn BinDatabase>>,
+    metrics: Option<Arc<ValidationMetrics>>,
+}
+
+impl Validator {
+    /// Creates a validator with default settings (all brands, standard strictness).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a validator from configuration, loading the BIN database if set.
+    pub fn from_config(config: &ValidatorConfig) -> Result<Self, ConfigError> {
+        let mut validator = Self::new().with_strictness(config.strictness);
+
+        if let Some(names) = &config.allowed_brands {
+            let brands = names
+                .iter()
+                .map(|n| {
+                    CardBrand::from_name(n).ok_or_else(|| ConfigError::UnknownBrand(n.clone()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            validator = validator.with_allowed_brands(brands);
+        }
+
+        if let Some(path) = &config.bin_db_path {
+            validator.bin_db = Some(Arc::from(crate::bin::open_database(path)?));
+        }
+
+        Ok(validator)
+    }
+
+    /// Creates a validator from `CC_VALIDATOR_*` environment variables.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Self::from_config(&ValidatorConfig::from_env()?)
+    }
+
+    /// Restricts validation to the given brands.
+    pub fn with_allowed_brands(mut self, brands: impl IntoIterator<Item = CardBrand>) -> Self {
+        self.allowed_brands = Some(brands.into_iter().collect());
+        self
+    }
+
+    /// Rejects brands not accepted in `country` (ISO 3166-1 alpha-2), such
+    /// as domestic-only schemes used abroad.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::{CardBrand, ValidationError, Validator};
+    ///
+    /// let validator = Validator::new().with_merchant_country("US");
+    /// assert!(validator.is_valid("4111111111111111"));
+    /// assert_eq!(
+    ///     validator.validate("2200000000000004").unwrap_err(),
+    ///     ValidationError::NotAcceptedInCountry {
+    ///         brand: CardBrand::Mir,
+    ///         country: "US".to_string(),
+    ///     }
+    /// );
+    /// ```
+    pub fn with_merchant_country(mut self, country: &str) -> Self {
+        self.merchant_country = Some(country.trim().to_ascii_uppercase());
+        self
+    }
+
+    /// Replaces the default [`AcceptanceMatrix`] used with
+    /// [`with_merchant_country`](Self::with_merchant_country).
+    pub fn with_acceptance_matrix(mut self, matrix: AcceptanceMatrix) -> Self {
+        self.acceptance = matrix;
+        self
+    }
+
+    /// Sets the validation strictness.
+    pub fn with_strictness(mut self, strictness: Strictness) -> Self {
+        self.strictness = strictness;
+        self
+    }
+
+    /// Sets what happens to well-known test card numbers, independently of
+    /// the strictness. [`Strictness::Strict`] rejects them whatever the
+    /// policy.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::testcard::TestCardPolicy;
+    /// use cc_validator::validator::{Strictness, Validator};
+    /// use cc_validator::ValidationError;
+    ///
+    /// let validator = Validator::new()
+    ///     .with_strictness(Strictness::Lenient)
+    ///     .with_test_card_policy(TestCardPolicy::Reject);
+    /// assert_eq!(
+    ///     validator.validate("4111111111111111").unwrap_err(),
+    ///     ValidationError::TestCard
+    /// );
+    /// assert!(validator.is_valid("9000000000000001"));
+    /// ```
+    pub fn with_test_card_policy(mut self, policy: TestCardPolicy) -> Self {
+        self.test_cards = policy;
+        self
+    }
+
+    /// Corrects common OCR misreads (e.g. `O` for `0`) before validating;
+    /// see [`crate::sanitize`]. Off by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::Validator;
+    ///
+    /// let validator = Validator::new().with_ocr_correction(true);
+    /// assert!(validator.is_valid("4lll 1111 1111 1111"));
+    /// assert!(!Validator::new().is_valid("4lll 1111 1111 1111"));
+    /// ```
+    pub fn with_ocr_correction(mut self, enabled: bool) -> Self {
+        self.ocr_correction = enabled;
+        self
+    }
+
+    /// Accepts card numbers of `min` to `max` digits instead of
+    /// [`MIN_CARD_DIGITS`](crate::MIN_CARD_DIGITS) to
+    /// [`MAX_CARD_DIGITS`](crate::MAX_CARD_DIGITS), for closed-loop gift
+    /// cards and other niche schemes.
+    ///
+    /// Known brands still require one of their own lengths, so numbers
+    /// outside the standard range usually need [`Strictness::Lenient`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min > max` or either bound is outside
+    /// [`MIN_SUPPORTED_DIGITS`] to [`MAX_SUPPORTED_DIGITS`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::validator::{Strictness, Validator};
+    ///
+    /// let gift_cards = Validator::new()
+    ///     .with_strictness(Strictness::Lenient)
+    ///     .with_digit_limits(12, 21);
+    /// assert!(gift_cards.is_valid("77777777777777777777"));
+    /// assert!(!Validator::new()
+    ///     .with_strictness(Strictness::Lenient)
+    ///     .is_valid("77777777777777777777"));
+    /// ```
+    pub fn with_digit_limits(mut self, min: usize, max: usize) -> Self {
+        assert!(
+            MIN_SUPPORTED_DIGITS <= min && min <= max && max <= MAX_SUPPORTED_DIGITS,
+            "digit limits {}..={} must lie within {}..={}",
+            min,
+            max,
+            MIN_SUPPORTED_DIGITS,
+            MAX_SUPPORTED_DIGITS
+        );
+        self.digit_limits = DigitLimits {
+            min: min as u8,
+            max: max as u8,
+        };
+        self
+    }
+
+    /// Attaches a BIN database used by [`Validator::lookup_bin`] and [`Validator::profile`].
+    pub fn with_bin_database(mut self, db: impl BinDatabase + 'static) -> Self {
+        self.bin_db = Some(Arc::new(db));
+        self
+    }
+
+    /// Counts every validation this validator runs in `metrics`; see
+    /// [`crate::metrics`].
+    pub fn with_metrics(mut self, metrics: Arc<ValidationMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Returns the allowed brands, or `None` if every brand is accepted.
+    pub fn allowed_brands(&self) -> Option<&[CardBrand]> {
+        self.allowed_brands.as_deref()
+    }
+
+    /// Returns the merchant country, if set.
+    pub fn merchant_country(&self) -> Option<&str> {
+        self.merchant_country.as_deref()
+    }
+
+    /// Returns the validation strictness.
+    pub fn strictness(&self) -> Strictness {
+        self.strictness
+    }
+
+    /// Returns the test card policy set with
+    /// [`with_test_card_policy`](Self::with_test_card_policy).
+    pub fn test_card_policy(&self) -> TestCardPolicy {
+        self.test_cards
+    }
+
+    /// Returns true if OCR correction is enabled.
+    pub fn ocr_correction(&self) -> bool {
+        self.ocr_correction
+    }
+
+    /// Returns the digit count limits.
+    pub(crate) fn digit_limits(&self) -> DigitLimits {
+        self.digit_limits
+    }
+
+    /// Returns the fewest digits accepted.
+    pub fn min_digits(&self) -> usize {
+        usize::from(self.digit_limits.min)
+    }
+
+    /// Returns the most digits accepted.
+    pub fn max_digits(&self) -> usize {
+        usize::from(self.digit_limits.max)
+    }
+
+    /// Returns the attached BIN database, if any.
+    pub fn bin_database(&self) -> Option<&dyn BinDatabase> {
+        self.bin_db.as_deref()
+    }
+
+    /// Returns a shared handle to the attached BIN database, if any, e.g. to
+    /// serve it to async code through [`SyncAdapter`](crate::bin::SyncAdapter).
+    pub fn shared_bin_database(&self) -> Option<Arc<dyn BinDatabase>> {
+        self.bin_db.clone()
+    }
+
+    /// Returns the attached metrics, if any.
+    pub fn metrics(&self) -> Option<&Arc<ValidationMetrics>> {
+        self.metrics.as_ref()
+    }
+
+    /// Validates a card number according to this validator's configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`crate::validate()`], plus
+    /// [`ValidationError::BrandNotAllowed`],
+    /// [`ValidationError::NotAcceptedInCountry`] and, in strict mode or with
+    /// [`TestCardPolicy::Reject`], [`ValidationError::TestCard`].
+    pub fn validate(&self, input: &str) -> Result<ValidatedCard, ValidationError> {
+        self.validate_with(&mut Workspace::new(), input)
+    }
+
+    /// Validates a card number like [`validate`](Self::validate), also
+    /// returning the non-fatal [`ValidationWarning`]s it raises.
+    ///
+    /// Strict mode and [`TestCardPolicy::Reject`] reject test cards
+    /// outright, so they never appear as [`ValidationWarning::TestCard`]
+    /// there.
+    ///
+    /// [`ValidationWarning`]: crate::ValidationWarning
+    /// [`ValidationWarning::TestCard`]: crate::ValidationWarning::TestCard
+    pub fn validate_with_warnings(
+        &self,
+        input: &str,
+    ) -> Result<ValidationOutcome, ValidationError> {
+        self.validate(input).map(ValidationOutcome::new)
+    }
+
+    /// Validates a card number like [`validate`](Self::validate) and its
+    /// CVV against the detected brand, reporting both results.
+    pub fn validate_with_cvv(&self, card_number: &str, cvv: &str) -> CardWithCvv {
+        CardWithCvv::new(self.validate(card_number), cvv)
+    }
+
+    /// Validates a card number held in a [`secrecy::SecretString`].
+    ///
+    /// Requires the `secrecy` feature.
+    #[cfg(feature = "secrecy")]
+    pub fn validate_secret(
+        &self,
+        input: &secrecy::SecretString,
+    ) -> Result<ValidatedCard, ValidationError> {
+        use secrecy::ExposeSecret;
+        self.validate(input.expose_secret())
+    }
+
+    /// Validates a card number like [`validate`](Self::validate), parsing
+    /// into a caller-owned [`Workspace`] that can be reused across calls.
+    pub fn validate_with(
+        &self,
+        workspace: &mut Workspace,
+        input: &str,
+    ) -> Result<ValidatedCard, ValidationError> {
+        self.validate_detected(workspace, input, detect_brand)
+    }
+
+    /// Validates like [`validate_with`](Self::validate_with), identifying the
+    /// brand with `detect`; batch validation passes a memoized lookup.
+    pub(crate) fn validate_detected(
+        &self,
+        workspace: &mut Workspace,
+        input: &str,
+        detect: impl FnOnce(&[u8]) -> Option<CardBrand>,
+    ) -> Result<ValidatedCard, ValidationError> {
+        let any_brand = self.strictness == Strictness::Lenient;
+        self.validate_checked(workspace, input, detect, any_brand)
+    }
+
+    /// Validates a card number like [`validate`](Self::validate), but
+    /// accepts numbers matching no brand range, as [`crate::validate_any`]
+    /// does, whatever the strictness.
+    ///
+    /// Such cards carry a placeholder brand; check
+    /// [`ValidationOutcome::new`] for
+    /// [`ValidationWarning::TentativeBrand`](crate::ValidationWarning::TentativeBrand)
+    /// before showing it. Strict test card rejection still applies, and a
+    /// validator with allowed brands or a merchant country rejects such
+    /// cards with [`ValidationError::UnknownBrand`], as their brand can't be
+    /// checked against those policies.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cc_validator::{ValidationOutcome, ValidationWarning, Validator};
+    ///
+    /// let validator = Validator::new();
+    /// assert!(validator.validate("9000000000000001").is_err());
+    ///
+    /// let card = validator.validate_any("9000000000000001").unwrap();
+    /// let outcome = ValidationOutcome::new(card);
+    /// assert_eq!(outcome.warnings, [ValidationWarning::TentativeBrand]);
+    /// ```
+    pub fn validate_any(&self, input: &str) -> Result<ValidatedCard, ValidationError> {
+        self.validate_checked(&mut Workspace::new(), input, detect_brand, true)
+    }
+
+    fn validate_checked(
+        &self,
+        workspace: &mut Workspace,
+        input: &str,
+        detect: impl FnOnce(&[u8]) -> Option<CardBrand>,
+        any_brand: bool,
+    ) -> Result<ValidatedCard, ValidationError> {
+        let result = self.check(workspace, input, detect, any_brand);
+        recorded(self.metrics.as_ref(), result)
+    }
+
+    fn check(
+        &self,
+        workspace: &mut Workspace,
+        input: &str,
+        detect: impl FnOnce(&[u8]) -> Option<CardBrand>,
+        any_brand: bool,
+    ) -> Result<ValidatedCard, ValidationError> {
+        let sanitized;
+        let input = if self.ocr_correction {
+            sanitized = sanitize_ocr(input);
+            sanitized.as_str()
+        } else {
+            input
+        };
+
+        let card = if any_brand {
+            workspace.validate_any_within(input, self.digit_limits, detect)?
+        } else {
+            workspace.validate_within(input, self.digit_limits, detect)?
+        };
+
+        // The placeholder brand of an unknown number must not pass brand
+        // policies
+        if any_brand
+            && (self.allowed_brands.is_some() || self.merchant_country.is_some())
+            && detect_brand(card.digits()).is_none()
+        {
+            return Err(ValidationError::UnknownBrand);
+        }
+
+        if let Some(allowed) = &self.allowed_brands {
+            if !allowed.contains(&card.brand()) {
+                return Err(ValidationError::BrandNotAllowed {
+                    brand: card.brand(),
+                });
+            }
+        }
+
+        if let Some(country) = &self.merchant_country {
+            if !self.acceptance.is_accepted(card.brand(), country) {
+                return Err(ValidationError::NotAcceptedInCountry {
+                    brand: card.brand(),
+                    country: country.clone(),
+                });
+            }
+        }
+
+        let reject_test_cards =
+            self.strictness == Strictness::Strict || self.test_cards == TestCardPolicy::Reject;
+        if reject_test_cards && card.is_known_test_card() {
+            return Err(ValidationError::TestCard);
+        }
+
+        Ok(card)
+    }
+
+    /// Returns `true` if the card number passes this validator.
+    pub fn is_valid(&self, input: &str) -> bool {
+        self.validate(input).is_ok()
+    }
+
+    /// Looks up BIN information for a card in the attached database.
+    pub fn lookup_bin(&self, card: &ValidatedCard) -> Option<BinInfo> {
+        self.bin_db.as_ref()?.lookup(card.bin_lookup_digits())
+    }
+
+    /// Validates a card number and returns a profile with BIN information attached.
+    pub fn profile(&self, input: &str) -> Result<CardProfile, ValidationError> {
+        let card = self.validate(input)?;
+        let profile = match &self.bin_db {
+            Some(db) => CardProfile::new(card).with_bin_lookup(db.as_ref()),
+            None => CardProfile::new(card),
+        };
+        Ok(profile)
+    }
+}
+
+impl fmt::Debug for Validator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Validator")
+            .field("allowed_brands", &self.allowed_brands)
+            .field("merchant_country", &self.merchant_country)
+            .field("acceptance", &self.acceptance)
+            .field("strictness", &self.strictness)
+            .field("test_cards", &self.test_cards)
+            .field("ocr_correction", &self.ocr_correction)
+            .field("digit_limits", &self.digit_limits)
+            .field("bin_db", &self.bin_db.as_ref().map(|db| db.len()))
+            .field("metrics", &self.metrics.is_some())
+            .finish()
+    }
+}
+
+static DEFAULT_VALIDATOR: OnceLock<Validator> = OnceLock::new();
+
+/// Returns the process-wide default validator.
+///
+/// On first use the validator is configured from the environment (see
+/// [`ValidatorConfig::from_env`]) unless [`set_default_validator`] was
+/// called earlier.
+///
+/// # Errors
+///
+/// Returns the [`ConfigError`] if the environment configuration is invalid.
+/// Nothing is cached in that case, so every call reports the error until
+/// the environment is fixed or [`set_default_validator`] installs one.
+pub fn default_validator() -> Result<&'static Validator, ConfigError> {
+    if let Some(validator) = DEFAULT_VALIDATOR.get() {
+        return Ok(validator);
+    }
+    let validator = Validator::from_env()?;
+    Ok(DEFAULT_VALIDATOR.get_or_init(|| validator))
+}
+
+/// Installs the process-wide default validator.
+///
+/// Must be called before the first call to [`default_validator`]. Returns
+/// the validator back as `Err` if a default was already set.
+// Hands the validator back like `OnceLock::set`
+#[allow(clippy::result_large_err)]
+pub fn set_default_validator(validator: Validator) -> Result<(), Validator> {
+    DEFAULT_VALIDATOR.set(validator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bin::MemoryBinDbBuilder;
+    use std::collections::HashMap;
+
+    fn vars(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| map.get(name).cloned()
+    }
+
+    #[test]
+    fn test_default_validator_matches_validate() {
+        let validator = Validator::new();
+        assert!(validator.is_valid("4111111111111111"));
+        assert!(validator.is_valid("378282246310005"));
+        assert_eq!(
+            validator.validate("4111111111111112").unwrap_err(),
+            ValidationError::InvalidChecksum
+        );
+    }
+
+    #[test]
+    fn test_allowed_brands() {
+        let validator = Validator::new().with_allowed_brands([CardBrand::Amex]);
+        assert!(validator.is_valid("378282246310005"));
+        assert_eq!(
+            validator.validate("4111111111111111").unwrap_err(),
+            ValidationError::BrandNotAllowed {
+                brand: CardBrand::Visa
+            }
+        );
+    }
+
+    #[test]
+    fn test_merchant_country() {
+        let validator = Validator::new().with_merchant_country(" ru ");
+        assert_eq!(validator.merchant_country(), Some("RU"));
+        assert!(validator.is_valid("2200000000000004"));
+        assert!(validator.is_valid("4111111111111111"));
+
+        let validator = Validator::new()
+            .with_merchant_country("DE")
+            .with_acceptance_matrix(AcceptanceMatrix::new().restrict(CardBrand::Visa, ["US"]));
+        let err = validator.validate("4111111111111111").unwrap_err();
+        assert_eq!(err.code(), "not_accepted_in_country");
+        assert_eq!(err.to_string(), "Visa cards are not accepted in DE");
+        assert_eq!(
+            validator
+                .validate("2200000000000004")
+                .unwrap_err()
+                .to_string(),
+            "Mir cards are not accepted in DE (domestic scheme of RU)"
+        );
+    }
+
+    #[test]
+    fn test_strictness() {
+        let strict = Validator::new().with_strictness(Strictness::Strict);
+        assert_eq!(
+            strict.validate("4111111111111111").unwrap_err(),
+            ValidationError::TestCard
+        );
+        assert!(strict.is_valid("4532015112830366"));
+
+        // validate_any only relaxes brand detection
+        assert!(strict.validate_any("9000000000000001").is_ok());
+        assert_eq!(
+            strict.validate_any("4111111111111111").unwrap_err(),
+            ValidationError::TestCard
+        );
+
+        // ...and never lets the placeholder brand through a brand policy
+        let visa_only = Validator::new().with_allowed_brands([CardBrand::Visa]);
+        assert_eq!(
+            visa_only.validate_any("9000000000000001").unwrap_err(),
+            ValidationError::UnknownBrand
+        );
+        assert!(visa_only.validate_any("4532015112830366").is_ok());
+        assert_eq!(
+            Validator::new()
+                .with_merchant_country("US")
+                .validate_any("9000000000000001")
+                .unwrap_err(),
+            ValidationError::UnknownBrand
+        );
+    }
+
+    #[test]
+    fn test_test_card_policy() {
+        let lenient = Validator::new()
+            .with_strictness(Strictness::Lenient)
+            .with_test_card_policy(TestCardPolicy::Reject);
+        assert_eq!(lenient.strictness(), Strictness::Lenient);
+        assert_eq!(
+            lenient.validate("4111111111111111").unwrap_err(),
+            ValidationError::TestCard
+        );
+        assert!(lenient.is_valid("9000000000000001"));
+        assert!(Validator::new()
+            .with_test_card_policy(TestCardPolicy::Allow)
+            .is_valid("4111111111111111"));
+    }
+
+    #[test]
+    fn test_lenient_unknown_brand_with_brand_policies() {
+        let lenient = Validator::new().with_strictness(Strictness::Lenient);
+        assert!(lenient.is_valid("9000000000000001"));
+
+        // The placeholder brand must not satisfy or trip an allowlist
+        for allowed in [CardBrand::Visa, CardBrand::Amex] {
+            let validator = lenient.clone().with_allowed_brands([allowed]);
+            assert_eq!(
+                validator.validate("9000000000000001").unwrap_err(),
+                ValidationError::UnknownBrand
+            );
+        }
+        assert!(lenient
+            .clone()
+            .with_allowed_brands([CardBrand::Visa])
+            .is_valid("4532015112830366"));
+
+        let validator = lenient.with_merchant_country("US");
+        assert_eq!(
+            validator.validate("9000000000000001").unwrap_err(),
+            ValidationError::UnknownBrand
+        );
+        assert!(validator.is_valid("4532015112830366"));
+    }
+
+    #[test]
+    fn test_digit_limits() {
+        let validator = Validator::new()
+            .with_strictness(Strictness::Lenient)
+            .with_digit_limits(10, 22);
+        assert_eq!((validator.min_digits(), validator.max_digits()), (10, 22));
+        assert_eq!(validator.validate("7777777777").unwrap().length(), 10);
+        assert_eq!(
+            validator.validate("77777777777777777777778").unwrap_err(),
+            ValidationError::TooLong {
+                length: 23,
+                maximum: 22
+            }
+        );
+        // Known brands keep their own lengths
+        assert!(matches!(
+            validator.validate("4111111110"),
+            Err(ValidationError::InvalidLengthForBrand { .. })
+        ));
+        // The free functions keep the standard limits
+        assert!(matches!(
+            crate::validate_any("7777777777"),
+            Err(ValidationError::TooShort { minimum: 12, .. })
+        ));
+        assert_eq!(Validator::new().max_digits(), crate::MAX_CARD_DIGITS);
+    }
+
+    #[test]
+    #[should_panic(expected = "digit limits 4..=19")]
+    fn test_digit_limits_out_of_range() {
+        let _ = Validator::new().with_digit_limits(4, 19);
+    }
+
+    #[test]
+    fn test_strictness_from_name() {
+        assert_eq!(Strictness::from_name("LENIENT"), Some(Strictness::Lenient));
+        assert_eq!(Strictness::from_name("strict"), Some(Strictness::Strict));
+        assert_eq!(Strictness::from_name("loose"), None);
+    }
+
+    #[test]
+    fn test_config_from_vars() {
+        let config = ValidatorConfig::from_vars(vars(&[
+            ("CC_VALIDATOR_ALLOWED_BRANDS", "visa, Mastercard,"),
+            ("CC_VALIDATOR_STRICTNESS", "strict"),
+        ]))
+        .unwrap();
+        assert_eq!(
+            config.allowed_brands,
+            Some(vec!["visa".to_string(), "Mastercard".to_string()])
+        );
+        assert_eq!(config.strictness, Strictness::Strict);
+
+        let validator = Validator::from_config(&config).unwrap();
+        assert_eq!(
+            validator.allowed_brands(),
+            Some(&[CardBrand::Visa, CardBrand::Mastercard][..])
+        );
+    }
+
+    #[test]
+    fn test_config_errors() {
+        let err = ValidatorConfig::from_vars(vars(&[("CC_VALIDATOR_STRICTNESS", "loose")]));
+        assert!(matches!(err, Err(ConfigError::InvalidStrictness(_))));
+
+        let config = ValidatorConfig {
+            allowed_brands: Some(vec!["visa".to_string(), "nope".to_string()]),
+            ..Default::default()
+        };
+        assert!(matches!(
+            Validator::from_config(&config),
+            Err(ConfigError::UnknownBrand(b)) if b == "nope"
+        ));
+
+        let config = ValidatorConfig {
+            bin_db_path: Some(PathBuf::from("bins.xml")),
+            ..Default::default()
+        };
+        assert!(matches!(
+            Validator::from_config(&config),
+            Err(ConfigError::BinDb(BinDbError::InvalidDatabase(_)))
+        ));
+    }
+
+    #[test]
+    fn test_empty_config_is_default() {
+        let config = ValidatorConfig::from_vars(|_| None).unwrap();
+        assert_eq!(config, ValidatorConfig::default());
+    }
+
+    #[test]
+    fn test_bin_lookup_and_profile() {
+        let db = MemoryBinDbBuilder::new()
+            .add("411111", BinInfo::with_bin("411111").issuer("Test Bank"))
+            .build();
+        let validator = Validator::new().with_bin_database(db);
+
+        let card = validator.validate("4111111111111111").unwrap();
+        assert_eq!(
+            validator.lookup_bin(&card).unwrap().issuer.as_deref(),
+            Some("Test Bank")
+        );
+
+        let profile = validator.profile("4111111111111111").unwrap();
+        assert!(profile.bin_info().is_some());
+        assert_eq!(validator.shared_bin_database().unwrap().len(), 1);
+    }
+
+    #[cfg(feature = "config")]
+    #[test]
+    fn test_config_from_toml() {
+        let config = ValidatorConfig::from_toml_str(
+            r#"
+            allowed_brands = ["visa", "amex"]
+            strictness = "lenient"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.strictness, Strictness::Lenient);
+        assert_eq!(config.allowed_brands.unwrap().len(), 2);
+
+        assert!(ValidatorConfig::from_toml_str("unknown_key = 1").is_err());
+    }
+
+    #[test]
+    fn test_validator_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Validator>();
+    }
+}
@@ -31,12 +31,18 @@ use wasm_bindgen::prelude::*;
 
 /// Result of card validation, returned to JavaScript.
 #[wasm_bindgen]
+#[derive(Clone)]
 pub struct ValidationResult {
     valid: bool,
     brand: Option<String>,
+    brand_known: Option<bool>,
     last_four: Option<String>,
     masked: Option<String>,
+    is_test_card: Option<bool>,
     error: Option<String>,
+    error_position: Option<usize>,
+    error_digit_index: Option<usize>,
+    warnings: Vec<String>,
 }
 
 #[wasm_bindgen]
@@ -51,6 +57,13 @@ impl ValidationResult {
         self.brand.clone()
     }
 
+    /// Whether the brand was detected; `false` for unknown brands accepted
+    /// by [`validate_any`], whose `brand` is undefined.
+    #[wasm_bindgen(getter)]
+    pub fn brand_known(&self) -> Option<bool> {
+        self.brand_known
+    }
+
     #[wasm_bindgen(getter)]
     pub fn last_four(&self) -> Option<String> {
         self.last_four.clone()
@@ -61,39 +74,122 @@ impl ValidationResult {
         self.masked.clone()
     }
 
+    #[wasm_bindgen(getter)]
+    pub fn is_test_card(&self) -> Option<bool> {
+        self.is_test_card
+    }
+
     #[wasm_bindgen(getter)]
     pub fn error(&self) -> Option<String> {
         self.error.clone()
     }
+
+    /// Character index (0-based) of the invalid character, for invalid
+    /// character errors.
+    #[wasm_bindgen(getter)]
+    pub fn error_position(&self) -> Option<usize> {
+        self.error_position
+    }
+
+    /// Number of digits before the invalid character, for invalid
+    /// character errors.
+    #[wasm_bindgen(getter)]
+    pub fn error_digit_index(&self) -> Option<usize> {
+        self.error_digit_index
+    }
+
+    /// Codes of non-fatal conditions such as `"test_card"` or
+    /// `"unusual_length"`; empty for invalid cards.
+    #[wasm_bindgen(getter)]
+    pub fn warnings(&self) -> js_sys::Array {
+        self.warnings.iter().map(JsValue::from).collect()
+    }
 }
 
 /// Validates a credit card number and returns detailed information.
 ///
-/// Accepts card numbers with spaces, dashes, or no separators.
+/// Accepts card numbers with spaces, dashes, or no separators. Throws if the
+/// `CC_VALIDATOR_*` configuration of the default validator is invalid.
 ///
 /// # Example
 /// ```javascript
 /// const result = validate_card("4111-1111-1111-1111");
 /// console.log(result.valid);  // true
 /// console.log(result.brand);  // "Visa"
+/// console.log(result.warnings);  // ["test_card"]
 /// ```
 #[wasm_bindgen]
-pub fn validate_card(card_number: &str) -> ValidationResult {
-    match crate::validate(card_number) {
-        Ok(card) => ValidationResult {
-            valid: true,
-            brand: Some(card.brand().name().to_string()),
-            last_four: Some(card.last_four().to_string()),
-            masked: Some(card.masked()),
-            error: None,
-        },
-        Err(e) => ValidationResult {
-            valid: false,
-            brand: None,
-            last_four: None,
-            masked: None,
-            error: Some(e.to_string()),
-        },
+pub fn validate_card(card_number: &str) -> Result<ValidationResult, JsValue> {
+    let result = default_validator()?.validate_with_warnings(card_number);
+    Ok(validation_result(result))
+}
+
+/// Validates a credit card number, accepting numbers that match no known
+/// brand as long as they pass the Luhn check.
+///
+/// With `CC_VALIDATOR_ALLOWED_BRANDS` set such numbers are rejected as
+/// `unknown_brand`, since their brand can't be checked against the
+/// allowlist.
+///
+/// # Example
+/// ```javascript
+/// const result = validate_any("9000000000000001");
+/// console.log(result.valid);        // true
+/// console.log(result.brand);        // undefined
+/// console.log(result.brand_known);  // false
+/// ```
+#[wasm_bindgen]
+pub fn validate_any(card_number: &str) -> Result<ValidationResult, JsValue> {
+    let result = default_validator()?.validate_any(card_number);
+    Ok(validation_result(result.map(crate::ValidationOutcome::new)))
+}
+
+/// The default validator, with its configuration error as a thrown string.
+fn default_validator() -> Result<&'static crate::Validator, JsValue> {
+    crate::default_validator().map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+fn validation_result(
+    result: Result<crate::ValidationOutcome, crate::ValidationError>,
+) -> ValidationResult {
+    match result {
+        Ok(crate::ValidationOutcome { card, warnings }) => {
+            let brand_known = !warnings.contains(&crate::ValidationWarning::TentativeBrand);
+            ValidationResult {
+                valid: true,
+                brand: brand_known.then(|| card.brand().name().to_string()),
+                brand_known: Some(brand_known),
+                last_four: Some(card.last_four().to_string()),
+                masked: Some(card.masked()),
+                is_test_card: Some(card.is_known_test_card()),
+                error: None,
+                error_position: None,
+                error_digit_index: None,
+                warnings: warnings.iter().map(|w| w.code().to_string()).collect(),
+            }
+        }
+        Err(e) => {
+            let (error_position, error_digit_index) = match e {
+                crate::ValidationError::InvalidCharacter {
+                    position,
+                    digit_index,
+                    ..
+                } => (Some(position), Some(digit_index)),
+                _ => (None, None),
+            };
+            ValidationResult {
+                valid: false,
+                brand: None,
+                brand_known: None,
+                last_four: None,
+                masked: None,
+                is_test_card: None,
+                error: Some(e.to_string()),
+                error_position,
+                error_digit_index,
+                warnings: Vec::new(),
+            }
+        }
     }
 }
 
@@ -150,6 +246,103 @@ pub fn format_card_with_separator(card_number: &str, separator: &str) -> String
     crate::format::format_with_separator(card_number, separator)
 }
 
+/// Options for [`format_card_with_options`].
+///
+/// # Example
+/// ```javascript
+/// const options = new FormatOptions();
+/// options.directional_isolates = true;  // for Arabic/Hebrew pages
+/// options.masked = true;
+/// format_card_with_options("4111111111111111", options)  // "\u2066•••• •••• •••• 1111\u2069"
+/// ```
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct FormatOptions {
+    separator: String,
+    directional_isolates: bool,
+    masked: bool,
+}
+
+#[wasm_bindgen]
+impl FormatOptions {
+    /// Creates options for space-separated, unmasked output.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> FormatOptions {
+        FormatOptions {
+            separator: " ".to_string(),
+            directional_isolates: false,
+            masked: false,
+        }
+    }
+
+    /// String placed between digit groups.
+    #[wasm_bindgen(getter)]
+    pub fn separator(&self) -> String {
+        self.separator.clone()
+    }
+
+    /// Sets the string placed between digit groups.
+    #[wasm_bindgen(setter)]
+    pub fn set_separator(&mut self, separator: String) {
+        self.separator = separator;
+    }
+
+    /// Whether the output is wrapped in a left-to-right isolate so its
+    /// groups keep their order in right-to-left text.
+    #[wasm_bindgen(getter)]
+    pub fn directional_isolates(&self) -> bool {
+        self.directional_isolates
+    }
+
+    /// Sets whether the output is wrapped in a left-to-right isolate.
+    #[wasm_bindgen(setter)]
+    pub fn set_directional_isolates(&mut self, enabled: bool) {
+        self.directional_isolates = enabled;
+    }
+
+    /// Whether all but the last 4 digits are masked.
+    #[wasm_bindgen(getter)]
+    pub fn masked(&self) -> bool {
+        self.masked
+    }
+
+    /// Sets whether all but the last 4 digits are masked.
+    #[wasm_bindgen(setter)]
+    pub fn set_masked(&mut self, masked: bool) {
+        self.masked = masked;
+    }
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats a card number with brand grouping and the given options.
+///
+/// Masked output requires a valid card number; otherwise an error is thrown.
+#[wasm_bindgen]
+pub fn format_card_with_options(
+    card_number: &str,
+    options: &FormatOptions,
+) -> Result<String, JsValue> {
+    if options.masked {
+        let card = crate::validate(card_number).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let style = crate::format::MaskedFormat::new()
+            .separator(options.separator.as_str())
+            .directional_isolates(options.directional_isolates);
+        return Ok(crate::format::format_masked(&card, &style));
+    }
+    let format_options = crate::format::FormatOptions::new()
+        .separator(options.separator.as_str())
+        .directional_isolates(options.directional_isolates);
+    Ok(crate::format::format_with_options(
+        card_number,
+        &format_options,
+    ))
+}
+
 /// Strips all formatting (spaces, dashes) from a card number.
 #[wasm_bindgen]
 pub fn strip_formatting(card_number: &str) -> String {
@@ -170,40 +363,112 @@ pub fn mask_card(card_number: &str) -> Result<String, JsValue> {
     }
 }
 
+/// Masks a partly typed card number for logging, showing at most the
+/// first 6 digits.
+///
+/// # Example
+/// ```javascript
+/// mask_partial("4111 1111 11")  // "411111****"
+/// ```
+#[wasm_bindgen]
+pub fn mask_partial(input: &str) -> String {
+    crate::partial::PartialCard::new(input).to_string()
+}
+
 /// Generates a valid test card number for the given brand.
 ///
-/// Supported brands: visa, mastercard, amex, discover, jcb, diners, unionpay, maestro
+/// Accepts any id or name from `supported_brands()`, and optionally one of
+/// the brand's valid lengths (the brand's usual length if omitted).
 ///
 /// # Example
 /// ```javascript
 /// const card = generate_test_card("visa");
 /// console.log(card);  // "4539578763621323" (example)
+/// const long = generate_test_card("visa", 19);
 /// ```
+///
+/// Not available with the `no-pan-exposure` feature.
+#[cfg(not(feature = "no-pan-exposure"))]
 #[wasm_bindgen]
-pub fn generate_test_card(brand: &str) -> Result<String, JsValue> {
-    let card_brand = match brand.to_lowercase().as_str() {
-        "visa" => crate::CardBrand::Visa,
-        "mastercard" | "mc" => crate::CardBrand::Mastercard,
-        "amex" | "american express" => crate::CardBrand::Amex,
-        "discover" => crate::CardBrand::Discover,
-        "jcb" => crate::CardBrand::Jcb,
-        "diners" | "dinersclub" | "diners club" => crate::CardBrand::DinersClub,
-        "unionpay" | "union pay" => crate::CardBrand::UnionPay,
-        "maestro" => crate::CardBrand::Maestro,
-        "mir" => crate::CardBrand::Mir,
-        "rupay" => crate::CardBrand::RuPay,
-        "verve" => crate::CardBrand::Verve,
-        "elo" => crate::CardBrand::Elo,
-        "troy" => crate::CardBrand::Troy,
-        "bccard" | "bc card" => crate::CardBrand::BcCard,
-        _ => return Err(JsValue::from_str(&format!("Unknown brand: {}", brand))),
-    };
+pub fn generate_test_card(brand: &str, length: Option<usize>) -> Result<String, JsValue> {
+    let card_brand = crate::CardBrand::from_name(brand)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown brand: {}", brand)))?;
+
+    match length {
+        Some(length) => crate::generate::CardGenerator::for_length(card_brand, length)
+            .map(|generator| generator.generate_deterministic())
+            .map_err(|e| JsValue::from_str(&e.to_string())),
+        None => Ok(crate::generate::generate_card_deterministic(card_brand)),
+    }
+}
+
+/// Details of a supported card brand.
+#[wasm_bindgen]
+pub struct BrandDetails {
+    brand: crate::CardBrand,
+}
+
+#[wasm_bindgen]
+impl BrandDetails {
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> String {
+        self.brand.id().to_string()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn name(&self) -> String {
+        self.brand.name().to_string()
+    }
+
+    /// Stable numeric id, as used by [`validate_batch_compact`].
+    #[wasm_bindgen(getter)]
+    pub fn numeric_id(&self) -> u8 {
+        self.brand.numeric_id()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn valid_lengths(&self) -> Vec<u8> {
+        self.brand.valid_lengths().to_vec()
+    }
 
-    Ok(crate::generate::generate_card_deterministic(card_brand))
+    #[wasm_bindgen(getter)]
+    pub fn cvv_length(&self) -> u8 {
+        self.brand.cvv_length() as u8
+    }
+
+    /// Regex for the brand's card numbers (digits only, unanchored).
+    #[wasm_bindgen(getter)]
+    pub fn input_pattern(&self) -> String {
+        self.brand.input_pattern()
+    }
+}
+
+/// Lists every supported card brand.
+///
+/// # Example
+/// ```javascript
+/// for (const brand of supported_brands()) {
+///     console.log(brand.id, brand.name);
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn supported_brands() -> js_sys::Array {
+    crate::CardBrand::all()
+        .iter()
+        .map(|&brand| JsValue::from(BrandDetails { brand }))
+        .collect()
+}
+
+/// Looks up a brand by its stable `numeric_id`, e.g. a brand byte from
+/// [`validate_batch_compact`]; `undefined` for `0` or unknown ids.
+#[wasm_bindgen]
+pub fn brand_for_numeric_id(id: u8) -> Option<BrandDetails> {
+    crate::CardBrand::from_numeric_id(id).map(|brand| BrandDetails { brand })
 }
 
 /// Result of CVV validation.
 #[wasm_bindgen]
+#[derive(Clone)]
 pub struct CvvResult {
     valid: bool,
     length: Option<u8>,
@@ -237,10 +502,14 @@ impl CvvResult {
 /// ```
 #[wasm_bindgen]
 pub fn validate_cvv(cvv: &str) -> CvvResult {
-    match crate::cvv::validate_cvv(cvv) {
+    cvv_result(crate::cvv::validate_cvv(cvv).map(Some))
+}
+
+fn cvv_result(result: Result<Option<crate::cvv::ValidatedCvv>, crate::cvv::CvvError>) -> CvvResult {
+    match result {
         Ok(validated) => CvvResult {
             valid: true,
-            length: Some(validated.length() as u8),
+            length: validated.map(|cvv| cvv.length() as u8),
             error: None,
         },
         Err(e) => CvvResult {
@@ -260,36 +529,64 @@ pub fn validate_cvv(cvv: &str) -> CvvResult {
 /// ```
 #[wasm_bindgen]
 pub fn validate_cvv_for_brand(cvv: &str, brand: &str) -> CvvResult {
-    let card_brand = match brand.to_lowercase().as_str() {
-        "visa" => crate::CardBrand::Visa,
-        "mastercard" | "mc" => crate::CardBrand::Mastercard,
-        "amex" | "american express" => crate::CardBrand::Amex,
-        "discover" => crate::CardBrand::Discover,
-        "jcb" => crate::CardBrand::Jcb,
-        "diners" | "dinersclub" => crate::CardBrand::DinersClub,
-        _ => {
-            return CvvResult {
-                valid: false,
-                length: None,
-                error: Some(format!("Unknown brand: {}", brand)),
-            }
-        }
-    };
-
-    match crate::cvv::validate_cvv_for_brand(cvv, card_brand) {
-        Ok(validated) => CvvResult {
-            valid: true,
-            length: Some(validated.length() as u8),
-            error: None,
-        },
-        Err(e) => CvvResult {
+    let Some(card_brand) = crate::CardBrand::from_name(brand) else {
+        return CvvResult {
             valid: false,
             length: None,
-            error: Some(e.to_string()),
-        },
+            error: Some(format!("Unknown brand: {}", brand)),
+        };
+    };
+
+    cvv_result(crate::cvv::validate_cvv_for_brand(cvv, card_brand).map(Some))
+}
+
+/// Result of validating a card number and its CVV together.
+#[wasm_bindgen]
+pub struct CardCvvResult {
+    card: ValidationResult,
+    cvv: CvvResult,
+}
+
+#[wasm_bindgen]
+impl CardCvvResult {
+    /// Whether both the card number and the CVV are valid.
+    #[wasm_bindgen(getter)]
+    pub fn valid(&self) -> bool {
+        self.card.valid && self.cvv.valid
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn card(&self) -> ValidationResult {
+        self.card.clone()
+    }
+
+    /// CVV result, checked against the card's brand when the card is
+    /// valid; `length` is undefined for brands without a CVV.
+    #[wasm_bindgen(getter)]
+    pub fn cvv(&self) -> CvvResult {
+        self.cvv.clone()
     }
 }
 
+/// Validates a card number and checks its CVV against the detected brand,
+/// in one call.
+///
+/// # Example
+/// ```javascript
+/// const result = validate_with_cvv("4111111111111111", "1234");
+/// console.log(result.valid);        // false
+/// console.log(result.card.valid);   // true
+/// console.log(result.cvv.error);    // "Visa cards require 3 digit CVV, got 4"
+/// ```
+#[wasm_bindgen]
+pub fn validate_with_cvv(card_number: &str, cvv: &str) -> Result<CardCvvResult, JsValue> {
+    let result = default_validator()?.validate_with_cvv(card_number, cvv);
+    Ok(CardCvvResult {
+        card: validation_result(result.card.map(crate::ValidationOutcome::new)),
+        cvv: cvv_result(result.cvv),
+    })
+}
+
 /// Result of expiry date validation.
 #[wasm_bindgen]
 pub struct ExpiryResult {
@@ -400,14 +697,91 @@ pub fn parse_expiry(date: &str) -> ExpiryResult {
 /// results.forEach(r => console.log(r.valid));
 /// ```
 #[wasm_bindgen]
-pub fn validate_batch(card_numbers: js_sys::Array) -> js_sys::Array {
+pub fn validate_batch(card_numbers: js_sys::Array) -> Result<js_sys::Array, JsValue> {
+    let validator = default_validator()?;
     let results = js_sys::Array::new();
 
     for card in card_numbers.iter() {
         if let Some(card_str) = card.as_string() {
-            results.push(&JsValue::from(validate_card(&card_str)));
+            let result = validation_result(validator.validate_with_warnings(&card_str));
+            results.push(&JsValue::from(result));
         }
     }
 
-    results
+    Ok(results)
+}
+
+/// Validates newline-delimited card numbers given as UTF-8 bytes.
+///
+/// Each line yields two bytes: a status (`0` for valid, otherwise an error,
+/// see [`batch_status_code`]) and a brand (`0` for none, otherwise its
+/// `numeric_id`, see [`brand_for_numeric_id`]). A trailing newline and `\r`
+/// line endings are ignored.
+///
+/// The whole batch crosses the JS boundary once in each direction, which is
+/// far cheaper than [`validate_batch`] for thousands of cards.
+///
+/// # Example
+/// ```javascript
+/// const input = new TextEncoder().encode("4111111111111111\n4111111111111112\n");
+/// const out = validate_batch_compact(input);
+/// for (let i = 0; i < out.length; i += 2) {
+///     const [status, brand] = [out[i], out[i + 1]];
+///     console.log(batch_status_code(status), brand_for_numeric_id(brand)?.id);
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn validate_batch_compact(input: &[u8]) -> Result<Vec<u8>, JsValue> {
+    crate::batch::validate_compact(input, b'\n').map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+/// Like [`validate_batch_compact`] but takes the card numbers as one
+/// newline-delimited string.
+///
+/// # Example
+/// ```javascript
+/// const out = validate_batch_lines(cards.join("\n"));
+/// ```
+#[wasm_bindgen]
+pub fn validate_batch_lines(input: &str) -> Result<Vec<u8>, JsValue> {
+    validate_batch_compact(input.as_bytes())
+}
+
+/// Returns the error code for a compact batch status, e.g. `"invalid_checksum"`.
+///
+/// Status `0` is `"valid"`; the other codes match the validation error codes
+/// reported elsewhere.
+#[wasm_bindgen]
+pub fn batch_status_code(status: u8) -> Option<String> {
+    crate::batch::compact_status_code(status).map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_batch() {
+        let out = validate_batch_lines("4111111111111111\r\n4111111111111112\n\n378282246310005\n")
+            .unwrap();
+        let visa = crate::CardBrand::Visa.numeric_id();
+        assert_eq!(out.len(), 8);
+        assert_eq!(&out[..2], &[0, visa]);
+        assert_eq!(
+            batch_status_code(out[2]).as_deref(),
+            Some("invalid_checksum")
+        );
+        assert_eq!(batch_status_code(out[4]).as_deref(), Some("empty"));
+        assert_eq!(out[6], 0);
+        assert_ne!(out[7], 0);
+
+        assert_eq!(
+            validate_batch_compact(b"4111111111111111").unwrap(),
+            &out[..2]
+        );
+        assert!(validate_batch_lines("").unwrap().is_empty());
+        assert!(batch_status_code(200).is_none());
+        assert_eq!(brand_for_numeric_id(out[7]).unwrap().id(), "amex");
+        assert!(brand_for_numeric_id(0).is_none());
+    }
 }
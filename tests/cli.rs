@@ -0,0 +1,107 @@
+//! Tests for the `ccvalidator` binary: stdin input, `--stdin-only` mode and
+//! generation options.
+
+#![cfg(feature = "cli-core")]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+/// Runs `ccvalidator` with `args`, feeding `stdin`.
+fn run(args: &[&str], env: &[(&str, &str)], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ccvalidator"))
+        .args(args)
+        .env_remove("CC_VALIDATOR_STDIN_ONLY")
+        .envs(env.iter().copied())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn test_card_numbers_from_stdin() {
+    let output = run(
+        &["validate"],
+        &[],
+        "4111111111111111\n\n 5500 0000 0000 0004 \n",
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).contains("2 of 2 valid"));
+
+    let output = run(&["--stdin-only", "luhn", "-"], &[], "4111111111111112\n");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).contains("FAIL"));
+
+    let output = run(&["mask", "--with-bin"], &[], "4111-1111-1111-1111");
+    assert_eq!(stdout(&output).trim(), "411111******1111");
+
+    let output = run(&["validate"], &[], "\n");
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_stdin_only_refuses_arguments() {
+    let refused = [
+        run(&["--stdin-only", "validate", "4111111111111111"], &[], ""),
+        run(&["detect", "--stdin-only", "4111"], &[], ""),
+        run(
+            &["format", "4111 1111 1111 1111"],
+            &[("CC_VALIDATOR_STDIN_ONLY", "1")],
+            "",
+        ),
+        // Card numbers are refused wherever they appear
+        run(&["--stdin-only", "cvv", "4111111111111111"], &[], ""),
+    ];
+    for output in &refused {
+        assert_eq!(output.status.code(), Some(2));
+        assert!(output.stdout.is_empty());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("pipe them on stdin"), "{}", stderr);
+        assert!(!stderr.contains("4111"));
+    }
+
+    let output = run(
+        &["luhn", "4111111111111111"],
+        &[("CC_VALIDATOR_STDIN_ONLY", "0")],
+        "",
+    );
+    if !cfg!(feature = "cli-stdin-only") {
+        assert_eq!(output.status.code(), Some(0));
+    }
+}
+
+#[cfg(all(feature = "cli", not(feature = "no-pan-exposure")))]
+#[test]
+fn test_generate_length() {
+    let output = run(
+        &[
+            "generate", "--brand", "discover", "--length", "18", "-c", "3",
+        ],
+        &[],
+        "",
+    );
+    assert_eq!(output.status.code(), Some(0));
+    let text = stdout(&output);
+    let cards: Vec<_> = text.lines().collect();
+    assert_eq!(cards.len(), 3);
+    for card in cards {
+        assert_eq!(card.len(), 18);
+        assert!(cc_validator::is_valid(card));
+    }
+
+    let output = run(&["generate", "--brand", "amex", "--length", "16"], &[], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+}
@@ -0,0 +1,20 @@
+//! The process-wide default validator reads `CC_VALIDATOR_*` variables, so
+//! these tests live in their own binary where no other test touches them.
+
+use cc_validator::batch::validate_compact;
+use cc_validator::validator::ConfigError;
+use cc_validator::{default_validator, set_default_validator, Validator};
+
+#[test]
+fn test_invalid_env_config_is_an_error() {
+    std::env::set_var("CC_VALIDATOR_STRICTNESS", "paranoid");
+
+    let err = default_validator().unwrap_err();
+    assert!(matches!(err, ConfigError::InvalidStrictness(ref s) if s == "paranoid"));
+    assert!(validate_compact(b"4111111111111111", b'\n').is_err());
+
+    // Failures aren't cached, so a validator installed afterwards is used
+    set_default_validator(Validator::new()).unwrap();
+    assert!(default_validator().unwrap().is_valid("4111111111111111"));
+    assert_eq!(validate_compact(b"4111111111111111", b'\n').unwrap()[0], 0);
+}
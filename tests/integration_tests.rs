@@ -174,6 +174,7 @@ fn test_all_jcb_test_cards() {
 // =============================================================================
 
 #[test]
+#[cfg(not(feature = "no-pan-exposure"))]
 fn test_various_separators() {
     let base = "4111111111111111";
 
@@ -229,6 +230,7 @@ fn test_invalid_characters() {
             Err(ValidationError::InvalidCharacter {
                 character,
                 position,
+                ..
             }) => {
                 assert_eq!(character, expected_char, "Wrong char for input: {}", input);
                 assert_eq!(
@@ -749,6 +751,7 @@ fn test_only_zeros() {
 }
 
 #[test]
+#[cfg(not(feature = "no-pan-exposure"))]
 fn test_leading_zeros_preserved() {
     // Card numbers can have leading zeros (though rare)
     // This tests that we handle them correctly
@@ -771,6 +774,7 @@ fn test_unicode_handling() {
 }
 
 #[test]
+#[cfg(not(feature = "no-pan-exposure"))]
 fn test_very_long_separators() {
     // Lots of separators between digits
     let with_many_separators = "4---1---1---1---1---1---1---1---1---1---1---1---1---1---1---1";
@@ -784,6 +788,7 @@ fn test_very_long_separators() {
 // =============================================================================
 
 #[test]
+#[cfg(not(feature = "no-pan-exposure"))]
 fn test_card_number_retrieval() {
     let card = validate("4111-1111-1111-1111").unwrap();
 